@@ -46,6 +46,7 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_budget,
         } = x;
 
         // Header
@@ -58,7 +59,8 @@ where
         } << HEADER_BITS;
         let mut n_exts = ((ext_qos != &declare::ext::QoSType::DEFAULT) as u8)
             + (ext_tstamp.is_some() as u8)
-            + ((ext_nodeid != &declare::ext::NodeIdType::DEFAULT) as u8);
+            + ((ext_nodeid != &declare::ext::NodeIdType::DEFAULT) as u8)
+            + (ext_budget.is_some() as u8);
         if n_exts != 0 {
             header |= declare::flag::Z;
         }
@@ -86,6 +88,11 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (*ext_nodeid, n_exts != 0))?;
         }
+        if let Some(b) = ext_budget.as_ref() {
+            n_exts -= 1;
+            let e = interest::ext::Budget::new(b.get() as u64);
+            self.write(&mut *writer, (&e, n_exts != 0))?;
+        }
 
         Ok(())
     }
@@ -146,6 +153,7 @@ where
         let mut ext_qos = declare::ext::QoSType::DEFAULT;
         let mut ext_tstamp = None;
         let mut ext_nodeid = declare::ext::NodeIdType::DEFAULT;
+        let mut ext_budget = None;
 
         let mut has_ext = imsg::has_flag(self.header, declare::flag::Z);
         while has_ext {
@@ -168,6 +176,11 @@ where
                     ext_nodeid = nid;
                     has_ext = ext;
                 }
+                interest::ext::Budget::ID => {
+                    let (b, ext): (interest::ext::Budget, bool) = eodec.read(&mut *reader)?;
+                    ext_budget = interest::ext::BudgetType::new(b.value as u32);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Declare", ext)?;
                 }
@@ -182,6 +195,7 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_budget,
         })
     }
 }
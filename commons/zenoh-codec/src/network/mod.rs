@@ -50,6 +50,8 @@ where
             NetworkBody::Interest(b) => self.write(&mut *writer, b),
             NetworkBody::Declare(b) => self.write(&mut *writer, b),
             NetworkBody::OAM(b) => self.write(&mut *writer, b),
+            NetworkBody::RequestCancel(b) => self.write(&mut *writer, b),
+            NetworkBody::RequestCredit(b) => self.write(&mut *writer, b),
         }
     }
 }
@@ -97,6 +99,8 @@ where
             id::INTEREST => NetworkBody::Interest(self.read(&mut *reader)?),
             id::DECLARE => NetworkBody::Declare(self.read(&mut *reader)?),
             id::OAM => NetworkBody::OAM(self.read(&mut *reader)?),
+            id::REQUEST_CANCEL => NetworkBody::RequestCancel(self.read(&mut *reader)?),
+            id::REQUEST_CREDIT => NetworkBody::RequestCredit(self.read(&mut *reader)?),
             _ => return Err(DidntRead),
         };
 
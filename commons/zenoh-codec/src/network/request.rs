@@ -21,7 +21,7 @@ use zenoh_protocol::{
     network::{
         id,
         request::{ext, flag},
-        Mapping, Request, RequestId,
+        Mapping, Request, RequestCancel, RequestCredit, RequestId,
     },
     zenoh::RequestBody,
 };
@@ -44,6 +44,9 @@ where
             ext::QueryTarget::BestMatching => 0,
             ext::QueryTarget::All => 1,
             ext::QueryTarget::AllComplete => 2,
+            ext::QueryTarget::RoundRobin => 3,
+            ext::QueryTarget::Random => 4,
+            ext::QueryTarget::LeastOutstanding => 5,
         };
         let ext = ext::Target::new(v);
         self.write(&mut *writer, (&ext, more))
@@ -62,6 +65,9 @@ where
             0 => ext::QueryTarget::BestMatching,
             1 => ext::QueryTarget::All,
             2 => ext::QueryTarget::AllComplete,
+            3 => ext::QueryTarget::RoundRobin,
+            4 => ext::QueryTarget::Random,
+            5 => ext::QueryTarget::LeastOutstanding,
             _ => return Err(DidntRead),
         };
         Ok((rt, more))
@@ -84,6 +90,7 @@ where
             ext_target,
             ext_budget,
             ext_timeout,
+            ext_credits,
             payload,
         } = x;
 
@@ -94,6 +101,7 @@ where
             + ((ext_target != &ext::QueryTarget::DEFAULT) as u8)
             + (ext_budget.is_some() as u8)
             + (ext_timeout.is_some() as u8)
+            + (ext_credits.is_some() as u8)
             + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8);
         if n_exts != 0 {
             header |= flag::Z;
@@ -133,6 +141,11 @@ where
             let e = ext::Timeout::new(to.as_millis() as u64);
             self.write(&mut *writer, (&e, n_exts != 0))?;
         }
+        if let Some(c) = ext_credits.as_ref() {
+            n_exts -= 1;
+            let e = ext::Credits::new(c.get() as u64);
+            self.write(&mut *writer, (&e, n_exts != 0))?;
+        }
         if ext_nodeid != &ext::NodeIdType::DEFAULT {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_nodeid, n_exts != 0))?;
@@ -187,6 +200,7 @@ where
         let mut ext_target = ext::QueryTarget::DEFAULT;
         let mut ext_limit = None;
         let mut ext_timeout = None;
+        let mut ext_credits = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -223,6 +237,11 @@ where
                     ext_timeout = Some(ext::TimeoutType::from_millis(to.value));
                     has_ext = ext;
                 }
+                ext::Credits::ID => {
+                    let (c, ext): (ext::Credits, bool) = eodec.read(&mut *reader)?;
+                    ext_credits = ext::CreditsType::new(c.value as u32);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Request", ext)?;
                 }
@@ -242,6 +261,226 @@ where
             ext_target,
             ext_budget: ext_limit,
             ext_timeout,
+            ext_credits,
+        })
+    }
+}
+
+// RequestCancel
+impl<W> WCodec<&RequestCancel, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &RequestCancel) -> Self::Output {
+        let RequestCancel {
+            id,
+            ext_qos,
+            ext_tstamp,
+        } = x;
+
+        // Header
+        let mut header = id::REQUEST_CANCEL;
+        let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8) + (ext_tstamp.is_some() as u8);
+        if n_exts != 0 {
+            header |= flag::Z;
+        }
+        self.write(&mut *writer, header)?;
+
+        // Body
+        self.write(&mut *writer, id)?;
+
+        // Extensions
+        if ext_qos != &ext::QoSType::DEFAULT {
+            n_exts -= 1;
+            self.write(&mut *writer, (*ext_qos, n_exts != 0))?;
+        }
+        if let Some(ts) = ext_tstamp.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (ts, n_exts != 0))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> RCodec<RequestCancel, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<RequestCancel, Self::Error> {
+        let header: u8 = self.read(&mut *reader)?;
+        let codec = Zenoh080Header::new(header);
+        codec.read(reader)
+    }
+}
+
+impl<R> RCodec<RequestCancel, &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<RequestCancel, Self::Error> {
+        if imsg::mid(self.header) != id::REQUEST_CANCEL {
+            return Err(DidntRead);
+        }
+
+        // Body
+        let bodec = Zenoh080Bounded::<RequestId>::new();
+        let id: RequestId = bodec.read(&mut *reader)?;
+
+        // Extensions
+        let mut ext_qos = ext::QoSType::DEFAULT;
+        let mut ext_tstamp = None;
+
+        let mut has_ext = imsg::has_flag(self.header, flag::Z);
+        while has_ext {
+            let ext: u8 = self.codec.read(&mut *reader)?;
+            let eodec = Zenoh080Header::new(ext);
+            match iext::eid(ext) {
+                ext::QoS::ID => {
+                    let (q, ext): (ext::QoSType, bool) = eodec.read(&mut *reader)?;
+                    ext_qos = q;
+                    has_ext = ext;
+                }
+                ext::Timestamp::ID => {
+                    let (t, ext): (ext::TimestampType, bool) = eodec.read(&mut *reader)?;
+                    ext_tstamp = Some(t);
+                    has_ext = ext;
+                }
+                _ => {
+                    has_ext = extension::skip(reader, "RequestCancel", ext)?;
+                }
+            }
+        }
+
+        Ok(RequestCancel {
+            id,
+            ext_qos,
+            ext_tstamp,
+        })
+    }
+}
+
+// RequestCredit
+impl<W> WCodec<&RequestCredit, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &RequestCredit) -> Self::Output {
+        let RequestCredit {
+            id,
+            credits,
+            ext_qos,
+            ext_tstamp,
+            ext_respid,
+        } = x;
+
+        // Header
+        let mut header = id::REQUEST_CREDIT;
+        let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8)
+            + (ext_tstamp.is_some() as u8)
+            + (ext_respid.is_some() as u8);
+        if n_exts != 0 {
+            header |= flag::Z;
+        }
+        self.write(&mut *writer, header)?;
+
+        // Body
+        self.write(&mut *writer, id)?;
+        self.write(&mut *writer, credits)?;
+
+        // Extensions
+        if ext_qos != &ext::QoSType::DEFAULT {
+            n_exts -= 1;
+            self.write(&mut *writer, (*ext_qos, n_exts != 0))?;
+        }
+        if let Some(ts) = ext_tstamp.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (ts, n_exts != 0))?;
+        }
+        if let Some(ri) = ext_respid.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (ri, n_exts != 0))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> RCodec<RequestCredit, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<RequestCredit, Self::Error> {
+        let header: u8 = self.read(&mut *reader)?;
+        let codec = Zenoh080Header::new(header);
+        codec.read(reader)
+    }
+}
+
+impl<R> RCodec<RequestCredit, &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<RequestCredit, Self::Error> {
+        if imsg::mid(self.header) != id::REQUEST_CREDIT {
+            return Err(DidntRead);
+        }
+
+        // Body
+        let bodec = Zenoh080Bounded::<RequestId>::new();
+        let id: RequestId = bodec.read(&mut *reader)?;
+        let bodec = Zenoh080Bounded::<u32>::new();
+        let credits: u32 = bodec.read(&mut *reader)?;
+
+        // Extensions
+        let mut ext_qos = ext::QoSType::DEFAULT;
+        let mut ext_tstamp = None;
+        let mut ext_respid = None;
+
+        let mut has_ext = imsg::has_flag(self.header, flag::Z);
+        while has_ext {
+            let ext: u8 = self.codec.read(&mut *reader)?;
+            let eodec = Zenoh080Header::new(ext);
+            match iext::eid(ext) {
+                ext::QoS::ID => {
+                    let (q, ext): (ext::QoSType, bool) = eodec.read(&mut *reader)?;
+                    ext_qos = q;
+                    has_ext = ext;
+                }
+                ext::Timestamp::ID => {
+                    let (t, ext): (ext::TimestampType, bool) = eodec.read(&mut *reader)?;
+                    ext_tstamp = Some(t);
+                    has_ext = ext;
+                }
+                ext::ResponderId::ID => {
+                    let (t, ext): (ext::ResponderIdType, bool) = eodec.read(&mut *reader)?;
+                    ext_respid = Some(t);
+                    has_ext = ext;
+                }
+                _ => {
+                    has_ext = extension::skip(reader, "RequestCredit", ext)?;
+                }
+            }
+        }
+
+        Ok(RequestCredit {
+            id,
+            credits,
+            ext_qos,
+            ext_tstamp,
+            ext_respid,
         })
     }
 }
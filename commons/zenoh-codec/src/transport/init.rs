@@ -52,7 +52,9 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         } = x;
 
         // Header
@@ -66,7 +68,9 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
-            + (*ext_patch != ext::PatchType::NONE) as u8;
+            + (ext_compression_algorithms.is_some() as u8)
+            + (*ext_patch != ext::PatchType::NONE) as u8
+            + (*ext_capabilities != ext::CapabilitiesType::NONE) as u8;
 
         #[cfg(feature = "shared-memory")]
         {
@@ -127,10 +131,18 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(algorithms) = ext_compression_algorithms.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (algorithms, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
         }
+        if *ext_capabilities != ext::CapabilitiesType::NONE {
+            n_exts -= 1;
+            self.write(&mut *writer, (*ext_capabilities, n_exts != 0))?;
+        }
 
         Ok(())
     }
@@ -192,7 +204,9 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_compression_algorithms = None;
         let mut ext_patch = ext::PatchType::NONE;
+        let mut ext_capabilities = ext::CapabilitiesType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -235,11 +249,21 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::CompressionAlgorithms::ID => {
+                    let (q, ext): (ext::CompressionAlgorithms, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithms = Some(q);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
                     has_ext = ext;
                 }
+                ext::Capabilities::ID => {
+                    let (c, ext): (ext::CapabilitiesType, bool) = eodec.read(&mut *reader)?;
+                    ext_capabilities = c;
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "InitSyn", ext)?;
                 }
@@ -260,7 +284,9 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         })
     }
 }
@@ -288,7 +314,9 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         } = x;
 
         // Header
@@ -302,7 +330,9 @@ where
             + (ext_mlink.is_some() as u8)
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
-            + (*ext_patch != ext::PatchType::NONE) as u8;
+            + (ext_compression_algorithms.is_some() as u8)
+            + (*ext_patch != ext::PatchType::NONE) as u8
+            + (*ext_capabilities != ext::CapabilitiesType::NONE) as u8;
 
         #[cfg(feature = "shared-memory")]
         {
@@ -366,10 +396,18 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (compression, n_exts != 0))?;
         }
+        if let Some(algorithms) = ext_compression_algorithms.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (algorithms, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
         }
+        if *ext_capabilities != ext::CapabilitiesType::NONE {
+            n_exts -= 1;
+            self.write(&mut *writer, (*ext_capabilities, n_exts != 0))?;
+        }
 
         Ok(())
     }
@@ -434,7 +472,9 @@ where
         let mut ext_mlink = None;
         let mut ext_lowlatency = None;
         let mut ext_compression = None;
+        let mut ext_compression_algorithms = None;
         let mut ext_patch = ext::PatchType::NONE;
+        let mut ext_capabilities = ext::CapabilitiesType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -477,11 +517,21 @@ where
                     ext_compression = Some(q);
                     has_ext = ext;
                 }
+                ext::CompressionAlgorithms::ID => {
+                    let (q, ext): (ext::CompressionAlgorithms, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithms = Some(q);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
                     has_ext = ext;
                 }
+                ext::Capabilities::ID => {
+                    let (c, ext): (ext::CapabilitiesType, bool) = eodec.read(&mut *reader)?;
+                    ext_capabilities = c;
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "InitAck", ext)?;
                 }
@@ -503,7 +553,9 @@ where
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         })
     }
 }
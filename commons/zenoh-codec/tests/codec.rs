@@ -587,6 +587,16 @@ fn codec_request() {
     run!(Request, Request::rand());
 }
 
+#[test]
+fn codec_request_cancel() {
+    run!(RequestCancel, RequestCancel::rand());
+}
+
+#[test]
+fn codec_request_credit() {
+    run!(RequestCredit, RequestCredit::rand());
+}
+
 #[test]
 fn codec_response() {
     run!(Response, Response::rand());
@@ -219,10 +219,15 @@ impl Default for QoSMulticastConf {
     }
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for CompressionUnicastConf {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            algorithms: vec![CompressionAlgorithmConf {
+                algorithm: CompressionAlgorithm::Lz4,
+                level: None,
+            }],
+        }
     }
 }
 
@@ -319,6 +324,34 @@ impl Default for AclConfig {
             rules: None,
             subjects: None,
             policies: None,
+            close_denied_transports: false,
+        }
+    }
+}
+
+impl Default for AuditConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            acl_decisions: AuditAclDecisions::Deny,
+            messages: AuditMessagesConf::default(),
+            sampling_rate: 1.0,
+            file: None,
+            publish: false,
+            queue_size: 4096,
+        }
+    }
+}
+
+// Make explicit the value and ignore clippy warning
+#[allow(clippy::derivable_impls)]
+impl Default for AuditMessagesConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            flows: None,
+            interfaces: None,
+            key_exprs: None,
         }
     }
 }
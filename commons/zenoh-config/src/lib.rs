@@ -27,7 +27,15 @@ pub mod wrappers;
 #[allow(unused_imports)]
 use std::convert::TryFrom; // This is a false positive from the rust analyser
 use std::{
-    any::Any, collections::HashSet, fmt, io::Read, net::SocketAddr, ops, path::Path, sync::Weak,
+    any::Any,
+    collections::HashSet,
+    fmt,
+    io::Read,
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU16,
+    ops,
+    path::Path,
+    sync::Weak,
 };
 
 use include::recursive_include;
@@ -77,13 +85,30 @@ impl Zeroize for SecretString {
 
 pub type SecretValue = Secret<SecretString>;
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum InterceptorFlow {
     Egress,
     Ingress,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    Lz4,
+    Zstd,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CompressionAlgorithmConf {
+    /// The compression algorithm: lz4, zstd
+    pub algorithm: CompressionAlgorithm,
+    /// The compression level, for algorithms supporting it (i.e. zstd).
+    /// The algorithm default level is used if the parameter is None.
+    pub level: Option<i32>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DownsamplingRuleConf {
     /// A list of key-expressions to which the downsampling will be applied.
@@ -104,6 +129,117 @@ pub struct DownsamplingItemConf {
     pub flow: InterceptorFlow,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadCompressionAction {
+    Compress,
+    Decompress,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadCompressionItemConf {
+    /// A list of interfaces to which the payload compression will be applied
+    /// Payload compression will be applied for all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// Payload compression flow direction: egress, ingress
+    pub flow: InterceptorFlow,
+    /// Payload compression action: compress, decompress
+    pub action: PayloadCompressionAction,
+    /// A list of key-expressions to which the payload compression will be applied.
+    pub key_exprs: Vec<OwnedKeyExpr>,
+    /// The compression algorithm used by the compress action: lz4, zstd (lz4 if None).
+    /// The decompress action relies on the algorithm tagged in the payload encoding.
+    pub algorithm: Option<CompressionAlgorithm>,
+    /// The compression level, for algorithms supporting it (i.e. zstd).
+    /// The algorithm default level is used if the parameter is None.
+    pub level: Option<i32>,
+    /// The maximum size in bytes of a decompressed payload (decompress action only, 16 MiB by default).
+    /// Payloads that would decompress beyond this size are dropped.
+    pub max_decompressed_size: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitStrategy {
+    /// Messages exceeding the limits are dropped
+    #[default]
+    Drop,
+    /// Messages exceeding the limits are queued and routed once the budget allows them
+    Delay,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitItemConf {
+    /// A list of interfaces to which the rate limit will be applied
+    /// Rate limit will be applied for all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// A list of subjects to which the rate limit will be applied, matched as ACL subjects.
+    /// Rate limit will be applied for all remotes if the parameter is None
+    pub subjects: Option<Vec<AclConfigSubjects>>,
+    /// Rate limit flow direction: egress, ingress
+    pub flow: InterceptorFlow,
+    /// A list of key-expressions to which the rate limit will be applied.
+    /// Rate limit will be applied for all key-expressions if the parameter is None
+    pub key_exprs: Option<Vec<OwnedKeyExpr>>,
+    /// The maximum number of messages per second
+    pub messages_per_second: Option<f64>,
+    /// The maximum number of payload bytes per second
+    pub bytes_per_second: Option<f64>,
+    /// The number of messages that can be sent in a burst (defaults to one second worth of messages)
+    pub burst_messages: Option<f64>,
+    /// The number of payload bytes that can be sent in a burst (defaults to one second worth of bytes)
+    pub burst_bytes: Option<f64>,
+    /// What to do with messages exceeding the limits: drop, delay
+    #[serde(default)]
+    pub strategy: RateLimitStrategy,
+    /// The maximum time in milliseconds a message is delayed before being dropped (delay strategy only, 1000 by default)
+    pub max_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TransportWeightConf {
+    /// The weight of the matching transports in the linkstate routing trees.
+    /// The lower the weight, the more the transport is preferred (default: 100).
+    pub weight: NonZeroU16,
+    /// A list of Zenoh IDs of the remotes to which the weight will be applied.
+    /// The weight will be applied for all remotes if the parameter is None
+    pub zids: Option<Vec<ZenohId>>,
+    /// A list of interfaces to which the weight will be applied.
+    /// The weight will be applied for all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// A list of endpoints, matched against the local and remote locators of the links,
+    /// to which the weight will be applied.
+    /// The weight will be applied for all endpoints if the parameter is None
+    pub endpoints: Option<Vec<EndPoint>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct KeyExprRemappingRuleConf {
+    /// The key-expression prefix to be replaced
+    pub from: OwnedKeyExpr,
+    /// The key-expression prefix replacing it
+    pub to: OwnedKeyExpr,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct KeyExprRemappingItemConf {
+    /// A list of interfaces to which the remapping will be applied
+    /// Remapping will be applied for all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// A list of subjects to which the remapping will be applied, matched as ACL subjects.
+    /// Remapping will be applied for all remotes if the parameter is None
+    pub subjects: Option<Vec<AclConfigSubjects>>,
+    /// Remapping flow direction: egress, ingress
+    pub flow: InterceptorFlow,
+    /// A list of prefix replacement rules, the first matching rule is applied.
+    pub rules: Vec<KeyExprRemappingRuleConf>,
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 pub struct AclConfigRule {
     pub id: String,
@@ -119,6 +255,14 @@ pub struct AclConfigSubjects {
     pub interfaces: Option<Vec<Interface>>,
     pub cert_common_names: Option<Vec<CertCommonName>>,
     pub usernames: Option<Vec<Username>>,
+    /// The Zenoh IDs of the remotes
+    pub zids: Option<Vec<ZenohId>>,
+    /// The fingerprints of the public keys of the remotes authenticated with the `pubkey` extension
+    pub pubkey_fingerprints: Option<Vec<PubKeyFingerprint>>,
+    /// The protocols of the links the remotes are connected with
+    pub link_protocols: Option<Vec<LinkProtocol>>,
+    /// The IP address ranges of the remotes, in CIDR notation
+    pub cidrs: Option<Vec<IpCidr>>,
 }
 
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -148,6 +292,155 @@ impl std::fmt::Display for Username {
     }
 }
 
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct PubKeyFingerprint(pub String);
+
+impl std::fmt::Display for PubKeyFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PubKeyFingerprint({})", self.0)
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkProtocol {
+    Tcp,
+    Udp,
+    Tls,
+    Quic,
+    UnixsockStream,
+    Ws,
+    Serial,
+    Unixpipe,
+    Vsock,
+}
+
+impl LinkProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkProtocol::Tcp => "tcp",
+            LinkProtocol::Udp => "udp",
+            LinkProtocol::Tls => "tls",
+            LinkProtocol::Quic => "quic",
+            LinkProtocol::UnixsockStream => "unixsock-stream",
+            LinkProtocol::Ws => "ws",
+            LinkProtocol::Serial => "serial",
+            LinkProtocol::Unixpipe => "unixpipe",
+            LinkProtocol::Vsock => "vsock",
+        }
+    }
+}
+
+impl std::str::FromStr for LinkProtocol {
+    type Err = zenoh_result::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "tcp" => LinkProtocol::Tcp,
+            "udp" => LinkProtocol::Udp,
+            "tls" => LinkProtocol::Tls,
+            "quic" => LinkProtocol::Quic,
+            "unixsock-stream" => LinkProtocol::UnixsockStream,
+            "ws" => LinkProtocol::Ws,
+            "serial" => LinkProtocol::Serial,
+            "unixpipe" => LinkProtocol::Unixpipe,
+            "vsock" => LinkProtocol::Vsock,
+            _ => bail!("Unknown link protocol: {s}"),
+        })
+    }
+}
+
+impl std::fmt::Display for LinkProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LinkProtocol({})", self.as_str())
+    }
+}
+
+/// A range of IP addresses, e.g. `192.168.1.0/24`, `fd00::/8` or `10.0.0.1`.
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Returns true if the given address belongs to the range.
+    ///
+    /// IPv4-mapped IPv6 addresses are matched as IPv4 addresses.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        let addr = match addr {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(*addr, IpAddr::V4),
+            IpAddr::V4(_) => *addr,
+        };
+        match (self.addr, addr) {
+            (IpAddr::V4(lhs), IpAddr::V4(rhs)) => {
+                Self::mask_v4(rhs, self.prefix_len) == u32::from(lhs)
+            }
+            (IpAddr::V6(lhs), IpAddr::V6(rhs)) => {
+                Self::mask_v6(rhs, self.prefix_len) == u128::from(lhs)
+            }
+            _ => false,
+        }
+    }
+
+    fn mask_v4(addr: std::net::Ipv4Addr, prefix_len: u8) -> u32 {
+        u32::from(addr) & u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0)
+    }
+
+    fn mask_v6(addr: std::net::Ipv6Addr, prefix_len: u8) -> u128 {
+        u128::from(addr) & u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0)
+    }
+}
+
+impl std::str::FromStr for IpCidr {
+    type Err = zenoh_result::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|e| zerror!("Invalid IP address in CIDR `{s}`: {e}"))?;
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => match prefix_len.parse::<u8>() {
+                Ok(prefix_len) if prefix_len <= max_prefix_len => prefix_len,
+                _ => bail!("Invalid prefix length in CIDR `{s}`: expected a number in [0, {max_prefix_len}]"),
+            },
+            None => max_prefix_len,
+        };
+        // Store the network address so that equal ranges compare equal
+        let addr = match addr {
+            IpAddr::V4(v4) => IpAddr::V4(Self::mask_v4(v4, prefix_len).into()),
+            IpAddr::V6(v6) => IpAddr::V6(Self::mask_v6(v6, prefix_len).into()),
+        };
+        Ok(Self { addr, prefix_len })
+    }
+}
+
+impl TryFrom<String> for IpCidr {
+    type Error = zenoh_result::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<IpCidr> for String {
+    fn from(value: IpCidr) -> Self {
+        format!("{}/{}", value.addr, value.prefix_len)
+    }
+}
+
+impl std::fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IpCidr({}/{})", self.addr, self.prefix_len)
+    }
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct AclConfigPolicyEntry {
     pub rules: Vec<String>,
@@ -156,6 +449,7 @@ pub struct AclConfigPolicyEntry {
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct PolicyRule {
+    pub rule_id: String,
     pub subject_id: usize,
     pub key_expr: String,
     pub message: AclMessage,
@@ -175,6 +469,11 @@ pub enum AclMessage {
     LivelinessToken,
     DeclareLivelinessSubscriber,
     LivelinessQuery,
+    DeclarePublisher,
+    DeclareQuerier,
+    Interest,
+    AdminSpaceRead,
+    AdminSpaceWrite,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, Hash, PartialEq)]
@@ -184,6 +483,29 @@ pub enum Permission {
     Deny,
 }
 
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAclDecisions {
+    /// No ACL decision is recorded
+    None,
+    /// Only the denied ACL decisions are recorded
+    #[default]
+    Deny,
+    /// All the ACL decisions are recorded
+    All,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AuditFileConf {
+    /// The path of the audit file
+    pub path: String,
+    /// The size in bytes above which the audit file is rotated (10 MiB if None)
+    pub max_size: Option<u64>,
+    /// The number of rotated audit files that are kept (5 if None)
+    pub max_files: Option<usize>,
+}
+
 pub trait ConfigValidator: Send + Sync {
     fn check_config(
         &self,
@@ -347,12 +669,20 @@ validated_struct::validator! {
                 /// connected to each other.
                 /// The failover brokering only works if gossip discovery is enabled.
                 peers_failover_brokering: Option<bool>,
+                /// The weights of the transports between routers, by remote, interface or endpoint.
+                /// The first matching item applies. The routing trees prefer the paths with the
+                /// lowest total weight.
+                transport_weights: Vec<TransportWeightConf>,
             },
             /// The routing strategy to use in peers and it's configuration.
             pub peer: #[derive(Default)]
             PeerRoutingConf {
                 /// The routing strategy to use in peers. ("peer_to_peer" or "linkstate").
                 mode: Option<String>,
+                /// The weights of the transports between linkstate peers, by remote, interface or endpoint.
+                /// The first matching item applies. The routing trees prefer the paths with the
+                /// lowest total weight.
+                transport_weights: Vec<TransportWeightConf>,
             },
             /// The interests-based routing configuration.
             /// This configuration applies regardless of the mode (router, peer or client).
@@ -405,6 +735,9 @@ validated_struct::validator! {
                     /// You must compile zenoh with "transport_compression" feature to be able to enable compression.
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
+                    /// The compression algorithms supported by this node, in order of preference.
+                    /// The first algorithm of this list that is also supported by the remote node is used. (default `[{ algorithm: "lz4" }]`).
+                    algorithms: Vec<CompressionAlgorithmConf>,
                 },
             },
             pub multicast: TransportMulticastConf {
@@ -510,6 +843,11 @@ validated_struct::validator! {
                     connect_certificate: Option<String>,
                     verify_name_on_connect: Option<bool>,
                     close_link_on_expiration: Option<bool>,
+                    /// Path to a PEM file of certificate revocation lists checked when verifying peer certificates
+                    certificate_revocation_list: Option<String>,
+                    /// Interval in milliseconds at which listeners check their certificate, key, root CA and CRL
+                    /// files for modifications and reload them (disabled if not set)
+                    certificate_reload_interval: Option<u64>,
                     /// Configure TCP write buffer size
                     pub so_sndbuf: Option<u32>,
                     /// Configure TCP read buffer size
@@ -554,16 +892,47 @@ validated_struct::validator! {
                     user: Option<String>,
                     password: Option<String>,
                     /// The path to a file containing the user password dictionary, a file containing `<user>:<password>`
+                    /// or `<user>:<verifier>` entries, the latter being generated by `zenohd usrpwd-verifier <user>`
                     dictionary_file: Option<String>,
                 } where (user_conf_validator),
                 pub pubkey: #[derive(Default)]
                 PubKeyConf {
+                    /// The key pair used by the authentication, either an RSA key pair in PKCS#1 format or an
+                    /// Ed25519 key pair in PKCS#8 format, provided in PEM format or as paths to PEM files
                     public_key_pem: Option<String>,
                     private_key_pem: Option<String>,
                     public_key_file: Option<String>,
                     private_key_file: Option<String>,
+                    /// The paths to further private keys in PEM format, presented along with the main one when
+                    /// opening a transport, e.g. while rotating keys
+                    additional_private_key_files: Option<Vec<String>>,
                     key_size: Option<usize>,
+                    /// The path to a file containing the public keys in PEM format allowed to open a transport
                     known_keys_file: Option<String>,
+                    /// The interval at which the known keys file is checked for changes, in milliseconds
+                    known_keys_reload_interval: Option<u64>,
+                    /// Whether to close the transports authenticated with a key removed from the known keys file
+                    close_revoked_transports: bool,
+                },
+                pub jwt: #[derive(Default)]
+                JwtConf {
+                    /// The bearer token, a JSON Web Token in compact form, presented when opening a transport
+                    token: Option<String>,
+                    /// The path to a file containing the bearer token presented when opening a transport
+                    token_file: Option<String>,
+                    /// The shared secret verifying the HS256 tokens presented by remotes
+                    secret: Option<String>,
+                    /// The RSA or Ed25519 public key verifying the RS256 or EdDSA tokens presented by remotes
+                    public_key_pem: Option<String>,
+                    public_key_file: Option<String>,
+                    /// The `iss` claim the tokens presented by remotes must hold
+                    issuer: Option<String>,
+                    /// The `aud` claim the tokens presented by remotes must hold
+                    audience: Option<String>,
+                    /// The claim used as username of the remote, `sub` by default
+                    username_claim: Option<String>,
+                    /// The clock skew tolerated when checking the expiry of the tokens, in seconds
+                    leeway: Option<u64>,
                 },
             },
 
@@ -595,6 +964,42 @@ validated_struct::validator! {
         /// Configuration of the downsampling.
         downsampling: Vec<DownsamplingItemConf>,
 
+        /// Configuration of the payload compression.
+        payload_compression: Vec<PayloadCompressionItemConf>,
+
+        /// Configuration of the rate limit.
+        rate_limit: Vec<RateLimitItemConf>,
+
+        /// Configuration of the key-expression remapping.
+        key_expr_remapping: Vec<KeyExprRemappingItemConf>,
+
+        /// Configuration of the audit log.
+        pub audit: AuditConf {
+            /// Enable the audit log
+            pub enabled: bool,
+            /// The ACL decisions to be recorded: none, deny, all
+            pub acl_decisions: AuditAclDecisions,
+            /// The recording of the messages flowing through the transports
+            pub messages: AuditMessagesConf {
+                pub enabled: bool,
+                /// The flows messages are recorded on: egress, ingress (both if None)
+                pub flows: Option<Vec<InterceptorFlow>>,
+                /// The interfaces messages are recorded on (all if None)
+                pub interfaces: Option<Vec<String>>,
+                /// The key-expressions messages are recorded on (all if None)
+                pub key_exprs: Option<Vec<OwnedKeyExpr>>,
+            },
+            /// The fraction of the allowed ACL decisions and of the messages that are recorded.
+            /// Denied ACL decisions are always recorded.
+            pub sampling_rate: f64 where (sampling_rate_validator),
+            /// The file records are written to, as JSON lines
+            pub file: Option<AuditFileConf>,
+            /// Whether records are published on `@/<zid>/<whatami>/audit/**`
+            pub publish: bool,
+            /// The maximum number of records waiting to be written, further records are dropped
+            pub queue_size: usize,
+        },
+
         ///Configuration of the access control (ACL)
         pub access_control: AclConfig {
             pub enabled: bool,
@@ -602,6 +1007,9 @@ validated_struct::validator! {
             pub rules: Option<Vec<AclConfigRule>>,
             pub subjects: Option<Vec<AclConfigSubjects>>,
             pub policies: Option<Vec<AclConfigPolicyEntry>>,
+            /// Whether to close the transports that are denied any message when the access control
+            /// configuration is changed at runtime.
+            pub close_denied_transports: bool,
         },
 
         /// A list of directories where plugins may be searched for if no `__path__` was specified for them.
@@ -712,6 +1120,29 @@ fn config_deser() {
         .unwrap(),
     )
     .unwrap_err());
+    let config = Config::from_deserializer(
+        &mut json5::Deserializer::from_str(
+            r#"{routing: { router: { transport_weights: [
+              { endpoints: ["tcp/192.168.1.1:7447"], weight: 10 },
+              { interfaces: ["wwan0"], weight: 1000 },
+            ]}}}"#,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let weights = config.routing().router().transport_weights();
+    assert_eq!(weights.len(), 2);
+    assert_eq!(weights[0].weight.get(), 10);
+    assert_eq!(weights[1].interfaces, Some(vec!["wwan0".to_string()]));
+    std::mem::drop(
+        Config::from_deserializer(
+            &mut json5::Deserializer::from_str(
+                r#"{routing: { router: { transport_weights: [{ weight: 0 }]}}}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err(),
+    );
     dbg!(Config::from_file("../../DEFAULT_CONFIG.json5").unwrap());
 }
 
@@ -877,10 +1308,40 @@ fn config_from_json() {
     println!("{}", serde_json::to_string_pretty(&config).unwrap());
 }
 
+#[test]
+fn ip_cidr() {
+    let cidr: IpCidr = "192.168.1.17/24".parse().unwrap();
+    assert_eq!(String::from(cidr), "192.168.1.0/24");
+    assert!(cidr.contains(&"192.168.1.1".parse().unwrap()));
+    assert!(cidr.contains(&"::ffff:192.168.1.1".parse().unwrap()));
+    assert!(!cidr.contains(&"192.168.2.1".parse().unwrap()));
+    assert!(!cidr.contains(&"fd00::1".parse().unwrap()));
+
+    let cidr: IpCidr = "fd00::/8".parse().unwrap();
+    assert!(cidr.contains(&"fd12::1".parse().unwrap()));
+    assert!(!cidr.contains(&"fe80::1".parse().unwrap()));
+
+    let cidr: IpCidr = "10.0.0.1".parse().unwrap();
+    assert!(cidr.contains(&"10.0.0.1".parse().unwrap()));
+    assert!(!cidr.contains(&"10.0.0.2".parse().unwrap()));
+    assert!("0.0.0.0/0"
+        .parse::<IpCidr>()
+        .unwrap()
+        .contains(&"1.2.3.4".parse().unwrap()));
+
+    assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+    assert!("10.0.0/8".parse::<IpCidr>().is_err());
+    assert!(serde_json::from_str::<IpCidr>(r#""fd00::/129""#).is_err());
+}
+
 fn sequence_number_resolution_validator(b: &Bits) -> bool {
     b <= &Bits::from(TransportSn::MAX)
 }
 
+fn sampling_rate_validator(rate: &f64) -> bool {
+    (0.0..=1.0).contains(rate)
+}
+
 fn queue_size_validator(q: &QueueSizeConf) -> bool {
     fn check(size: &usize) -> bool {
         (QueueSizeConf::MIN..=QueueSizeConf::MAX).contains(size)
@@ -13,6 +13,7 @@
 //
 use hmac::{Hmac, Mac};
 use sha3::{Digest, Sha3_256};
+use subtle::ConstantTimeEq;
 use zenoh_result::ZResult;
 
 pub fn sign(key: &[u8], data: &[u8]) -> ZResult<Vec<u8>> {
@@ -24,3 +25,16 @@ pub fn sign(key: &[u8], data: &[u8]) -> ZResult<Vec<u8>> {
 pub fn digest(data: &[u8]) -> Vec<u8> {
     Sha3_256::digest(data).as_slice().to_vec()
 }
+
+/// Derives a key from a password with PBKDF2, using HMAC-SHA3-256 as pseudorandom function.
+/// The derived key is as long as a SHA3-256 digest.
+pub fn pbkdf2(password: &[u8], salt: &[u8], iterations: u32) -> ZResult<Vec<u8>> {
+    let mut key = vec![0u8; Sha3_256::output_size()];
+    pbkdf2::pbkdf2::<Hmac<Sha3_256>>(password, salt, iterations, &mut key)?;
+    Ok(key)
+}
+
+/// Compares two digests or signatures in constant time.
+pub fn verify(expected: &[u8], actual: &[u8]) -> bool {
+    expected.ct_eq(actual).into()
+}
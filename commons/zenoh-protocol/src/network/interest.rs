@@ -147,6 +147,7 @@ pub struct Interest {
     pub ext_qos: ext::QoSType,
     pub ext_tstamp: Option<ext::TimestampType>,
     pub ext_nodeid: ext::NodeIdType,
+    pub ext_budget: Option<ext::BudgetType>,
 }
 
 /// The resolution of a RequestId
@@ -192,6 +193,10 @@ pub mod ext {
 
     pub type NodeId = zextz64!(0x3, true);
     pub type NodeIdType = crate::network::ext::NodeIdType<{ NodeId::ID }>;
+
+    // The maximum number of replies to a current interest
+    pub type Budget = zextz64!(0x4, false);
+    pub type BudgetType = core::num::NonZeroU32;
 }
 
 impl Interest {
@@ -225,6 +230,11 @@ impl Interest {
         let ext_qos = ext::QoSType::rand();
         let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
         let ext_nodeid = ext::NodeIdType::rand();
+        let ext_budget = if rng.gen_bool(0.5) {
+            ext::BudgetType::new(rng.gen())
+        } else {
+            None
+        };
 
         Self {
             id,
@@ -234,6 +244,7 @@ impl Interest {
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_budget,
         }
     }
 }
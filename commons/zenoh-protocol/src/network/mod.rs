@@ -27,7 +27,7 @@ pub use declare::{
 pub use interest::Interest;
 pub use oam::Oam;
 pub use push::Push;
-pub use request::{AtomicRequestId, Request, RequestId};
+pub use request::{AtomicRequestId, Request, RequestCancel, RequestCredit, RequestId};
 pub use response::{Response, ResponseFinal};
 
 use crate::core::{CongestionControl, Priority, Reliability};
@@ -42,6 +42,8 @@ pub mod id {
     pub const RESPONSE: u8 = 0x1b;
     pub const RESPONSE_FINAL: u8 = 0x1a;
     pub const INTEREST: u8 = 0x19;
+    pub const REQUEST_CANCEL: u8 = 0x18;
+    pub const REQUEST_CREDIT: u8 = 0x17;
 }
 
 #[repr(u8)]
@@ -78,6 +80,8 @@ pub enum NetworkBody {
     Interest(Interest),
     Declare(Declare),
     OAM(Oam),
+    RequestCancel(RequestCancel),
+    RequestCredit(RequestCredit),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -95,13 +99,15 @@ impl NetworkMessage {
 
         let mut rng = rand::thread_rng();
 
-        let body = match rng.gen_range(0..6) {
+        let body = match rng.gen_range(0..8) {
             0 => NetworkBody::Push(Push::rand()),
             1 => NetworkBody::Request(Request::rand()),
             2 => NetworkBody::Response(Response::rand()),
             3 => NetworkBody::ResponseFinal(ResponseFinal::rand()),
             4 => NetworkBody::Declare(Declare::rand()),
             5 => NetworkBody::OAM(Oam::rand()),
+            6 => NetworkBody::RequestCancel(RequestCancel::rand()),
+            7 => NetworkBody::RequestCredit(RequestCredit::rand()),
             _ => unreachable!(),
         };
 
@@ -123,6 +129,8 @@ impl NetworkMessage {
             NetworkBody::Interest(msg) => msg.ext_qos.is_express(),
             NetworkBody::Declare(msg) => msg.ext_qos.is_express(),
             NetworkBody::OAM(msg) => msg.ext_qos.is_express(),
+            NetworkBody::RequestCancel(msg) => msg.ext_qos.is_express(),
+            NetworkBody::RequestCredit(msg) => msg.ext_qos.is_express(),
         }
     }
 
@@ -140,6 +148,8 @@ impl NetworkMessage {
             NetworkBody::Interest(msg) => msg.ext_qos.get_congestion_control(),
             NetworkBody::Declare(msg) => msg.ext_qos.get_congestion_control(),
             NetworkBody::OAM(msg) => msg.ext_qos.get_congestion_control(),
+            NetworkBody::RequestCancel(msg) => msg.ext_qos.get_congestion_control(),
+            NetworkBody::RequestCredit(msg) => msg.ext_qos.get_congestion_control(),
         };
 
         cc == CongestionControl::Drop
@@ -155,6 +165,8 @@ impl NetworkMessage {
             NetworkBody::Interest(msg) => msg.ext_qos.get_priority(),
             NetworkBody::Declare(msg) => msg.ext_qos.get_priority(),
             NetworkBody::OAM(msg) => msg.ext_qos.get_priority(),
+            NetworkBody::RequestCancel(msg) => msg.ext_qos.get_priority(),
+            NetworkBody::RequestCredit(msg) => msg.ext_qos.get_priority(),
         }
     }
 }
@@ -170,6 +182,8 @@ impl fmt::Display for NetworkMessage {
             ResponseFinal(_) => write!(f, "ResponseFinal"),
             Interest(_) => write!(f, "Interest"),
             Declare(_) => write!(f, "Declare"),
+            RequestCancel(_) => write!(f, "RequestCancel"),
+            RequestCredit(_) => write!(f, "RequestCredit"),
         }
     }
 }
@@ -204,6 +218,18 @@ impl From<Request> for NetworkMessage {
     }
 }
 
+impl From<RequestCancel> for NetworkMessage {
+    fn from(cancel: RequestCancel) -> Self {
+        NetworkBody::RequestCancel(cancel).into()
+    }
+}
+
+impl From<RequestCredit> for NetworkMessage {
+    fn from(credit: RequestCredit) -> Self {
+        NetworkBody::RequestCredit(credit).into()
+    }
+}
+
 impl From<Response> for NetworkMessage {
     fn from(response: Response) -> Self {
         NetworkBody::Response(response).into()
@@ -61,6 +61,7 @@ pub struct Request {
     pub ext_target: ext::QueryTarget,
     pub ext_budget: Option<ext::BudgetType>,
     pub ext_timeout: Option<ext::TimeoutType>,
+    pub ext_credits: Option<ext::CreditsType>,
     pub payload: RequestBody,
 }
 
@@ -102,11 +103,27 @@ pub mod ext {
         All,
         /// Deliver the query to all queryables matching the query's key expression that are declared as complete.
         AllComplete,
+        /// Deliver the query to exactly one of the matching queryables, picked in turn.
+        RoundRobin,
+        /// Deliver the query to exactly one of the matching queryables, picked at random.
+        Random,
+        /// Deliver the query to exactly one of the matching queryables, picking the one with the
+        /// least queries awaiting their final response.
+        LeastOutstanding,
     }
 
     impl QueryTarget {
         pub const DEFAULT: Self = Self::BestMatching;
 
+        /// Returns `true` if the query is delivered to a single queryable picked among the
+        /// matching ones.
+        pub fn is_load_balanced(&self) -> bool {
+            matches!(
+                self,
+                QueryTarget::RoundRobin | QueryTarget::Random | QueryTarget::LeastOutstanding
+            )
+        }
+
         #[cfg(feature = "test")]
         pub fn rand() -> Self {
             use rand::prelude::*;
@@ -116,6 +133,9 @@ pub mod ext {
                 QueryTarget::All,
                 QueryTarget::AllComplete,
                 QueryTarget::BestMatching,
+                QueryTarget::RoundRobin,
+                QueryTarget::Random,
+                QueryTarget::LeastOutstanding,
             ]
             .choose(&mut rng)
             .unwrap()
@@ -129,6 +149,15 @@ pub mod ext {
     // The timeout of the request
     pub type Timeout = zextz64!(0x6, false);
     pub type TimeoutType = Duration;
+
+    // The initial number of responses each responder may send before waiting for the
+    // credits granted by the requester with RequestCredit messages
+    pub type Credits = zextz64!(0x7, false);
+    pub type CreditsType = NonZeroU32;
+
+    // The responder a RequestCredit grants its credits to, every responder when absent
+    pub type ResponderId = zextzbuf!(0x8, false);
+    pub type ResponderIdType = crate::network::ext::EntityGlobalIdType<{ ResponderId::ID }>;
 }
 
 impl Request {
@@ -156,6 +185,11 @@ impl Request {
         } else {
             None
         };
+        let ext_credits = if rng.gen_bool(0.5) {
+            NonZeroU32::new(rng.gen())
+        } else {
+            None
+        };
 
         Self {
             wire_expr,
@@ -167,6 +201,113 @@ impl Request {
             ext_target,
             ext_budget,
             ext_timeout,
+            ext_credits,
+        }
+    }
+}
+
+/// # RequestCancel message
+///
+/// ```text
+/// Flags:
+/// - X: Reserved
+/// - X: Reserved
+/// - Z: Extension      If Z==1 then at least one extension is present
+///
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// |Z|X|X|ReqCancel|
+/// +-+-+-+---------+
+/// ~ request_id:z32~  (*)
+/// +---------------+
+/// ~   [req_exts]  ~  if Z==1
+/// +---------------+
+///
+/// (*) The resolution of the request id is negotiated during the session establishment.
+///     This implementation limits the resolution to 32bit.
+/// ```
+///
+/// A RequestCancel is sent by the node that sent a [`Request`] when it is no longer interested
+/// in the responses, for the request to be dropped along its route. It is only sent to the
+/// nodes that negotiated the corresponding capability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestCancel {
+    pub id: RequestId,
+    pub ext_qos: ext::QoSType,
+    pub ext_tstamp: Option<ext::TimestampType>,
+}
+
+impl RequestCancel {
+    #[cfg(feature = "test")]
+    pub fn rand() -> Self {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let id: RequestId = rng.gen();
+        let ext_qos = ext::QoSType::rand();
+        let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
+
+        Self {
+            id,
+            ext_qos,
+            ext_tstamp,
+        }
+    }
+}
+
+/// # RequestCredit message
+///
+/// ```text
+/// Flags:
+/// - X: Reserved
+/// - X: Reserved
+/// - Z: Extension      If Z==1 then at least one extension is present
+///
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// |Z|X|X|ReqCredit|
+/// +-+-+-+---------+
+/// ~ request_id:z32~  (*)
+/// +---------------+
+/// ~  credits:z32  ~
+/// +---------------+
+/// ~   [req_exts]  ~  if Z==1
+/// +---------------+
+///
+/// (*) The resolution of the request id is negotiated during the session establishment.
+///     This implementation limits the resolution to 32bit.
+/// ```
+///
+/// A RequestCredit is sent by the node that sent a [`Request`] with a credits extension, to
+/// allow the responder identified by its responder id extension to send `credits` more
+/// responses. It is only sent to the nodes that negotiated the corresponding capability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestCredit {
+    pub id: RequestId,
+    pub credits: u32,
+    pub ext_qos: ext::QoSType,
+    pub ext_tstamp: Option<ext::TimestampType>,
+    pub ext_respid: Option<ext::ResponderIdType>,
+}
+
+impl RequestCredit {
+    #[cfg(feature = "test")]
+    pub fn rand() -> Self {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let id: RequestId = rng.gen();
+        let credits: u32 = rng.gen();
+        let ext_qos = ext::QoSType::rand();
+        let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
+        let ext_respid = rng.gen_bool(0.5).then(ext::ResponderIdType::rand);
+
+        Self {
+            id,
+            credits,
+            ext_qos,
+            ext_tstamp,
+            ext_respid,
         }
     }
 }
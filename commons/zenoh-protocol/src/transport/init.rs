@@ -131,7 +131,9 @@ pub struct InitSyn {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_compression_algorithms: Option<ext::CompressionAlgorithms>,
     pub ext_patch: ext::PatchType,
+    pub ext_capabilities: ext::CapabilitiesType,
 }
 
 // Extensions
@@ -166,6 +168,9 @@ pub mod ext {
     /// # Compression extension
     /// Used to negotiate the use of compression on the link
     pub type Compression = zextunit!(0x6, false);
+    /// Used to negotiate the compression algorithm to use on the link.
+    /// The value is a bitmask of the supported compression algorithms.
+    pub type CompressionAlgorithms = zextz64!(0x6, false);
 
     /// # Patch extension
     /// Used to negotiate the patch version of the protocol
@@ -173,6 +178,13 @@ pub mod ext {
     /// if >= 1, then fragmentation first/drop markers
     pub type Patch = zextz64!(0x7, false);
     pub type PatchType = crate::transport::ext::PatchType<{ Patch::ID }>;
+
+    /// # Capabilities extension
+    /// Used to negotiate the optional features of the protocol
+    /// if not present (or 0), then none of them is supported
+    /// the value is a bitmask of the supported features
+    pub type Capabilities = zextz64!(0x8, false);
+    pub type CapabilitiesType = crate::transport::ext::CapabilitiesType<{ Capabilities::ID }>;
 }
 
 impl InitSyn {
@@ -197,7 +209,9 @@ impl InitSyn {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_compression_algorithms = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
         let ext_patch = ext::PatchType::rand();
+        let ext_capabilities = ext::CapabilitiesType::rand();
 
         Self {
             version,
@@ -213,7 +227,9 @@ impl InitSyn {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         }
     }
 }
@@ -244,7 +260,9 @@ pub struct InitAck {
     pub ext_mlink: Option<ext::MultiLink>,
     pub ext_lowlatency: Option<ext::LowLatency>,
     pub ext_compression: Option<ext::Compression>,
+    pub ext_compression_algorithms: Option<ext::CompressionAlgorithms>,
     pub ext_patch: ext::PatchType,
+    pub ext_capabilities: ext::CapabilitiesType,
 }
 
 impl InitAck {
@@ -274,7 +292,9 @@ impl InitAck {
         let ext_mlink = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
         let ext_lowlatency = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
+        let ext_compression_algorithms = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
         let ext_patch = ext::PatchType::rand();
+        let ext_capabilities = ext::CapabilitiesType::rand();
 
         Self {
             version,
@@ -291,7 +311,9 @@ impl InitAck {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         }
     }
 }
@@ -349,4 +349,73 @@ pub mod ext {
             ZExtZ64::new(ext.0 as u64)
         }
     }
+
+    /// The optional features of the protocol supported by a node, as a bitmask.
+    ///
+    /// Unlike the patch version, each capability is negotiated independently of the others: the
+    /// capabilities of a transport are the ones advertised by both of its ends.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CapabilitiesType<const ID: u8>(u64);
+
+    impl<const ID: u8> CapabilitiesType<ID> {
+        pub const REQUEST_CANCEL: u64 = 1;
+        pub const REQUEST_CREDIT: u64 = 1 << 1;
+        pub const LOAD_BALANCED_QUERY_TARGETS: u64 = 1 << 2;
+        pub const LINK_WEIGHTS: u64 = 1 << 3;
+
+        pub const NONE: Self = Self(0);
+        pub const CURRENT: Self = Self(
+            Self::REQUEST_CANCEL
+                | Self::REQUEST_CREDIT
+                | Self::LOAD_BALANCED_QUERY_TARGETS
+                | Self::LINK_WEIGHTS,
+        );
+
+        pub const fn new(bits: u64) -> Self {
+            Self(bits)
+        }
+
+        pub const fn raw(self) -> u64 {
+            self.0
+        }
+
+        /// Returns the capabilities supported by both `self` and `other`.
+        pub const fn intersection(self, other: Self) -> Self {
+            Self(self.0 & other.0)
+        }
+
+        pub const fn has_request_cancel(&self) -> bool {
+            self.0 & Self::REQUEST_CANCEL != 0
+        }
+
+        pub const fn has_request_credit(&self) -> bool {
+            self.0 & Self::REQUEST_CREDIT != 0
+        }
+
+        pub const fn has_load_balanced_query_targets(&self) -> bool {
+            self.0 & Self::LOAD_BALANCED_QUERY_TARGETS != 0
+        }
+
+        pub const fn has_link_weights(&self) -> bool {
+            self.0 & Self::LINK_WEIGHTS != 0
+        }
+
+        #[cfg(feature = "test")]
+        pub fn rand() -> Self {
+            use rand::Rng;
+            Self(rand::thread_rng().gen())
+        }
+    }
+
+    impl<const ID: u8> From<ZExtZ64<ID>> for CapabilitiesType<ID> {
+        fn from(ext: ZExtZ64<ID>) -> Self {
+            Self(ext.value)
+        }
+    }
+
+    impl<const ID: u8> From<CapabilitiesType<ID>> for ZExtZ64<ID> {
+        fn from(ext: CapabilitiesType<ID>) -> Self {
+            ZExtZ64::new(ext.0)
+        }
+    }
 }
@@ -1,14 +1,14 @@
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 
 use rustls::{
     client::{
         danger::{ServerCertVerified, ServerCertVerifier},
-        verify_server_cert_signed_by_trust_anchor,
+        verify_server_cert_signed_by_trust_anchor, VerifierBuilderError, WebPkiServerVerifier,
     },
     crypto::{verify_tls12_signature, verify_tls13_signature},
-    pki_types::{CertificateDer, ServerName, UnixTime},
+    pki_types::{CertificateDer, CertificateRevocationListDer, ServerName, UnixTime},
     server::ParsedCertificate,
-    RootCertStore,
+    CertificateError, RootCertStore,
 };
 use webpki::ALL_VERIFICATION_ALGS;
 
@@ -16,14 +16,30 @@ impl ServerCertVerifier for WebPkiVerifierAnyServerName {
     /// Will verify the certificate is valid in the following ways:
     /// - Signed by a  trusted `RootCertStore` CA
     /// - Not Expired
+    /// - Not revoked (if CRLs were provided)
     fn verify_server_cert(
         &self,
         end_entity: &CertificateDer<'_>,
         intermediates: &[CertificateDer<'_>],
-        _server_name: &ServerName<'_>,
-        _ocsp_response: &[u8],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
         now: UnixTime,
     ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(verifier) = &self.crl_verifier {
+            // The server name is verified last, any other error is a verification failure
+            return match verifier.verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                ocsp_response,
+                now,
+            ) {
+                Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForName)) => {
+                    Ok(ServerCertVerified::assertion())
+                }
+                res => res,
+            };
+        }
         let cert = ParsedCertificate::try_from(end_entity)?;
         verify_server_cert_signed_by_trust_anchor(
             &cert,
@@ -75,6 +91,7 @@ impl ServerCertVerifier for WebPkiVerifierAnyServerName {
 #[derive(Debug)]
 pub struct WebPkiVerifierAnyServerName {
     roots: RootCertStore,
+    crl_verifier: Option<Arc<WebPkiServerVerifier>>,
 }
 
 #[allow(unreachable_pub)]
@@ -83,7 +100,26 @@ impl WebPkiVerifierAnyServerName {
     ///
     /// `roots` is the set of trust anchors to trust for issuing server certs.
     pub fn new(roots: RootCertStore) -> Self {
-        Self { roots }
+        Self {
+            roots,
+            crl_verifier: None,
+        }
+    }
+
+    /// Constructs a new `WebPkiVerifierAnyServerName` that also checks the revocation
+    /// status of the server end-entity certificate against the given `crls`.
+    pub fn with_crls(
+        roots: RootCertStore,
+        crls: Vec<CertificateRevocationListDer<'static>>,
+    ) -> Result<Self, VerifierBuilderError> {
+        let crl_verifier = WebPkiServerVerifier::builder(Arc::new(roots.clone()))
+            .with_crls(crls)
+            .only_check_end_entity_revocation()
+            .build()?;
+        Ok(Self {
+            roots,
+            crl_verifier: Some(crl_verifier),
+        })
     }
 }
 
@@ -207,3 +243,91 @@ pub mod expiration {
         }
     }
 }
+
+pub mod reload {
+    use std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, RwLock},
+        time::{Duration, SystemTime},
+    };
+
+    use tokio::time::{Interval, MissedTickBehavior};
+    use zenoh_core::{zread, zwrite};
+
+    use crate::ListenerCertificate;
+
+    /// Periodically checks the modification time of the files a listener loads its
+    /// certificates, keys and CRLs from.
+    #[derive(Debug)]
+    pub struct CertificateFilesWatcher {
+        files: Vec<(String, Option<SystemTime>)>,
+        interval: Option<Interval>,
+    }
+
+    impl CertificateFilesWatcher {
+        /// Records the current modification time of `files`, which are then checked every `period`.
+        /// No check is performed if `period` is `None`.
+        pub fn new<'a>(files: impl IntoIterator<Item = &'a str>, period: Option<Duration>) -> Self {
+            let files = files
+                .into_iter()
+                .map(|path| (path.to_string(), modified_time(path)))
+                .collect();
+            let interval = period.map(|period| {
+                let mut interval = tokio::time::interval(period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                interval
+            });
+            Self { files, interval }
+        }
+
+        /// Waits until at least one of the watched files has been modified, created or removed.
+        ///
+        /// Never completes if no file is watched or if periodic checks are disabled.
+        /// This method is cancel safe.
+        pub async fn modified(&mut self) {
+            match self.interval.as_mut() {
+                Some(interval) if !self.files.is_empty() => loop {
+                    interval.tick().await;
+                    let mut modified = false;
+                    for (path, last) in self.files.iter_mut() {
+                        let current = modified_time(path);
+                        if current != *last {
+                            tracing::debug!("Certificate file {} has been modified", path);
+                            *last = current;
+                            modified = true;
+                        }
+                    }
+                    if modified {
+                        return;
+                    }
+                },
+                _ => std::future::pending().await,
+            }
+        }
+    }
+
+    fn modified_time(path: &str) -> Option<SystemTime> {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// The certificates currently served by the listeners of a link manager, indexed by listener address.
+    #[derive(Clone, Debug, Default)]
+    pub struct ListenerCertificates(Arc<RwLock<HashMap<SocketAddr, ListenerCertificate>>>);
+
+    impl ListenerCertificates {
+        pub fn insert(&self, addr: SocketAddr, certificate: ListenerCertificate) {
+            zwrite!(self.0).insert(addr, certificate);
+        }
+
+        pub fn remove(&self, addr: &SocketAddr) {
+            zwrite!(self.0).remove(addr);
+        }
+
+        pub fn get_certificates(&self) -> Vec<ListenerCertificate> {
+            zread!(self.0).values().cloned().collect()
+        }
+    }
+}
@@ -17,7 +17,7 @@ use core::{
     hash::{Hash, Hasher},
     ops::Deref,
 };
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::SystemTime};
 
 use async_trait::async_trait;
 use serde::Serialize;
@@ -35,9 +35,24 @@ pub trait LinkManagerUnicastTrait: Send + Sync {
     async fn del_listener(&self, endpoint: &EndPoint) -> ZResult<()>;
     async fn get_listeners(&self) -> Vec<EndPoint>;
     async fn get_locators(&self) -> Vec<Locator>;
+    /// Returns the certificates currently served by the listeners of this link manager.
+    async fn get_certificates(&self) -> Vec<ListenerCertificate> {
+        vec![]
+    }
 }
 pub type NewLinkChannelSender = flume::Sender<LinkUnicast>;
 
+/// Certificate loaded by a listener of a certificate based link (e.g. TLS or QUIC).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListenerCertificate {
+    pub locator: Locator,
+    pub subject: String,
+    /// Minimum expiration time of the certificate chain
+    pub not_after: SystemTime,
+    /// Time at which the certificate was (re)loaded
+    pub loaded_at: SystemTime,
+}
+
 pub trait ConstructibleLinkManagerUnicast<T>: Sized {
     fn new(new_link_sender: NewLinkChannelSender, config: T) -> ZResult<Self>;
 }
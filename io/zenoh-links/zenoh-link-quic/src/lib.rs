@@ -116,4 +116,10 @@ pub mod config {
 
     pub const TLS_CLOSE_LINK_ON_EXPIRATION: &str = "close_link_on_expiration";
     pub const TLS_CLOSE_LINK_ON_EXPIRATION_DEFAULT: bool = false;
+
+    pub const TLS_CRL_FILE: &str = "crl_file";
+    pub const TLS_CRL_RAW: &str = "crl_raw";
+
+    /// The time duration in milliseconds between two checks of the listener certificate files.
+    pub const TLS_CERTIFICATE_RELOAD_INTERVAL_MS: &str = "certificate_reload_interval_ms";
 }
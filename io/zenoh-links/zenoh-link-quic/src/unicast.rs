@@ -16,7 +16,7 @@ use std::{
     fmt::{self, Debug},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use async_trait::async_trait;
@@ -31,9 +31,12 @@ use x509_parser::prelude::{FromDer, X509Certificate};
 use zenoh_core::zasynclock;
 use zenoh_link_commons::{
     get_ip_interface_names,
-    tls::expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+    tls::{
+        expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+        reload::{CertificateFilesWatcher, ListenerCertificates},
+    },
     LinkAuthId, LinkAuthType, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait,
-    ListenersUnicastIP, NewLinkChannelSender,
+    ListenerCertificate, ListenersUnicastIP, NewLinkChannelSender,
 };
 use zenoh_protocol::{
     core::{EndPoint, Locator},
@@ -236,6 +239,7 @@ impl fmt::Debug for LinkUnicastQuic {
 pub struct LinkManagerUnicastQuic {
     manager: NewLinkChannelSender,
     listeners: ListenersUnicastIP,
+    certificates: ListenerCertificates,
 }
 
 impl LinkManagerUnicastQuic {
@@ -243,6 +247,7 @@ impl LinkManagerUnicastQuic {
         Self {
             manager,
             listeners: ListenersUnicastIP::new(),
+            certificates: ListenerCertificates::default(),
         }
     }
 }
@@ -357,11 +362,9 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastQuic {
         let host = get_quic_host(&epaddr)?;
 
         // Server config
-        let mut server_crypto = TlsServerConfig::new(&epconf)
+        let server_crypto = TlsServerConfig::new(&epconf)
             .await
             .map_err(|e| zerror!("Cannot create a new QUIC listener on {addr}: {e}"))?;
-        server_crypto.server_config.alpn_protocols =
-            ALPN_QUIC_HTTP.iter().map(|&x| x.into()).collect();
 
         // Install ring based rustls CryptoProvider.
         rustls::crypto::ring::default_provider()
@@ -373,20 +376,8 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastQuic {
             // when there are multiple quic links, and all but the first execution will fail.
             .ok();
 
-        let quic_config: QuicServerConfig = server_crypto
-            .server_config
-            .try_into()
+        let server_config = get_quic_server_config(server_crypto.server_config)
             .map_err(|e| zerror!("Can not create a new QUIC listener on {addr}: {e}"))?;
-        let mut server_config = quinn::ServerConfig::with_crypto(Arc::new(quic_config));
-
-        // We do not accept unidireactional streams.
-        Arc::get_mut(&mut server_config.transport)
-            .unwrap()
-            .max_concurrent_uni_streams(0_u8.into());
-        // For the time being we only allow one bidirectional stream
-        Arc::get_mut(&mut server_config.transport)
-            .unwrap()
-            .max_concurrent_bidi_streams(1_u8.into());
 
         // Initialize the Endpoint
         let quic_endpoint = if let Some(iface) = server_crypto.bind_iface {
@@ -430,21 +421,31 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastQuic {
             endpoint.config(),
         )?;
 
+        let certificate = get_listener_certificate(&locator, &server_crypto.certificate_chain)?;
+        self.certificates.insert(local_addr, certificate);
+
         // Spawn the accept loop for the listener
         let token = self.listeners.token.child_token();
 
         let task = {
             let token = token.clone();
             let manager = self.manager.clone();
+            let endpoint = endpoint.clone();
+            let certificates = self.certificates.clone();
 
             async move {
-                accept_task(
+                let res = accept_task(
                     quic_endpoint,
                     token,
                     manager,
                     server_crypto.tls_close_link_on_expiration,
+                    endpoint,
+                    server_crypto.certificate_files,
+                    certificates.clone(),
                 )
-                .await
+                .await;
+                certificates.remove(&local_addr);
+                res
             }
         };
 
@@ -469,6 +470,27 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastQuic {
     async fn get_locators(&self) -> Vec<Locator> {
         self.listeners.get_locators()
     }
+
+    async fn get_certificates(&self) -> Vec<ListenerCertificate> {
+        self.certificates.get_certificates()
+    }
+}
+
+fn get_quic_server_config(mut server_config: rustls::ServerConfig) -> ZResult<quinn::ServerConfig> {
+    server_config.alpn_protocols = ALPN_QUIC_HTTP.iter().map(|&x| x.into()).collect();
+
+    let quic_config: QuicServerConfig = server_config.try_into()?;
+    let mut server_config = quinn::ServerConfig::with_crypto(Arc::new(quic_config));
+
+    // We do not accept unidireactional streams.
+    Arc::get_mut(&mut server_config.transport)
+        .unwrap()
+        .max_concurrent_uni_streams(0_u8.into());
+    // For the time being we only allow one bidirectional stream
+    Arc::get_mut(&mut server_config.transport)
+        .unwrap()
+        .max_concurrent_bidi_streams(1_u8.into());
+    Ok(server_config)
 }
 
 async fn accept_task(
@@ -476,6 +498,9 @@ async fn accept_task(
     token: CancellationToken,
     manager: NewLinkChannelSender,
     tls_close_link_on_expiration: bool,
+    endpoint: EndPoint,
+    mut certificate_files: CertificateFilesWatcher,
+    certificates: ListenerCertificates,
 ) -> ZResult<()> {
     async fn accept(acceptor: quinn::Accept<'_>) -> ZResult<quinn::Connection> {
         let qc = acceptor
@@ -502,6 +527,32 @@ async fn accept_task(
         tokio::select! {
             _ = token.cancelled() => break,
 
+            _ = certificate_files.modified() => {
+                // Only the following handshakes use the reloaded files, established links are kept
+                let epconf = endpoint.config();
+                let res = TlsServerConfig::new(&epconf).await.and_then(|config| {
+                    let certificate =
+                        get_listener_certificate(&endpoint.to_locator(), &config.certificate_chain)?;
+                    Ok((get_quic_server_config(config.server_config)?, certificate))
+                });
+                match res {
+                    Ok((server_config, certificate)) => {
+                        tracing::info!(
+                            "Reloaded QUIC certificates of listener on {:?}: {}",
+                            src_addr,
+                            certificate.subject,
+                        );
+                        quic_endpoint.set_server_config(Some(server_config));
+                        certificates.insert(src_addr, certificate);
+                    }
+                    Err(e) => tracing::warn!(
+                        "Can not reload QUIC certificates of listener on {:?}, keeping the current ones: {}",
+                        src_addr,
+                        e,
+                    ),
+                }
+            }
+
             res = accept(quic_endpoint.accept()) => {
                 match res {
                     Ok(quic_conn) => {
@@ -628,6 +679,30 @@ fn get_cert_chain_expiration(conn: &quinn::Connection) -> ZResult<Option<OffsetD
     Ok(link_expiration)
 }
 
+fn get_listener_certificate(
+    locator: &Locator,
+    cert_chain: &[rustls_pki_types::CertificateDer],
+) -> ZResult<ListenerCertificate> {
+    let mut not_after: Option<OffsetDateTime> = None;
+    for cert in cert_chain {
+        let (_, cert) = X509Certificate::from_der(cert.as_ref())?;
+        let cert_expiration = cert.validity().not_after.to_datetime();
+        not_after = not_after
+            .map(|current_min| current_min.min(cert_expiration))
+            .or(Some(cert_expiration));
+    }
+    let (Some(cert), Some(not_after)) = (cert_chain.first(), not_after) else {
+        bail!("No certificate found for QUIC listener {}", locator);
+    };
+    let (_, cert) = X509Certificate::from_der(cert.as_ref())?;
+    Ok(ListenerCertificate {
+        locator: locator.clone(),
+        subject: cert.subject().to_string(),
+        not_after: not_after.into(),
+        loaded_at: SystemTime::now(),
+    })
+}
+
 #[derive(Clone)]
 struct QuicAuthId {
     auth_value: Option<String>,
@@ -16,20 +16,24 @@ use std::{
     io,
     io::{BufReader, Cursor},
     net::SocketAddr,
+    str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 
 use rustls::{
-    pki_types::{CertificateDer, PrivateKeyDer, TrustAnchor},
+    client::{WantsClientCert, WebPkiServerVerifier},
+    pki_types::{CertificateDer, CertificateRevocationListDer, PrivateKeyDer, TrustAnchor},
     server::WebPkiClientVerifier,
     version::TLS13,
-    ClientConfig, RootCertStore, ServerConfig,
+    ClientConfig, ConfigBuilder, RootCertStore, ServerConfig, WantsVerifier,
 };
 use secrecy::ExposeSecret;
 use webpki::anchor_from_trusted_cert;
 use zenoh_config::Config as ZenohConfig;
 use zenoh_link_commons::{
-    tls::WebPkiVerifierAnyServerName, ConfigurationInspector, BIND_INTERFACE,
+    tls::{reload::CertificateFilesWatcher, WebPkiVerifierAnyServerName},
+    ConfigurationInspector, BIND_INTERFACE,
 };
 use zenoh_protocol::core::{
     endpoint::{Address, Config},
@@ -149,12 +153,24 @@ impl ConfigurationInspector<ZenohConfig> for TlsConfigurator {
             false => ps.push((TLS_CLOSE_LINK_ON_EXPIRATION, "false")),
         }
 
+        if let Some(crl) = c.certificate_revocation_list() {
+            ps.push((TLS_CRL_FILE, crl));
+        }
+
+        let reload_interval;
+        if let Some(interval) = c.certificate_reload_interval() {
+            reload_interval = interval.to_string();
+            ps.push((TLS_CERTIFICATE_RELOAD_INTERVAL_MS, &reload_interval));
+        }
+
         Ok(parameters::from_iter(ps.drain(..)))
     }
 }
 
 pub(crate) struct TlsServerConfig<'a> {
     pub(crate) server_config: ServerConfig,
+    pub(crate) certificate_chain: Vec<CertificateDer<'static>>,
+    pub(crate) certificate_files: CertificateFilesWatcher,
     pub(crate) tls_close_link_on_expiration: bool,
     pub(crate) bind_iface: Option<&'a str>,
 }
@@ -173,6 +189,25 @@ impl<'a> TlsServerConfig<'a> {
                 .map_err(|_| zerror!("Unknown close on expiration argument: {}", s))?,
             None => TLS_CLOSE_LINK_ON_EXPIRATION_DEFAULT,
         };
+        let certificate_reload_interval = config
+            .get(TLS_CERTIFICATE_RELOAD_INTERVAL_MS)
+            .map(u64::from_str)
+            .transpose()
+            .map_err(|_| zerror!("Unknown certificate reload interval argument"))?
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis);
+        // Record the state of the files before loading them to not miss any later modification
+        let certificate_files = CertificateFilesWatcher::new(
+            [
+                TLS_ROOT_CA_CERTIFICATE_FILE,
+                TLS_CRL_FILE,
+                TLS_LISTEN_PRIVATE_KEY_FILE,
+                TLS_LISTEN_CERTIFICATE_FILE,
+            ]
+            .into_iter()
+            .filter_map(|key| config.get(key)),
+            certificate_reload_interval,
+        );
         let tls_server_private_key = TlsServerConfig::load_tls_private_key(config).await?;
         let tls_server_certificate = TlsServerConfig::load_tls_certificate(config).await?;
 
@@ -220,19 +255,25 @@ impl<'a> TlsServerConfig<'a> {
                 || Err(zerror!("Missing root certificates while mTLS is enabled.")),
                 Ok,
             )?;
-            let client_auth = WebPkiClientVerifier::builder(root_cert_store.into()).build()?;
+            let client_auth = WebPkiClientVerifier::builder(root_cert_store.into())
+                .with_crls(load_crls(config)?)
+                .only_check_end_entity_revocation()
+                .build()?;
             ServerConfig::builder_with_protocol_versions(&[&TLS13])
                 .with_client_cert_verifier(client_auth)
-                .with_single_cert(certs, keys.remove(0))
+                .with_single_cert(certs.clone(), keys.remove(0))
                 .map_err(|e| zerror!(e))?
         } else {
             ServerConfig::builder()
                 .with_no_client_auth()
-                .with_single_cert(certs, keys.remove(0))
+                .with_single_cert(certs.clone(), keys.remove(0))
                 .map_err(|e| zerror!(e))?
         };
+
         Ok(TlsServerConfig {
             server_config: sc,
+            certificate_chain: certs,
+            certificate_files,
             tls_close_link_on_expiration,
             bind_iface: config.get(BIND_INTERFACE),
         })
@@ -301,6 +342,7 @@ impl<'a> TlsClientConfig<'a> {
             tracing::debug!("Loading user-generated certificates.");
             root_cert_store.extend(custom_root_cert.roots);
         }
+        let crls = load_crls(config)?;
 
         // Install ring based rustls CryptoProvider.
         rustls::crypto::ring::default_provider()
@@ -347,35 +389,22 @@ impl<'a> TlsClientConfig<'a> {
                 bail!("No private key found for TLS client.");
             }
 
-            let builder = ClientConfig::builder_with_protocol_versions(&[&TLS13]);
-
-            if tls_server_name_verification {
-                builder
-                    .with_root_certificates(root_cert_store)
-                    .with_client_auth_cert(certs, keys.remove(0))
-            } else {
-                builder
-                    .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
-                    .with_client_auth_cert(certs, keys.remove(0))
-            }
+            with_server_verifier(
+                ClientConfig::builder_with_protocol_versions(&[&TLS13]),
+                root_cert_store,
+                crls,
+                tls_server_name_verification,
+            )?
+            .with_client_auth_cert(certs, keys.remove(0))
             .map_err(|e| zerror!("Bad certificate/key: {}", e))?
         } else {
-            let builder = ClientConfig::builder();
-            if tls_server_name_verification {
-                builder
-                    .with_root_certificates(root_cert_store)
-                    .with_no_client_auth()
-            } else {
-                builder
-                    .dangerous()
-                    .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
-                        root_cert_store,
-                    )))
-                    .with_no_client_auth()
-            }
+            with_server_verifier(
+                ClientConfig::builder(),
+                root_cert_store,
+                crls,
+                tls_server_name_verification,
+            )?
+            .with_no_client_auth()
         };
         Ok(TlsClientConfig {
             client_config: cc,
@@ -473,6 +502,60 @@ async fn load_tls_certificate(
     Err(zerror!("Missing tls certificates.").into())
 }
 
+/// Sets the verifier of the server certificates, checking their revocation status if `crls` is not empty.
+fn with_server_verifier(
+    builder: ConfigBuilder<ClientConfig, WantsVerifier>,
+    root_cert_store: RootCertStore,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+    server_name_verification: bool,
+) -> ZResult<ConfigBuilder<ClientConfig, WantsClientCert>> {
+    let builder = match (server_name_verification, crls.is_empty()) {
+        (true, true) => builder.with_root_certificates(root_cert_store),
+        (true, false) => builder.with_webpki_verifier(
+            WebPkiServerVerifier::builder(Arc::new(root_cert_store))
+                .with_crls(crls)
+                .only_check_end_entity_revocation()
+                .build()?,
+        ),
+        (false, true) => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::new(
+                root_cert_store,
+            ))),
+        (false, false) => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(WebPkiVerifierAnyServerName::with_crls(
+                root_cert_store,
+                crls,
+            )?)),
+    };
+    Ok(builder)
+}
+
+fn process_crls(pem: &mut dyn io::BufRead) -> ZResult<Vec<CertificateRevocationListDer<'static>>> {
+    let crls: Vec<CertificateRevocationListDer> = rustls_pemfile::crls(pem)
+        .collect::<Result<_, _>>()
+        .map_err(|err| zerror!("Error processing CRLs: {err}."))?;
+    if crls.is_empty() {
+        bail!("No CRL found in the certificate revocation list.");
+    }
+    Ok(crls)
+}
+
+fn load_crls(config: &Config<'_>) -> ZResult<Vec<CertificateRevocationListDer<'static>>> {
+    if let Some(value) = config.get(TLS_CRL_RAW) {
+        let mut pem = BufReader::new(value.as_bytes());
+        return process_crls(&mut pem);
+    }
+
+    if let Some(filename) = config.get(TLS_CRL_FILE) {
+        let mut pem =
+            BufReader::new(File::open(filename).map_err(|e| zerror!("Invalid CRL file: {}", e))?);
+        return process_crls(&mut pem);
+    }
+    Ok(vec![])
+}
+
 fn load_trust_anchors(config: &Config<'_>) -> ZResult<Option<RootCertStore>> {
     let mut root_cert_store = RootCertStore::empty();
     if let Some(value) = config.get(TLS_ROOT_CA_CERTIFICATE_RAW) {
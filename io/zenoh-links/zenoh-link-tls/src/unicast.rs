@@ -17,7 +17,7 @@ use std::{
     fmt::{self, Debug},
     net::SocketAddr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use async_trait::async_trait;
@@ -33,15 +33,18 @@ use x509_parser::prelude::{FromDer, X509Certificate};
 use zenoh_core::zasynclock;
 use zenoh_link_commons::{
     get_ip_interface_names,
-    tls::expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+    tls::{
+        expiration::{LinkCertExpirationManager, LinkWithCertExpiration},
+        reload::{CertificateFilesWatcher, ListenerCertificates},
+    },
     LinkAuthId, LinkAuthType, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait,
-    ListenersUnicastIP, NewLinkChannelSender,
+    ListenerCertificate, ListenersUnicastIP, NewLinkChannelSender,
 };
 use zenoh_protocol::{
     core::{EndPoint, Locator},
     transport::BatchSize,
 };
-use zenoh_result::{zerror, ZResult};
+use zenoh_result::{bail, zerror, ZResult};
 
 use crate::{
     utils::{get_tls_addr, get_tls_host, get_tls_server_name, TlsClientConfig, TlsServerConfig},
@@ -303,6 +306,7 @@ impl fmt::Debug for LinkUnicastTls {
 pub struct LinkManagerUnicastTls {
     manager: NewLinkChannelSender,
     listeners: ListenersUnicastIP,
+    certificates: ListenerCertificates,
 }
 
 impl LinkManagerUnicastTls {
@@ -310,6 +314,7 @@ impl LinkManagerUnicastTls {
         Self {
             manager,
             listeners: ListenersUnicastIP::new(),
+            certificates: ListenerCertificates::default(),
         }
     }
 }
@@ -406,6 +411,22 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
 
         let local_port = local_addr.port();
 
+        // Update the endpoint locator address
+        let locator = Locator::new(
+            endpoint.protocol(),
+            format!("{host}:{local_port}"),
+            endpoint.metadata(),
+        )?;
+        let endpoint = EndPoint::new(
+            locator.protocol(),
+            locator.address(),
+            locator.metadata(),
+            endpoint.config(),
+        )?;
+
+        let certificate = get_listener_certificate(&locator, &tls_server_config.certificate_chain)?;
+        self.certificates.insert(local_addr, certificate);
+
         // Initialize the TlsAcceptor
         let token = self.listeners.token.child_token();
 
@@ -413,32 +434,27 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
             let acceptor = TlsAcceptor::from(Arc::new(tls_server_config.server_config));
             let token = token.clone();
             let manager = self.manager.clone();
+            let endpoint = endpoint.clone();
+            let certificates = self.certificates.clone();
 
             async move {
-                accept_task(
+                let res = accept_task(
                     socket,
                     acceptor,
                     token,
                     manager,
                     tls_server_config.tls_handshake_timeout,
                     tls_server_config.tls_close_link_on_expiration,
+                    endpoint,
+                    tls_server_config.certificate_files,
+                    certificates.clone(),
                 )
-                .await
+                .await;
+                certificates.remove(&local_addr);
+                res
             }
         };
 
-        // Update the endpoint locator address
-        let locator = Locator::new(
-            endpoint.protocol(),
-            format!("{host}:{local_port}"),
-            endpoint.metadata(),
-        )?;
-        let endpoint = EndPoint::new(
-            locator.protocol(),
-            locator.address(),
-            locator.metadata(),
-            endpoint.config(),
-        )?;
         self.listeners
             .add_listener(endpoint, local_addr, task, token)
             .await?;
@@ -459,8 +475,13 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
     async fn get_locators(&self) -> Vec<Locator> {
         self.listeners.get_locators()
     }
+
+    async fn get_certificates(&self) -> Vec<ListenerCertificate> {
+        self.certificates.get_certificates()
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn accept_task(
     socket: TcpListener,
     acceptor: TlsAcceptor,
@@ -468,6 +489,9 @@ async fn accept_task(
     manager: NewLinkChannelSender,
     tls_handshake_timeout: Duration,
     tls_close_link_on_expiration: bool,
+    endpoint: EndPoint,
+    mut certificate_files: CertificateFilesWatcher,
+    certificates: ListenerCertificates,
 ) -> ZResult<()> {
     let src_addr = socket.local_addr().map_err(|e| {
         let e = zerror!("Can not accept TLS connections: {}", e);
@@ -484,6 +508,32 @@ async fn accept_task(
         tokio::select! {
             _ = token.cancelled() => break,
 
+            _ = certificate_files.modified() => {
+                // Only the following handshakes use the reloaded files, established links are kept
+                let epconf = endpoint.config();
+                let res = TlsServerConfig::new(&epconf).await.and_then(|config| {
+                    let certificate =
+                        get_listener_certificate(&endpoint.to_locator(), &config.certificate_chain)?;
+                    Ok((config.server_config, certificate))
+                });
+                match res {
+                    Ok((server_config, certificate)) => {
+                        tracing::info!(
+                            "Reloaded TLS certificates of listener on {:?}: {}",
+                            src_addr,
+                            certificate.subject,
+                        );
+                        listener.replace_acceptor(TlsAcceptor::from(Arc::new(server_config)));
+                        certificates.insert(src_addr, certificate);
+                    }
+                    Err(e) => tracing::warn!(
+                        "Can not reload TLS certificates of listener on {:?}, keeping the current ones: {}",
+                        src_addr,
+                        e,
+                    ),
+                }
+            }
+
             res = listener.accept() => {
                 match res {
                     Ok((tls_stream, dst_addr)) => {
@@ -614,6 +664,24 @@ fn get_cert_chain_expiration(
     Ok(link_expiration)
 }
 
+fn get_listener_certificate(
+    locator: &Locator,
+    cert_chain: &[rustls_pki_types::CertificateDer],
+) -> ZResult<ListenerCertificate> {
+    let Some(cert) = cert_chain.first() else {
+        bail!("No certificate found for TLS listener {}", locator);
+    };
+    let (_, cert) = X509Certificate::from_der(cert.as_ref())?;
+    let not_after = get_cert_chain_expiration(&Some(cert_chain))?
+        .ok_or_else(|| zerror!("No certificate found for TLS listener {}", locator))?;
+    Ok(ListenerCertificate {
+        locator: locator.clone(),
+        subject: cert.subject().to_string(),
+        not_after: not_after.into(),
+        loaded_at: SystemTime::now(),
+    })
+}
+
 struct TlsAuthId {
     auth_value: Option<String>,
 }
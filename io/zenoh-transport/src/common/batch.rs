@@ -29,7 +29,7 @@ use zenoh_protocol::{
 };
 use zenoh_result::{zerror, ZResult};
 #[cfg(feature = "transport_compression")]
-use {std::sync::Arc, zenoh_protocol::common::imsg};
+use {std::sync::Arc, zenoh_config::CompressionAlgorithm, zenoh_protocol::common::imsg};
 
 const L_LEN: usize = (BatchSize::BITS / 8) as usize;
 const H_LEN: usize = BatchHeader::SIZE;
@@ -77,13 +77,66 @@ macro_rules! zsplit_mut {
     }};
 }
 
+// Batch compression
+#[cfg(feature = "transport_compression")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BatchCompression {
+    Lz4,
+    Zstd { level: i32 },
+}
+
+#[cfg(feature = "transport_compression")]
+impl BatchCompression {
+    pub fn new(algorithm: CompressionAlgorithm, level: Option<i32>) -> Self {
+        match algorithm {
+            CompressionAlgorithm::Lz4 => Self::Lz4,
+            CompressionAlgorithm::Zstd => Self::Zstd {
+                level: level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL),
+            },
+        }
+    }
+
+    pub const fn algorithm(&self) -> CompressionAlgorithm {
+        match self {
+            Self::Lz4 => CompressionAlgorithm::Lz4,
+            Self::Zstd { .. } => CompressionAlgorithm::Zstd,
+        }
+    }
+
+    /// The size of the support buffer required to compress `len` bytes in the worst case.
+    pub fn max_output_size(&self, len: usize) -> usize {
+        match self {
+            Self::Lz4 => lz4_flex::block::get_maximum_output_size(len),
+            Self::Zstd { .. } => zstd::zstd_safe::compress_bound(len),
+        }
+    }
+
+    fn compress(&self, input: &[u8], output: &mut [u8]) -> ZResult<usize> {
+        match self {
+            Self::Lz4 => lz4_flex::block::compress_into(input, output)
+                .map_err(|e| zerror!("Compression error: {e}").into()),
+            Self::Zstd { level } => zstd::bulk::compress_to_buffer(input, output, *level)
+                .map_err(|e| zerror!("Compression error: {e}").into()),
+        }
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> ZResult<usize> {
+        match self {
+            Self::Lz4 => lz4_flex::block::decompress_into(input, output)
+                .map_err(|e| zerror!("Decompression error: {e}").into()),
+            Self::Zstd { .. } => zstd::bulk::decompress_to_buffer(input, output)
+                .map_err(|e| zerror!("Decompression error: {e}").into()),
+        }
+    }
+}
+
 // Batch config
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct BatchConfig {
     pub mtu: BatchSize,
     pub is_streamed: bool,
     #[cfg(feature = "transport_compression")]
-    pub is_compression: bool,
+    pub compression: Option<BatchCompression>,
 }
 
 impl Default for BatchConfig {
@@ -92,7 +145,7 @@ impl Default for BatchConfig {
             mtu: BatchSize::MAX,
             is_streamed: false,
             #[cfg(feature = "transport_compression")]
-            is_compression: false,
+            compression: None,
         }
     }
 }
@@ -105,7 +158,7 @@ impl BatchConfig {
         }
         #[cfg(feature = "transport_compression")]
         {
-            self.is_compression
+            self.compression.is_some()
         }
     }
 
@@ -116,7 +169,8 @@ impl BatchConfig {
         }
         #[cfg(feature = "transport_compression")]
         {
-            self.is_compression
+            self.compression
+                .is_some()
                 .then_some(BatchHeader::new(BatchHeader::COMPRESSION))
         }
     }
@@ -155,12 +209,18 @@ impl BatchHeader {
 #[derive(Clone, Copy, Debug, Default)]
 pub struct WBatchStats {
     pub t_msgs: usize,
+    #[cfg(feature = "transport_compression")]
+    pub is_compressed: bool,
 }
 
 #[cfg(feature = "stats")]
 impl WBatchStats {
     fn clear(&mut self) {
         self.t_msgs = 0;
+        #[cfg(feature = "transport_compression")]
+        {
+            self.is_compressed = false;
+        }
     }
 }
 
@@ -322,6 +382,11 @@ impl WBatch {
 
     #[cfg(feature = "transport_compression")]
     fn compress(&mut self, support: &mut BBuf) -> ZResult<Finalize> {
+        let compression = self
+            .config
+            .compression
+            .ok_or_else(|| zerror!("Compression not configured"))?;
+
         // Write the initial bytes for the batch
         support.clear();
         Self::init(support, &self.config);
@@ -330,17 +395,24 @@ impl WBatch {
         let (_length, _header, payload) = Self::split(self.buffer.as_slice(), &self.config);
         let mut writer = support.writer();
         // SAFETY: assertion ensures `with_slot` precondition
-        unsafe {
+        let res = unsafe {
             writer.with_slot(writer.remaining(), |b| {
-                let len = lz4_flex::block::compress_into(payload, b).unwrap_or(0);
+                // A failed compression (e.g. not enough space) writes nothing and
+                // falls back on sending the uncompressed batch below
+                let len = compression.compress(payload, b).unwrap_or(0);
                 assert!(len <= b.len());
                 len
             })
-        }
-        .map_err(|_| zerror!("Compression error"))?;
+        };
+        res.map_err(|_| zerror!("Compression error"))?;
 
         // Verify whether the resulting compressed data is smaller than the initial input
-        if support.len() < self.buffer.len() {
+        let (_l, _h, p) = Self::split(support.as_slice(), &self.config);
+        if !p.is_empty() && support.len() < self.buffer.len() {
+            #[cfg(feature = "stats")]
+            {
+                self.stats.is_compressed = true;
+            }
             Ok(Finalize::Buffer)
         } else {
             // Keep the original uncompressed buffer and unset the compression flag from the header
@@ -490,9 +562,12 @@ impl RBatch {
     where
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
     {
+        let compression = self
+            .config
+            .compression
+            .ok_or_else(|| zerror!("Compression not configured"))?;
         let mut into = (buff)();
-        let n = lz4_flex::block::decompress_into(payload, into.as_mut())
-            .map_err(|_| zerror!("Decompression error"))?;
+        let n = compression.decompress(payload, into.as_mut())?;
         let zslice = ZSlice::new(Arc::new(into), 0, n)
             .map_err(|_| zerror!("Invalid decompression buffer length"))?;
         Ok(zslice)
@@ -560,7 +635,13 @@ mod tests {
                     mtu: BatchSize::MAX,
                     is_streamed: rng.gen_bool(0.5),
                     #[cfg(feature = "transport_compression")]
-                    is_compression: rng.gen_bool(0.5),
+                    compression: match rng.gen_range(0..3) {
+                        0 => None,
+                        1 => Some(BatchCompression::Lz4),
+                        _ => Some(BatchCompression::Zstd {
+                            level: rng.gen_range(1..=9),
+                        }),
+                    },
                 };
                 let mut wbatch = WBatch::new(config);
                 wbatch.encode(&msg_in).unwrap();
@@ -568,9 +649,9 @@ mod tests {
 
                 let mut buffer = zcondfeat!(
                     "transport_compression",
-                    config.is_compression.then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(wbatch.as_slice().len()),
-                    )),
+                    config
+                        .compression
+                        .map(|c| BBuf::with_capacity(c.max_output_size(wbatch.as_slice().len()))),
                     None
                 );
 
@@ -601,7 +682,7 @@ mod tests {
             mtu: BatchSize::MAX,
             is_streamed: false,
             #[cfg(feature = "transport_compression")]
-            is_compression: false,
+            compression: None,
         };
         let mut batch = WBatch::new(config);
 
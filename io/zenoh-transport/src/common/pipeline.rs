@@ -954,6 +954,8 @@ mod tests {
     use zenoh_result::ZResult;
 
     use super::*;
+    #[cfg(feature = "transport_compression")]
+    use crate::common::batch::BatchCompression;
 
     const SLEEP: Duration = Duration::from_millis(100);
     const TIMEOUT: Duration = Duration::from_secs(60);
@@ -963,7 +965,7 @@ mod tests {
             mtu: BatchSize::MAX,
             is_streamed: true,
             #[cfg(feature = "transport_compression")]
-            is_compression: true,
+            compression: Some(BatchCompression::Lz4),
         },
         queue_size: [1; Priority::NUM],
         batching_enabled: true,
@@ -977,7 +979,7 @@ mod tests {
             mtu: BatchSize::MAX,
             is_streamed: false,
             #[cfg(feature = "transport_compression")]
-            is_compression: false,
+            compression: None,
         },
         queue_size: [1; Priority::NUM],
         batching_enabled: true,
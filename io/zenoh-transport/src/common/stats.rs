@@ -178,6 +178,14 @@ stats_struct! {
     }
 }
 
+stats_struct! {
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct CompressionStats {
+        pub lz4,
+        pub zstd,
+    }
+}
+
 stats_struct! {
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct TransportStats {
@@ -197,6 +205,10 @@ stats_struct! {
         # TYPE "counter"
         pub tx_n_dropped,
 
+        # HELP "Counter of sent compressed batches per compression algorithm."
+        # TYPE "counter"
+        pub tx_compressed_batches CompressionStats,
+
         # HELP "Counter of sent zenoh put messages."
         # TYPE "counter"
         pub tx_z_put_msgs DiscriminatedStats,
@@ -278,3 +290,19 @@ stats_struct! {
         pub rx_z_reply_pl_bytes DiscriminatedStats,
     }
 }
+
+#[cfg(feature = "transport_compression")]
+impl TransportStats {
+    pub(crate) fn inc_tx_compressed_batches(&self, batch: &crate::common::batch::WBatch) {
+        use crate::common::batch::BatchCompression;
+
+        if !batch.stats.is_compressed {
+            return;
+        }
+        match batch.config.compression {
+            Some(BatchCompression::Lz4) => self.tx_compressed_batches.inc_lz4(1),
+            Some(BatchCompression::Zstd { .. }) => self.tx_compressed_batches.inc_zstd(1),
+            None => {}
+        }
+    }
+}
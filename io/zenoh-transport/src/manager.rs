@@ -453,6 +453,9 @@ impl TransportManager {
                 }
             });
 
+        #[cfg(feature = "auth_pubkey")]
+        this.watch_known_keys();
+
         this
     }
 
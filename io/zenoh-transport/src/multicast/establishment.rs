@@ -22,6 +22,8 @@ use zenoh_protocol::{
 };
 use zenoh_result::{bail, ZResult};
 
+#[cfg(feature = "transport_compression")]
+use crate::common::batch::BatchCompression;
 use crate::{
     common::{batch::BatchConfig, seq_num},
     multicast::{
@@ -66,8 +68,13 @@ pub(crate) async fn open_link(
     let config = TransportLinkMulticastConfig {
         batch: BatchConfig {
             mtu: link.get_mtu(),
+            // Multicast does not negotiate the compression algorithm, lz4 is always used
             #[cfg(feature = "transport_compression")]
-            is_compression: manager.config.multicast.is_compression,
+            compression: manager
+                .config
+                .multicast
+                .is_compression
+                .then_some(BatchCompression::Lz4),
             ..Default::default()
         },
     };
@@ -71,12 +71,9 @@ impl TransportLinkMulticast {
             inner: self.clone(),
             buffer: zcondfeat!(
                 "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
+                self.config.batch.compression.map(|c| BBuf::with_capacity(
+                    c.max_output_size(self.config.batch.mtu as usize)
+                )),
                 None
             ),
         }
@@ -442,6 +439,8 @@ async fn tx_task(
                         {
                             stats.inc_tx_t_msgs(batch.stats.t_msgs);
                             stats.inc_tx_bytes(batch.len() as usize);
+                            #[cfg(feature = "transport_compression")]
+                            stats.inc_tx_compressed_batches(&batch);
                         }
                         // Reinsert the batch into the queue
                         pipeline.refill(batch, priority);
@@ -464,6 +463,8 @@ async fn tx_task(
                             {
                                 stats.inc_tx_t_msgs(b.stats.t_msgs);
                                 stats.inc_tx_bytes(b.len() as usize);
+                                #[cfg(feature = "transport_compression")]
+                                stats.inc_tx_compressed_batches(&b);
                             }
                         }
                         break;
@@ -75,6 +75,8 @@ pub fn map_zmsg_to_partner<ShmCfg: PartnerShmConfig>(
             ResponseBody::Err(b) => b.map_to_partner(partner_shm_cfg),
         },
         NetworkBody::ResponseFinal(_)
+        | NetworkBody::RequestCancel(_)
+        | NetworkBody::RequestCredit(_)
         | NetworkBody::Interest(_)
         | NetworkBody::Declare(_)
         | NetworkBody::OAM(_) => Ok(()),
@@ -95,6 +97,8 @@ pub fn map_zmsg_to_shmbuf(msg: &mut NetworkMessage, shmr: &ShmReader) -> ZResult
             ResponseBody::Reply(b) => b.map_to_shmbuf(shmr),
         },
         NetworkBody::ResponseFinal(_)
+        | NetworkBody::RequestCancel(_)
+        | NetworkBody::RequestCredit(_)
         | NetworkBody::Interest(_)
         | NetworkBody::Declare(_)
         | NetworkBody::OAM(_) => Ok(()),
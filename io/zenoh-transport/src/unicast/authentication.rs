@@ -13,6 +13,10 @@
 //
 use zenoh_link::{LinkAuthId, LinkAuthType};
 
+#[cfg(feature = "auth_jwt")]
+use super::establishment::ext::auth::JwtId;
+#[cfg(feature = "auth_pubkey")]
+use super::establishment::ext::auth::PubKeyId;
 #[cfg(feature = "auth_usrpwd")]
 use super::establishment::ext::auth::UsrPwdId;
 
@@ -20,6 +24,7 @@ use super::establishment::ext::auth::UsrPwdId;
 pub enum AuthId {
     CertCommonName(String),
     Username(String),
+    PubKeyFingerprint(String),
     None,
 }
 
@@ -34,6 +39,16 @@ impl From<LinkAuthId> for AuthId {
     }
 }
 
+#[cfg(feature = "auth_pubkey")]
+impl From<PubKeyId> for AuthId {
+    fn from(pubkey_id: PubKeyId) -> Self {
+        match pubkey_id.0 {
+            Some(fingerprint) => AuthId::PubKeyFingerprint(fingerprint),
+            None => AuthId::None,
+        }
+    }
+}
+
 #[cfg(feature = "auth_usrpwd")]
 impl From<UsrPwdId> for AuthId {
     fn from(user_password_id: UsrPwdId) -> Self {
@@ -52,3 +67,13 @@ impl From<UsrPwdId> for AuthId {
         }
     }
 }
+
+#[cfg(feature = "auth_jwt")]
+impl From<JwtId> for AuthId {
+    fn from(jwt_id: JwtId) -> Self {
+        match jwt_id.0 {
+            Some(claims) => AuthId::Username(claims.username),
+            None => AuthId::None,
+        }
+    }
+}
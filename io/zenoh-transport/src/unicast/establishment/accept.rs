@@ -31,6 +31,10 @@ use zenoh_protocol::{
 };
 use zenoh_result::ZResult;
 
+#[cfg(feature = "auth_jwt")]
+use super::ext::auth::JwtId;
+#[cfg(feature = "auth_pubkey")]
+use super::ext::auth::PubKeyId;
 #[cfg(feature = "auth_usrpwd")]
 use super::ext::auth::UsrPwdId;
 #[cfg(feature = "shared-memory")]
@@ -62,6 +66,7 @@ struct StateTransport {
     ext_shm: ext::shm::StateAccept,
     ext_lowlatency: ext::lowlatency::StateAccept,
     ext_patch: ext::patch::StateAccept,
+    ext_capabilities: ext::capabilities::StateAccept,
 }
 
 #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
@@ -114,8 +119,12 @@ struct RecvOpenSynOut {
     other_whatami: WhatAmI,
     other_lease: Duration,
     other_initial_sn: TransportSn,
+    #[cfg(feature = "auth_pubkey")]
+    other_pubkey_id: PubKeyId,
     #[cfg(feature = "auth_usrpwd")]
     other_auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    other_jwt_id: JwtId,
 }
 
 // OpenAck
@@ -145,6 +154,7 @@ struct AcceptLink<'a> {
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
+    ext_capabilities: ext::capabilities::CapabilitiesFsm<'a>,
 }
 
 #[async_trait]
@@ -266,7 +276,13 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_syn((&mut state.link.ext_compression, init_syn.ext_compression))
+            .recv_init_syn((
+                &mut state.link.ext_compression,
+                (
+                    init_syn.ext_compression,
+                    init_syn.ext_compression_algorithms,
+                ),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -276,6 +292,15 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Capabilities
+        self.ext_capabilities
+            .recv_init_syn((
+                &mut state.transport.ext_capabilities,
+                init_syn.ext_capabilities,
+            ))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let output = RecvInitSynOut {
             other_zid: init_syn.zid,
             other_whatami: init_syn.whatami,
@@ -339,13 +364,13 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithms) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_ack(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
         // Extension Patch
@@ -355,6 +380,13 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Capabilities
+        let ext_capabilities = self
+            .ext_capabilities
+            .send_init_ack(&state.transport.ext_capabilities)
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         // Create the cookie
         let (cookie, cookie_nonce): (ZSlice, u64) = {
             let mut prng = zasynclock!(self.prng);
@@ -377,6 +409,7 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 #[cfg(feature = "transport_compression")]
                 ext_compression: state.link.ext_compression,
                 ext_patch: state.transport.ext_patch,
+                ext_capabilities: state.transport.ext_capabilities,
             };
 
             let mut encrypted = vec![];
@@ -411,7 +444,9 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         }
         .into();
 
@@ -514,6 +549,7 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 ext_shm: cookie.ext_shm,
                 ext_lowlatency: cookie.ext_lowlatency,
                 ext_patch: cookie.ext_patch,
+                ext_capabilities: cookie.ext_capabilities,
             },
             #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
             link: StateLink {
@@ -540,13 +576,12 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
         }
 
         // Extension Auth
-        #[cfg(feature = "auth_usrpwd")]
-        let user_password_id = self
+        #[cfg(feature = "transport_auth")]
+        let ext_auth = self
             .ext_auth
             .recv_open_syn((&mut state.link.ext_auth, open_syn.ext_auth))
             .await
-            .map_err(|e| (e, Some(close::reason::GENERIC)))?
-            .auth_id;
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension MultiLink
         #[cfg(feature = "transport_multilink")]
@@ -573,8 +608,12 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             other_whatami: cookie.whatami,
             other_lease: open_syn.lease,
             other_initial_sn: open_syn.initial_sn,
+            #[cfg(feature = "auth_pubkey")]
+            other_pubkey_id: ext_auth.pubkey_id,
             #[cfg(feature = "auth_usrpwd")]
-            other_auth_id: user_password_id,
+            other_auth_id: ext_auth.auth_id,
+            #[cfg(feature = "auth_jwt")]
+            other_jwt_id: ext_auth.jwt_id,
         };
         Ok((state, output))
     }
@@ -679,7 +718,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
             mtu,
             is_streamed,
             #[cfg(feature = "transport_compression")]
-            is_compression: false,
+            compression: None,
         },
         priorities: None,
         reliability: None,
@@ -703,8 +742,9 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         ext_auth: manager.state.unicast.authenticator.fsm(&manager.prng),
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
-        ext_compression: ext::compression::CompressionFsm::new(),
+        ext_compression: ext::compression::CompressionFsm::new(&manager.config.unicast.compression),
         ext_patch: ext::patch::PatchFsm::new(),
+        ext_capabilities: ext::capabilities::CapabilitiesFsm::new(),
     };
 
     // Init handshake
@@ -748,6 +788,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
                         manager.config.unicast.is_lowlatency,
                     ),
                     ext_patch: ext::patch::StateAccept::new(),
+                    ext_capabilities: ext::capabilities::StateAccept::new(),
                 },
                 #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
                 link: StateLink {
@@ -810,9 +851,14 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
             false => None,
         },
         is_lowlatency: state.transport.ext_lowlatency.is_lowlatency(),
+        #[cfg(feature = "auth_pubkey")]
+        pubkey_id: osyn_out.other_pubkey_id,
         #[cfg(feature = "auth_usrpwd")]
         auth_id: osyn_out.other_auth_id,
+        #[cfg(feature = "auth_jwt")]
+        jwt_id: osyn_out.other_jwt_id,
         patch: state.transport.ext_patch.get(),
+        capabilities: state.transport.ext_capabilities.get(),
     };
 
     let a_config = TransportLinkUnicastConfig {
@@ -821,7 +867,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
             mtu: state.transport.batch_size,
             is_streamed,
             #[cfg(feature = "transport_compression")]
-            is_compression: state.link.ext_compression.is_compression(),
+            compression: state.link.ext_compression.compression(),
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
@@ -45,6 +45,7 @@ pub(crate) struct Cookie {
     #[cfg(feature = "transport_compression")]
     pub(crate) ext_compression: ext::compression::StateAccept,
     pub(crate) ext_patch: ext::patch::StateAccept,
+    pub(crate) ext_capabilities: ext::capabilities::StateAccept,
 }
 
 impl<W> WCodec<&Cookie, &mut W> for Zenoh080
@@ -72,6 +73,7 @@ where
         #[cfg(feature = "transport_compression")]
         self.write(&mut *writer, &x.ext_compression)?;
         self.write(&mut *writer, &x.ext_patch)?;
+        self.write(&mut *writer, &x.ext_capabilities)?;
 
         Ok(())
     }
@@ -103,6 +105,7 @@ where
         #[cfg(feature = "transport_compression")]
         let ext_compression: ext::compression::StateAccept = self.read(&mut *reader)?;
         let ext_patch: ext::patch::StateAccept = self.read(&mut *reader)?;
+        let ext_capabilities: ext::capabilities::StateAccept = self.read(&mut *reader)?;
 
         let cookie = Cookie {
             zid,
@@ -121,6 +124,7 @@ where
             #[cfg(feature = "transport_compression")]
             ext_compression,
             ext_patch,
+            ext_capabilities,
         };
 
         Ok(cookie)
@@ -193,6 +197,7 @@ impl Cookie {
             #[cfg(feature = "transport_compression")]
             ext_compression: ext::compression::StateAccept::rand(),
             ext_patch: ext::patch::StateAccept::rand(),
+            ext_capabilities: ext::capabilities::StateAccept::rand(),
         }
     }
 }
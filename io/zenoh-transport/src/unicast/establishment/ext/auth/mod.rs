@@ -11,6 +11,8 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+#[cfg(feature = "auth_jwt")]
+pub(crate) mod jwt;
 #[cfg(feature = "auth_pubkey")]
 pub(crate) mod pubkey;
 #[cfg(feature = "auth_usrpwd")]
@@ -19,6 +21,8 @@ pub(crate) mod usrpwd;
 use std::{convert::TryInto, marker::PhantomData};
 
 use async_trait::async_trait;
+#[cfg(feature = "auth_jwt")]
+pub use jwt::*;
 #[cfg(feature = "auth_pubkey")]
 pub use pubkey::*;
 use rand::{CryptoRng, Rng};
@@ -46,6 +50,10 @@ pub(crate) mod id {
     pub(crate) const PUBKEY: u8 = 0x1;
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) const USRPWD: u8 = 0x2;
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) const JWT: u8 = 0x3;
+    #[cfg(feature = "auth_usrpwd")]
+    pub(crate) const USRPWD_SCRAM: u8 = 0x4;
 }
 
 #[derive(Debug, Default)]
@@ -54,22 +62,41 @@ pub struct Auth {
     pubkey: Option<RwLock<AuthPubKey>>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<RwLock<AuthUsrPwd>>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<RwLock<AuthJwt>>,
 }
 
 impl Auth {
     pub(crate) async fn from_config(config: &Config) -> ZResult<Self> {
         let auth = config.transport().auth();
 
+        #[cfg(feature = "auth_usrpwd")]
+        let usrpwd = AuthUsrPwd::from_config(auth.usrpwd()).await?;
+        #[cfg(feature = "auth_jwt")]
+        let jwt = AuthJwt::from_config(auth.jwt()).await?;
+        // Both would report a username for the same remote, which access control can't tell apart
+        #[cfg(all(feature = "auth_usrpwd", feature = "auth_jwt"))]
+        if usrpwd.as_ref().is_some_and(AuthUsrPwd::has_users)
+            && jwt.as_ref().is_some_and(AuthJwt::has_keys)
+        {
+            bail!("UsrPwd and Jwt authentication of remotes can't be enabled at the same time.");
+        }
+
         Ok(Self {
             #[cfg(feature = "auth_pubkey")]
             pubkey: AuthPubKey::from_config(auth.pubkey())?.map(RwLock::new),
             #[cfg(feature = "auth_usrpwd")]
-            usrpwd: AuthUsrPwd::from_config(auth.usrpwd())
-                .await?
-                .map(RwLock::new),
+            usrpwd: usrpwd.map(RwLock::new),
+            #[cfg(feature = "auth_jwt")]
+            jwt: jwt.map(RwLock::new),
         })
     }
 
+    #[cfg(feature = "auth_pubkey")]
+    pub(crate) fn pubkey(&self) -> Option<&RwLock<AuthPubKey>> {
+        self.pubkey.as_ref()
+    }
+
     pub(crate) fn open<R>(&self, #[allow(unused)] prng: &mut R) -> StateOpen
     where
         R: Rng + CryptoRng,
@@ -82,6 +109,8 @@ impl Auth {
                 .usrpwd
                 .is_some()
                 .then_some(usrpwd::StateOpen::new(prng)),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.is_some().then_some(jwt::StateOpen::new()),
         }
     }
 
@@ -97,6 +126,8 @@ impl Auth {
                 .usrpwd
                 .is_some()
                 .then_some(usrpwd::StateAccept::new(prng)),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.is_some().then_some(jwt::StateAccept::new()),
         }
     }
 
@@ -106,6 +137,8 @@ impl Auth {
             pubkey: self.pubkey.as_ref().map(|x| AuthPubKeyFsm::new(x, prng)),
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: self.usrpwd.as_ref().map(AuthUsrPwdFsm::new),
+            #[cfg(feature = "auth_jwt")]
+            jwt: self.jwt.as_ref().map(AuthJwtFsm::new),
             _a: PhantomData,
         }
     }
@@ -119,6 +152,8 @@ impl Auth {
             pubkey: None,
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: None,
+            #[cfg(feature = "auth_jwt")]
+            jwt: None,
         }
     }
 
@@ -141,6 +176,16 @@ impl Auth {
     pub fn get_usrpwd(&self) -> Option<&RwLock<AuthUsrPwd>> {
         self.usrpwd.as_ref()
     }
+
+    #[cfg(feature = "auth_jwt")]
+    pub fn set_jwt(&mut self, jwt: Option<AuthJwt>) {
+        self.jwt = jwt.map(RwLock::new);
+    }
+
+    #[cfg(feature = "auth_jwt")]
+    pub fn get_jwt(&self) -> Option<&RwLock<AuthJwt>> {
+        self.jwt.as_ref()
+    }
 }
 
 pub(crate) struct AuthFsm<'a> {
@@ -148,6 +193,8 @@ pub(crate) struct AuthFsm<'a> {
     pubkey: Option<AuthPubKeyFsm<'a>>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<AuthUsrPwdFsm<'a>>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<AuthJwtFsm<'a>>,
     _a: PhantomData<&'a ()>, // Required only when all auth features are disabled
 }
 
@@ -157,6 +204,8 @@ pub(crate) struct StateOpen {
     pubkey: Option<pubkey::StateOpen>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<usrpwd::StateOpen>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<jwt::StateOpen>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -165,6 +214,8 @@ pub(crate) struct StateAccept {
     pubkey: Option<pubkey::StateAccept>,
     #[cfg(feature = "auth_usrpwd")]
     usrpwd: Option<usrpwd::StateAccept>,
+    #[cfg(feature = "auth_jwt")]
+    jwt: Option<jwt::StateAccept>,
 }
 
 impl StateAccept {
@@ -176,6 +227,8 @@ impl StateAccept {
             pubkey: rng.gen_bool(0.5).then_some(pubkey::StateAccept::rand()),
             #[cfg(feature = "auth_usrpwd")]
             usrpwd: rng.gen_bool(0.5).then_some(usrpwd::StateAccept::rand()),
+            #[cfg(feature = "auth_jwt")]
+            jwt: rng.gen_bool(0.5).then_some(jwt::StateAccept::rand()),
         }
     }
 }
@@ -210,6 +263,15 @@ where
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            if let Some(jwt) = x.jwt.as_ref() {
+                self.write(&mut wbuf, id::JWT)?;
+                self.write(&mut wbuf, jwt)?;
+                count += 1;
+            }
+        }
+
         self.write(&mut *writer, count)?;
         if !buff.is_empty() {
             let mut rbuf = buff.reader();
@@ -233,6 +295,8 @@ where
         let mut pubkey: Option<pubkey::StateAccept> = None;
         #[cfg(feature = "auth_usrpwd")]
         let mut usrpwd: Option<usrpwd::StateAccept> = None;
+        #[cfg(feature = "auth_jwt")]
+        let mut jwt: Option<jwt::StateAccept> = None;
 
         while count > 0 {
             let e: u8 = self.read(&mut *reader)?;
@@ -245,6 +309,10 @@ where
                 id::USRPWD => {
                     usrpwd = Some(self.read(&mut *reader)?);
                 }
+                #[cfg(feature = "auth_jwt")]
+                id::JWT => {
+                    jwt = Some(self.read(&mut *reader)?);
+                }
                 _ => return Err(DidntRead),
             }
 
@@ -256,6 +324,8 @@ where
             pubkey,
             #[cfg(feature = "auth_usrpwd")]
             usrpwd,
+            #[cfg(feature = "auth_jwt")]
+            jwt,
         };
         Ok(state)
     }
@@ -316,7 +386,11 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
         {
             match (self.usrpwd.as_ref(), state.usrpwd.as_ref()) {
                 (Some(e), Some(s)) => {
-                    if let Some(e) = e.send_init_syn(s).await?.take() {
+                    let (usrpwd, scram) = e.send_init_syn(s).await?;
+                    if let Some(e) = usrpwd {
+                        exts.push(e.into())
+                    }
+                    if let Some(e) = scram {
                         exts.push(e.into())
                     }
                 }
@@ -325,6 +399,19 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_init_syn(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -370,13 +457,27 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             match (self.usrpwd.as_ref(), state.usrpwd.as_mut()) {
                 (Some(e), Some(s)) => {
                     let x = ztake!(exts, id::USRPWD);
-                    e.recv_init_ack((s, ztryinto!(x, S))).await?;
+                    let y = ztake!(exts, id::USRPWD_SCRAM);
+                    e.recv_init_ack((s, ztryinto!(x, S), ztryinto!(y, S)))
+                        .await?;
                 }
                 (None, None) => {}
                 _ => bail!("{S} Invalid UsrPwd configuration."),
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_init_ack((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         Ok(())
     }
 
@@ -407,7 +508,11 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
         {
             match (self.usrpwd.as_ref(), state.usrpwd.as_ref()) {
                 (Some(e), Some(s)) => {
-                    if let Some(e) = e.send_open_syn(s).await?.take() {
+                    let (usrpwd, scram) = e.send_open_syn(s).await?;
+                    if let Some(e) = usrpwd {
+                        exts.push(e.into())
+                    }
+                    if let Some(e) = scram {
                         exts.push(e.into())
                     }
                 }
@@ -416,6 +521,19 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_open_syn(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -461,13 +579,27 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
             match (self.usrpwd.as_ref(), state.usrpwd.as_mut()) {
                 (Some(e), Some(s)) => {
                     let x = ztake!(exts, id::USRPWD);
-                    e.recv_open_ack((s, ztryinto!(x, S))).await?;
+                    let y = ztake!(exts, id::USRPWD_SCRAM);
+                    e.recv_open_ack((s, ztryinto!(x, S), ztryinto!(y, S)))
+                        .await?;
                 }
                 (None, None) => {}
                 _ => bail!("{S} Invalid UsrPwd configuration."),
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_open_ack((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         Ok(())
     }
 }
@@ -476,8 +608,12 @@ impl<'a> OpenFsm for &'a AuthFsm<'a> {
 /*            ACCEPT                 */
 /*************************************/
 pub(crate) struct RecvOpenSynOut {
+    #[cfg(feature = "auth_pubkey")]
+    pub(crate) pubkey_id: PubKeyId,
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) jwt_id: JwtId,
 }
 
 #[async_trait]
@@ -518,13 +654,27 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             match (self.usrpwd.as_ref(), state.usrpwd.as_mut()) {
                 (Some(e), Some(s)) => {
                     let x = ztake!(exts, id::USRPWD);
-                    e.recv_init_syn((s, ztryinto!(x, S))).await?;
+                    let y = ztake!(exts, id::USRPWD_SCRAM);
+                    e.recv_init_syn((s, ztryinto!(x, S), ztryinto!(y, S)))
+                        .await?;
                 }
                 (None, None) => {}
                 _ => bail!("{S} Invalid UsrPwd configuration."),
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    e.recv_init_syn((s, ztryinto!(x, S))).await?;
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         Ok(())
     }
 
@@ -555,7 +705,11 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
         {
             match (self.usrpwd.as_ref(), state.usrpwd.as_ref()) {
                 (Some(e), Some(s)) => {
-                    if let Some(e) = e.send_init_ack(s).await?.take() {
+                    let (usrpwd, scram) = e.send_init_ack(s).await?;
+                    if let Some(e) = usrpwd {
+                        exts.push(e.into())
+                    }
+                    if let Some(e) = scram {
                         exts.push(e.into())
                     }
                 }
@@ -564,6 +718,19 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_init_ack(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -594,14 +761,20 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             .read(&mut reader)
             .map_err(|_| zerror!("{S} Decoding error."))?;
 
+        #[cfg(feature = "auth_pubkey")]
+        let pubkey_id: PubKeyId;
+
         #[cfg(feature = "auth_pubkey")]
         {
             match (self.pubkey.as_ref(), state.pubkey.as_mut()) {
                 (Some(e), Some(s)) => {
                     let x = ztake!(exts, id::PUBKEY);
-                    e.recv_open_syn((s, ztryinto!(x, S))).await?;
+                    let fingerprint = e.recv_open_syn((s, ztryinto!(x, S))).await?;
+                    pubkey_id = PubKeyId(Some(fingerprint));
+                }
+                (None, None) => {
+                    pubkey_id = PubKeyId(None);
                 }
-                (None, None) => {}
                 _ => bail!("{S} Invalid PubKey configuration."),
             }
         }
@@ -614,7 +787,10 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             match (self.usrpwd.as_ref(), state.usrpwd.as_mut()) {
                 (Some(e), Some(s)) => {
                     let x = ztake!(exts, id::USRPWD);
-                    let username = e.recv_open_syn((s, ztryinto!(x, S))).await?;
+                    let y = ztake!(exts, id::USRPWD_SCRAM);
+                    let username = e
+                        .recv_open_syn((s, ztryinto!(x, S), ztryinto!(y, S)))
+                        .await?;
                     auth_id = UsrPwdId(Some(username));
                 }
                 (None, None) => {
@@ -623,9 +799,31 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
                 _ => bail!("{S} Invalid UsrPwd configuration."),
             }
         }
+
+        #[cfg(feature = "auth_jwt")]
+        let jwt_id: JwtId;
+
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_mut()) {
+                (Some(e), Some(s)) => {
+                    let x = ztake!(exts, id::JWT);
+                    let claims = e.recv_open_syn((s, ztryinto!(x, S))).await?;
+                    jwt_id = JwtId(Some(claims));
+                }
+                (None, None) => {
+                    jwt_id = JwtId(None);
+                }
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
         Ok(RecvOpenSynOut {
+            #[cfg(feature = "auth_pubkey")]
+            pubkey_id,
             #[cfg(feature = "auth_usrpwd")]
             auth_id,
+            #[cfg(feature = "auth_jwt")]
+            jwt_id,
         })
     }
 
@@ -656,7 +854,11 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
         {
             match (self.usrpwd.as_ref(), state.usrpwd.as_ref()) {
                 (Some(e), Some(s)) => {
-                    if let Some(e) = e.send_open_ack(s).await?.take() {
+                    let (usrpwd, scram) = e.send_open_ack(s).await?;
+                    if let Some(e) = usrpwd {
+                        exts.push(e.into())
+                    }
+                    if let Some(e) = scram {
                         exts.push(e.into())
                     }
                 }
@@ -665,6 +867,19 @@ impl<'a> AcceptFsm for &'a AuthFsm<'a> {
             }
         }
 
+        #[cfg(feature = "auth_jwt")]
+        {
+            match (self.jwt.as_ref(), state.jwt.as_ref()) {
+                (Some(e), Some(s)) => {
+                    if let Some(e) = e.send_open_ack(s).await?.take() {
+                        exts.push(e.into())
+                    }
+                }
+                (None, None) => {}
+                _ => bail!("{S} Invalid Jwt configuration."),
+            }
+        }
+
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
@@ -11,15 +11,25 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{collections::HashSet, fmt, ops::Deref, path::Path};
+use std::{
+    collections::HashSet,
+    fmt,
+    fmt::Write,
+    iter,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use async_trait::async_trait;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::Rng;
 use rsa::{
-    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey},
+    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPublicKey},
+    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePublicKey},
     traits::PublicKeyParts,
     BigUint, Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey,
 };
+use sha3::{Digest, Sha3_256};
 use tokio::sync::{Mutex, RwLock};
 use zenoh_buffers::{
     reader::{DidntRead, HasReader, Reader},
@@ -44,12 +54,18 @@ mod ext {
     pub(super) type OpenAck = zextunit!(PUBKEY, false);
 }
 
+// The context prepended to the nonce signed with an Ed25519 key
+const SIGNATURE_CONTEXT: &[u8] = b"zenoh-auth-pubkey";
+
 // Authenticator
 #[derive(Debug)]
 pub struct AuthPubKey {
     lookup: Option<HashSet<ZPublicKey>>,
     pub_key: ZPublicKey,
     pri_key: ZPrivateKey,
+    extra_keys: Vec<(ZPublicKey, ZPrivateKey)>,
+    known_keys_file: Option<KnownKeysFile>,
+    close_revoked_transports: bool,
 }
 
 impl AuthPubKey {
@@ -58,6 +74,9 @@ impl AuthPubKey {
             lookup: Some(HashSet::new()),
             pub_key,
             pri_key,
+            extra_keys: vec![],
+            known_keys_file: None,
+            close_revoked_transports: false,
         }
     }
 
@@ -79,92 +98,284 @@ impl AuthPubKey {
         Ok(())
     }
 
+    /// Adds a key pair presented along with the main one when opening a transport, the remote
+    /// accepting the transport if it knows any of the presented keys. This allows rotating keys
+    /// without being rejected by the remotes that only know the previous or the next key.
+    pub fn add_key_pair(&mut self, pub_key: ZPublicKey, pri_key: ZPrivateKey) {
+        self.extra_keys.push((pub_key, pri_key));
+    }
+
+    // The private keys of the key pairs presented when opening a transport, the main one first
+    fn pri_keys(&self) -> impl Iterator<Item = &ZPrivateKey> {
+        iter::once(&self.pri_key).chain(self.extra_keys.iter().map(|(_, pri_key)| pri_key))
+    }
+
     pub fn from_config(config: &PubKeyConf) -> ZResult<Option<Self>> {
         const S: &str = "PubKey extension - From config.";
 
-        // First, check if PEM keys are provided
-        match (config.public_key_pem(), config.private_key_pem()) {
-            (Some(public), Some(private)) => {
-                let pub_key = RsaPublicKey::from_pkcs1_pem(public)
-                    .map_err(|e| zerror!("{} Rsa Public Key: {}.", S, e))?;
-                let pri_key = RsaPrivateKey::from_pkcs1_pem(private)
-                    .map_err(|e| zerror!("{} Rsa Private Key: {}.", S, e))?;
-                return Ok(Some(Self::new(pub_key.into(), pri_key.into())));
-            }
-            (Some(_), None) => {
-                bail!("{S} Missing Rsa Private Key: PEM.")
-            }
-            (None, Some(_)) => {
-                bail!("{S} Missing Rsa Public Key: PEM.")
-            }
-            (None, None) => {}
+        let read_file = |path: &str| {
+            std::fs::read_to_string(path).map_err(|e| zerror!("{S} Failed to read {path}: {e}."))
+        };
+
+        // First, check if PEM keys are provided, then if PEM files are provided
+        let (pub_key, pri_key) = match (config.public_key_pem(), config.private_key_pem()) {
+            (Some(public), Some(private)) => (
+                ZPublicKey::from_pem(public).map_err(|e| zerror!("{S} Public Key: {e}."))?,
+                ZPrivateKey::from_pem(private).map_err(|e| zerror!("{S} Private Key: {e}."))?,
+            ),
+            (Some(_), None) => bail!("{S} Missing Private Key: PEM."),
+            (None, Some(_)) => bail!("{S} Missing Public Key: PEM."),
+            (None, None) => match (config.public_key_file(), config.private_key_file()) {
+                (Some(public), Some(private)) => (
+                    ZPublicKey::from_pem(&read_file(public)?)
+                        .map_err(|e| zerror!("{S} Public Key: {e}."))?,
+                    ZPrivateKey::from_pem(&read_file(private)?)
+                        .map_err(|e| zerror!("{S} Private Key: {e}."))?,
+                ),
+                (Some(_), None) => bail!("{S} Missing Private Key: file."),
+                (None, Some(_)) => bail!("{S} Missing Public Key: file."),
+                (None, None) => return Ok(None),
+            },
+        };
+        if pri_key.public_key() != pub_key {
+            bail!("{S} The Public Key does not match the Private Key.");
         }
 
-        // Second, check if PEM files are provided
-        match (config.public_key_file(), config.private_key_file()) {
-            (Some(public), Some(private)) => {
-                let path = Path::new(public);
-                let pub_key = RsaPublicKey::read_pkcs1_pem_file(path)
-                    .map_err(|e| zerror!("{} Rsa Public Key: {}.", S, e))?;
-                let path = Path::new(private);
-                let pri_key = RsaPrivateKey::read_pkcs1_pem_file(path)
-                    .map_err(|e| zerror!("{} Rsa Private Key: {}.", S, e))?;
-                return Ok(Some(Self::new(pub_key.into(), pri_key.into())));
-            }
-            (Some(_), None) => {
-                bail!("{S} Missing Rsa Private Key: file.")
-            }
-            (None, Some(_)) => {
-                bail!("{S} Missing Rsa Public Key: file.")
-            }
-            (None, None) => {}
+        let mut auth = Self::new(pub_key, pri_key);
+        for path in config.additional_private_key_files().iter().flatten() {
+            let pri_key = ZPrivateKey::from_pem(&read_file(path)?)
+                .map_err(|e| zerror!("{S} Private Key {path}: {e}."))?;
+            auth.add_key_pair(pri_key.public_key(), pri_key);
+        }
+
+        if let Some(path) = config.known_keys_file() {
+            let mut known_keys_file = KnownKeysFile {
+                path: path.into(),
+                modified: None,
+                reload_interval: config
+                    .known_keys_reload_interval()
+                    .map(Duration::from_millis),
+            };
+            auth.lookup = Some(known_keys_file.read()?);
+            auth.known_keys_file = Some(known_keys_file);
+        }
+        auth.close_revoked_transports = *config.close_revoked_transports();
+
+        Ok(Some(auth))
+    }
+
+    pub(crate) fn known_keys_file(&self) -> Option<KnownKeysFile> {
+        self.known_keys_file.clone()
+    }
+
+    /// Replaces the known keys with the ones reloaded from the known keys file.
+    pub(crate) fn set_known_keys(&mut self, known_keys: KnownKeys) {
+        if let Some(known_keys_file) = self.known_keys_file.as_mut() {
+            known_keys_file.modified = known_keys.modified;
         }
+        self.lookup = Some(known_keys.keys);
+    }
 
-        // @TODO: populate lookup file
+    pub(crate) fn known_keys_reload_interval(&self) -> Option<Duration> {
+        self.known_keys_file
+            .as_ref()
+            .and_then(|known_keys_file| known_keys_file.reload_interval)
+    }
 
-        Ok(None)
+    pub(crate) fn close_revoked_transports(&self) -> bool {
+        self.close_revoked_transports
+    }
+
+    /// Returns the fingerprints of the known keys, or `None` if any key is accepted.
+    pub(crate) fn known_fingerprints(&self) -> Option<HashSet<String>> {
+        self.lookup.as_ref().map(|lookup| {
+            lookup
+                .iter()
+                .filter_map(|pub_key| pub_key.fingerprint().ok())
+                .collect()
+        })
     }
 }
 
-#[repr(transparent)]
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct ZPublicKey(RsaPublicKey);
+#[derive(Clone, Debug)]
+pub(crate) struct KnownKeysFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    reload_interval: Option<Duration>,
+}
 
-impl Deref for ZPublicKey {
-    type Target = RsaPublicKey;
+/// The known keys read from the known keys file, along with its modification time.
+pub(crate) struct KnownKeys {
+    modified: Option<SystemTime>,
+    keys: HashSet<ZPublicKey>,
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl KnownKeysFile {
+    fn read(&mut self) -> ZResult<HashSet<ZPublicKey>> {
+        self.modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let content = std::fs::read_to_string(&self.path).map_err(|e| {
+            zerror!(
+                "Failed to read known keys file {}: {e}",
+                self.path.display()
+            )
+        })?;
+        self.parse(&content)
+    }
+
+    /// Reads the file again if it has been modified since it was last read, returning `None`
+    /// otherwise. The file is read asynchronously, e.g. without holding the authenticator.
+    pub(crate) async fn reload(&self) -> ZResult<Option<KnownKeys>> {
+        let modified = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified == self.modified {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            zerror!(
+                "Failed to read known keys file {}: {e}",
+                self.path.display()
+            )
+        })?;
+        let keys = self.parse(&content)?;
+        Ok(Some(KnownKeys { modified, keys }))
+    }
+
+    // Parses the concatenated PEM public keys of the file
+    fn parse(&self, content: &str) -> ZResult<HashSet<ZPublicKey>> {
+        let path = self.path.display();
+        let mut keys = HashSet::new();
+        let mut pem = String::new();
+        for line in content.lines() {
+            pem.push_str(line);
+            pem.push('\n');
+            if line.starts_with("-----END ") {
+                let key = ZPublicKey::from_pem(&pem)
+                    .map_err(|e| zerror!("Invalid key in known keys file {path}: {e}"))?;
+                keys.insert(key);
+                pem.clear();
+            }
+        }
+        if !pem.trim().is_empty() {
+            bail!("Invalid known keys file {path}: truncated PEM key");
+        }
+        Ok(keys)
     }
 }
 
+/// A public key, either RSA or Ed25519.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum ZPublicKey {
+    Rsa(RsaPublicKey),
+    Ed25519(VerifyingKey),
+}
+
 impl fmt::Debug for ZPublicKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for b in self.0.n().to_bytes_le() {
-            write!(f, "{:02x}", b)?;
-        }
-        for b in self.0.e().to_bytes_le() {
-            write!(f, "{:02x}", b)?;
+        match self {
+            ZPublicKey::Rsa(key) => {
+                for b in key.n().to_bytes_le() {
+                    write!(f, "{:02x}", b)?;
+                }
+                for b in key.e().to_bytes_le() {
+                    write!(f, "{:02x}", b)?;
+                }
+            }
+            ZPublicKey::Ed25519(key) => {
+                for b in key.as_bytes() {
+                    write!(f, "{:02x}", b)?;
+                }
+            }
         }
         Ok(())
     }
 }
 
+impl ZPublicKey {
+    /// Parses a public key in PEM format: an RSA key in PKCS#1 or SubjectPublicKeyInfo format,
+    /// or an Ed25519 key in SubjectPublicKeyInfo format.
+    pub fn from_pem(pem: &str) -> ZResult<Self> {
+        if let Ok(key) = RsaPublicKey::from_pkcs1_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+        if let Ok(key) = VerifyingKey::from_public_key_pem(pem) {
+            return Ok(Self::Ed25519(key));
+        }
+        match RsaPublicKey::from_public_key_pem(pem) {
+            Ok(key) => Ok(Self::Rsa(key)),
+            Err(e) => bail!("Neither an RSA nor an Ed25519 public key: {e}"),
+        }
+    }
+
+    /// Returns the lowercase hexadecimal SHA3-256 digest of the DER encoding of the key, in PKCS#1
+    /// format for RSA keys and in SubjectPublicKeyInfo format for Ed25519 keys.
+    ///
+    /// It can be computed with `openssl rsa -RSAPublicKey_in -RSAPublicKey_out -outform DER | openssl dgst -sha3-256`
+    /// for RSA keys and with `openssl pkey -pubin -outform DER | openssl dgst -sha3-256` for Ed25519 keys.
+    pub fn fingerprint(&self) -> ZResult<String> {
+        let der = match self {
+            ZPublicKey::Rsa(key) => key
+                .to_pkcs1_der()
+                .map_err(|e| zerror!("Failed to encode public key: {e}"))?
+                .into_vec(),
+            ZPublicKey::Ed25519(key) => key
+                .to_public_key_der()
+                .map_err(|e| zerror!("Failed to encode public key: {e}"))?
+                .into_vec(),
+        };
+        Ok(Sha3_256::digest(der)
+            .iter()
+            .fold(String::new(), |mut output, b| {
+                let _ = write!(output, "{b:02x}");
+                output
+            }))
+    }
+}
+
 impl From<RsaPublicKey> for ZPublicKey {
     fn from(x: RsaPublicKey) -> Self {
-        Self(x)
+        Self::Rsa(x)
+    }
+}
+
+impl From<VerifyingKey> for ZPublicKey {
+    fn from(x: VerifyingKey) -> Self {
+        Self::Ed25519(x)
     }
 }
 
-#[repr(transparent)]
+/// A private key, either RSA or Ed25519.
 #[derive(Clone, PartialEq, Eq)]
-pub struct ZPrivateKey(RsaPrivateKey);
+pub enum ZPrivateKey {
+    Rsa(RsaPrivateKey),
+    Ed25519(SigningKey),
+}
 
-impl Deref for ZPrivateKey {
-    type Target = RsaPrivateKey;
+impl ZPrivateKey {
+    /// Parses a private key in PEM format: an RSA key in PKCS#1 or PKCS#8 format, or an Ed25519
+    /// key in PKCS#8 format.
+    pub fn from_pem(pem: &str) -> ZResult<Self> {
+        if let Ok(key) = RsaPrivateKey::from_pkcs1_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+        if let Ok(key) = SigningKey::from_pkcs8_pem(pem) {
+            return Ok(Self::Ed25519(key));
+        }
+        match RsaPrivateKey::from_pkcs8_pem(pem) {
+            Ok(key) => Ok(Self::Rsa(key)),
+            Err(e) => bail!("Neither an RSA nor an Ed25519 private key: {e}"),
+        }
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    /// Returns the public key of the key pair.
+    pub fn public_key(&self) -> ZPublicKey {
+        match self {
+            ZPrivateKey::Rsa(key) => RsaPublicKey::from(key).into(),
+            ZPrivateKey::Ed25519(key) => key.verifying_key().into(),
+        }
     }
 }
 
@@ -176,10 +387,19 @@ impl fmt::Debug for ZPrivateKey {
 
 impl From<RsaPrivateKey> for ZPrivateKey {
     fn from(x: RsaPrivateKey) -> Self {
-        Self(x)
+        Self::Rsa(x)
+    }
+}
+
+impl From<SigningKey> for ZPrivateKey {
+    fn from(x: SigningKey) -> Self {
+        Self::Ed25519(x)
     }
 }
 
+// RSA keys are encoded as their modulus followed by their exponent. Ed25519 keys are encoded as an
+// empty modulus followed by their 32 bytes: an empty modulus being an invalid RSA key, the encoding
+// of RSA keys remains the same as for the peers not supporting Ed25519 keys.
 impl<W> WCodec<&ZPublicKey, &mut W> for Zenoh080
 where
     W: Writer,
@@ -187,8 +407,16 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &ZPublicKey) -> Self::Output {
-        self.write(&mut *writer, x.n().to_bytes_le().as_slice())?;
-        self.write(&mut *writer, x.e().to_bytes_le().as_slice())?;
+        match x {
+            ZPublicKey::Rsa(key) => {
+                self.write(&mut *writer, key.n().to_bytes_le().as_slice())?;
+                self.write(&mut *writer, key.e().to_bytes_le().as_slice())?;
+            }
+            ZPublicKey::Ed25519(key) => {
+                self.write(&mut *writer, &[] as &[u8])?;
+                self.write(&mut *writer, key.as_bytes().as_slice())?;
+            }
+        }
         Ok(())
     }
 }
@@ -201,12 +429,16 @@ where
 
     fn read(self, reader: &mut R) -> Result<ZPublicKey, Self::Error> {
         let n: Vec<u8> = self.read(&mut *reader)?;
-        let n = BigUint::from_bytes_le(n.as_slice());
         let e: Vec<u8> = self.read(&mut *reader)?;
+        if n.is_empty() {
+            let key = VerifyingKey::try_from(e.as_slice()).map_err(|_| DidntRead)?;
+            return Ok(ZPublicKey::Ed25519(key));
+        }
+        let n = BigUint::from_bytes_le(n.as_slice());
         let e = BigUint::from_bytes_le(e.as_slice());
         let rsa = RsaPublicKey::new(n, e).map_err(|_| DidntRead)?;
 
-        Ok(ZPublicKey(rsa))
+        Ok(ZPublicKey::Rsa(rsa))
     }
 }
 
@@ -218,11 +450,20 @@ where
 /// +-+-+-+-+-+-+-+-+
 /// ~  public key   ~
 /// +---------------+
+/// ~  public key   ~ -- Further keys presented along with the main one, if any
+/// +---------------+
 ///
 /// ZExtZBuf
 /// ```
 pub(crate) struct InitSyn {
     pub(crate) alice_pubkey: ZPublicKey,
+    pub(crate) alice_extra_pubkeys: Vec<ZPublicKey>,
+}
+
+impl InitSyn {
+    fn alice_pubkeys(&self) -> impl Iterator<Item = &ZPublicKey> {
+        iter::once(&self.alice_pubkey).chain(self.alice_extra_pubkeys.iter())
+    }
 }
 
 impl<W> WCodec<&InitSyn, &mut W> for Zenoh080
@@ -232,7 +473,9 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &InitSyn) -> Self::Output {
-        self.write(&mut *writer, &x.alice_pubkey)?;
+        for pubkey in x.alice_pubkeys() {
+            self.write(&mut *writer, pubkey)?;
+        }
         Ok(())
     }
 }
@@ -245,7 +488,15 @@ where
 
     fn read(self, reader: &mut R) -> Result<InitSyn, Self::Error> {
         let alice_pubkey: ZPublicKey = self.read(&mut *reader)?;
-        Ok(InitSyn { alice_pubkey })
+        let mut alice_extra_pubkeys = vec![];
+        while reader.can_read() {
+            let pubkey: ZPublicKey = self.read(&mut *reader)?;
+            alice_extra_pubkeys.push(pubkey);
+        }
+        Ok(InitSyn {
+            alice_pubkey,
+            alice_extra_pubkeys,
+        })
     }
 }
 
@@ -257,14 +508,17 @@ where
 /// +-+-+-+-+-+-+-+-+
 /// ~  public key   ~
 /// +---------------+
-/// ~ ciphered nonce~
+/// ~     nonce     ~ -- Ciphered with the RSA key of alice, in clear for an Ed25519 key
+/// +---------------+
+/// %   key index   % -- The index of the key of alice the nonce is for, if not the main one
 /// +---------------+
 ///
 /// ZExtZBuf
 /// ```
 pub(crate) struct InitAck {
     pub(crate) bob_pubkey: ZPublicKey,
-    pub(crate) nonce_encrypted_with_alice_pubkey: Vec<u8>,
+    pub(crate) nonce: Vec<u8>,
+    pub(crate) alice_pubkey_index: usize,
 }
 
 impl<W> WCodec<&InitAck, &mut W> for Zenoh080
@@ -275,7 +529,10 @@ where
 
     fn write(self, writer: &mut W, x: &InitAck) -> Self::Output {
         self.write(&mut *writer, &x.bob_pubkey)?;
-        self.write(&mut *writer, x.nonce_encrypted_with_alice_pubkey.as_slice())?;
+        self.write(&mut *writer, x.nonce.as_slice())?;
+        if x.alice_pubkey_index != 0 {
+            self.write(&mut *writer, x.alice_pubkey_index as u64)?;
+        }
         Ok(())
     }
 }
@@ -288,10 +545,17 @@ where
 
     fn read(self, reader: &mut R) -> Result<InitAck, Self::Error> {
         let bob_pubkey: ZPublicKey = self.read(&mut *reader)?;
-        let nonce_encrypted_with_alice_pubkey: Vec<u8> = self.read(&mut *reader)?;
+        let nonce: Vec<u8> = self.read(&mut *reader)?;
+        let alice_pubkey_index = if reader.can_read() {
+            let index: u64 = self.read(&mut *reader)?;
+            usize::try_from(index).map_err(|_| DidntRead)?
+        } else {
+            0
+        };
         Ok(InitAck {
             bob_pubkey,
-            nonce_encrypted_with_alice_pubkey,
+            nonce,
+            alice_pubkey_index,
         })
     }
 }
@@ -302,13 +566,13 @@ where
 /// ```text
 ///  7 6 5 4 3 2 1 0
 /// +-+-+-+-+-+-+-+-+
-/// ~ ciphered nonce~
-/// +---------------+
+/// ~   response    ~ -- The nonce signed with the Ed25519 key of alice, or else the
+/// +---------------+    nonce ciphered with the RSA key of bob, in clear for an Ed25519 key
 ///
 /// ZExtZBuf
 /// ```
 pub(crate) struct OpenSyn {
-    pub(crate) nonce_encrypted_with_bob_pubkey: Vec<u8>,
+    pub(crate) response: Vec<u8>,
 }
 
 impl<W> WCodec<&OpenSyn, &mut W> for Zenoh080
@@ -318,7 +582,7 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &OpenSyn) -> Self::Output {
-        self.write(&mut *writer, x.nonce_encrypted_with_bob_pubkey.as_slice())?;
+        self.write(&mut *writer, x.response.as_slice())?;
         Ok(())
     }
 }
@@ -330,10 +594,8 @@ where
     type Error = DidntRead;
 
     fn read(self, reader: &mut R) -> Result<OpenSyn, Self::Error> {
-        let nonce_encrypted_with_bob_pubkey: Vec<u8> = self.read(&mut *reader)?;
-        Ok(OpenSyn {
-            nonce_encrypted_with_bob_pubkey,
-        })
+        let response: Vec<u8> = self.read(&mut *reader)?;
+        Ok(OpenSyn { response })
     }
 }
 
@@ -375,9 +637,16 @@ impl<'a> OpenFsm for &'a AuthPubKeyFsm<'a> {
         const S: &str = "PubKey extension - Send InitSyn.";
         tracing::trace!("{S}");
 
+        let r_inner = zasyncread!(self.inner);
         let init_syn = InitSyn {
-            alice_pubkey: zasyncread!(self.inner).pub_key.clone(),
+            alice_pubkey: r_inner.pub_key.clone(),
+            alice_extra_pubkeys: r_inner
+                .extra_keys
+                .iter()
+                .map(|(pub_key, _)| pub_key.clone())
+                .collect(),
         };
+        drop(r_inner);
 
         let codec = Zenoh080::new();
         let mut buff = vec![];
@@ -417,20 +686,29 @@ impl<'a> OpenFsm for &'a AuthPubKeyFsm<'a> {
             }
         }
 
-        let mut prng = zasynclock!(self.prng);
-        let nonce = r_inner
-            .pri_key
-            .decrypt_blinded(
-                &mut *prng,
-                Pkcs1v15Encrypt,
-                init_ack.nonce_encrypted_with_alice_pubkey.as_slice(),
-            )
-            .map_err(|_| zerror!("{S} Decryption error."))?;
-        drop(r_inner);
+        let pri_key = r_inner
+            .pri_keys()
+            .nth(init_ack.alice_pubkey_index)
+            .ok_or_else(|| zerror!("{S} Invalid PubKey index."))?;
 
-        state.nonce = init_ack
-            .bob_pubkey
-            .encrypt(&mut *prng, Pkcs1v15Encrypt, nonce.as_slice())?;
+        let mut prng = zasynclock!(self.prng);
+        state.nonce = match pri_key {
+            ZPrivateKey::Rsa(pri_key) => {
+                let nonce = pri_key
+                    .decrypt_blinded(&mut *prng, Pkcs1v15Encrypt, init_ack.nonce.as_slice())
+                    .map_err(|_| zerror!("{S} Decryption error."))?;
+                match &init_ack.bob_pubkey {
+                    ZPublicKey::Rsa(bob_pubkey) => {
+                        bob_pubkey.encrypt(&mut *prng, Pkcs1v15Encrypt, nonce.as_slice())?
+                    }
+                    // An Ed25519 key can not cipher
+                    ZPublicKey::Ed25519(_) => nonce,
+                }
+            }
+            ZPrivateKey::Ed25519(pri_key) => pri_key
+                .sign(&[SIGNATURE_CONTEXT, init_ack.nonce.as_slice()].concat())
+                .to_vec(),
+        };
 
         Ok(())
     }
@@ -445,7 +723,7 @@ impl<'a> OpenFsm for &'a AuthPubKeyFsm<'a> {
         tracing::trace!("{S}");
 
         let open_syn = OpenSyn {
-            nonce_encrypted_with_bob_pubkey: state.nonce.clone(),
+            response: state.nonce.clone(),
         };
 
         let codec = Zenoh080::new();
@@ -483,13 +761,23 @@ impl<'a> OpenFsm for &'a AuthPubKeyFsm<'a> {
 pub(crate) struct StateAccept {
     nonce: Vec<u8>,
     challenge: u64,
+    fingerprint: String,
+    alice_pubkey_index: usize,
+    // The Ed25519 key of alice, verifying the signature of the nonce
+    alice_verifying_key: Option<VerifyingKey>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PubKeyId(pub Option<String>);
+
 impl StateAccept {
     pub(crate) const fn new() -> Self {
         Self {
             nonce: vec![],
             challenge: 0,
+            fingerprint: String::new(),
+            alice_pubkey_index: 0,
+            alice_verifying_key: None,
         }
     }
 
@@ -501,6 +789,11 @@ impl StateAccept {
         Self {
             nonce,
             challenge: rng.gen(),
+            fingerprint: format!("{:064x}", rng.gen::<u128>()),
+            alice_pubkey_index: 0,
+            alice_verifying_key: rng
+                .gen_bool(0.5)
+                .then(|| SigningKey::generate(&mut rng).verifying_key()),
         }
     }
 }
@@ -513,7 +806,12 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
-        self.write(&mut *writer, x.challenge)
+        self.write(&mut *writer, x.challenge)?;
+        self.write(&mut *writer, x.fingerprint.as_str())?;
+        match x.alice_verifying_key.as_ref() {
+            Some(key) => self.write(&mut *writer, key.as_bytes().as_slice()),
+            None => self.write(&mut *writer, &[] as &[u8]),
+        }
     }
 }
 
@@ -525,9 +823,19 @@ where
 
     fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
         let challenge: u64 = self.read(&mut *reader)?;
+        let fingerprint: String = self.read(&mut *reader)?;
+        let key: Vec<u8> = self.read(&mut *reader)?;
+        let alice_verifying_key = if key.is_empty() {
+            None
+        } else {
+            Some(VerifyingKey::try_from(key.as_slice()).map_err(|_| DidntRead)?)
+        };
         Ok(StateAccept {
             nonce: vec![],
             challenge,
+            fingerprint,
+            alice_pubkey_index: 0,
+            alice_verifying_key,
         })
     }
 }
@@ -535,6 +843,8 @@ where
 impl PartialEq for StateAccept {
     fn eq(&self, other: &Self) -> bool {
         self.challenge == other.challenge
+            && self.fingerprint == other.fingerprint
+            && self.alice_verifying_key == other.alice_verifying_key
     }
 }
 
@@ -563,19 +873,35 @@ impl<'a> AcceptFsm for &'a AuthPubKeyFsm<'a> {
             .read(&mut reader)
             .map_err(|_| zerror!("{S} Decoding error."))?;
 
+        // The first presented key known by bob authenticates alice
         let r_inner = zasyncread!(self.inner);
-        if let Some(lookup) = r_inner.lookup.as_ref() {
-            if !lookup.contains(&init_syn.alice_pubkey) {
-                bail!("{S} Unauthorized PubKey.");
-            }
-        }
+        let (index, alice_pubkey) = match r_inner.lookup.as_ref() {
+            Some(lookup) => init_syn
+                .alice_pubkeys()
+                .enumerate()
+                .find(|(_, pubkey)| lookup.contains(pubkey))
+                .ok_or_else(|| zerror!("{S} Unauthorized PubKey."))?,
+            None => (0, &init_syn.alice_pubkey),
+        };
+
+        state.fingerprint = alice_pubkey.fingerprint()?;
+        state.alice_pubkey_index = index;
 
         let mut prng = zasynclock!(self.prng);
         state.challenge = prng.gen();
-        state.nonce = init_syn
-            .alice_pubkey
-            .encrypt(&mut *prng, Pkcs1v15Encrypt, &state.challenge.to_le_bytes())
-            .map_err(|_| zerror!("{S} Encoding error."))?;
+        match alice_pubkey {
+            ZPublicKey::Rsa(alice_pubkey) => {
+                state.nonce = alice_pubkey
+                    .encrypt(&mut *prng, Pkcs1v15Encrypt, &state.challenge.to_le_bytes())
+                    .map_err(|_| zerror!("{S} Encoding error."))?;
+                state.alice_verifying_key = None;
+            }
+            // Alice proves it owns an Ed25519 key by signing the nonce
+            ZPublicKey::Ed25519(alice_pubkey) => {
+                state.nonce = state.challenge.to_le_bytes().to_vec();
+                state.alice_verifying_key = Some(*alice_pubkey);
+            }
+        }
 
         Ok(())
     }
@@ -591,7 +917,8 @@ impl<'a> AcceptFsm for &'a AuthPubKeyFsm<'a> {
 
         let init_ack = InitAck {
             bob_pubkey: zasyncread!(self.inner).pub_key.clone(),
-            nonce_encrypted_with_alice_pubkey: state.nonce.clone(),
+            nonce: state.nonce.clone(),
+            alice_pubkey_index: state.alice_pubkey_index,
         };
 
         let codec = Zenoh080::new();
@@ -605,7 +932,7 @@ impl<'a> AcceptFsm for &'a AuthPubKeyFsm<'a> {
     }
 
     type RecvOpenSynIn = (&'a mut StateAccept, Option<ext::OpenSyn>);
-    type RecvOpenSynOut = ();
+    type RecvOpenSynOut = String;
     async fn recv_open_syn(
         self,
         input: Self::RecvOpenSynIn,
@@ -625,22 +952,39 @@ impl<'a> AcceptFsm for &'a AuthPubKeyFsm<'a> {
             .read(&mut reader)
             .map_err(|_| zerror!("{S} Decoding error."))?;
 
-        let mut prng = zasynclock!(self.prng);
-        let nonce = zasyncread!(self.inner)
-            .pri_key
-            .decrypt_blinded(
-                &mut *prng,
-                Pkcs1v15Encrypt,
-                open_syn.nonce_encrypted_with_bob_pubkey.as_slice(),
-            )
-            .map_err(|_| zerror!("{S} Decryption error."))?;
-
-        if nonce.as_slice() != state.challenge.to_le_bytes() {
-            println!("{:02x?}\n{:02x?}", nonce, state.nonce);
-            bail!("{S} Invalid nonce.");
+        let challenge = state.challenge.to_le_bytes();
+        match state.alice_verifying_key.as_ref() {
+            Some(alice_pubkey) => {
+                let signature = Signature::from_slice(open_syn.response.as_slice())
+                    .map_err(|_| zerror!("{S} Decoding error."))?;
+                alice_pubkey
+                    .verify(
+                        &[SIGNATURE_CONTEXT, challenge.as_slice()].concat(),
+                        &signature,
+                    )
+                    .map_err(|_| zerror!("{S} Invalid signature."))?;
+            }
+            None => {
+                let nonce = match &zasyncread!(self.inner).pri_key {
+                    ZPrivateKey::Rsa(pri_key) => {
+                        let mut prng = zasynclock!(self.prng);
+                        pri_key
+                            .decrypt_blinded(
+                                &mut *prng,
+                                Pkcs1v15Encrypt,
+                                open_syn.response.as_slice(),
+                            )
+                            .map_err(|_| zerror!("{S} Decryption error."))?
+                    }
+                    ZPrivateKey::Ed25519(_) => open_syn.response,
+                };
+                if nonce.as_slice() != challenge {
+                    bail!("{S} Invalid nonce.");
+                }
+            }
         }
 
-        Ok(())
+        Ok(state.fingerprint.clone())
     }
 
     type SendOpenAckIn = &'a StateAccept;
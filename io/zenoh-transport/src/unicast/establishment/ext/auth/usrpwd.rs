@@ -11,9 +11,10 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, str::FromStr};
 
 use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine};
 use rand::{CryptoRng, Rng};
 use tokio::sync::RwLock;
 use zenoh_buffers::{
@@ -31,21 +32,177 @@ use crate::unicast::establishment::{ext::auth::id, AcceptFsm, OpenFsm};
 mod ext {
     use zenoh_protocol::{zextunit, zextz64, zextzbuf};
 
-    use super::{id::USRPWD, ZExtUnit, ZExtZ64, ZExtZBuf};
+    use super::{
+        id::{USRPWD, USRPWD_SCRAM},
+        ZExtUnit, ZExtZ64, ZExtZBuf,
+    };
 
+    // Legacy exchange, proving the knowledge of the cleartext password
     pub(super) type InitSyn = zextunit!(USRPWD, false);
     pub(super) type InitAck = zextz64!(USRPWD, false);
     pub(super) type OpenSyn = zextzbuf!(USRPWD, false);
     pub(super) type OpenAck = zextunit!(USRPWD, false);
+
+    // Salted exchange, proving the knowledge of the client key of a verifier
+    pub(super) type ScramInitSyn = zextzbuf!(USRPWD_SCRAM, false);
+    pub(super) type ScramInitAck = zextzbuf!(USRPWD_SCRAM, false);
+    pub(super) type ScramOpenSyn = zextzbuf!(USRPWD_SCRAM, false);
+    pub(super) type ScramOpenAck = zextunit!(USRPWD_SCRAM, false);
+}
+
+// Verifier
+/// The default number of PBKDF2 iterations used to derive a [`UsrPwdVerifier`].
+pub const USRPWD_DEFAULT_ITERATIONS: u32 = 4096;
+/// The maximum number of PBKDF2 iterations of a [`UsrPwdVerifier`], which is also the maximum a
+/// client accepts to compute, preventing a malicious router from making it spin on the handshake.
+pub const USRPWD_MAX_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+/// The prefix of a [`UsrPwdVerifier`] written in a user-password dictionary.
+pub const USRPWD_VERIFIER_PREFIX: &str = "$scram-sha3-256$";
+const CLIENT_KEY: &[u8] = b"Client Key";
+
+/// A salted verifier of a password, as stored in the user-password dictionary.
+///
+/// The verifier is derived from the password in a SCRAM-like fashion:
+/// ```text
+/// SaltedPassword := PBKDF2-HMAC-SHA3-256(password, salt, iterations)
+/// ClientKey      := HMAC-SHA3-256(SaltedPassword, "Client Key")
+/// StoredKey      := SHA3-256(ClientKey)
+/// ```
+/// Only the salt, the number of iterations and the `StoredKey` are kept by the router, which is
+/// enough to authenticate a client proving the knowledge of `ClientKey`. A verifier is written as
+/// `$scram-sha3-256$<iterations>$<base64 salt>$<base64 StoredKey>`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct UsrPwdVerifier {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Vec<u8>,
+}
+
+impl UsrPwdVerifier {
+    pub fn new<R>(password: &[u8], iterations: u32, prng: &mut R) -> ZResult<Self>
+    where
+        R: Rng + CryptoRng,
+    {
+        if iterations == 0 || iterations > USRPWD_MAX_ITERATIONS {
+            bail!(
+                "The number of iterations of a user-password verifier must be between 1 and {USRPWD_MAX_ITERATIONS}."
+            );
+        }
+        let mut salt = vec![0u8; SALT_LEN];
+        prng.fill(salt.as_mut_slice());
+        let client_key = client_key(password, &salt, iterations)?;
+        Ok(Self {
+            iterations,
+            salt,
+            stored_key: hmac::digest(&client_key),
+        })
+    }
+
+    /// Checks a password against this verifier, e.g. one presented out of the handshake.
+    pub fn verify_password(&self, password: &[u8]) -> ZResult<bool> {
+        let client_key = client_key(password, &self.salt, self.iterations)?;
+        Ok(hmac::verify(&self.stored_key, &hmac::digest(&client_key)))
+    }
+
+    fn verify(&self, user: &[u8], nonce: u64, proof: &[u8]) -> ZResult<bool> {
+        let signature = hmac::sign(&self.stored_key, &auth_message(user, nonce))?;
+        if proof.len() != signature.len() {
+            return Ok(false);
+        }
+        let client_key = xor(proof, &signature);
+        Ok(hmac::verify(&self.stored_key, &hmac::digest(&client_key)))
+    }
+}
+
+impl fmt::Display for UsrPwdVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{USRPWD_VERIFIER_PREFIX}{}${}${}",
+            self.iterations,
+            general_purpose::STANDARD.encode(&self.salt),
+            general_purpose::STANDARD.encode(&self.stored_key)
+        )
+    }
+}
+
+impl fmt::Debug for UsrPwdVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsrPwdVerifier")
+            .field("iterations", &self.iterations)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FromStr for UsrPwdVerifier {
+    type Err = ZError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.strip_prefix(USRPWD_VERIFIER_PREFIX).ok_or_else(|| {
+            zerror!("Invalid verifier: expected the `{USRPWD_VERIFIER_PREFIX}` prefix.")
+        })?;
+        let mut fields = fields.split('$');
+        let (Some(iterations), Some(salt), Some(stored_key), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!(
+                "Invalid verifier: expected `{USRPWD_VERIFIER_PREFIX}<iterations>$<salt>$<stored key>`."
+            );
+        };
+        let iterations: u32 = iterations
+            .parse()
+            .map_err(|e| zerror!("Invalid verifier iterations: {e}."))?;
+        if iterations == 0 || iterations > USRPWD_MAX_ITERATIONS {
+            bail!("Invalid verifier iterations: must be between 1 and {USRPWD_MAX_ITERATIONS}.");
+        }
+        let salt = general_purpose::STANDARD
+            .decode(salt)
+            .map_err(|e| zerror!("Invalid verifier salt: {e}."))?;
+        let stored_key = general_purpose::STANDARD
+            .decode(stored_key)
+            .map_err(|e| zerror!("Invalid verifier stored key: {e}."))?;
+        Ok(Self {
+            iterations,
+            salt,
+            stored_key,
+        })
+    }
+}
+
+fn client_key(password: &[u8], salt: &[u8], iterations: u32) -> ZResult<Vec<u8>> {
+    let salted_password = hmac::pbkdf2(password, salt, iterations)?;
+    hmac::sign(&salted_password, CLIENT_KEY)
+}
+
+fn auth_message(user: &[u8], nonce: u64) -> Vec<u8> {
+    let mut message = nonce.to_le_bytes().to_vec();
+    message.extend_from_slice(user);
+    message
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b.iter()).map(|(a, b)| a ^ b).collect()
 }
 
 // Authenticator
 type User = Vec<u8>;
 type Password = Vec<u8>;
 
+// The verifier authenticates the peers supporting the salted exchange, while the
+// cleartext password, when known, authenticates the peers only supporting the legacy one.
+#[derive(Debug, PartialEq, Eq)]
+struct Credentials {
+    verifier: UsrPwdVerifier,
+    password: Option<Password>,
+}
+
 pub struct AuthUsrPwd {
-    lookup: HashMap<User, Password>,
+    lookup: HashMap<User, Credentials>,
     credentials: Option<(User, Password)>,
+    // Key used to derive the salts presented to unknown users, so that they
+    // cannot be told apart from the known ones during the handshake.
+    mock_key: [u8; 32],
 }
 
 impl AuthUsrPwd {
@@ -53,11 +210,30 @@ impl AuthUsrPwd {
         Self {
             lookup: HashMap::new(),
             credentials,
+            mock_key: rand::thread_rng().gen(),
         }
     }
 
     pub async fn add_user(&mut self, user: User, password: Password) -> ZResult<()> {
-        self.lookup.insert(user, password);
+        let verifier = UsrPwdVerifier::new(
+            &password,
+            USRPWD_DEFAULT_ITERATIONS,
+            &mut rand::thread_rng(),
+        )?;
+        let credentials = Credentials {
+            verifier,
+            password: Some(password),
+        };
+        self.lookup.insert(user, credentials);
+        Ok(())
+    }
+
+    pub async fn add_user_verifier(&mut self, user: User, verifier: UsrPwdVerifier) -> ZResult<()> {
+        let credentials = Credentials {
+            verifier,
+            password: None,
+        };
+        self.lookup.insert(user, credentials);
         Ok(())
     }
 
@@ -69,7 +245,7 @@ impl AuthUsrPwd {
     pub async fn from_config(config: &UsrPwdConf) -> ZResult<Option<Self>> {
         const S: &str = "UsrPwd extension - From config.";
 
-        let mut lookup: HashMap<User, Password> = HashMap::new();
+        let mut auth = Self::new(None);
         if let Some(dict) = config.dictionary_file() {
             let content = tokio::fs::read_to_string(dict)
                 .await
@@ -78,9 +254,10 @@ impl AuthUsrPwd {
             // Populate the user-password dictionary
             // The config file is expected to be in the form of:
             //      usr1:pwd1
-            //      usr2:pwd2
+            //      usr2:$scram-sha3-256$4096$<salt>$<stored key>
             //      usr3:pwd3
-            // I.e.: one <user>:<password> entry per line
+            // I.e.: one <user>:<password> or <user>:<verifier> entry per line
+            let mut cleartext = false;
             for l in content.lines() {
                 let line = l.trim();
                 if line.is_empty() {
@@ -93,33 +270,71 @@ impl AuthUsrPwd {
                 if user.is_empty() {
                     bail!("{S} Invalid user-password dictionary file: empty user.")
                 }
-                let password = line[idx + 1..].trim().as_bytes().to_owned();
+                let password = line[idx + 1..].trim();
                 if password.is_empty() {
                     bail!("{S} Invalid user-password dictionary file: empty password.")
                 }
-                lookup.insert(user, password);
+                let credentials = if password.starts_with(USRPWD_VERIFIER_PREFIX) {
+                    Credentials {
+                        verifier: password.parse().map_err(|e| {
+                            zerror!("{S} Invalid user-password dictionary file: {e}")
+                        })?,
+                        password: None,
+                    }
+                } else {
+                    cleartext = true;
+                    Credentials {
+                        verifier: UsrPwdVerifier::new(
+                            password.as_bytes(),
+                            USRPWD_DEFAULT_ITERATIONS,
+                            &mut rand::thread_rng(),
+                        )?,
+                        password: Some(password.as_bytes().to_owned()),
+                    }
+                };
+                auth.lookup.insert(user, credentials);
+            }
+            if cleartext {
+                tracing::warn!(
+                    "{S} User-password dictionary contains cleartext passwords, consider replacing them with verifiers generated by `zenohd usrpwd-verifier`."
+                );
             }
             tracing::debug!("{S} User-password dictionary has been configured.");
         }
 
-        let mut credentials: Option<(User, Password)> = None;
         if let Some(user) = config.user() {
             if let Some(password) = config.password() {
                 tracing::debug!("{S} User-password has been configured.");
-                credentials = Some((user.as_bytes().to_owned(), password.as_bytes().to_owned()));
+                auth.credentials =
+                    Some((user.as_bytes().to_owned(), password.as_bytes().to_owned()));
             }
         }
 
-        if !lookup.is_empty() || credentials.is_some() {
+        if !auth.lookup.is_empty() || auth.credentials.is_some() {
             tracing::debug!("{S} User-password authentication is enabled.");
-            Ok(Some(Self {
-                lookup,
-                credentials,
-            }))
+            Ok(Some(auth))
         } else {
             Ok(None)
         }
     }
+
+    // Whether the remotes are authenticated against the dictionary, providing their username
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) fn has_users(&self) -> bool {
+        !self.lookup.is_empty()
+    }
+
+    // The salt and iterations to present to a user, mocked if the user is unknown
+    fn challenge(&self, user: &[u8]) -> ZResult<(Vec<u8>, u32)> {
+        match self.lookup.get(user) {
+            Some(Credentials { verifier, .. }) => Ok((verifier.salt.clone(), verifier.iterations)),
+            None => {
+                let mut salt = hmac::sign(&self.mock_key, user)?;
+                salt.truncate(SALT_LEN);
+                Ok((salt, USRPWD_DEFAULT_ITERATIONS))
+            }
+        }
+    }
 }
 
 impl fmt::Debug for AuthUsrPwd {
@@ -147,6 +362,9 @@ impl fmt::Debug for AuthUsrPwd {
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct StateOpen {
     nonce: u64,
+    scram: bool,
+    salt: Vec<u8>,
+    iterations: u32,
 }
 
 impl StateOpen {
@@ -154,13 +372,20 @@ impl StateOpen {
     where
         R: Rng + CryptoRng,
     {
-        Self { nonce: prng.gen() }
+        Self {
+            nonce: prng.gen(),
+            scram: false,
+            salt: vec![],
+            iterations: 0,
+        }
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct StateAccept {
     nonce: u64,
+    scram: bool,
+    user: Vec<u8>,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct UsrPwdId(pub Option<Vec<u8>>);
@@ -170,13 +395,20 @@ impl StateAccept {
     where
         R: Rng + CryptoRng,
     {
-        Self { nonce: prng.gen() }
+        Self {
+            nonce: prng.gen(),
+            scram: false,
+            user: vec![],
+        }
     }
 
     #[cfg(all(test, feature = "test"))]
     pub(crate) fn rand() -> Self {
         let mut rng = rand::thread_rng();
-        Self::new(&mut rng)
+        let mut state = Self::new(&mut rng);
+        state.scram = rng.gen_bool(0.5);
+        state.user = (0..rng.gen_range(0..16)).map(|_| rng.gen()).collect();
+        state
     }
 }
 
@@ -188,7 +420,10 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
-        self.write(&mut *writer, x.nonce)
+        self.write(&mut *writer, x.nonce)?;
+        self.write(&mut *writer, u8::from(x.scram))?;
+        self.write(&mut *writer, x.user.as_slice())?;
+        Ok(())
     }
 }
 
@@ -200,7 +435,13 @@ where
 
     fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
         let nonce: u64 = self.read(&mut *reader)?;
-        Ok(StateAccept { nonce })
+        let scram: u8 = self.read(&mut *reader)?;
+        let user: Vec<u8> = self.read(&mut *reader)?;
+        Ok(StateAccept {
+            nonce,
+            scram: scram != 0,
+            user,
+        })
     }
 }
 
@@ -214,6 +455,114 @@ impl<'a> AuthUsrPwdFsm<'a> {
     }
 }
 
+/*************************************/
+/*             InitSyn               */
+/*************************************/
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// +---------------+
+///
+/// ZExtUnit
+/// ```
+
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// ~     user      ~
+/// +---------------+
+///
+/// ZExtZBuf
+/// ```
+struct ScramInitSyn {
+    user: Vec<u8>,
+}
+
+impl<W> WCodec<&ScramInitSyn, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &ScramInitSyn) -> Self::Output {
+        self.write(&mut *writer, x.user.as_slice())
+    }
+}
+
+impl<R> RCodec<ScramInitSyn, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<ScramInitSyn, Self::Error> {
+        let user: Vec<u8> = self.read(&mut *reader)?;
+        Ok(ScramInitSyn { user })
+    }
+}
+
+/*************************************/
+/*             InitAck               */
+/*************************************/
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// %     nonce     %
+/// +---------------+
+///
+/// ZExtZ64
+/// ```
+
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// %     nonce     %
+/// +---------------+
+/// %  iterations   %
+/// +---------------+
+/// ~     salt      ~
+/// +---------------+
+///
+/// ZExtZBuf
+/// ```
+struct ScramInitAck {
+    nonce: u64,
+    iterations: u32,
+    salt: Vec<u8>,
+}
+
+impl<W> WCodec<&ScramInitAck, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &ScramInitAck) -> Self::Output {
+        self.write(&mut *writer, x.nonce)?;
+        self.write(&mut *writer, x.iterations)?;
+        self.write(&mut *writer, x.salt.as_slice())?;
+        Ok(())
+    }
+}
+
+impl<R> RCodec<ScramInitAck, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<ScramInitAck, Self::Error> {
+        let nonce: u64 = self.read(&mut *reader)?;
+        let iterations: u32 = self.read(&mut *reader)?;
+        let salt: Vec<u8> = self.read(&mut *reader)?;
+        Ok(ScramInitAck {
+            nonce,
+            iterations,
+            salt,
+        })
+    }
+}
+
 /*************************************/
 /*             OpenSyn               */
 /*************************************/
@@ -258,6 +607,41 @@ where
     }
 }
 
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// ~     proof     ~
+/// +---------------+
+///
+/// ZExtZBuf
+/// ```
+struct ScramOpenSyn {
+    proof: Vec<u8>,
+}
+
+impl<W> WCodec<&ScramOpenSyn, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &ScramOpenSyn) -> Self::Output {
+        self.write(&mut *writer, x.proof.as_slice())
+    }
+}
+
+impl<R> RCodec<ScramOpenSyn, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<ScramOpenSyn, Self::Error> {
+        let proof: Vec<u8> = self.read(&mut *reader)?;
+        Ok(ScramOpenSyn { proof })
+    }
+}
+
 /*************************************/
 /*             OpenAck               */
 /*************************************/
@@ -273,42 +657,80 @@ where
 impl<'a> OpenFsm for &'a AuthUsrPwdFsm<'a> {
     type Error = ZError;
 
+    // Both the legacy and the salted extensions are sent, the accepting side replying to the
+    // salted one when it supports it and to the legacy one otherwise.
     type SendInitSynIn = &'a StateOpen;
-    type SendInitSynOut = Option<ext::InitSyn>;
+    type SendInitSynOut = (Option<ext::InitSyn>, Option<ext::ScramInitSyn>);
     async fn send_init_syn(
         self,
         _input: Self::SendInitSynIn,
     ) -> Result<Self::SendInitSynOut, Self::Error> {
-        let output = zasyncread!(self.inner)
-            .credentials
-            .is_some()
-            .then_some(ZExtUnit::new());
+        const S: &str = "UsrPwd extension - Send InitSyn.";
+
+        // If credentials are not configured, don't initiate the USRPWD authentication
+        let r_inner = zasyncread!(self.inner);
+        let Some((user, _)) = r_inner.credentials.as_ref() else {
+            return Ok((None, None));
+        };
+        let init_syn = ScramInitSyn { user: user.clone() };
+        drop(r_inner);
+
+        let codec = Zenoh080::new();
+        let mut buff = vec![];
+        let mut writer = buff.writer();
+        codec
+            .write(&mut writer, &init_syn)
+            .map_err(|_| zerror!("{S} Encoding error."))?;
+
+        let output = (Some(ZExtUnit::new()), Some(ZExtZBuf::new(buff.into())));
         Ok(output)
     }
 
-    type RecvInitAckIn = (&'a mut StateOpen, Option<ext::InitAck>);
+    type RecvInitAckIn = (
+        &'a mut StateOpen,
+        Option<ext::InitAck>,
+        Option<ext::ScramInitAck>,
+    );
     type RecvInitAckOut = ();
     async fn recv_init_ack(
         self,
         input: Self::RecvInitAckIn,
     ) -> Result<Self::RecvInitAckOut, Self::Error> {
-        const S: &str = "UsrPwd extension - Recv InitSyn.";
+        const S: &str = "UsrPwd extension - Recv InitAck.";
 
         if zasyncread!(self.inner).credentials.is_none() {
             return Ok(());
         };
 
-        let (state, mut ext_userwpd) = input;
-        let ext_usrpwd = ext_userwpd
-            .take()
-            .ok_or_else(|| zerror!("{S} Decoding error."))?;
-        state.nonce = ext_usrpwd.value;
+        let (state, ext_usrpwd, ext_scram) = input;
+        match (ext_usrpwd, ext_scram) {
+            (_, Some(ext_scram)) => {
+                let codec = Zenoh080::new();
+                let mut reader = ext_scram.value.reader();
+                let init_ack: ScramInitAck = codec
+                    .read(&mut reader)
+                    .map_err(|_| zerror!("{S} Decoding error."))?;
+                if init_ack.iterations == 0 || init_ack.iterations > USRPWD_MAX_ITERATIONS {
+                    bail!("{S} Invalid number of iterations: {}.", init_ack.iterations);
+                }
+
+                state.nonce = init_ack.nonce;
+                state.scram = true;
+                state.salt = init_ack.salt;
+                state.iterations = init_ack.iterations;
+            }
+            (Some(ext_usrpwd), None) => {
+                state.nonce = ext_usrpwd.value;
+                state.scram = false;
+            }
+            (None, None) => bail!("{S} Expected extension."),
+        }
 
         Ok(())
     }
 
     type SendOpenSynIn = &'a StateOpen;
-    type SendOpenSynOut = Option<ext::OpenSyn>;
+    type SendOpenSynOut = (Option<ext::OpenSyn>, Option<ext::ScramOpenSyn>);
     async fn send_open_syn(
         self,
         state: Self::SendOpenSynIn,
@@ -319,31 +741,58 @@ impl<'a> OpenFsm for &'a AuthUsrPwdFsm<'a> {
         let r_inner = zasyncread!(self.inner);
         let (user, password) = match r_inner.credentials.as_ref() {
             Some(cr) => cr,
-            None => return Ok(None),
-        };
-
-        // Create the HMAC of the password using the nonce received as a key (it's a challenge)
-        let key = state.nonce.to_le_bytes();
-        let hmac = hmac::sign(&key, password).map_err(|_| zerror!("{S} Encoding error."))?;
-        // Create the OpenSyn extension
-        let open_syn = OpenSyn {
-            user: user.to_vec(),
-            hmac,
+            None => return Ok((None, None)),
         };
-        drop(r_inner);
 
         let codec = Zenoh080::new();
         let mut buff = vec![];
         let mut writer = buff.writer();
-        codec
-            .write(&mut writer, &open_syn)
-            .map_err(|_| zerror!("{S} Encoding error."))?;
-
-        let output = Some(ZExtZBuf::new(buff.into()));
-        Ok(output)
+        if state.scram {
+            let (user, password) = (user.clone(), password.clone());
+            drop(r_inner);
+
+            // The key derivation is costly, it runs on a blocking thread not to stall the runtime
+            let (salt, iterations) = (state.salt.clone(), state.iterations);
+            let client_key =
+                tokio::task::spawn_blocking(move || client_key(&password, &salt, iterations))
+                    .await
+                    .map_err(|_| zerror!("{S} Encoding error."))?
+                    .map_err(|_| zerror!("{S} Encoding error."))?;
+            // Prove the knowledge of the client key by masking it with a signature
+            // of the nonce received as challenge, keyed by the stored key
+            let stored_key = hmac::digest(&client_key);
+            let signature = hmac::sign(&stored_key, &auth_message(&user, state.nonce))
+                .map_err(|_| zerror!("{S} Encoding error."))?;
+            let open_syn = ScramOpenSyn {
+                proof: xor(&client_key, &signature),
+            };
+
+            codec
+                .write(&mut writer, &open_syn)
+                .map_err(|_| zerror!("{S} Encoding error."))?;
+            Ok((None, Some(ZExtZBuf::new(buff.into()))))
+        } else {
+            // Create the HMAC of the password using the nonce received as a key (it's a challenge)
+            let key = state.nonce.to_le_bytes();
+            let hmac = hmac::sign(&key, password).map_err(|_| zerror!("{S} Encoding error."))?;
+            let open_syn = OpenSyn {
+                user: user.to_vec(),
+                hmac,
+            };
+            drop(r_inner);
+
+            codec
+                .write(&mut writer, &open_syn)
+                .map_err(|_| zerror!("{S} Encoding error."))?;
+            Ok((Some(ZExtZBuf::new(buff.into())), None))
+        }
     }
 
-    type RecvOpenAckIn = (&'a mut StateOpen, Option<ext::OpenAck>);
+    type RecvOpenAckIn = (
+        &'a mut StateOpen,
+        Option<ext::OpenAck>,
+        Option<ext::ScramOpenAck>,
+    );
     type RecvOpenAckOut = ();
     async fn recv_open_ack(
         self,
@@ -351,8 +800,13 @@ impl<'a> OpenFsm for &'a AuthUsrPwdFsm<'a> {
     ) -> Result<Self::RecvOpenAckOut, Self::Error> {
         const S: &str = "UsrPwd extension - Recv OpenAck.";
 
-        let (_, ext) = input;
-        if zasyncread!(self.inner).credentials.is_some() && ext.is_none() {
+        let (state, ext_usrpwd, ext_scram) = input;
+        let ext = if state.scram {
+            ext_scram.is_some()
+        } else {
+            ext_usrpwd.is_some()
+        };
+        if zasyncread!(self.inner).credentials.is_some() && !ext {
             bail!("{S} Expected extension.");
         }
 
@@ -367,7 +821,11 @@ impl<'a> OpenFsm for &'a AuthUsrPwdFsm<'a> {
 impl<'a> AcceptFsm for &'a AuthUsrPwdFsm<'a> {
     type Error = ZError;
 
-    type RecvInitSynIn = (&'a mut StateAccept, Option<ext::InitSyn>);
+    type RecvInitSynIn = (
+        &'a mut StateAccept,
+        Option<ext::InitSyn>,
+        Option<ext::ScramInitSyn>,
+    );
     type RecvInitSynOut = ();
     async fn recv_init_syn(
         self,
@@ -375,24 +833,60 @@ impl<'a> AcceptFsm for &'a AuthUsrPwdFsm<'a> {
     ) -> Result<Self::RecvInitSynOut, Self::Error> {
         const S: &str = "UsrPwd extension - Recv InitSyn.";
 
-        let (_, ext_usrpwd) = input;
-        if ext_usrpwd.is_none() {
-            bail!("{S} Expected extension.");
+        let (state, ext_usrpwd, ext_scram) = input;
+        match (ext_usrpwd, ext_scram) {
+            // Prefer the salted exchange when the peer supports it
+            (_, Some(ext_scram)) => {
+                let codec = Zenoh080::new();
+                let mut reader = ext_scram.value.reader();
+                let init_syn: ScramInitSyn = codec
+                    .read(&mut reader)
+                    .map_err(|_| zerror!("{S} Decoding error."))?;
+                state.scram = true;
+                state.user = init_syn.user;
+            }
+            (Some(_), None) => state.scram = false,
+            (None, None) => bail!("{S} Expected extension."),
         }
 
         Ok(())
     }
 
     type SendInitAckIn = &'a StateAccept;
-    type SendInitAckOut = Option<ext::InitAck>;
+    type SendInitAckOut = (Option<ext::InitAck>, Option<ext::ScramInitAck>);
     async fn send_init_ack(
         self,
         state: Self::SendInitAckIn,
     ) -> Result<Self::SendInitAckOut, Self::Error> {
-        Ok(Some(ZExtZ64::new(state.nonce)))
+        const S: &str = "UsrPwd extension - Send InitAck.";
+
+        if !state.scram {
+            return Ok((Some(ZExtZ64::new(state.nonce)), None));
+        }
+
+        let (salt, iterations) = zasyncread!(self.inner).challenge(&state.user)?;
+        let init_ack = ScramInitAck {
+            nonce: state.nonce,
+            iterations,
+            salt,
+        };
+
+        let codec = Zenoh080::new();
+        let mut buff = vec![];
+        let mut writer = buff.writer();
+        codec
+            .write(&mut writer, &init_ack)
+            .map_err(|_| zerror!("{S} Encoding error."))?;
+
+        let output = (None, Some(ZExtZBuf::new(buff.into())));
+        Ok(output)
     }
 
-    type RecvOpenSynIn = (&'a mut StateAccept, Option<ext::OpenSyn>);
+    type RecvOpenSynIn = (
+        &'a mut StateAccept,
+        Option<ext::OpenSyn>,
+        Option<ext::ScramOpenSyn>,
+    );
     type RecvOpenSynOut = Vec<u8>; //value of userid is returned if recvopensynout is processed as valid
     async fn recv_open_syn(
         self,
@@ -400,40 +894,67 @@ impl<'a> AcceptFsm for &'a AuthUsrPwdFsm<'a> {
     ) -> Result<Self::RecvOpenSynOut, Self::Error> {
         const S: &str = "UsrPwd extension - Recv OpenSyn.";
 
-        let (state, mut ext_usrpwd) = input;
-        let ext_usrpwd = ext_usrpwd
-            .take()
-            .ok_or_else(|| zerror!("{S} Expected extension."))?;
-
+        let (state, ext_usrpwd, ext_scram) = input;
         let codec = Zenoh080::new();
-        let mut reader = ext_usrpwd.value.reader();
-        let open_syn: OpenSyn = codec
-            .read(&mut reader)
-            .map_err(|_| zerror!("{S} Decoding error."))?;
-
-        let r_inner = zasyncread!(self.inner);
-        let pwd = r_inner
-            .lookup
-            .get(&open_syn.user)
-            .ok_or_else(|| zerror!("{S} Invalid user."))?;
-
-        // Create the HMAC of the password using the nonce received as challenge
-        let key = state.nonce.to_le_bytes();
-        let hmac = hmac::sign(&key, pwd).map_err(|_| zerror!("{S} Encoding error."))?;
-        if hmac != open_syn.hmac {
-            bail!("{S} Invalid password.");
+        if state.scram {
+            let ext_scram = ext_scram.ok_or_else(|| zerror!("{S} Expected extension."))?;
+            let mut reader = ext_scram.value.reader();
+            let open_syn: ScramOpenSyn = codec
+                .read(&mut reader)
+                .map_err(|_| zerror!("{S} Decoding error."))?;
+
+            let r_inner = zasyncread!(self.inner);
+            let credentials = r_inner
+                .lookup
+                .get(&state.user)
+                .ok_or_else(|| zerror!("{S} Invalid user."))?;
+
+            // Check the proof against the stored key using the nonce sent as challenge
+            if !credentials
+                .verifier
+                .verify(&state.user, state.nonce, &open_syn.proof)?
+            {
+                bail!("{S} Invalid password.");
+            }
+            Ok(state.user.to_owned())
+        } else {
+            let ext_usrpwd = ext_usrpwd.ok_or_else(|| zerror!("{S} Expected extension."))?;
+            let mut reader = ext_usrpwd.value.reader();
+            let open_syn: OpenSyn = codec
+                .read(&mut reader)
+                .map_err(|_| zerror!("{S} Decoding error."))?;
+
+            let r_inner = zasyncread!(self.inner);
+            let credentials = r_inner
+                .lookup
+                .get(&open_syn.user)
+                .ok_or_else(|| zerror!("{S} Invalid user."))?;
+            // Users only known by their verifier need a peer supporting the salted exchange
+            let pwd = credentials.password.as_ref().ok_or_else(|| {
+                zerror!("{S} Invalid password: the user requires salted credentials.")
+            })?;
+
+            // Create the HMAC of the password using the nonce received as challenge
+            let key = state.nonce.to_le_bytes();
+            let hmac = hmac::sign(&key, pwd).map_err(|_| zerror!("{S} Encoding error."))?;
+            if !hmac::verify(&hmac, &open_syn.hmac) {
+                bail!("{S} Invalid password.");
+            }
+            Ok(open_syn.user)
         }
-        let username = open_syn.user.to_owned();
-        Ok(username)
     }
 
     type SendOpenAckIn = &'a StateAccept;
-    type SendOpenAckOut = Option<ext::OpenAck>;
+    type SendOpenAckOut = (Option<ext::OpenAck>, Option<ext::ScramOpenAck>);
     async fn send_open_ack(
         self,
-        _input: Self::SendOpenAckIn,
+        state: Self::SendOpenAckIn,
     ) -> Result<Self::SendOpenAckOut, Self::Error> {
-        Ok(Some(ZExtUnit::new()))
+        if state.scram {
+            Ok((None, Some(ZExtUnit::new())))
+        } else {
+            Ok((Some(ZExtUnit::new()), None))
+        }
     }
 }
 
@@ -445,7 +966,7 @@ mod tests {
 
             use zenoh_config::UsrPwdConf;
 
-            use super::AuthUsrPwd;
+            use super::{AuthUsrPwd, UsrPwdVerifier};
 
             /* [CONFIG] */
             let f1 = "zenoh-test-auth-usrpwd.txt";
@@ -490,10 +1011,124 @@ mod tests {
             writeln!(c, ":").unwrap();
             drop(c);
             assert!(AuthUsrPwd::from_config(&config).await.is_err());
+            // Valid verifier
+            let verifier = UsrPwdVerifier::new(b"pwd1", 16, &mut rand::thread_rng()).unwrap();
+            let mut c = zconfig!();
+            writeln!(c, "usr1:{verifier}").unwrap();
+            drop(c);
+            let auth = AuthUsrPwd::from_config(&config).await.unwrap().unwrap();
+            let credentials = auth.lookup.get("usr1".as_bytes()).unwrap();
+            assert_eq!(credentials.verifier, verifier);
+            assert!(credentials.password.is_none());
+            // Invalid verifier
+            let mut c = zconfig!();
+            writeln!(c, "usr1:$scram-sha3-256$0$AAAA$AAAA").unwrap();
+            drop(c);
+            assert!(AuthUsrPwd::from_config(&config).await.is_err());
+            let mut c = zconfig!();
+            writeln!(c, "usr1:$scram-sha3-256$16$AAAA").unwrap();
+            drop(c);
+            assert!(AuthUsrPwd::from_config(&config).await.is_err());
+            let mut c = zconfig!();
+            writeln!(c, "usr1:$scram-sha3-256$100001$AAAA$AAAA").unwrap();
+            drop(c);
+            assert!(AuthUsrPwd::from_config(&config).await.is_err());
 
             let _ = std::fs::remove_file(f1);
         }
 
         inner().await;
     }
+
+    #[test]
+    fn authenticator_usrpwd_verifier() {
+        use zenoh_crypto::hmac;
+
+        use super::{auth_message, client_key, xor, UsrPwdVerifier, USRPWD_MAX_ITERATIONS};
+
+        assert!(UsrPwdVerifier::new(b"pwd1", 0, &mut rand::thread_rng()).is_err());
+        assert!(
+            UsrPwdVerifier::new(b"pwd1", USRPWD_MAX_ITERATIONS + 1, &mut rand::thread_rng())
+                .is_err()
+        );
+        let verifier = UsrPwdVerifier::new(b"pwd1", 16, &mut rand::thread_rng()).unwrap();
+        assert_eq!(
+            verifier.to_string().parse::<UsrPwdVerifier>().unwrap(),
+            verifier
+        );
+
+        let proof = |password: &[u8], user: &[u8], nonce: u64| {
+            let client_key = client_key(password, &verifier.salt, verifier.iterations).unwrap();
+            let stored_key = hmac::digest(&client_key);
+            let signature = hmac::sign(&stored_key, &auth_message(user, nonce)).unwrap();
+            xor(&client_key, &signature)
+        };
+        assert!(verifier
+            .verify(b"usr1", 42, &proof(b"pwd1", b"usr1", 42))
+            .unwrap());
+        // Wrong password
+        assert!(!verifier
+            .verify(b"usr1", 42, &proof(b"pwd2", b"usr1", 42))
+            .unwrap());
+        // Replayed proof
+        assert!(!verifier
+            .verify(b"usr1", 43, &proof(b"pwd1", b"usr1", 42))
+            .unwrap());
+        // Truncated proof
+        assert!(!verifier
+            .verify(b"usr1", 42, &proof(b"pwd1", b"usr1", 42)[1..])
+            .unwrap());
+
+        assert!(verifier.verify_password(b"pwd1").unwrap());
+        assert!(!verifier.verify_password(b"pwd2").unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn authenticator_usrpwd_legacy() {
+        use tokio::sync::RwLock;
+        use zenoh_buffers::writer::HasWriter;
+        use zenoh_codec::{WCodec, Zenoh080};
+        use zenoh_crypto::hmac;
+        use zenoh_protocol::common::{ZExtUnit, ZExtZBuf};
+        use zenoh_result::ZResult;
+
+        use super::{AuthUsrPwd, AuthUsrPwdFsm, OpenSyn, StateAccept, UsrPwdVerifier};
+        use crate::unicast::establishment::AcceptFsm;
+
+        let mut auth = AuthUsrPwd::new(None);
+        auth.add_user(b"usr1".to_vec(), b"pwd1".to_vec())
+            .await
+            .unwrap();
+        let verifier = UsrPwdVerifier::new(b"pwd2", 16, &mut rand::thread_rng()).unwrap();
+        auth.add_user_verifier(b"usr2".to_vec(), verifier)
+            .await
+            .unwrap();
+        let auth = RwLock::new(auth);
+        let fsm = AuthUsrPwdFsm::new(&auth);
+
+        // A peer only supporting the legacy exchange
+        async fn open(fsm: &AuthUsrPwdFsm<'_>, user: &[u8], password: &[u8]) -> ZResult<Vec<u8>> {
+            let mut state = StateAccept::new(&mut rand::thread_rng());
+            fsm.recv_init_syn((&mut state, Some(ZExtUnit::new()), None))
+                .await
+                .unwrap();
+            let (init_ack, scram) = fsm.send_init_ack(&state).await.unwrap();
+            assert!(scram.is_none());
+
+            let open_syn = OpenSyn {
+                user: user.to_vec(),
+                hmac: hmac::sign(&init_ack.unwrap().value.to_le_bytes(), password).unwrap(),
+            };
+            let mut buff = vec![];
+            Zenoh080::new()
+                .write(&mut buff.writer(), &open_syn)
+                .unwrap();
+            fsm.recv_open_syn((&mut state, Some(ZExtZBuf::new(buff.into())), None))
+                .await
+        }
+        assert_eq!(open(&fsm, b"usr1", b"pwd1").await.unwrap(), b"usr1");
+        assert!(open(&fsm, b"usr1", b"pwd2").await.is_err());
+        // The cleartext password of a verifier is unknown
+        assert!(open(&fsm, b"usr2", b"pwd2").await.is_err());
+    }
 }
@@ -11,27 +11,89 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use core::marker::PhantomData;
-
 use async_trait::async_trait;
 use zenoh_buffers::{
     reader::{DidntRead, Reader},
     writer::{DidntWrite, Writer},
 };
 use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_config::CompressionAlgorithm;
 use zenoh_protocol::transport::{init, open};
 use zenoh_result::Error as ZError;
 
-use crate::unicast::establishment::{AcceptFsm, OpenFsm};
+use crate::{
+    common::batch::BatchCompression,
+    unicast::establishment::{AcceptFsm, OpenFsm},
+};
+
+// Each compression algorithm is identified by a bit in the CompressionAlgorithms extension.
+// The compression level is not exchanged since each side compresses with its own level.
+const fn algorithm_mask(algorithm: CompressionAlgorithm) -> u64 {
+    match algorithm {
+        CompressionAlgorithm::Lz4 => 1 << 0,
+        CompressionAlgorithm::Zstd => 1 << 1,
+    }
+}
 
 // Extension Fsm
 pub(crate) struct CompressionFsm<'a> {
-    _a: PhantomData<&'a ()>,
+    // The locally supported compression algorithms in order of preference
+    algorithms: &'a [BatchCompression],
 }
 
-impl CompressionFsm<'_> {
-    pub(crate) const fn new() -> Self {
-        Self { _a: PhantomData }
+impl<'a> CompressionFsm<'a> {
+    pub(crate) const fn new(algorithms: &'a [BatchCompression]) -> Self {
+        Self { algorithms }
+    }
+
+    fn mask(&self) -> u64 {
+        self.algorithms
+            .iter()
+            .fold(0, |mask, c| mask | algorithm_mask(c.algorithm()))
+    }
+
+    fn lz4(&self) -> Option<BatchCompression> {
+        self.algorithms
+            .iter()
+            .find(|c| c.algorithm() == CompressionAlgorithm::Lz4)
+            .copied()
+    }
+
+    // Select the first locally preferred algorithm that is also supported by the other side
+    fn select(
+        &self,
+        other_ext: Option<init::ext::Compression>,
+        other_algorithms: Option<init::ext::CompressionAlgorithms>,
+    ) -> Option<BatchCompression> {
+        match (other_algorithms, other_ext) {
+            (Some(other), _) => self
+                .algorithms
+                .iter()
+                .find(|c| other.value & algorithm_mask(c.algorithm()) != 0)
+                .copied(),
+            // The other side does not support algorithm negotiation, fallback on lz4
+            (None, Some(_)) => self.lz4(),
+            (None, None) => None,
+        }
+    }
+
+    fn exts(
+        &self,
+        is_compression: bool,
+        mask: u64,
+    ) -> (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithms>,
+    ) {
+        if !is_compression || mask == 0 {
+            return (None, None);
+        }
+        // The Compression extension is kept for backward compatibility with nodes
+        // that only support lz4 and do not understand the CompressionAlgorithms extension
+        let ext = (mask & algorithm_mask(CompressionAlgorithm::Lz4) != 0)
+            .then_some(init::ext::Compression::new());
+        let ext_algorithms = Some(init::ext::CompressionAlgorithms::new(mask));
+        (ext, ext_algorithms)
     }
 }
 
@@ -41,15 +103,19 @@ impl CompressionFsm<'_> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateOpen {
     is_compression: bool,
+    compression: Option<BatchCompression>,
 }
 
 impl StateOpen {
     pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+        Self {
+            is_compression,
+            compression: None,
+        }
     }
 
-    pub(crate) const fn is_compression(&self) -> bool {
-        self.is_compression
+    pub(crate) const fn compression(&self) -> Option<BatchCompression> {
+        self.compression
     }
 }
 
@@ -58,25 +124,34 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
     type SendInitSynIn = &'a StateOpen;
-    type SendInitSynOut = Option<init::ext::Compression>;
+    type SendInitSynOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithms>,
+    );
     async fn send_init_syn(
         self,
         state: Self::SendInitSynIn,
     ) -> Result<Self::SendInitSynOut, Self::Error> {
-        let output = state
-            .is_compression
-            .then_some(init::ext::Compression::new());
-        Ok(output)
+        Ok(self.exts(state.is_compression, self.mask()))
     }
 
-    type RecvInitAckIn = (&'a mut StateOpen, Option<init::ext::Compression>);
+    type RecvInitAckIn = (
+        &'a mut StateOpen,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithms>,
+        ),
+    );
     type RecvInitAckOut = ();
     async fn recv_init_ack(
         self,
         input: Self::RecvInitAckIn,
     ) -> Result<Self::RecvInitAckOut, Self::Error> {
-        let (state, other_ext) = input;
-        state.is_compression &= other_ext.is_some();
+        let (state, (other_ext, other_algorithms)) = input;
+        if state.is_compression {
+            state.compression = self.select(other_ext, other_algorithms);
+        }
+        state.is_compression = state.compression.is_some();
         Ok(())
     }
 
@@ -105,22 +180,36 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateAccept {
     is_compression: bool,
+    compression: Option<BatchCompression>,
 }
 
 impl StateAccept {
     pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+        Self {
+            is_compression,
+            compression: None,
+        }
     }
 
-    pub(crate) const fn is_compression(&self) -> bool {
-        self.is_compression
+    pub(crate) const fn compression(&self) -> Option<BatchCompression> {
+        self.compression
     }
 
     #[cfg(test)]
     pub(crate) fn rand() -> Self {
         use rand::Rng;
         let mut rng = rand::thread_rng();
-        Self::new(rng.gen_bool(0.5))
+        let compression = match rng.gen_range(0..3) {
+            0 => None,
+            1 => Some(BatchCompression::Lz4),
+            _ => Some(BatchCompression::Zstd {
+                level: rng.gen_range(-5..=22),
+            }),
+        };
+        Self {
+            is_compression: compression.is_some(),
+            compression,
+        }
     }
 }
 
@@ -132,8 +221,14 @@ where
     type Output = Result<(), DidntWrite>;
 
     fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
-        let is_compression = u8::from(x.is_compression);
-        self.write(&mut *writer, is_compression)?;
+        match x.compression {
+            None => self.write(&mut *writer, 0u8)?,
+            Some(BatchCompression::Lz4) => self.write(&mut *writer, 1u8)?,
+            Some(BatchCompression::Zstd { level }) => {
+                self.write(&mut *writer, 2u8)?;
+                self.write(&mut *writer, level as u32)?;
+            }
+        }
         Ok(())
     }
 }
@@ -145,9 +240,22 @@ where
     type Error = DidntRead;
 
     fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
-        let is_compression: u8 = self.read(&mut *reader)?;
-        let is_compression = is_compression == 1;
-        Ok(StateAccept { is_compression })
+        let algorithm: u8 = self.read(&mut *reader)?;
+        let compression = match algorithm {
+            0 => None,
+            1 => Some(BatchCompression::Lz4),
+            2 => {
+                let level: u32 = self.read(&mut *reader)?;
+                Some(BatchCompression::Zstd {
+                    level: level as i32,
+                })
+            }
+            _ => return Err(DidntRead),
+        };
+        Ok(StateAccept {
+            is_compression: compression.is_some(),
+            compression,
+        })
     }
 }
 
@@ -155,27 +263,41 @@ where
 impl<'a> AcceptFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
-    type RecvInitSynIn = (&'a mut StateAccept, Option<init::ext::Compression>);
+    type RecvInitSynIn = (
+        &'a mut StateAccept,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithms>,
+        ),
+    );
     type RecvInitSynOut = ();
     async fn recv_init_syn(
         self,
         input: Self::RecvInitSynIn,
     ) -> Result<Self::RecvInitSynOut, Self::Error> {
-        let (state, other_ext) = input;
-        state.is_compression &= other_ext.is_some();
+        let (state, (other_ext, other_algorithms)) = input;
+        if state.is_compression {
+            state.compression = self.select(other_ext, other_algorithms);
+        }
+        state.is_compression = state.compression.is_some();
         Ok(())
     }
 
     type SendInitAckIn = &'a StateAccept;
-    type SendInitAckOut = Option<init::ext::Compression>;
+    type SendInitAckOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithms>,
+    );
     async fn send_init_ack(
         self,
         state: Self::SendInitAckIn,
     ) -> Result<Self::SendInitAckOut, Self::Error> {
-        let output = state
-            .is_compression
-            .then_some(init::ext::Compression::new());
-        Ok(output)
+        // Only advertise the selected algorithm
+        let mask = state
+            .compression
+            .map(|c| algorithm_mask(c.algorithm()))
+            .unwrap_or(0);
+        Ok(self.exts(state.is_compression, mask))
     }
 
     type RecvOpenSynIn = (&'a mut StateAccept, Option<open::ext::Compression>);
@@ -196,3 +318,102 @@ impl<'a> AcceptFsm for &'a CompressionFsm<'a> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zenoh_protocol::transport::init;
+    use zenoh_result::ZResult;
+
+    use super::{CompressionFsm, StateAccept, StateOpen};
+    use crate::{
+        common::batch::BatchCompression,
+        unicast::establishment::{AcceptFsm, OpenFsm},
+    };
+
+    const LZ4: BatchCompression = BatchCompression::Lz4;
+    const ZSTD: BatchCompression = BatchCompression::Zstd { level: 3 };
+
+    async fn test_negotiation(
+        open: &[BatchCompression],
+        accept: &[BatchCompression],
+    ) -> ZResult<(StateOpen, StateAccept)> {
+        let fsm_open = CompressionFsm::new(open);
+        let fsm_accept = CompressionFsm::new(accept);
+        let mut state_open = StateOpen::new(!open.is_empty());
+        let mut state_accept = StateAccept::new(!accept.is_empty());
+
+        let ext = (&fsm_open).send_init_syn(&state_open).await?;
+        (&fsm_accept)
+            .recv_init_syn((&mut state_accept, ext))
+            .await?;
+
+        let ext = (&fsm_accept).send_init_ack(&state_accept).await?;
+        (&fsm_open).recv_init_ack((&mut state_open, ext)).await?;
+
+        Ok((state_open, state_accept))
+    }
+
+    async fn test_negotiation_ok(
+        open: &[BatchCompression],
+        accept: &[BatchCompression],
+        expected: Option<BatchCompression>,
+    ) {
+        let (state_open, state_accept) = test_negotiation(open, accept).await.unwrap();
+        assert_eq!(
+            state_open.compression().map(|c| c.algorithm()),
+            expected.map(|c| c.algorithm())
+        );
+        assert_eq!(
+            state_accept.compression().map(|c| c.algorithm()),
+            expected.map(|c| c.algorithm())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_scenario_1() {
+        test_negotiation_ok(&[], &[], None).await;
+        test_negotiation_ok(&[LZ4], &[], None).await;
+        test_negotiation_ok(&[], &[ZSTD], None).await;
+        test_negotiation_ok(&[LZ4], &[ZSTD], None).await;
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_scenario_2() {
+        test_negotiation_ok(&[LZ4], &[LZ4], Some(LZ4)).await;
+        test_negotiation_ok(&[ZSTD], &[ZSTD], Some(ZSTD)).await;
+        test_negotiation_ok(&[LZ4, ZSTD], &[ZSTD], Some(ZSTD)).await;
+        test_negotiation_ok(&[ZSTD, LZ4], &[LZ4, ZSTD], Some(LZ4)).await;
+        test_negotiation_ok(&[LZ4, ZSTD], &[ZSTD, LZ4], Some(ZSTD)).await;
+    }
+
+    #[tokio::test]
+    async fn test_compression_negotiation_scenario_3() {
+        // The opener does not support algorithm negotiation
+        let fsm = CompressionFsm::new(&[ZSTD, LZ4]);
+        let mut state = StateAccept::new(true);
+        (&fsm)
+            .recv_init_syn((&mut state, (Some(init::ext::Compression::new()), None)))
+            .await
+            .unwrap();
+        assert_eq!(state.compression(), Some(LZ4));
+        let (ext, _) = (&fsm).send_init_ack(&state).await.unwrap();
+        assert!(ext.is_some());
+
+        // The accepter does not support algorithm negotiation
+        let fsm = CompressionFsm::new(&[ZSTD, LZ4]);
+        let mut state = StateOpen::new(true);
+        (&fsm)
+            .recv_init_ack((&mut state, (Some(init::ext::Compression::new()), None)))
+            .await
+            .unwrap();
+        assert_eq!(state.compression(), Some(LZ4));
+
+        let fsm = CompressionFsm::new(&[ZSTD]);
+        let mut state = StateOpen::new(true);
+        (&fsm)
+            .recv_init_ack((&mut state, (Some(init::ext::Compression::new()), None)))
+            .await
+            .unwrap();
+        assert_eq!(state.compression(), None);
+    }
+}
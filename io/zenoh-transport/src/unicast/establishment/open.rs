@@ -32,6 +32,10 @@ use zenoh_result::ZResult;
 use super::ext::shm::AuthSegment;
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
+#[cfg(feature = "auth_jwt")]
+use crate::unicast::establishment::ext::auth::JwtId;
+#[cfg(feature = "auth_pubkey")]
+use crate::unicast::establishment::ext::auth::PubKeyId;
 #[cfg(feature = "auth_usrpwd")]
 use crate::unicast::establishment::ext::auth::UsrPwdId;
 use crate::{
@@ -59,6 +63,7 @@ struct StateTransport {
     ext_shm: ext::shm::StateOpen,
     ext_lowlatency: ext::lowlatency::StateOpen,
     ext_patch: ext::patch::StateOpen,
+    ext_capabilities: ext::capabilities::StateOpen,
 }
 
 #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
@@ -126,6 +131,7 @@ struct OpenLink<'a> {
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
+    ext_capabilities: ext::capabilities::CapabilitiesFsm<'a>,
 }
 
 #[async_trait]
@@ -185,13 +191,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithms) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_syn(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
         // Extension Patch
@@ -201,6 +207,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Capabilities
+        let ext_capabilities = self
+            .ext_capabilities
+            .send_init_syn(&state.transport.ext_capabilities)
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let msg: TransportMessage = InitSyn {
             version: input.mine_version,
             whatami: input.mine_whatami,
@@ -215,7 +228,9 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             ext_mlink,
             ext_lowlatency,
             ext_compression,
+            ext_compression_algorithms,
             ext_patch,
+            ext_capabilities,
         }
         .into();
 
@@ -353,7 +368,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_ack((&mut state.link.ext_compression, init_ack.ext_compression))
+            .recv_init_ack((
+                &mut state.link.ext_compression,
+                (
+                    init_ack.ext_compression,
+                    init_ack.ext_compression_algorithms,
+                ),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -363,6 +384,15 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Capabilities
+        self.ext_capabilities
+            .recv_init_ack((
+                &mut state.transport.ext_capabilities,
+                init_ack.ext_capabilities,
+            ))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let output = RecvInitAckOut {
             other_zid: init_ack.zid,
             other_whatami: init_ack.whatami,
@@ -569,7 +599,7 @@ pub(crate) async fn open_link(
             mtu: link.get_mtu(),
             is_streamed,
             #[cfg(feature = "transport_compression")]
-            is_compression: false, // Perform the exchange Init/Open exchange with no compression
+            compression: None, // Perform the exchange Init/Open exchange with no compression
         },
         priorities: None,
         reliability: None,
@@ -590,8 +620,9 @@ pub(crate) async fn open_link(
         ext_auth: manager.state.unicast.authenticator.fsm(&manager.prng),
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
-        ext_compression: ext::compression::CompressionFsm::new(),
+        ext_compression: ext::compression::CompressionFsm::new(&manager.config.unicast.compression),
         ext_patch: ext::patch::PatchFsm::new(),
+        ext_capabilities: ext::capabilities::CapabilitiesFsm::new(),
     };
 
     // Clippy raises a warning because `batch_size::UNICAST` is currently equal to `BatchSize::MAX`.
@@ -624,6 +655,7 @@ pub(crate) async fn open_link(
                     manager.config.unicast.is_lowlatency,
                 ),
                 ext_patch: ext::patch::StateOpen::new(),
+                ext_capabilities: ext::capabilities::StateOpen::new(),
             },
             #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
             link: StateLink {
@@ -687,9 +719,14 @@ pub(crate) async fn open_link(
             false => None,
         },
         is_lowlatency: state.transport.ext_lowlatency.is_lowlatency(),
+        #[cfg(feature = "auth_pubkey")]
+        pubkey_id: PubKeyId(None),
         #[cfg(feature = "auth_usrpwd")]
         auth_id: UsrPwdId(None),
+        #[cfg(feature = "auth_jwt")]
+        jwt_id: JwtId(None),
         patch: state.transport.ext_patch.get(),
+        capabilities: state.transport.ext_capabilities.get(),
     };
 
     let o_config = TransportLinkUnicastConfig {
@@ -698,7 +735,7 @@ pub(crate) async fn open_link(
             mtu: state.transport.batch_size,
             is_streamed,
             #[cfg(feature = "transport_compression")]
-            is_compression: state.link.ext_compression.is_compression(),
+            compression: state.link.ext_compression.compression(),
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
@@ -72,12 +72,9 @@ impl TransportLinkUnicast {
             inner: self.clone(),
             buffer: zcondfeat!(
                 "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
+                self.config.batch.compression.map(|c| BBuf::with_capacity(
+                    c.max_output_size(self.config.batch.mtu as usize)
+                )),
                 None
             ),
         }
@@ -290,10 +287,9 @@ impl MaybeOpenAck {
                     // !!! Workaround !!! as the state of the link is set with compression once the OpenSyn is received.
                     // Here we are disabling the compression just to send the OpenAck (that is not supposed to be compressed).
                     // Then then we re-enable it, in case it was enabled, after the OpenAck has been sent.
-                    let compression = self.link.inner.config.batch.is_compression;
-                    self.link.inner.config.batch.is_compression = false;
+                    let compression = self.link.inner.config.batch.compression.take();
                     self.link.send(&msg.into()).await?;
-                    self.link.inner.config.batch.is_compression = compression;
+                    self.link.inner.config.batch.compression = compression;
                 },
                 {
                     self.link.send(&msg.into()).await?;
@@ -27,6 +27,8 @@ use zenoh_config::CompressionUnicastConf;
 use zenoh_config::ShmConf;
 use zenoh_config::{Config, LinkTxConf, QoSUnicastConf, TransportUnicastConf};
 use zenoh_core::{zasynclock, zcondfeat};
+#[cfg(feature = "auth_pubkey")]
+use zenoh_core::{zasyncread, zasyncwrite};
 use zenoh_crypto::PseudoRng;
 use zenoh_link::*;
 use zenoh_protocol::{
@@ -40,6 +42,10 @@ use zenoh_shm::reader::ShmReader;
 #[cfg(feature = "shared-memory")]
 use super::establishment::ext::shm::AuthUnicast;
 use super::{link::LinkUnicastWithOpenAck, transport_unicast_inner::InitTransportResult};
+#[cfg(feature = "transport_compression")]
+use crate::common::batch::BatchCompression;
+#[cfg(feature = "auth_pubkey")]
+use crate::unicast::authentication::AuthId;
 #[cfg(feature = "transport_auth")]
 use crate::unicast::establishment::ext::auth::Auth;
 #[cfg(feature = "transport_multilink")]
@@ -72,6 +78,8 @@ pub struct TransportManagerConfigUnicast {
     pub is_shm: bool,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub compression: Vec<BatchCompression>,
 }
 
 pub struct TransportManagerStateUnicast {
@@ -120,6 +128,8 @@ pub struct TransportManagerBuilderUnicast {
     pub(super) is_lowlatency: bool,
     #[cfg(feature = "transport_compression")]
     pub(super) is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub(super) compression: Vec<BatchCompression>,
 }
 
 impl TransportManagerBuilderUnicast {
@@ -187,6 +197,13 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    /// The compression algorithms to negotiate, in order of preference.
+    #[cfg(feature = "transport_compression")]
+    pub fn compression_algorithms(mut self, algorithms: Vec<BatchCompression>) -> Self {
+        self.compression = algorithms;
+        self
+    }
+
     pub async fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderUnicast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -217,7 +234,9 @@ impl TransportManagerBuilderUnicast {
         }
         #[cfg(feature = "transport_compression")]
         {
-            self = self.compression(*config.transport().unicast().compression().enabled());
+            let compression = config.transport().unicast().compression();
+            self = self.compression(*compression.enabled());
+            self = self.compression_algorithms(batch_compressions(compression));
         }
 
         Ok(self)
@@ -247,6 +266,8 @@ impl TransportManagerBuilderUnicast {
             is_lowlatency: self.is_lowlatency,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_compression")]
+            compression: self.compression,
         };
 
         let state = TransportManagerStateUnicast {
@@ -272,6 +293,22 @@ impl TransportManagerBuilderUnicast {
     }
 }
 
+#[cfg(feature = "transport_compression")]
+fn batch_compressions(conf: &CompressionUnicastConf) -> Vec<BatchCompression> {
+    let mut compressions: Vec<BatchCompression> = vec![];
+    for c in conf.algorithms() {
+        let compression = BatchCompression::new(c.algorithm, c.level);
+        // Only keep the first occurrence of each algorithm
+        if !compressions
+            .iter()
+            .any(|x| x.algorithm() == compression.algorithm())
+        {
+            compressions.push(compression);
+        }
+    }
+    compressions
+}
+
 impl Default for TransportManagerBuilderUnicast {
     fn default() -> Self {
         let transport = TransportUnicastConf::default();
@@ -299,6 +336,8 @@ impl Default for TransportManagerBuilderUnicast {
             is_lowlatency: *transport.lowlatency(),
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_compression")]
+            compression: batch_compressions(&compression),
         }
     }
 }
@@ -438,6 +477,14 @@ impl TransportManager {
         vec
     }
 
+    pub async fn get_certificates_unicast(&self) -> Vec<ListenerCertificate> {
+        let mut vec: Vec<ListenerCertificate> = vec![];
+        for p in zasynclock!(self.state.unicast.protocols).values() {
+            vec.extend(p.get_certificates().await);
+        }
+        vec
+    }
+
     /*************************************/
     /*             TRANSPORT             */
     /*************************************/
@@ -492,6 +539,90 @@ impl TransportManager {
         Ok(transport)
     }
 
+    #[cfg(feature = "auth_jwt")]
+    fn close_transport_unicast_on_expiry(
+        &self,
+        transport: &Arc<dyn TransportUnicastTrait>,
+        expiry: std::time::SystemTime,
+    ) {
+        let transport = Arc::downgrade(transport);
+        let timeout = expiry
+            .duration_since(std::time::SystemTime::now())
+            .unwrap_or_default();
+        self.task_controller
+            .spawn_abortable_with_rt(zenoh_runtime::ZRuntime::Net, async move {
+                tokio::time::sleep(timeout).await;
+                if let Some(transport) = transport.upgrade() {
+                    tracing::debug!(
+                        "Closing transport with peer {}: its bearer token expired",
+                        transport.get_zid()
+                    );
+                    let _ = transport.close(close::reason::EXPIRED).await;
+                }
+            });
+    }
+
+    /// Reloads the known keys file of the pubkey authenticator whenever it is modified, closing
+    /// the transports authenticated with a removed key if configured to do so.
+    #[cfg(feature = "auth_pubkey")]
+    pub(crate) fn watch_known_keys(&self) {
+        if self.state.unicast.authenticator.pubkey().is_none() {
+            return;
+        }
+        let manager = self.clone();
+        self.task_controller
+            .spawn_abortable_with_rt(zenoh_runtime::ZRuntime::Net, async move {
+                let Some(pubkey) = manager.state.unicast.authenticator.pubkey() else {
+                    return;
+                };
+                let Some(interval) = zasyncread!(pubkey).known_keys_reload_interval() else {
+                    return;
+                };
+                loop {
+                    tokio::time::sleep(interval).await;
+                    // Read the file before locking the authenticator, not to stall the handshakes
+                    let Some(known_keys_file) = zasyncread!(pubkey).known_keys_file() else {
+                        return;
+                    };
+                    let known_keys = match known_keys_file.reload().await {
+                        Ok(Some(known_keys)) => known_keys,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            tracing::error!("Couldn't reload known keys file: {}", e);
+                            continue;
+                        }
+                    };
+                    let mut w_pubkey = zasyncwrite!(pubkey);
+                    w_pubkey.set_known_keys(known_keys);
+                    tracing::info!("Known keys file has been reloaded");
+                    if !w_pubkey.close_revoked_transports() {
+                        continue;
+                    }
+                    let Some(known) = w_pubkey.known_fingerprints() else {
+                        continue;
+                    };
+                    drop(w_pubkey);
+
+                    let transports = zasynclock!(manager.state.unicast.transports)
+                        .values()
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    for transport in transports {
+                        let is_revoked = transport.get_auth_ids().iter().any(|id| {
+                            matches!(id, AuthId::PubKeyFingerprint(fingerprint) if !known.contains(fingerprint))
+                        });
+                        if is_revoked {
+                            tracing::debug!(
+                                "Closing transport with peer {}: its public key has been revoked",
+                                transport.get_zid()
+                            );
+                            let _ = transport.close(close::reason::GENERIC).await;
+                        }
+                    }
+                }
+            });
+    }
+
     fn notify_new_link_unicast(transport: &Arc<dyn TransportUnicastTrait>, link: Link) {
         if let Some(callback) = &transport.get_callback() {
             callback.new_link(link);
@@ -619,6 +750,12 @@ impl TransportManager {
 
         drop(add_link_guard);
 
+        // Close the transport once the bearer token of the remote expires
+        #[cfg(feature = "auth_jwt")]
+        if let Some(expiry) = config.jwt_id.expiry() {
+            self.close_transport_unicast_on_expiry(&t, expiry);
+        }
+
         zcondfeat!(
             "shared-memory",
             {
@@ -34,7 +34,11 @@ use zenoh_link::Link;
 use zenoh_protocol::{
     core::{Bits, WhatAmI, ZenohIdProto},
     network::NetworkMessage,
-    transport::{close, init::ext::PatchType, TransportSn},
+    transport::{
+        close,
+        init::ext::{CapabilitiesType, PatchType},
+        TransportSn,
+    },
 };
 use zenoh_result::{zerror, ZResult};
 
@@ -43,6 +47,10 @@ use super::{TransportPeer, TransportPeerEventHandler};
 #[cfg(feature = "shared-memory")]
 use crate::shm::TransportShmConfig;
 use crate::unicast::authentication::AuthId;
+#[cfg(feature = "auth_jwt")]
+use crate::unicast::establishment::ext::auth::JwtId;
+#[cfg(feature = "auth_pubkey")]
+use crate::unicast::establishment::ext::auth::PubKeyId;
 #[cfg(feature = "auth_usrpwd")]
 use crate::unicast::establishment::ext::auth::UsrPwdId;
 
@@ -61,9 +69,14 @@ pub(crate) struct TransportConfigUnicast {
     #[cfg(feature = "shared-memory")]
     pub(crate) shm: Option<TransportShmConfig>,
     pub(crate) is_lowlatency: bool,
+    #[cfg(feature = "auth_pubkey")]
+    pub(crate) pubkey_id: PubKeyId,
     #[cfg(feature = "auth_usrpwd")]
     pub(crate) auth_id: UsrPwdId,
+    #[cfg(feature = "auth_jwt")]
+    pub(crate) jwt_id: JwtId,
     pub(crate) patch: PatchType,
+    pub(crate) capabilities: CapabilitiesType,
 }
 
 /// [`TransportUnicast`] is the transport handler returned
@@ -117,6 +130,13 @@ impl TransportUnicast {
         Ok(tp)
     }
 
+    /// Returns the optional features of the protocol negotiated with the peer.
+    #[inline(always)]
+    pub fn get_capabilities(&self) -> ZResult<CapabilitiesType> {
+        let transport = self.get_inner()?;
+        Ok(transport.get_config().capabilities)
+    }
+
     #[inline(always)]
     pub fn get_links(&self) -> ZResult<Vec<Link>> {
         let transport = self.get_inner()?;
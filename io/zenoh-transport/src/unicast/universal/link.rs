@@ -59,7 +59,7 @@ impl TransportLinkUnicastUniversal {
                 mtu: link.config.batch.mtu,
                 is_streamed: link.link.is_streamed(),
                 #[cfg(feature = "transport_compression")]
-                is_compression: link.config.batch.is_compression,
+                compression: link.config.batch.compression,
             },
             queue_size: transport.manager.config.queue_size,
             wait_before_drop: transport.manager.config.wait_before_drop,
@@ -189,6 +189,8 @@ async fn tx_task(
                         {
                             stats.inc_tx_t_msgs(batch.stats.t_msgs);
                             stats.inc_tx_bytes(batch.len() as usize);
+                            #[cfg(feature = "transport_compression")]
+                            stats.inc_tx_compressed_batches(&batch);
                         }
 
                         // Reinsert the batch into the queue
@@ -230,6 +232,8 @@ async fn tx_task(
         {
             stats.inc_tx_t_msgs(b.stats.t_msgs);
             stats.inc_tx_bytes(b.len() as usize);
+            #[cfg(feature = "transport_compression")]
+            stats.inc_tx_compressed_batches(&b);
         }
     }
 
@@ -389,9 +389,15 @@ impl TransportUnicastTrait for TransportUnicastUniversal {
             .iter()
             .map(|l| l.link.link.get_auth_id().to_owned().into())
             .collect();
+        // Convert pubkey auth id to AuthId
+        #[cfg(feature = "auth_pubkey")]
+        auth_ids.push(self.config.pubkey_id.clone().into());
         // Convert usrpwd auth id to AuthId
         #[cfg(feature = "auth_usrpwd")]
         auth_ids.push(self.config.auth_id.clone().into());
+        // Convert jwt auth id to AuthId
+        #[cfg(feature = "auth_jwt")]
+        auth_ids.push(self.config.jwt_id.clone().into());
         auth_ids
     }
 
@@ -103,7 +103,8 @@ async fn auth_pubkey(endpoint: &EndPoint, lowlatency_transport: bool) {
     use rsa::{BigUint, RsaPrivateKey, RsaPublicKey};
     use zenoh_transport::{
         unicast::{
-            establishment::ext::auth::AuthPubKey,
+            authentication::AuthId,
+            establishment::ext::auth::{AuthPubKey, ZPublicKey},
             test_helpers::make_basic_transport_manager_builder,
         },
         TransportManager,
@@ -121,6 +122,9 @@ async fn auth_pubkey(endpoint: &EndPoint, lowlatency_transport: bool) {
     ]);
     let e = BigUint::from_bytes_le(&[0x01, 0x00, 0x01]);
     let client01_pub_key = RsaPublicKey::new(n, e).unwrap();
+    let client01_fingerprint = ZPublicKey::from(client01_pub_key.clone())
+        .fingerprint()
+        .unwrap();
 
     let n = BigUint::from_bytes_le(&[
         0x41, 0x74, 0xc6, 0x40, 0x18, 0x63, 0xbd, 0x59, 0xe6, 0x0d, 0xe9, 0x23, 0x3e, 0x95, 0xca,
@@ -320,6 +324,12 @@ async fn auth_pubkey(endpoint: &EndPoint, lowlatency_transport: bool) {
     println!("Transport Authenticator PubKey [2a1]");
     let c_ses1 = ztimeout!(client01_manager.open_transport_unicast(endpoint.clone())).unwrap();
     assert_eq!(c_ses1.get_links().unwrap().len(), 1);
+    // The router identifies client01 by the fingerprint of its public key
+    let r_ses1 = ztimeout!(router_manager.get_transport_unicast(&client01_id)).unwrap();
+    assert!(r_ses1
+        .get_auth_ids()
+        .unwrap()
+        .contains(&AuthId::PubKeyFingerprint(client01_fingerprint)));
 
     /* [2b] */
     // Open a first transport from client02 to the router
@@ -402,6 +412,230 @@ async fn auth_pubkey(endpoint: &EndPoint, lowlatency_transport: bool) {
     tokio::time::sleep(SLEEP).await;
 }
 
+#[cfg(feature = "auth_pubkey")]
+async fn auth_pubkey_rotation(endpoint: &EndPoint, lowlatency_transport: bool) {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ed25519_dalek::{
+        pkcs8::{EncodePrivateKey, EncodePublicKey},
+        SigningKey,
+    };
+    use rsa::{pkcs1::EncodeRsaPublicKey, pkcs8::LineEnding, RsaPrivateKey, RsaPublicKey};
+    use zenoh_config::PubKeyConf;
+    use zenoh_transport::{
+        unicast::{
+            authentication::AuthId,
+            establishment::ext::auth::{AuthPubKey, ZPublicKey},
+            test_helpers::make_basic_transport_manager_builder,
+        },
+        TransportManager,
+    };
+
+    let mut rng = rand::thread_rng();
+
+    /* [CLIENT] */
+    // Client01 rotates its Ed25519 key, presenting both its new and its old key
+    let client01_id = ZenohIdProto::try_from([2]).unwrap();
+    let client01_old_key = SigningKey::generate(&mut rng);
+    let client01_new_key = SigningKey::generate(&mut rng);
+    let mut auth_pubkey = AuthPubKey::new(
+        client01_new_key.verifying_key().into(),
+        client01_new_key.clone().into(),
+    );
+    auth_pubkey.add_key_pair(
+        client01_old_key.verifying_key().into(),
+        client01_old_key.clone().into(),
+    );
+    let mut auth = Auth::empty();
+    auth.set_pubkey(Some(auth_pubkey));
+    let unicast = make_basic_transport_manager_builder(
+        #[cfg(feature = "shared-memory")]
+        false,
+        lowlatency_transport,
+    )
+    .authenticator(auth);
+    let client01_manager = TransportManager::builder()
+        .whatami(WhatAmI::Client)
+        .zid(client01_id)
+        .unicast(unicast)
+        .build(Arc::new(SHClientAuthenticator))
+        .unwrap();
+
+    // Client02 has an RSA key
+    let client02_id = ZenohIdProto::try_from([3]).unwrap();
+    let client02_pri_key = RsaPrivateKey::new(&mut rng, 512).unwrap();
+    let client02_pub_key = RsaPublicKey::from(&client02_pri_key);
+    let mut auth = Auth::empty();
+    auth.set_pubkey(Some(AuthPubKey::new(
+        client02_pub_key.clone().into(),
+        client02_pri_key.into(),
+    )));
+    let unicast = make_basic_transport_manager_builder(
+        #[cfg(feature = "shared-memory")]
+        false,
+        lowlatency_transport,
+    )
+    .authenticator(auth);
+    let client02_manager = TransportManager::builder()
+        .whatami(WhatAmI::Client)
+        .zid(client02_id)
+        .unicast(unicast)
+        .build(Arc::new(SHClientAuthenticator))
+        .unwrap();
+
+    /* [ROUTER] */
+    // The router only knows the old key of client01 at first. The file is named after a counter
+    // rather than the endpoint, which may embed certificates in PEM format.
+    static KNOWN_KEYS_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+    let known_keys_file = std::env::temp_dir().join(format!(
+        "zenoh-test-known-keys-{}-{}.pem",
+        std::process::id(),
+        KNOWN_KEYS_FILE_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(
+        &known_keys_file,
+        client01_old_key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap(),
+    )
+    .unwrap();
+
+    let router_id = ZenohIdProto::try_from([1]).unwrap();
+    let router_handler = Arc::new(SHRouterAuthenticator::new());
+    let router_key = SigningKey::generate(&mut rng);
+    let mut config = PubKeyConf::default();
+    config
+        .set_public_key_pem(Some(
+            router_key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        ))
+        .unwrap();
+    config
+        .set_private_key_pem(Some(
+            router_key.to_pkcs8_pem(LineEnding::LF).unwrap().to_string(),
+        ))
+        .unwrap();
+    config
+        .set_known_keys_file(Some(known_keys_file.to_str().unwrap().to_owned()))
+        .unwrap();
+    config.set_known_keys_reload_interval(Some(100)).unwrap();
+    config.set_close_revoked_transports(true).unwrap();
+    let mut auth = Auth::empty();
+    auth.set_pubkey(AuthPubKey::from_config(&config).unwrap());
+    let unicast = make_basic_transport_manager_builder(
+        #[cfg(feature = "shared-memory")]
+        false,
+        lowlatency_transport,
+    )
+    .authenticator(auth);
+    let router_manager = TransportManager::builder()
+        .whatami(WhatAmI::Router)
+        .zid(router_id)
+        .unicast(unicast)
+        .build(router_handler.clone())
+        .unwrap();
+
+    /* [1] */
+    println!("\nTransport Authenticator PubKey Rotation [1a1]");
+    // Add the locator on the router
+    ztimeout!(router_manager.add_listener(endpoint.clone())).unwrap();
+
+    /* [2] */
+    // Open a transport from client02 to the router
+    // -> This should be rejected
+    println!("Transport Authenticator PubKey Rotation [2a1]");
+    let res = ztimeout!(client02_manager.open_transport_unicast(endpoint.clone()));
+    println!("Transport Authenticator PubKey Rotation [2a1]: {res:?}");
+    assert!(res.is_err());
+
+    // Open a transport from client01 to the router
+    // -> This should be accepted with the old key of client01
+    println!("Transport Authenticator PubKey Rotation [2b1]");
+    let c_ses1 = ztimeout!(client01_manager.open_transport_unicast(endpoint.clone())).unwrap();
+    assert_eq!(c_ses1.get_links().unwrap().len(), 1);
+    let r_ses1 = ztimeout!(router_manager.get_transport_unicast(&client01_id)).unwrap();
+    let fingerprint = ZPublicKey::from(client01_old_key.verifying_key())
+        .fingerprint()
+        .unwrap();
+    assert!(r_ses1
+        .get_auth_ids()
+        .unwrap()
+        .contains(&AuthId::PubKeyFingerprint(fingerprint)));
+
+    /* [3] */
+    // Revoke the old key of client01 and allow its new key and the key of client02
+    println!("Transport Authenticator PubKey Rotation [3a1]");
+    std::fs::write(
+        &known_keys_file,
+        [
+            client01_new_key
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+            client02_pub_key.to_pkcs1_pem(LineEnding::LF).unwrap(),
+        ]
+        .concat(),
+    )
+    .unwrap();
+
+    // The transport of client01 is closed since its key has been revoked
+    println!("Transport Authenticator PubKey Rotation [3a2]");
+    ztimeout!(async {
+        while !router_manager.get_transports_unicast().await.is_empty()
+            || !client01_manager.get_transports_unicast().await.is_empty()
+        {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    // Open a transport from client01 and client02 to the router
+    // -> These should be accepted, client01 being authenticated with its new key
+    println!("Transport Authenticator PubKey Rotation [3b1]");
+    let c_ses1 = ztimeout!(client01_manager.open_transport_unicast(endpoint.clone())).unwrap();
+    let r_ses1 = ztimeout!(router_manager.get_transport_unicast(&client01_id)).unwrap();
+    let fingerprint = ZPublicKey::from(client01_new_key.verifying_key())
+        .fingerprint()
+        .unwrap();
+    assert!(r_ses1
+        .get_auth_ids()
+        .unwrap()
+        .contains(&AuthId::PubKeyFingerprint(fingerprint)));
+    println!("Transport Authenticator PubKey Rotation [3b2]");
+    let c_ses2 = ztimeout!(client02_manager.open_transport_unicast(endpoint.clone())).unwrap();
+
+    /* [4] */
+    // Close the sessions
+    println!("Transport Authenticator PubKey Rotation [4a1]");
+    ztimeout!(c_ses1.close()).unwrap();
+    ztimeout!(c_ses2.close()).unwrap();
+    ztimeout!(async {
+        while !router_manager.get_transports_unicast().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    /* [5] */
+    // Perform clean up of the open locators
+    println!("Transport Authenticator PubKey Rotation [5a1]");
+    ztimeout!(router_manager.del_listener(endpoint)).unwrap();
+    ztimeout!(async {
+        while !router_manager.get_listeners().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    ztimeout!(client01_manager.close());
+    ztimeout!(client02_manager.close());
+    ztimeout!(router_manager.close());
+    let _ = std::fs::remove_file(&known_keys_file);
+
+    // Wait a little bit
+    tokio::time::sleep(SLEEP).await;
+}
+
 #[cfg(feature = "auth_usrpwd")]
 async fn auth_usrpwd(endpoint: &EndPoint, lowlatency_transport: bool) {
     use zenoh_transport::{
@@ -610,11 +844,154 @@ async fn auth_usrpwd(endpoint: &EndPoint, lowlatency_transport: bool) {
     tokio::time::sleep(SLEEP).await;
 }
 
+#[cfg(feature = "auth_jwt")]
+async fn auth_jwt(endpoint: &EndPoint, lowlatency_transport: bool) {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use zenoh_transport::{
+        unicast::{
+            authentication::AuthId, establishment::ext::auth::AuthJwt,
+            test_helpers::make_basic_transport_manager_builder,
+        },
+        TransportManager,
+    };
+
+    let secret = b"secret";
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let token = |sub: &str, exp: u64, secret: &[u8]| {
+        encode(
+            &Header::default(),
+            &serde_json::json!({ "sub": sub, "exp": exp }),
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    };
+
+    /* [CLIENT] */
+    // A token expiring shortly
+    let client01_id = ZenohIdProto::try_from([2]).unwrap();
+    let token01 = token("user01", now + 3, secret);
+    // A token signed with another secret
+    let client02_id = ZenohIdProto::try_from([3]).unwrap();
+    let token02 = token("user02", now + 3600, b"invalid");
+    // An expired token
+    let client03_id = ZenohIdProto::try_from([4]).unwrap();
+    let token03 = token("user03", now - 3600, secret);
+
+    /* [ROUTER] */
+    let router_id = ZenohIdProto::try_from([1]).unwrap();
+    let router_handler = Arc::new(SHRouterAuthenticator::new());
+    let mut auth_jwt_router = AuthJwt::new(None);
+    auth_jwt_router.add_secret(secret);
+    auth_jwt_router.set_leeway(0);
+    let mut auth_router = Auth::empty();
+    auth_router.set_jwt(Some(auth_jwt_router));
+    let unicast = make_basic_transport_manager_builder(
+        #[cfg(feature = "shared-memory")]
+        false,
+        lowlatency_transport,
+    )
+    .authenticator(auth_router);
+    let router_manager = TransportManager::builder()
+        .whatami(WhatAmI::Router)
+        .zid(router_id)
+        .unicast(unicast)
+        .build(router_handler.clone())
+        .unwrap();
+
+    let client_manager = |zid: ZenohIdProto, token: String| {
+        let mut auth_client = Auth::empty();
+        auth_client.set_jwt(Some(AuthJwt::new(Some(token))));
+        let unicast = make_basic_transport_manager_builder(
+            #[cfg(feature = "shared-memory")]
+            false,
+            lowlatency_transport,
+        )
+        .authenticator(auth_client);
+        TransportManager::builder()
+            .whatami(WhatAmI::Client)
+            .zid(zid)
+            .unicast(unicast)
+            .build(Arc::new(SHClientAuthenticator))
+            .unwrap()
+    };
+    let client01_manager = client_manager(client01_id, token01);
+    let client02_manager = client_manager(client02_id, token02);
+    let client03_manager = client_manager(client03_id, token03);
+
+    /* [1] */
+    println!("\nTransport Authenticator Jwt [1a1]");
+    // Add the locator on the router
+    let res = ztimeout!(router_manager.add_listener(endpoint.clone()));
+    println!("Transport Authenticator Jwt [1a1]: {res:?}");
+    assert!(res.is_ok());
+
+    /* [2] */
+    // Open a transport from client01 to the router
+    // -> This should be accepted
+    println!("Transport Authenticator Jwt [2a1]");
+    let res = ztimeout!(client01_manager.open_transport_unicast(endpoint.clone()));
+    println!("Transport Authenticator Jwt [2a1]: {res:?}");
+    assert!(res.is_ok());
+
+    // The router identifies client01 by the subject of its token
+    let r_ses1 = ztimeout!(router_manager.get_transport_unicast(&client01_id)).unwrap();
+    assert!(r_ses1
+        .get_auth_ids()
+        .unwrap()
+        .contains(&AuthId::Username("user01".to_owned())));
+
+    /* [3] */
+    // Open a transport from client02 and client03 to the router
+    // -> These should be rejected
+    println!("Transport Authenticator Jwt [3a1]");
+    let res = ztimeout!(client02_manager.open_transport_unicast(endpoint.clone()));
+    println!("Transport Authenticator Jwt [3a1]: {res:?}");
+    assert!(res.is_err());
+    println!("Transport Authenticator Jwt [3a2]");
+    let res = ztimeout!(client03_manager.open_transport_unicast(endpoint.clone()));
+    println!("Transport Authenticator Jwt [3a2]: {res:?}");
+    assert!(res.is_err());
+
+    /* [4] */
+    // The transport of client01 is closed once its token expires
+    println!("Transport Authenticator Jwt [4a1]");
+    ztimeout!(async {
+        while !router_manager.get_transports_unicast().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    /* [5] */
+    // Perform clean up of the open locators
+    println!("Transport Authenticator Jwt [5a1]");
+    let res = ztimeout!(router_manager.del_listener(endpoint));
+    println!("Transport Authenticator Jwt [5a1]: {res:?}");
+    assert!(res.is_ok());
+
+    ztimeout!(async {
+        while !router_manager.get_listeners().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    // Wait a little bit
+    tokio::time::sleep(SLEEP).await;
+}
+
 async fn run(endpoint: &EndPoint, lowlatency_transport: bool) {
     #[cfg(feature = "auth_pubkey")]
     auth_pubkey(endpoint, lowlatency_transport).await;
+    #[cfg(feature = "auth_pubkey")]
+    auth_pubkey_rotation(endpoint, lowlatency_transport).await;
     #[cfg(feature = "auth_usrpwd")]
     auth_usrpwd(endpoint, lowlatency_transport).await;
+    #[cfg(feature = "auth_jwt")]
+    auth_jwt(endpoint, lowlatency_transport).await;
 }
 
 async fn run_with_universal_transport(endpoint: &EndPoint) {
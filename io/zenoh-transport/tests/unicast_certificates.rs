@@ -0,0 +1,295 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(any(feature = "transport_tls", feature = "transport_quic"))]
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, CertificateRevocationListParams,
+    DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa, KeyIdMethod, KeyPair,
+    KeyUsagePurpose, RevocationReason, RevokedCertParams, SerialNumber,
+};
+use time::OffsetDateTime;
+use zenoh_core::ztimeout;
+use zenoh_protocol::core::{EndPoint, WhatAmI, ZenohIdProto};
+use zenoh_transport::{DummyTransportEventHandler, TransportManager};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_millis(100);
+const RELOAD_INTERVAL_MS: &str = "100";
+
+struct TestCa {
+    certificate: Certificate,
+    key: KeyPair,
+}
+
+impl TestCa {
+    fn new() -> Self {
+        let mut params = CertificateParams::default();
+        params.distinguished_name = distinguished_name("zenoh-test-ca");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        let key = KeyPair::generate().unwrap();
+        let certificate = params.self_signed(&key).unwrap();
+        Self { certificate, key }
+    }
+
+    /// Returns the PEM certificate and key of a leaf certificate signed by this CA.
+    fn issue(&self, name: &str, serial: u64, usage: ExtendedKeyUsagePurpose) -> (String, String) {
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.distinguished_name = distinguished_name(name);
+        params.serial_number = Some(SerialNumber::from(serial));
+        params.extended_key_usages = vec![usage];
+        params.use_authority_key_identifier_extension = true;
+        let key = KeyPair::generate().unwrap();
+        let certificate = params
+            .signed_by(&key, &self.certificate, &self.key)
+            .unwrap();
+        (certificate.pem(), key.serialize_pem())
+    }
+
+    /// Returns a PEM CRL signed by this CA revoking the given serial numbers.
+    fn crl(&self, number: u64, revoked: &[u64]) -> String {
+        let now = OffsetDateTime::now_utc();
+        CertificateRevocationListParams {
+            this_update: now - time::Duration::minutes(1),
+            next_update: now + time::Duration::days(1),
+            crl_number: SerialNumber::from(number),
+            issuing_distribution_point: None,
+            revoked_certs: revoked
+                .iter()
+                .map(|serial| RevokedCertParams {
+                    serial_number: SerialNumber::from(*serial),
+                    revocation_time: now - time::Duration::minutes(1),
+                    reason_code: Some(RevocationReason::KeyCompromise),
+                    invalidity_date: None,
+                })
+                .collect(),
+            key_identifier_method: KeyIdMethod::Sha256,
+        }
+        .signed_by(&self.certificate, &self.key)
+        .unwrap()
+        .pem()
+        .unwrap()
+    }
+}
+
+fn distinguished_name(common_name: &str) -> DistinguishedName {
+    let mut name = DistinguishedName::new();
+    name.push(DnType::CommonName, common_name);
+    name
+}
+
+struct TestFiles {
+    dir: PathBuf,
+}
+
+impl TestFiles {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "zenoh-test-certificates-{name}-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    fn write(&self, file: &str, content: &str) -> String {
+        let path = self.dir.join(file);
+        // Write a temporary file and rename it to never expose a partially written file
+        let tmp = self.dir.join(format!("{file}.tmp"));
+        std::fs::write(&tmp, content).unwrap();
+        std::fs::rename(&tmp, &path).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+}
+
+impl Drop for TestFiles {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn certificates_reload_and_revocation(locator: &str, name: &str) {
+    // TLS and QUIC links share the same configuration keys
+    #[cfg(not(feature = "transport_tls"))]
+    use zenoh_link::quic::config::*;
+    #[cfg(feature = "transport_tls")]
+    use zenoh_link::tls::config::*;
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let ca = TestCa::new();
+    let (server01_cert, server01_key) =
+        ca.issue("server01", 1, ExtendedKeyUsagePurpose::ServerAuth);
+    let (server02_cert, server02_key) =
+        ca.issue("server02", 2, ExtendedKeyUsagePurpose::ServerAuth);
+    let (client01_cert, client01_key) =
+        ca.issue("client01", 3, ExtendedKeyUsagePurpose::ClientAuth);
+    let (client02_cert, client02_key) =
+        ca.issue("client02", 4, ExtendedKeyUsagePurpose::ClientAuth);
+    let ca_cert = ca.certificate.pem();
+
+    let files = TestFiles::new(name);
+    let ca_file = files.write("ca.pem", &ca_cert);
+    let crl_file = files.write("crl.pem", &ca.crl(1, &[]));
+    let cert_file = files.write("cert.pem", &server01_cert);
+    let key_file = files.write("key.pem", &server01_key);
+
+    let mut listen_endpoint: EndPoint = locator.parse().unwrap();
+    listen_endpoint
+        .config_mut()
+        .extend_from_iter(
+            [
+                (TLS_ROOT_CA_CERTIFICATE_FILE, ca_file.as_str()),
+                (TLS_LISTEN_CERTIFICATE_FILE, cert_file.as_str()),
+                (TLS_LISTEN_PRIVATE_KEY_FILE, key_file.as_str()),
+                (TLS_CRL_FILE, crl_file.as_str()),
+                (TLS_ENABLE_MTLS, "true"),
+                (TLS_CERTIFICATE_RELOAD_INTERVAL_MS, RELOAD_INTERVAL_MS),
+            ]
+            .iter()
+            .copied(),
+        )
+        .unwrap();
+
+    let connect_endpoint = |cert: &str, key: &str| {
+        let mut endpoint: EndPoint = locator.parse().unwrap();
+        endpoint
+            .config_mut()
+            .extend_from_iter(
+                [
+                    (TLS_ROOT_CA_CERTIFICATE_RAW, ca_cert.as_str()),
+                    (TLS_CONNECT_CERTIFICATE_RAW, cert),
+                    (TLS_CONNECT_PRIVATE_KEY_RAW, key),
+                    (TLS_ENABLE_MTLS, "true"),
+                ]
+                .iter()
+                .copied(),
+            )
+            .unwrap();
+        endpoint
+    };
+
+    /* [ROUTER] */
+    let router_manager = TransportManager::builder()
+        .whatami(WhatAmI::Router)
+        .zid(ZenohIdProto::try_from([1]).unwrap())
+        .build(Arc::new(DummyTransportEventHandler))
+        .unwrap();
+
+    /* [CLIENTS] */
+    let client01_manager = TransportManager::builder()
+        .whatami(WhatAmI::Client)
+        .zid(ZenohIdProto::try_from([2]).unwrap())
+        .build(Arc::new(DummyTransportEventHandler))
+        .unwrap();
+    let client02_manager = TransportManager::builder()
+        .whatami(WhatAmI::Client)
+        .zid(ZenohIdProto::try_from([3]).unwrap())
+        .build(Arc::new(DummyTransportEventHandler))
+        .unwrap();
+
+    /* [1] */
+    // The loaded listener certificate is exposed
+    let res = ztimeout!(router_manager.add_listener(listen_endpoint.clone()));
+    println!("Certificates [1a1]: {res:?}");
+    assert!(res.is_ok());
+    let certificates = ztimeout!(router_manager.get_certificates_unicast());
+    println!("Certificates [1a2]: {certificates:?}");
+    assert_eq!(certificates.len(), 1);
+    assert_eq!(certificates[0].subject, "CN=server01");
+
+    // Both clients are accepted
+    let res =
+        ztimeout!(client01_manager
+            .open_transport_unicast(connect_endpoint(&client01_cert, &client01_key)));
+    println!("Certificates [1b1]: {res:?}");
+    assert!(res.is_ok());
+    ztimeout!(res.unwrap().close()).unwrap();
+    let res =
+        ztimeout!(client02_manager
+            .open_transport_unicast(connect_endpoint(&client02_cert, &client02_key)));
+    println!("Certificates [1b2]: {res:?}");
+    assert!(res.is_ok());
+    ztimeout!(res.unwrap().close()).unwrap();
+
+    /* [2] */
+    // Rotate the listener certificate and revoke the certificate of client02
+    files.write("crl.pem", &ca.crl(2, &[4]));
+    files.write("key.pem", &server02_key);
+    files.write("cert.pem", &server02_cert);
+    ztimeout!(async {
+        loop {
+            let certificates = router_manager.get_certificates_unicast().await;
+            if certificates.iter().any(|c| c.subject == "CN=server02") {
+                break;
+            }
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+    // Wait for all the modified files to be reloaded
+    tokio::time::sleep(5 * SLEEP).await;
+
+    // Only client01 is accepted
+    let res =
+        ztimeout!(client01_manager
+            .open_transport_unicast(connect_endpoint(&client01_cert, &client01_key)));
+    println!("Certificates [2a1]: {res:?}");
+    assert!(res.is_ok());
+    ztimeout!(res.unwrap().close()).unwrap();
+    let res =
+        ztimeout!(client02_manager
+            .open_transport_unicast(connect_endpoint(&client02_cert, &client02_key)));
+    println!("Certificates [2a2]: {res:?}");
+    assert!(res.is_err());
+
+    /* [3] */
+    // An invalid file keeps the current certificate
+    files.write("cert.pem", "invalid");
+    tokio::time::sleep(5 * SLEEP).await;
+    let certificates = ztimeout!(router_manager.get_certificates_unicast());
+    println!("Certificates [3a1]: {certificates:?}");
+    assert_eq!(certificates.len(), 1);
+    assert_eq!(certificates[0].subject, "CN=server02");
+
+    /* [4] */
+    // Removing the listener removes its certificate
+    let res = ztimeout!(router_manager.del_listener(&listen_endpoint));
+    println!("Certificates [4a1]: {res:?}");
+    assert!(res.is_ok());
+    assert!(ztimeout!(router_manager.get_certificates_unicast()).is_empty());
+
+    ztimeout!(router_manager.close());
+    ztimeout!(client01_manager.close());
+    ztimeout!(client02_manager.close());
+
+    // Wait a little bit
+    tokio::time::sleep(SLEEP).await;
+}
+
+#[cfg(feature = "transport_tls")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn certificates_tls_only() {
+    certificates_reload_and_revocation(&format!("tls/localhost:{}", 13090), "tls").await;
+}
+
+#[cfg(feature = "transport_quic")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn certificates_quic_only() {
+    certificates_reload_and_revocation(&format!("quic/localhost:{}", 13091), "quic").await;
+}
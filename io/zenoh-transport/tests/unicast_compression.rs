@@ -39,6 +39,7 @@ mod tests {
     };
     use zenoh_result::ZResult;
     use zenoh_transport::{
+        common::batch::BatchCompression,
         multicast::TransportMulticast,
         unicast::{test_helpers::make_transport_manager_builder, TransportUnicast},
         TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
@@ -160,6 +161,7 @@ mod tests {
         client_endpoints: &[EndPoint],
         server_endpoints: &[EndPoint],
         lowlatency_transport: bool,
+        compression: &[BatchCompression],
     ) -> (
         TransportManager,
         Arc<SHRouter>,
@@ -179,7 +181,8 @@ mod tests {
             false,
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_algorithms(compression.to_vec());
         let router_manager = TransportManager::builder()
             .zid(router_id)
             .whatami(WhatAmI::Router)
@@ -201,7 +204,8 @@ mod tests {
             false,
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_algorithms(compression.to_vec());
         let client_manager = TransportManager::builder()
             .whatami(WhatAmI::Client)
             .zid(client_id)
@@ -333,15 +337,22 @@ mod tests {
         channel: Channel,
         msg_size: usize,
         lowlatency_transport: bool,
+        compression: &[BatchCompression],
     ) {
         println!(
-            "\n>>> Running test for:  {:?}, {:?}, {:?}, {}",
-            client_endpoints, server_endpoints, channel, msg_size
+            "\n>>> Running test for:  {:?}, {:?}, {:?}, {}, {:?}",
+            client_endpoints, server_endpoints, channel, msg_size, compression
         );
 
         #[allow(unused_variables)] // Used when stats feature is enabled
         let (router_manager, router_handler, client_manager, client_transport) =
-            open_transport_unicast(client_endpoints, server_endpoints, lowlatency_transport).await;
+            open_transport_unicast(
+                client_endpoints,
+                server_endpoints,
+                lowlatency_transport,
+                compression,
+            )
+            .await;
 
         test_transport(
             router_handler.clone(),
@@ -380,16 +391,23 @@ mod tests {
         msg_size: &[usize],
         lowlatency_transport: bool,
     ) {
+        let compressions = [
+            vec![BatchCompression::Lz4],
+            vec![BatchCompression::Zstd { level: 3 }],
+        ];
         for ch in channel.iter() {
             for ms in msg_size.iter() {
-                run_single(
-                    client_endpoints,
-                    server_endpoints,
-                    *ch,
-                    *ms,
-                    lowlatency_transport,
-                )
-                .await;
+                for c in compressions.iter() {
+                    run_single(
+                        client_endpoints,
+                        server_endpoints,
+                        *ch,
+                        *ms,
+                        lowlatency_transport,
+                        c,
+                    )
+                    .await;
+                }
             }
         }
     }
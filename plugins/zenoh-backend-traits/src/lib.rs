@@ -122,13 +122,19 @@
 
 use async_trait::async_trait;
 use const_format::concatcp;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
 use zenoh::{
     bytes::{Encoding, ZBytes},
     key_expr::{keyexpr, OwnedKeyExpr},
+    query::Parameters,
     time::Timestamp,
     Result as ZResult,
 };
 use zenoh_plugin_trait::{PluginControl, PluginInstance, PluginStatusRec, StructVersion};
+use zenoh_result::{bail, zerror};
 use zenoh_util::concat_enabled_features;
 
 pub mod config;
@@ -167,6 +173,109 @@ pub enum History {
     All,
 }
 
+/// The position from which the reading of the entries of a storage resumes: the entries are
+/// ordered by key, and the reading resumes after the key of the cursor.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntryCursor(pub Option<OwnedKeyExpr>);
+
+/// The order of the entries replied to a wildcard query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntriesOrder {
+    /// Ordered by key.
+    #[default]
+    Key,
+    /// Ordered by timestamp, the oldest first.
+    Time,
+}
+
+/// The standardized selector parameters allowing to page through the keys matching a wildcard
+/// query on a storage: `_limit`, `_offset`, `_order=key|time` and `_keysonly`.
+///
+/// These parameters are applied by the storage manager, which pushes them down to the storage
+/// when possible: the keys are read with the cursor and limit of [`Storage::get_entries_stream`]
+/// or, for `_order=time`, with [`Storage::get_entries_stream_by_time`], the entries not matching
+/// the query or beyond the limit being filtered out by the storage manager. As they apply to the
+/// keys rather than to the data of a key, they are removed from the parameters passed to
+/// [`Storage::get`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PagingParameters {
+    /// The maximum number of keys to reply, unbounded if None.
+    pub limit: Option<usize>,
+    /// The number of matching keys to skip.
+    pub offset: usize,
+    /// The order of the keys.
+    pub order: EntriesOrder,
+    /// Whether only the keys and timestamps are replied, with an empty payload.
+    pub keys_only: bool,
+}
+
+impl PagingParameters {
+    pub const LIMIT_KEY: &'static str = "_limit";
+    pub const OFFSET_KEY: &'static str = "_offset";
+    pub const ORDER_KEY: &'static str = "_order";
+    pub const KEYS_ONLY_KEY: &'static str = "_keysonly";
+
+    /// Returns the selector parameters without the paging parameters.
+    pub fn strip(parameters: &Parameters<'_>) -> Parameters<'static> {
+        let keys = [
+            Self::LIMIT_KEY,
+            Self::OFFSET_KEY,
+            Self::ORDER_KEY,
+            Self::KEYS_ONLY_KEY,
+        ];
+        parameters
+            .iter()
+            .filter(|(key, _)| !keys.contains(key))
+            .collect()
+    }
+}
+
+impl TryFrom<&Parameters<'_>> for PagingParameters {
+    type Error = zenoh::Error;
+
+    fn try_from(parameters: &Parameters<'_>) -> ZResult<Self> {
+        let parse_usize = |key: &str| -> ZResult<Option<usize>> {
+            parameters
+                .get(key)
+                .map(|value| {
+                    value.parse::<usize>().map_err(|_| {
+                        zerror!("Invalid `{key}` parameter: expected an unsigned integer, found `{value}`")
+                            .into()
+                    })
+                })
+                .transpose()
+        };
+        let order = match parameters.get(Self::ORDER_KEY) {
+            None | Some("key") => EntriesOrder::Key,
+            Some("time") => EntriesOrder::Time,
+            Some(order) => bail!(
+                "Invalid `{}` parameter: expected `key` or `time`, found `{order}`",
+                Self::ORDER_KEY
+            ),
+        };
+        let keys_only = match parameters.get(Self::KEYS_ONLY_KEY) {
+            None | Some("false") => false,
+            Some("") | Some("true") => true,
+            Some(keys_only) => bail!(
+                "Invalid `{}` parameter: expected `true` or `false`, found `{keys_only}`",
+                Self::KEYS_ONLY_KEY
+            ),
+        };
+        Ok(Self {
+            limit: parse_usize(Self::LIMIT_KEY)?,
+            offset: parse_usize(Self::OFFSET_KEY)?.unwrap_or_default(),
+            order,
+            keys_only,
+        })
+    }
+}
+
+/// A stream of the data associated with a key.
+pub type StoredDataStream<'a> = BoxStream<'a, ZResult<StoredData>>;
+
+/// A stream of the entries (key, timestamp) of a storage.
+pub type EntriesStream<'a> = BoxStream<'a, ZResult<(Option<OwnedKeyExpr>, Timestamp)>>;
+
 pub enum StorageInsertionResult {
     Outdated,
     Inserted,
@@ -221,6 +330,12 @@ pub trait Storage: Send + Sync {
     /// on the administration space for this storage.
     fn get_admin_status(&self) -> serde_json::Value;
 
+    /// Returns the capability of this storage if it differs from the one of its volume,
+    /// e.g. when it depends on the storage configuration.
+    fn get_capability(&self) -> Option<Capability> {
+        None
+    }
+
     /// Function called for each incoming data ([`Sample`](zenoh::sample::Sample)) to be stored in this storage.
     /// A key can be `None` if it matches the `strip_prefix` exactly.
     /// In order to avoid data loss, the storage must store the `value` and `timestamp` associated with the `None` key
@@ -257,4 +372,60 @@ pub trait Storage: Send + Sync {
     /// The latest Timestamp corresponding to each key is either the timestamp of the delete or put whichever is the latest.
     /// Remember to fetch the entry corresponding to the `None` key
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>>;
+
+    /// Function to stream the samples associated with a single key, without materializing all of
+    /// them in memory.
+    /// The default implementation streams the result of [`Storage::get`].
+    fn get_stream<'a>(
+        &'a mut self,
+        key: Option<OwnedKeyExpr>,
+        parameters: &'a str,
+    ) -> StoredDataStream<'a> {
+        stream::once(self.get(key, parameters))
+            .map_ok(|stored_data| stream::iter(stored_data.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+
+    /// Function to stream the storage content (key, timestamp), ordered by key, starting after
+    /// the `cursor` if any and ending after at most `limit` entries if any.
+    /// When all the entries of the storage match a query, the `limit` is the number of entries
+    /// still needed to reply to it according to its [`PagingParameters`].
+    /// The default implementation sorts the result of [`Storage::get_all_entries`] on each call,
+    /// hence storages that may contain many keys should override it along with
+    /// [`Storage::has_entries_cursor`].
+    fn get_entries_stream(
+        &self,
+        cursor: Option<EntryCursor>,
+        limit: Option<usize>,
+    ) -> EntriesStream<'_> {
+        stream::once(self.get_all_entries())
+            .map_ok(move |mut entries| {
+                entries.sort_unstable_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                if let Some(EntryCursor(cursor)) = &cursor {
+                    entries.retain(|(key, _)| key > cursor);
+                }
+                entries.truncate(limit.unwrap_or(usize::MAX));
+                stream::iter(entries.into_iter().map(Ok))
+            })
+            .try_flatten()
+            .boxed()
+    }
+
+    /// Returns whether [`Storage::get_entries_stream`] only reads the entries following its
+    /// cursor, making it efficient to page through the storage content.
+    /// Otherwise, the storage content is read at once with [`Storage::get_all_entries`].
+    fn has_entries_cursor(&self) -> bool {
+        false
+    }
+
+    /// Function to stream the storage content (key, timestamp), ordered by timestamp, the oldest
+    /// first, to reply to the queries with `_order=time`.
+    /// When all the entries of the storage match the query, `limit` is the `_offset` plus the
+    /// `_limit` of its [`PagingParameters`], and the stream may end after `limit` entries.
+    /// Returns None if the storage cannot order its content by timestamp, which is the default:
+    /// the storage manager then reads all the entries to sort them.
+    fn get_entries_stream_by_time(&self, _limit: Option<usize>) -> Option<EntriesStream<'_>> {
+        None
+    }
 }
@@ -0,0 +1,360 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose, Engine};
+use http_types::Method;
+use rand::Rng;
+use subtle::ConstantTimeEq;
+use tide::{http::Url, Middleware, Next, Request, StatusCode};
+use zenoh::{
+    internal::{access_control::AclMessage, bail, runtime::Runtime, zerror, zlock},
+    key_expr::keyexpr,
+    session::Session,
+    Result as ZResult,
+};
+use zenoh_crypto::hmac;
+use zenoh_transport::unicast::establishment::ext::auth::{UsrPwdVerifier, USRPWD_VERIFIER_PREFIX};
+
+use crate::{
+    config::AuthConfig, first_accept, path_to_key_expr, response, spawn_blocking_runtime, websocket,
+};
+
+const AUTHORIZATION: &str = "authorization";
+const WWW_AUTHENTICATE: &str = "www-authenticate";
+const ACCESS_TOKEN: &str = "access_token";
+const CHALLENGE: &str = r#"Basic realm="zenoh", Bearer realm="zenoh""#;
+
+enum Password {
+    Cleartext(String),
+    Verifier(UsrPwdVerifier),
+}
+
+/// The credentials of the HTTP clients: passwords for the Basic scheme, tokens for the Bearer one.
+pub(crate) struct Authenticator {
+    users: HashMap<String, Password>,
+    tokens: Vec<(String, String)>,
+    // The digests of the last password checked against the verifier of each user, keyed with a
+    // random secret, not to derive the verifier on every request of the same client
+    verified: Mutex<HashMap<String, Vec<u8>>>,
+    secret: [u8; 32],
+}
+
+impl Authenticator {
+    pub(crate) fn from_config(config: &AuthConfig) -> ZResult<Self> {
+        let mut users = HashMap::new();
+        if let Some(file) = &config.dictionary_file {
+            for (user, password) in read_dictionary(file)? {
+                let password = if password.starts_with(USRPWD_VERIFIER_PREFIX) {
+                    Password::Verifier(
+                        password
+                            .parse()
+                            .map_err(|e| zerror!("Invalid dictionary file '{file}': {e}"))?,
+                    )
+                } else {
+                    Password::Cleartext(password)
+                };
+                users.insert(user, password);
+            }
+        }
+        let mut tokens = vec![];
+        if let Some(file) = &config.tokens_file {
+            for (user, token) in read_dictionary(file)? {
+                tokens.push((token, user));
+            }
+        }
+        if users.is_empty() && tokens.is_empty() {
+            bail!("Authentication is configured without any user");
+        }
+        Ok(Self {
+            users,
+            tokens,
+            verified: Mutex::new(HashMap::new()),
+            secret: rand::thread_rng().gen(),
+        })
+    }
+
+    /// Returns the username of the credentials of an `Authorization` header, if valid.
+    pub(crate) async fn authenticate(&self, authorization: &str) -> Option<String> {
+        let (scheme, credentials) = authorization.trim().split_once(' ')?;
+        let credentials = credentials.trim();
+        if scheme.eq_ignore_ascii_case("basic") {
+            let credentials = general_purpose::STANDARD.decode(credentials).ok()?;
+            let credentials = String::from_utf8(credentials).ok()?;
+            let (user, password) = credentials.split_once(':')?;
+            let valid = match self.users.get(user)? {
+                // Compared in constant time not to leak the length of the matching prefix
+                Password::Cleartext(expected) => {
+                    expected.as_bytes().ct_eq(password.as_bytes()).into()
+                }
+                Password::Verifier(verifier) => {
+                    self.verify_password(user, verifier, password).await
+                }
+            };
+            valid.then(|| user.to_owned())
+        } else if scheme.eq_ignore_ascii_case("bearer") {
+            // All the tokens are compared in constant time not to leak which one is matching
+            let mut user = None;
+            for (token, owner) in &self.tokens {
+                if bool::from(token.as_bytes().ct_eq(credentials.as_bytes())) {
+                    user = Some(owner);
+                }
+            }
+            user.cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Checks a password against the verifier of a user, deriving it on a blocking thread.
+    async fn verify_password(&self, user: &str, verifier: &UsrPwdVerifier, password: &str) -> bool {
+        let Ok(digest) = hmac::sign(&self.secret, password.as_bytes()) else {
+            return false;
+        };
+        if let Some(expected) = zlock!(self.verified).get(user) {
+            if hmac::verify(expected, &digest) {
+                return true;
+            }
+        }
+        let (verifier, password) = (verifier.clone(), password.to_owned());
+        let valid = spawn_blocking_runtime(move || verifier.verify_password(password.as_bytes()))
+            .await
+            .map_or(false, |valid| valid.unwrap_or(false));
+        if valid {
+            zlock!(self.verified).insert(user.to_owned(), digest);
+        }
+        valid
+    }
+}
+
+/// Reads a file of `<user>:<secret>` lines.
+fn read_dictionary(file: &str) -> ZResult<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| zerror!("Invalid dictionary file '{file}': {e}"))?;
+    let mut entries = vec![];
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some((user, secret)) = line.split_once(':') else {
+            bail!("Invalid dictionary file '{file}': invalid format");
+        };
+        let (user, secret) = (user.trim(), secret.trim());
+        if user.is_empty() || secret.is_empty() {
+            bail!("Invalid dictionary file '{file}': empty user or secret");
+        }
+        entries.push((user.to_owned(), secret.to_owned()));
+    }
+    Ok(entries)
+}
+
+/// Returns a URL without its `access_token` query parameter, for it not to be logged.
+fn redact_access_token(url: &Url) -> Url {
+    let mut url = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != ACCESS_TOKEN)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url
+}
+
+/// An HTTP client, on behalf of which messages are sent.
+#[derive(Clone)]
+pub(crate) struct Client {
+    runtime: Runtime,
+    username: Option<String>,
+    remote_ip: Option<IpAddr>,
+}
+
+impl Client {
+    /// Returns whether the `access_control` rules allow this client to send a message.
+    pub(crate) fn authorize(&self, message: AclMessage, key_expr: &keyexpr) -> bool {
+        self.runtime
+            .authorize(self.username.as_deref(), self.remote_ip, message, key_expr)
+    }
+}
+
+/// Authenticates the HTTP clients and enforces the `access_control` rules on their requests.
+///
+/// The [`Client`] of a request is set as an extension of the request, for the messages sent
+/// over a WebSocket to be checked by its handler.
+pub(crate) struct AccessControlMiddleware {
+    runtime: Runtime,
+    authenticator: Option<Arc<Authenticator>>,
+}
+
+impl AccessControlMiddleware {
+    pub(crate) fn new(runtime: Runtime, authenticator: Option<Authenticator>) -> Self {
+        Self {
+            runtime,
+            authenticator: authenticator.map(Arc::new),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware<(Arc<Session>, String)> for AccessControlMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request<(Arc<Session>, String)>,
+        next: Next<'_, (Arc<Session>, String)>,
+    ) -> tide::Result {
+        let websocket = websocket::is_upgrade(&req);
+        let username = match &self.authenticator {
+            Some(authenticator) => {
+                let username = match req.header(AUTHORIZATION) {
+                    Some(values) => authenticator.authenticate(values.last().as_str()).await,
+                    // Browsers cannot set the headers of a WebSocket, which may present its
+                    // token as a query parameter instead
+                    None if websocket => {
+                        let token = req
+                            .url()
+                            .query_pairs()
+                            .find(|(name, _)| name == ACCESS_TOKEN)
+                            .map(|(_, token)| format!("Bearer {token}"));
+                        match token {
+                            Some(token) => authenticator.authenticate(&token).await,
+                            None => None,
+                        }
+                    }
+                    None => None,
+                };
+                match username {
+                    Some(username) => Some(username),
+                    None => {
+                        tracing::debug!(
+                            "Unauthenticated REST request: {} {}",
+                            req.method(),
+                            redact_access_token(req.url())
+                        );
+                        let mut res =
+                            response(StatusCode::Unauthorized, "text/plain", "Unauthorized");
+                        res.insert_header(WWW_AUTHENTICATE, CHALLENGE);
+                        return Ok(res);
+                    }
+                }
+            }
+            None => None,
+        };
+        let client = Client {
+            runtime: self.runtime.clone(),
+            username,
+            remote_ip: req
+                .peer_addr()
+                .and_then(|addr| addr.parse::<SocketAddr>().ok())
+                .map(|addr| addr.ip()),
+        };
+
+        // The messages sent over a WebSocket are checked by its handler
+        if !websocket {
+            let message = match req.method() {
+                Method::Get | Method::Post if first_accept(&req) == "text/event-stream" => {
+                    Some(AclMessage::DeclareSubscriber)
+                }
+                Method::Get | Method::Post => Some(AclMessage::Query),
+                Method::Put | Method::Patch => Some(AclMessage::Put),
+                Method::Delete => Some(AclMessage::Delete),
+                _ => None,
+            };
+            // Invalid key expressions are reported by the handlers
+            if let (Some(message), Ok(key_expr)) =
+                (message, path_to_key_expr(req.url().path(), &req.state().1))
+            {
+                if !client.authorize(message, &key_expr) {
+                    return Ok(response(StatusCode::Forbidden, "text/plain", "Forbidden"));
+                }
+            }
+        }
+        req.set_ext(client);
+        Ok(next.run(req).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose, Engine};
+    use tide::http::Url;
+    use zenoh_transport::unicast::establishment::ext::auth::UsrPwdVerifier;
+
+    use super::{redact_access_token, Authenticator};
+    use crate::{config::AuthConfig, TOKIO_RUNTIME};
+
+    #[test]
+    fn test_authenticate() {
+        let dir = std::env::temp_dir();
+        let dictionary_file = dir.join(format!("zenoh-rest-users-{}.txt", std::process::id()));
+        let tokens_file = dir.join(format!("zenoh-rest-tokens-{}.txt", std::process::id()));
+        let verifier = UsrPwdVerifier::new(b"pwd2", 16, &mut rand::thread_rng()).unwrap();
+        std::fs::write(&dictionary_file, format!("usr1:pwd1\nusr2:{verifier}\n")).unwrap();
+        std::fs::write(&tokens_file, "usr3:token3\n").unwrap();
+
+        let authenticator = Authenticator::from_config(&AuthConfig {
+            dictionary_file: Some(dictionary_file.to_str().unwrap().to_owned()),
+            tokens_file: Some(tokens_file.to_str().unwrap().to_owned()),
+        })
+        .unwrap();
+        let _ = std::fs::remove_file(&dictionary_file);
+        let _ = std::fs::remove_file(&tokens_file);
+
+        TOKIO_RUNTIME.block_on(async {
+            let basic = |credentials: &str| {
+                let authorization =
+                    format!("Basic {}", general_purpose::STANDARD.encode(credentials));
+                let authenticator = &authenticator;
+                async move { authenticator.authenticate(&authorization).await }
+            };
+            assert_eq!(basic("usr1:pwd1").await.as_deref(), Some("usr1"));
+            assert_eq!(basic("usr2:pwd2").await.as_deref(), Some("usr2"));
+            // Checked against the cached digest
+            assert_eq!(basic("usr2:pwd2").await.as_deref(), Some("usr2"));
+            assert_eq!(basic("usr1:pwd2").await, None);
+            assert_eq!(basic("usr2:pwd1").await, None);
+            assert_eq!(basic("usr3:token3").await, None);
+            assert_eq!(
+                authenticator.authenticate("bearer token3").await.as_deref(),
+                Some("usr3")
+            );
+            assert_eq!(authenticator.authenticate("Bearer token").await, None);
+            assert_eq!(authenticator.authenticate("Bearer pwd1").await, None);
+            assert_eq!(authenticator.authenticate("Digest token3").await, None);
+            assert_eq!(authenticator.authenticate("token3").await, None);
+        });
+
+        assert!(Authenticator::from_config(&AuthConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_redact_access_token() {
+        let redact = |url: &str| redact_access_token(&Url::parse(url).unwrap()).to_string();
+        assert_eq!(
+            redact("http://localhost:8000/demo/**?access_token=secret"),
+            "http://localhost:8000/demo/**"
+        );
+        assert_eq!(
+            redact("http://localhost:8000/demo/**?_method=GET&access_token=secret"),
+            "http://localhost:8000/demo/**?_method=GET"
+        );
+        assert_eq!(
+            redact("http://localhost:8000/demo/**"),
+            "http://localhost:8000/demo/**"
+        );
+    }
+}
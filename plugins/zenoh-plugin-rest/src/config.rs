@@ -33,6 +33,10 @@ pub struct Config {
     pub work_thread_num: usize,
     #[serde(default = "default_max_block_thread_num")]
     pub max_block_thread_num: usize,
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
     #[serde(default, deserialize_with = "deserialize_path")]
     __path__: Option<Vec<String>>,
     __required__: Option<bool>,
@@ -40,6 +44,27 @@ pub struct Config {
     __plugin__: Option<String>,
 }
 
+/// The authentication of the HTTP clients, whose usernames are subject to the `access_control` rules.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// A file of `<user>:<password>` lines for HTTP Basic authentication, in the format of the
+    /// `usrpwd` dictionary file of the transports.
+    pub dictionary_file: Option<String>,
+    /// A file of `<user>:<token>` lines for HTTP Bearer authentication.
+    pub tokens_file: Option<String>,
+}
+
+/// The certificate of the HTTPS server.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// Path to the PEM certificate chain.
+    pub listen_certificate: String,
+    /// Path to the PEM private key.
+    pub listen_private_key: String,
+}
+
 impl From<&Config> for serde_json::Value {
     fn from(c: &Config) -> Self {
         serde_json::to_value(c).unwrap()
@@ -234,4 +259,33 @@ mod tests {
         assert_eq!(__path__, None);
         assert_eq!(__required__, None);
     }
+
+    #[test]
+    fn test_auth_and_tls_fields() {
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "auth": {"dictionary_file": "users.txt"}, "tls": {"listen_certificate": "cert.pem", "listen_private_key": "key.pem"}}"#,
+        )
+        .unwrap();
+        let auth = config.auth.unwrap();
+        assert_eq!(auth.dictionary_file.as_deref(), Some("users.txt"));
+        assert_eq!(auth.tokens_file, None);
+        let tls = config.tls.unwrap();
+        assert_eq!(tls.listen_certificate, "cert.pem");
+        assert_eq!(tls.listen_private_key, "key.pem");
+
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8080}"#).unwrap();
+        assert!(config.auth.is_none());
+        assert!(config.tls.is_none());
+
+        // Unknown fields are rejected
+        assert!(serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "auth": {"password_file": "users.txt"}}"#
+        )
+        .is_err());
+        // Both the certificate and the private key are required
+        assert!(serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "tls": {"listen_certificate": "cert.pem"}}"#
+        )
+        .is_err());
+    }
 }
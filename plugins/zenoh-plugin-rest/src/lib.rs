@@ -37,6 +37,7 @@ use tide::{http::Mime, sse::Sender, Request, Response, Server, StatusCode};
 use tokio::{task::JoinHandle, time::timeout};
 use zenoh::{
     bytes::{Encoding, ZBytes},
+    handlers::RingChannel,
     internal::{
         bail,
         plugins::{RunningPluginTrait, ZenohPlugin},
@@ -51,7 +52,10 @@ use zenoh::{
 };
 use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
 
+mod auth;
 mod config;
+mod tls;
+mod websocket;
 pub use config::Config;
 use zenoh::query::ReplyError;
 
@@ -60,6 +64,8 @@ lazy_static::lazy_static! {
     static ref LONG_VERSION: String = format!("{} built with {}", GIT_VERSION, env!("RUSTC_VERSION"));
 }
 const RAW_KEY: &str = "_raw";
+/// The number of samples buffered per SSE stream before dropping the oldest ones.
+const SSE_SUBSCRIPTION_BUFFER: usize = 1024;
 
 lazy_static::lazy_static! {
     static ref WORKER_THREAD_NUM: AtomicUsize = AtomicUsize::new(config::DEFAULT_WORK_THREAD_NUM);
@@ -106,6 +112,17 @@ where
     }
 }
 
+pub(crate) fn spawn_blocking_runtime<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(rt) => rt.spawn_blocking(f),
+        Err(_) => TOKIO_RUNTIME.spawn_blocking(f),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct JSONSample {
     key: String,
@@ -355,10 +372,8 @@ fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     result
 }
 
-async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Response> {
-    tracing::trace!("Incoming GET request: {:?}", req);
-
-    let first_accept = match req.header("accept") {
+fn first_accept<State>(req: &Request<State>) -> String {
+    match req.header("accept") {
         Some(accept) => accept[0]
             .to_string()
             .split(';')
@@ -369,7 +384,24 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
             .unwrap()
             .to_string(),
         None => "application/json".to_string(),
-    };
+    }
+}
+
+fn consolidation(parameters: &Parameters) -> QueryConsolidation {
+    if parameters.time_range().is_some() {
+        QueryConsolidation::from(zenoh::query::ConsolidationMode::None)
+    } else {
+        QueryConsolidation::from(zenoh::query::ConsolidationMode::Latest)
+    }
+}
+
+async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Response> {
+    tracing::trace!("Incoming GET request: {:?}", req);
+
+    if websocket::is_upgrade(&req) {
+        return websocket::upgrade(req).await;
+    }
+    let first_accept = first_accept(&req);
     if first_accept == "text/event-stream" {
         Ok(tide::sse::upgrade(
             req,
@@ -386,35 +418,35 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
                 spawn_runtime(async move {
                     tracing::debug!("Subscribe to {} for SSE stream", key_expr);
                     let sender = &sender;
-                    let sub = req.state().0.declare_subscriber(&key_expr).await.unwrap();
-                    loop {
-                        let sample = sub.recv_async().await.unwrap();
+                    // Samples are dropped, oldest first, while the client does not keep up
+                    let sub = match req
+                        .state()
+                        .0
+                        .declare_subscriber(&key_expr)
+                        .with(RingChannel::new(SSE_SUBSCRIPTION_BUFFER))
+                        .await
+                    {
+                        Ok(sub) => sub,
+                        Err(e) => {
+                            tracing::error!("Error declaring subscriber: {}", e);
+                            return;
+                        }
+                    };
+                    while let Ok(sample) = sub.recv_async().await {
                         let json_sample =
                             serde_json::to_string(&sample_to_json(&sample)).unwrap_or("{}".into());
 
-                        match timeout(
-                            std::time::Duration::new(10, 0),
-                            sender.send(&sample.kind().to_string(), json_sample, None),
-                        )
-                        .await
+                        if let Err(e) = sender
+                            .send(&sample.kind().to_string(), json_sample, None)
+                            .await
                         {
-                            Ok(Ok(_)) => {}
-                            Ok(Err(e)) => {
-                                tracing::debug!("SSE error ({})! Unsubscribe and terminate", e);
-                                if let Err(e) = sub.undeclare().await {
-                                    tracing::error!("Error undeclaring subscriber: {}", e);
-                                }
-                                break;
-                            }
-                            Err(_) => {
-                                tracing::debug!("SSE timeout! Unsubscribe and terminate",);
-                                if let Err(e) = sub.undeclare().await {
-                                    tracing::error!("Error undeclaring subscriber: {}", e);
-                                }
-                                break;
-                            }
+                            tracing::debug!("SSE error ({})! Unsubscribe and terminate", e);
+                            break;
                         }
                     }
+                    if let Err(e) = sub.undeclare().await {
+                        tracing::error!("Error undeclaring subscriber: {}", e);
+                    }
                 });
                 Ok(())
             },
@@ -434,17 +466,12 @@ async fn query(mut req: Request<(Arc<Session>, String)>) -> tide::Result<Respons
         };
         let query_part = url.query();
         let parameters = Parameters::from(query_part.unwrap_or_default());
-        let consolidation = if parameters.time_range().is_some() {
-            QueryConsolidation::from(zenoh::query::ConsolidationMode::None)
-        } else {
-            QueryConsolidation::from(zenoh::query::ConsolidationMode::Latest)
-        };
         let raw = parameters.contains_key(RAW_KEY);
         let mut query = req
             .state()
             .0
             .get(Selector::borrowed(&key_expr, &parameters))
-            .consolidation(consolidation)
+            .consolidation(consolidation(&parameters))
             .with(flume::unbounded());
         if !body.is_empty() {
             let encoding: Encoding = req
@@ -521,7 +548,15 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
     // But cannot be done twice in case of static link.
     zenoh::init_log_from_env_or("error");
 
+    let authenticator = conf
+        .auth
+        .as_ref()
+        .map(auth::Authenticator::from_config)
+        .transpose()?;
+    let tls_config = conf.tls.as_ref().map(tls::server_config).transpose()?;
+
     let zid = runtime.zid().to_string();
+    let access_control = auth::AccessControlMiddleware::new(runtime.clone(), authenticator);
     let session = zenoh::session::init(runtime).await.unwrap();
 
     let mut app = Server::with_state((Arc::new(session), zid));
@@ -535,6 +570,7 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
             .allow_origin(tide::security::Origin::from("*"))
             .allow_credentials(false),
     );
+    app.with(access_control);
 
     app.at("/")
         .get(query)
@@ -549,7 +585,14 @@ pub async fn run(runtime: Runtime, conf: Config) -> ZResult<()> {
         .patch(write)
         .delete(write);
 
-    if let Err(e) = app.listen(conf.http_port).await {
+    let res = match tls_config {
+        Some(tls_config) => {
+            app.listen(tls::TlsListener::new(conf.http_port, tls_config))
+                .await
+        }
+        None => app.listen(conf.http_port).await,
+    };
+    if let Err(e) = res {
         tracing::error!("Unable to start http server for REST: {:?}", e);
         return Err(e.into());
     }
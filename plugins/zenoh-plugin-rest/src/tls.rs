@@ -0,0 +1,183 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{fmt, fs::File, io, io::BufReader, sync::Arc, time::Duration};
+
+use rustls::ServerConfig;
+use tide::{
+    listener::{ListenInfo, Listener, ToListener},
+    Server,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use zenoh::{
+    internal::{bail, zerror},
+    Result as ZResult,
+};
+
+use crate::{config::TlsConfig, spawn_runtime};
+
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_millis(500);
+
+pub(crate) fn server_config(config: &TlsConfig) -> ZResult<Arc<ServerConfig>> {
+    let certs = rustls_pemfile::certs(&mut BufReader::new(
+        File::open(&config.listen_certificate)
+            .map_err(|e| zerror!("Invalid TLS certificate file: {e}"))?,
+    ))
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|e| zerror!("Invalid TLS certificate file: {e}"))?;
+    if certs.is_empty() {
+        bail!("No certificate found in '{}'", config.listen_certificate);
+    }
+    let key = rustls_pemfile::private_key(&mut BufReader::new(
+        File::open(&config.listen_private_key)
+            .map_err(|e| zerror!("Invalid TLS private key file: {e}"))?,
+    ))
+    .map_err(|e| zerror!("Invalid TLS private key file: {e}"))?
+    .ok_or_else(|| zerror!("No private key found in '{}'", config.listen_private_key))?;
+
+    // Install ring based rustls CryptoProvider, which fails if it has already been installed.
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .ok();
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| zerror!("Invalid TLS certificate or private key: {e}"))?;
+    Ok(Arc::new(config))
+}
+
+/// A tide [`Listener`] serving HTTPS.
+pub(crate) struct TlsListener<State> {
+    addr: String,
+    config: Arc<ServerConfig>,
+    listener: Option<TcpListener>,
+    server: Option<Server<State>>,
+    info: Option<ListenInfo>,
+}
+
+impl<State> TlsListener<State> {
+    pub(crate) fn new(addr: String, config: Arc<ServerConfig>) -> Self {
+        Self {
+            addr,
+            config,
+            listener: None,
+            server: None,
+            info: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<State> Listener<State> for TlsListener<State>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    async fn bind(&mut self, server: Server<State>) -> io::Result<()> {
+        assert!(self.server.is_none(), "`bind` should only be called once");
+        self.server = Some(server);
+        self.listener = Some(TcpListener::bind(&self.addr).await?);
+        self.info = Some(ListenInfo::new(self.to_string(), "tcp".to_owned(), true));
+        Ok(())
+    }
+
+    async fn accept(&mut self) -> io::Result<()> {
+        let server = self
+            .server
+            .take()
+            .expect("`Listener::bind` must be called before `Listener::accept`");
+        let listener = self
+            .listener
+            .take()
+            .expect("`Listener::bind` must be called before `Listener::accept`");
+        let acceptor = TlsAcceptor::from(self.config.clone());
+
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    tracing::warn!("Error accepting HTTPS connection: {e}");
+                    tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+                    continue;
+                }
+            };
+            let server = server.clone();
+            let acceptor = acceptor.clone();
+            spawn_runtime(async move {
+                let local_addr = stream.local_addr().ok();
+                let stream = match tokio::time::timeout(
+                    TLS_HANDSHAKE_TIMEOUT,
+                    acceptor.accept(stream),
+                )
+                .await
+                {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        tracing::debug!("TLS handshake with {peer_addr} failed: {e}");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!("TLS handshake with {peer_addr} timed out");
+                        return;
+                    }
+                };
+                // async-h1 requires a clonable stream
+                let stream = async_dup::Arc::new(async_dup::Mutex::new(stream.compat()));
+                let res = async_h1::accept(stream, |mut req| async {
+                    req.set_local_addr(local_addr);
+                    req.set_peer_addr(Some(peer_addr));
+                    server.respond(req).await
+                })
+                .await;
+                if let Err(e) = res {
+                    tracing::debug!("HTTPS connection with {peer_addr} failed: {e}");
+                }
+            });
+        }
+    }
+
+    fn info(&self) -> Vec<ListenInfo> {
+        self.info.iter().cloned().collect()
+    }
+}
+
+impl<State> ToListener<State> for TlsListener<State>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    type Listener = Self;
+
+    fn to_listener(self) -> io::Result<Self::Listener> {
+        Ok(self)
+    }
+}
+
+impl<State> fmt::Debug for TlsListener<State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsListener")
+            .field("addr", &self.addr)
+            .field("listener", &self.listener)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<State> fmt::Display for TlsListener<State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.listener.as_ref().and_then(|l| l.local_addr().ok()) {
+            Some(addr) => write!(f, "https://{addr}"),
+            None => write!(f, "https://{}", self.addr),
+        }
+    }
+}
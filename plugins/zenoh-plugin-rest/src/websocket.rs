@@ -0,0 +1,588 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The WebSocket endpoint of the REST plugin.
+//!
+//! A WebSocket can be opened on any path of the REST server. Over a single connection, a client
+//! sends requests as JSON objects with an `op` field:
+//! - `{"op": "subscribe", "id": 1, "key_expr": "demo/**"}`
+//! - `{"op": "unsubscribe", "id": 2, "subscription": 1}`
+//! - `{"op": "put", "id": 3, "key_expr": "demo/a", "value": "hello", "encoding": "text/plain"}`
+//! - `{"op": "delete", "id": 4, "key_expr": "demo/a"}`
+//! - `{"op": "get", "id": 5, "selector": "demo/**?arg=1", "value": 42, "timeout": 1000}`
+//!
+//! and receives JSON objects with a `type` field: `ok` and `error` acknowledge the requests,
+//! `sample` carries the samples of a subscription, `reply` and `reply_error` the replies of a
+//! query, which are followed by a `final` one.
+//!
+//! With the `?format=binary` query parameter, the messages carrying a payload are binary frames
+//! made of a big-endian `u32` length, the JSON object (without `value`) and the raw payload.
+//! Clients may send binary frames in any format, the payload replacing the `value` of a put or
+//! a get.
+//!
+//! The samples of a subscription are buffered, the oldest ones being dropped when the client
+//! does not keep up.
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tide::{Request, Response, StatusCode};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+use tokio_util::compat::FuturesAsyncReadCompatExt;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    handlers::RingChannel,
+    internal::access_control::AclMessage,
+    key_expr::keyexpr,
+    query::{Parameters, Selector},
+    session::Session,
+};
+
+use crate::{
+    auth::Client, consolidation, path_to_key_expr, payload_to_json, response, spawn_runtime,
+};
+
+/// The number of messages waiting to be sent to a client.
+const WS_OUTGOING_BUFFER: usize = 256;
+/// The number of samples buffered per subscription before dropping the oldest ones.
+const WS_SUBSCRIPTION_BUFFER: usize = 1024;
+
+const FORMAT: &str = "format";
+const UPGRADE: &str = "upgrade";
+const CONNECTION: &str = "connection";
+const SEC_WEBSOCKET_KEY: &str = "sec-websocket-key";
+const SEC_WEBSOCKET_VERSION: &str = "sec-websocket-version";
+const SEC_WEBSOCKET_ACCEPT: &str = "sec-websocket-accept";
+const WEBSOCKET_VERSION: &str = "13";
+
+/// Returns whether a request is a WebSocket opening handshake.
+pub(crate) fn is_upgrade<State>(req: &Request<State>) -> bool {
+    req.header(UPGRADE)
+        .map(|values| values.last().as_str().eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Json,
+    Binary,
+}
+
+/// Accepts a WebSocket and serves it in a spawned task.
+pub(crate) async fn upgrade(req: Request<(Arc<Session>, String)>) -> tide::Result<Response> {
+    let Some(key) = req.header(SEC_WEBSOCKET_KEY).map(|k| k.last().to_string()) else {
+        return Ok(response(
+            StatusCode::BadRequest,
+            "text/plain",
+            "Missing Sec-WebSocket-Key header",
+        ));
+    };
+    if req
+        .header(SEC_WEBSOCKET_VERSION)
+        .map_or(true, |v| v.last().as_str() != WEBSOCKET_VERSION)
+    {
+        let mut res = response(
+            StatusCode::UpgradeRequired,
+            "text/plain",
+            "Unsupported WebSocket version",
+        );
+        res.insert_header(SEC_WEBSOCKET_VERSION, WEBSOCKET_VERSION);
+        return Ok(res);
+    }
+    let format = match req.url().query_pairs().find(|(name, _)| name == FORMAT) {
+        None => Format::Json,
+        Some((_, format)) if format == "json" => Format::Json,
+        Some((_, format)) if format == "binary" => Format::Binary,
+        Some((_, format)) => {
+            return Ok(response(
+                StatusCode::BadRequest,
+                "text/plain",
+                &format!("Invalid WebSocket format '{format}'"),
+            ))
+        }
+    };
+    let Some(client) = req.ext::<Client>().cloned() else {
+        return Ok(response(
+            StatusCode::InternalServerError,
+            "text/plain",
+            "Missing client",
+        ));
+    };
+    let (session, zid) = req.state().clone();
+
+    let mut res = Response::new(StatusCode::SwitchingProtocols);
+    res.insert_header(UPGRADE, "websocket");
+    res.insert_header(CONNECTION, "Upgrade");
+    res.insert_header(SEC_WEBSOCKET_ACCEPT, derive_accept_key(key.as_bytes()));
+    let connection = AsMut::<http_types::Response>::as_mut(&mut res)
+        .recv_upgrade()
+        .await;
+    spawn_runtime(async move {
+        let Some(connection) = connection.await else {
+            tracing::debug!("WebSocket upgrade failed");
+            return;
+        };
+        let stream =
+            WebSocketStream::from_raw_socket(connection.compat(), Role::Server, None).await;
+        WebSocket {
+            session,
+            zid,
+            client,
+            format,
+        }
+        .serve(stream)
+        .await;
+    });
+    Ok(res)
+}
+
+/// A request of a client.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum ClientRequest {
+    Subscribe {
+        id: u64,
+        key_expr: String,
+    },
+    Unsubscribe {
+        id: u64,
+        subscription: u64,
+    },
+    Put {
+        id: u64,
+        key_expr: String,
+        #[serde(default)]
+        value: serde_json::Value,
+        encoding: Option<String>,
+    },
+    Delete {
+        id: u64,
+        key_expr: String,
+    },
+    Get {
+        id: u64,
+        selector: String,
+        value: Option<serde_json::Value>,
+        encoding: Option<String>,
+        /// The timeout of the query in milliseconds.
+        timeout: Option<u64>,
+    },
+}
+
+impl ClientRequest {
+    fn id(&self) -> u64 {
+        match self {
+            Self::Subscribe { id, .. }
+            | Self::Unsubscribe { id, .. }
+            | Self::Put { id, .. }
+            | Self::Delete { id, .. }
+            | Self::Get { id, .. } => *id,
+        }
+    }
+}
+
+/// A message sent to a client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Ok {
+        id: u64,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+        error: String,
+    },
+    Sample {
+        subscription: u64,
+        kind: String,
+        key: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<serde_json::Value>,
+        encoding: String,
+        timestamp: Option<String>,
+    },
+    Reply {
+        id: u64,
+        key: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<serde_json::Value>,
+        encoding: String,
+        timestamp: Option<String>,
+    },
+    ReplyError {
+        id: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<serde_json::Value>,
+        encoding: String,
+    },
+    Final {
+        id: u64,
+    },
+}
+
+impl Format {
+    /// Returns the `value` of a message carrying a payload.
+    fn value(self, payload: &ZBytes, encoding: &Encoding) -> Option<serde_json::Value> {
+        match self {
+            Format::Json => Some(payload_to_json(payload, encoding)),
+            Format::Binary => None,
+        }
+    }
+
+    fn frame(self, message: &ServerMessage, payload: Option<&ZBytes>) -> Message {
+        let header = serde_json::to_string(message).unwrap_or_else(|_| "{}".into());
+        match (self, payload) {
+            (Format::Binary, Some(payload)) => {
+                Message::Binary(binary_frame(header.as_bytes(), &payload.to_bytes()))
+            }
+            _ => Message::Text(header),
+        }
+    }
+}
+
+fn binary_frame(header: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + header.len() + payload.len());
+    frame.extend_from_slice(&(header.len() as u32).to_be_bytes());
+    frame.extend_from_slice(header);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Returns the header and the payload of a binary frame.
+fn split_binary_frame(frame: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(frame.get(..4)?.try_into().ok()?) as usize;
+    let header = frame.get(4..4usize.checked_add(len)?)?;
+    Some((header, &frame[4 + len..]))
+}
+
+/// Returns the payload and the default encoding of a JSON value.
+fn json_to_payload(value: serde_json::Value) -> (Vec<u8>, Encoding) {
+    match value {
+        serde_json::Value::Null => (vec![], Encoding::default()),
+        serde_json::Value::String(s) => (s.into_bytes(), Encoding::TEXT_PLAIN),
+        value => (
+            serde_json::to_vec(&value).unwrap_or_default(),
+            Encoding::APPLICATION_JSON,
+        ),
+    }
+}
+
+/// A WebSocket connection with a client.
+struct WebSocket {
+    session: Arc<Session>,
+    zid: String,
+    client: Client,
+    format: Format,
+}
+
+impl WebSocket {
+    async fn serve<S>(self, stream: WebSocketStream<S>)
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, mut stream) = stream.split();
+        // The subscriptions and the queries send their messages through a bounded channel, for a
+        // slow client to slow them down rather than to be disconnected
+        let (out, outgoing) = flume::bounded::<Message>(WS_OUTGOING_BUFFER);
+        let writer = spawn_runtime(async move {
+            while let Ok(message) = outgoing.recv_async().await {
+                if let Err(e) = sink.send(message).await {
+                    tracing::debug!("WebSocket error ({e})! Terminate");
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let mut subscriptions = HashMap::new();
+        while let Some(frame) = stream.next().await {
+            let request = match frame {
+                Ok(Message::Text(text)) => serde_json::from_str::<ClientRequest>(&text)
+                    .map(|request| (request, None))
+                    .map_err(|e| e.to_string()),
+                Ok(Message::Binary(frame)) => match split_binary_frame(&frame) {
+                    Some((header, payload)) => serde_json::from_slice::<ClientRequest>(header)
+                        .map(|request| (request, Some(payload.to_vec())))
+                        .map_err(|e| e.to_string()),
+                    None => Err("invalid binary frame".to_string()),
+                },
+                Ok(Message::Close(_)) => break,
+                // Pings are answered by tungstenite
+                Ok(_) => continue,
+                Err(e) => {
+                    tracing::debug!("WebSocket error ({e})! Terminate");
+                    break;
+                }
+            };
+            let message = match request {
+                Ok((request, payload)) => {
+                    let id = request.id();
+                    match self
+                        .handle(request, payload, &mut subscriptions, &out)
+                        .await
+                    {
+                        Ok(Some(message)) => message,
+                        Ok(None) => continue,
+                        Err(error) => ServerMessage::Error {
+                            id: Some(id),
+                            error,
+                        },
+                    }
+                }
+                Err(e) => ServerMessage::Error {
+                    id: None,
+                    error: format!("Invalid request: {e}"),
+                },
+            };
+            if out
+                .send_async(self.format.frame(&message, None))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        for (_, subscription) in subscriptions {
+            subscription.abort();
+        }
+        // The writer ends once the pending queries have sent their replies
+        drop(out);
+        let _ = writer.await;
+    }
+
+    /// Executes a request, returning the message acknowledging it if any.
+    async fn handle(
+        &self,
+        request: ClientRequest,
+        payload: Option<Vec<u8>>,
+        subscriptions: &mut HashMap<u64, JoinHandle<()>>,
+        out: &flume::Sender<Message>,
+    ) -> Result<Option<ServerMessage>, String> {
+        match request {
+            ClientRequest::Subscribe { id, key_expr } => {
+                if subscriptions.contains_key(&id) {
+                    return Err(format!("Subscription {id} already exists"));
+                }
+                let key_expr = path_to_key_expr(&key_expr, &self.zid)
+                    .map_err(|e| e.to_string())?
+                    .into_owned();
+                self.authorize(AclMessage::DeclareSubscriber, &key_expr)?;
+                // Samples are dropped, oldest first, while the client does not keep up
+                let subscriber = self
+                    .session
+                    .declare_subscriber(&key_expr)
+                    .with(RingChannel::new(WS_SUBSCRIPTION_BUFFER))
+                    .await
+                    .map_err(|e| e.to_string())?;
+                tracing::debug!("Subscribe to {key_expr} for WebSocket");
+                let (out, format) = (out.clone(), self.format);
+                let task = spawn_runtime(async move {
+                    while let Ok(sample) = subscriber.recv_async().await {
+                        let message = ServerMessage::Sample {
+                            subscription: id,
+                            kind: sample.kind().to_string(),
+                            key: sample.key_expr().to_string(),
+                            value: format.value(sample.payload(), sample.encoding()),
+                            encoding: sample.encoding().to_string(),
+                            timestamp: sample.timestamp().map(|ts| ts.to_string()),
+                        };
+                        let frame = format.frame(&message, Some(sample.payload()));
+                        if out.send_async(frame).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                subscriptions.insert(id, task);
+                Ok(Some(ServerMessage::Ok { id }))
+            }
+            ClientRequest::Unsubscribe { id, subscription } => {
+                match subscriptions.remove(&subscription) {
+                    // Dropping the subscriber undeclares it
+                    Some(task) => task.abort(),
+                    None => return Err(format!("Unknown subscription {subscription}")),
+                }
+                Ok(Some(ServerMessage::Ok { id }))
+            }
+            ClientRequest::Put {
+                id,
+                key_expr,
+                value,
+                encoding,
+            } => {
+                let key_expr = path_to_key_expr(&key_expr, &self.zid).map_err(|e| e.to_string())?;
+                self.authorize(AclMessage::Put, &key_expr)?;
+                let (payload, encoding) = payload_and_encoding(payload, Some(value), encoding);
+                self.session
+                    .put(&key_expr, payload)
+                    .encoding(encoding)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(Some(ServerMessage::Ok { id }))
+            }
+            ClientRequest::Delete { id, key_expr } => {
+                let key_expr = path_to_key_expr(&key_expr, &self.zid).map_err(|e| e.to_string())?;
+                self.authorize(AclMessage::Delete, &key_expr)?;
+                self.session
+                    .delete(&key_expr)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(Some(ServerMessage::Ok { id }))
+            }
+            ClientRequest::Get {
+                id,
+                selector,
+                value,
+                encoding,
+                timeout,
+            } => {
+                let (key_expr, parameters) = selector.split_once('?').unwrap_or((&selector, ""));
+                let key_expr = path_to_key_expr(key_expr, &self.zid).map_err(|e| e.to_string())?;
+                self.authorize(AclMessage::Query, &key_expr)?;
+                let parameters = Parameters::from(parameters);
+                let mut query = self
+                    .session
+                    .get(Selector::borrowed(&key_expr, &parameters))
+                    .consolidation(consolidation(&parameters))
+                    .with(flume::unbounded());
+                if payload.is_some() || value.is_some() {
+                    let (payload, encoding) = payload_and_encoding(payload, value, encoding);
+                    query = query.payload(payload).encoding(encoding);
+                }
+                if let Some(timeout) = timeout {
+                    query = query.timeout(Duration::from_millis(timeout));
+                }
+                let replies = query.await.map_err(|e| e.to_string())?;
+                let (out, format) = (out.clone(), self.format);
+                spawn_runtime(async move {
+                    while let Ok(reply) = replies.recv_async().await {
+                        let frame = match reply.result() {
+                            Ok(sample) => format.frame(
+                                &ServerMessage::Reply {
+                                    id,
+                                    key: sample.key_expr().to_string(),
+                                    value: format.value(sample.payload(), sample.encoding()),
+                                    encoding: sample.encoding().to_string(),
+                                    timestamp: sample.timestamp().map(|ts| ts.to_string()),
+                                },
+                                Some(sample.payload()),
+                            ),
+                            Err(err) => format.frame(
+                                &ServerMessage::ReplyError {
+                                    id,
+                                    value: format.value(err.payload(), err.encoding()),
+                                    encoding: err.encoding().to_string(),
+                                },
+                                Some(err.payload()),
+                            ),
+                        };
+                        if out.send_async(frame).await.is_err() {
+                            return;
+                        }
+                    }
+                    let _ = out
+                        .send_async(format.frame(&ServerMessage::Final { id }, None))
+                        .await;
+                });
+                Ok(None)
+            }
+        }
+    }
+
+    fn authorize(&self, message: AclMessage, key_expr: &keyexpr) -> Result<(), String> {
+        if self.client.authorize(message, key_expr) {
+            Ok(())
+        } else {
+            Err("Forbidden".to_string())
+        }
+    }
+}
+
+/// Returns the payload of a put or a get, from a binary frame or else from a JSON value.
+fn payload_and_encoding(
+    payload: Option<Vec<u8>>,
+    value: Option<serde_json::Value>,
+    encoding: Option<String>,
+) -> (Vec<u8>, Encoding) {
+    let (payload, default_encoding) = match payload {
+        Some(payload) => (payload, Encoding::default()),
+        None => json_to_payload(value.unwrap_or_default()),
+    };
+    let encoding = encoding.map(Encoding::from).unwrap_or(default_encoding);
+    (payload, encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh::bytes::Encoding;
+
+    use super::{binary_frame, payload_and_encoding, split_binary_frame, ClientRequest};
+
+    #[test]
+    fn test_websocket_frames() {
+        let request: ClientRequest = serde_json::from_str(
+            r#"{"op": "put", "id": 1, "key_expr": "demo/a", "value": {"a": 1}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            ClientRequest::Put {
+                id: 1,
+                key_expr: "demo/a".into(),
+                value: serde_json::json!({"a": 1}),
+                encoding: None,
+            }
+        );
+        assert!(serde_json::from_str::<ClientRequest>(
+            r#"{"op": "subscribe", "id": 1, "key_expr": "demo/**", "foo": 0}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<ClientRequest>(r#"{"op": "publish", "id": 1}"#).is_err());
+
+        let (payload, encoding) = payload_and_encoding(None, Some(serde_json::json!([1])), None);
+        assert_eq!(
+            (payload.as_slice(), encoding),
+            (&b"[1]"[..], Encoding::APPLICATION_JSON)
+        );
+        let (payload, encoding) = payload_and_encoding(None, Some("abc".into()), None);
+        assert_eq!(
+            (payload.as_slice(), encoding),
+            (&b"abc"[..], Encoding::TEXT_PLAIN)
+        );
+        let (payload, encoding) = payload_and_encoding(
+            Some(vec![0, 1]),
+            None,
+            Some("application/octet-stream".into()),
+        );
+        assert_eq!(
+            (payload.as_slice(), encoding),
+            (&[0, 1][..], Encoding::APPLICATION_OCTET_STREAM)
+        );
+
+        let header = br#"{"op": "delete", "id": 2, "key_expr": "demo/a"}"#;
+        let frame = binary_frame(header, &[0, 1, 2]);
+        assert_eq!(
+            split_binary_frame(&frame),
+            Some((&header[..], &[0, 1, 2][..]))
+        );
+        assert_eq!(split_binary_frame(&frame[..10]), None);
+        assert_eq!(split_binary_frame(&[0, 0]), None);
+        assert_eq!(split_binary_frame(&[0, 0, 0, 0]), Some((&[][..], &[][..])));
+    }
+}
@@ -0,0 +1,551 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fs::{self, File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    ops::Bound,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crc::{Crc, CRC_32_ISCSI};
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    time::{Timestamp, NTP64},
+    Result as ZResult,
+};
+use zenoh_backend_traits::{StorageInsertionResult, StoredData};
+
+const SEGMENT_EXTENSION: &str = "log";
+// A record is prefixed by the length of its body and the checksum of its body, both as u32 LE
+const HEADER_LEN: usize = 8;
+const CASTAGNOLI: Crc<u32> = Crc::<u32>::new(&CRC_32_ISCSI);
+// The log is never compacted below this size, the gain being not worth the rewrite
+const MIN_COMPACTION_SIZE: u64 = 1024 * 1024;
+
+/// When the appended records are flushed to the disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FsyncPolicy {
+    /// After each record.
+    Always,
+    /// By a background task once per period if records were appended since the last sync, and
+    /// when the log is closed.
+    Periodic(Duration),
+    /// Left to the operating system.
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LogOptions {
+    pub(crate) fsync: FsyncPolicy,
+    pub(crate) segment_size: u64,
+    pub(crate) compaction_threshold: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum Record {
+    Put {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+        encoding: String,
+        payload: Vec<u8>,
+    },
+    Delete {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    },
+}
+
+/// The position of a record in the log, header included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Location {
+    segment: u64,
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    timestamp: Timestamp,
+    location: Location,
+}
+
+/// An append-only log of the puts and deletes of a storage, split into segment files.
+///
+/// The latest put of each key is indexed in memory with its location in the log, and read from
+/// the disk on demand. The latest delete of each key not put since is kept as a tombstone, for
+/// older puts and deletes, e.g. received through replication, to be rejected after a restart,
+/// until it expires.
+/// The records made obsolete by a more recent put or delete are reclaimed by rewriting the live
+/// records in new segments once their share of the log exceeds the compaction threshold.
+pub(crate) struct SegmentLog {
+    dir: PathBuf,
+    options: LogOptions,
+    index: BTreeMap<Option<OwnedKeyExpr>, IndexEntry>,
+    tombstones: BTreeMap<Option<OwnedKeyExpr>, IndexEntry>,
+    // The size of each segment, by id
+    segments: BTreeMap<u64, u64>,
+    active_id: u64,
+    active: File,
+    // The read handles of the segments, opened on their first read
+    readers: BTreeMap<u64, File>,
+    // Whether records were written since the last sync
+    dirty: bool,
+    total_bytes: u64,
+    live_bytes: u64,
+}
+
+fn segment_path(dir: &Path, id: u64) -> PathBuf {
+    dir.join(format!("{id:020}.{SEGMENT_EXTENSION}"))
+}
+
+fn encode(record: &Record) -> ZResult<Vec<u8>> {
+    let body = bincode::serialize(record)?;
+    let Ok(len) = u32::try_from(body.len()) else {
+        bail!("Record of {} bytes is too large", body.len());
+    };
+    let mut buf = Vec::with_capacity(HEADER_LEN + body.len());
+    buf.extend_from_slice(&len.to_le_bytes());
+    buf.extend_from_slice(&CASTAGNOLI.checksum(&body).to_le_bytes());
+    buf.extend_from_slice(&body);
+    Ok(buf)
+}
+
+/// Decodes the record at the start of `buf`, returning it with its length, header included.
+///
+/// Returns None if the record is truncated or corrupted.
+fn decode(buf: &[u8]) -> Option<(Record, u64)> {
+    let header = buf.get(..HEADER_LEN)?;
+    let len = u32::from_le_bytes(header[..4].try_into().ok()?) as usize;
+    let checksum = u32::from_le_bytes(header[4..].try_into().ok()?);
+    let body = buf.get(HEADER_LEN..HEADER_LEN + len)?;
+    if CASTAGNOLI.checksum(body) != checksum {
+        return None;
+    }
+    let record = bincode::deserialize(body).ok()?;
+    Some((record, (HEADER_LEN + len) as u64))
+}
+
+impl SegmentLog {
+    /// Opens the log stored in `dir`, creating it if needed.
+    ///
+    /// The segments are replayed in order to rebuild the index. A segment ending with a truncated
+    /// or corrupted record, e.g. after a crash, is truncated to its last valid record.
+    pub(crate) fn open(dir: PathBuf, options: LogOptions) -> ZResult<Self> {
+        fs::create_dir_all(&dir)
+            .map_err(|e| zerror!("Cannot create directory {}: {e}", dir.display()))?;
+
+        let mut ids = vec![];
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+                continue;
+            }
+            match path
+                .file_stem()
+                .and_then(|stem| stem.to_str()?.parse::<u64>().ok())
+            {
+                Some(id) => ids.push(id),
+                None => tracing::warn!("Ignoring unexpected file {}", path.display()),
+            }
+        }
+        ids.sort_unstable();
+
+        let mut index = BTreeMap::new();
+        let mut tombstones = BTreeMap::new();
+        let mut segments = BTreeMap::new();
+        for id in ids {
+            let path = segment_path(&dir, id);
+            let buf = fs::read(&path)?;
+            let mut offset = 0;
+            while (offset as usize) < buf.len() {
+                let Some((record, len)) = decode(&buf[offset as usize..]) else {
+                    break;
+                };
+                let location = Location {
+                    segment: id,
+                    offset,
+                    len,
+                };
+                // The log only contains accepted records: the last one of a key is its state
+                match record {
+                    Record::Put { key, timestamp, .. } => {
+                        tombstones.remove(&key);
+                        index.insert(
+                            key,
+                            IndexEntry {
+                                timestamp,
+                                location,
+                            },
+                        );
+                    }
+                    Record::Delete { key, timestamp } => {
+                        index.remove(&key);
+                        tombstones.insert(
+                            key,
+                            IndexEntry {
+                                timestamp,
+                                location,
+                            },
+                        );
+                    }
+                }
+                offset += len;
+            }
+            if (offset as usize) < buf.len() {
+                tracing::warn!(
+                    "Truncating {} from {} to {} bytes: found a truncated or corrupted record",
+                    path.display(),
+                    buf.len(),
+                    offset
+                );
+                OpenOptions::new()
+                    .write(true)
+                    .open(&path)?
+                    .set_len(offset)?;
+            }
+            segments.insert(id, offset);
+        }
+
+        let active_id = segments.keys().next_back().copied().unwrap_or_default();
+        let active = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&dir, active_id))?;
+        segments.entry(active_id).or_insert(0);
+
+        let total_bytes = segments.values().sum();
+        let live_bytes = index
+            .values()
+            .chain(tombstones.values())
+            .map(|entry| entry.location.len)
+            .sum();
+        Ok(Self {
+            dir,
+            options,
+            index,
+            tombstones,
+            segments,
+            active_id,
+            active,
+            readers: BTreeMap::new(),
+            dirty: false,
+            total_bytes,
+            live_bytes,
+        })
+    }
+
+    pub(crate) fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        if self.is_outdated(&key, &timestamp) {
+            return Ok(StorageInsertionResult::Outdated);
+        }
+        let record = Record::Put {
+            key: key.clone(),
+            timestamp,
+            encoding: encoding.to_string(),
+            payload: payload.to_bytes().into_owned(),
+        };
+        let location = self.append(&encode(&record)?)?;
+        self.live_bytes += location.len;
+        if let Some(tombstone) = self.tombstones.remove(&key) {
+            self.live_bytes -= tombstone.location.len;
+        }
+        let result = match self.index.insert(
+            key,
+            IndexEntry {
+                timestamp,
+                location,
+            },
+        ) {
+            Some(replaced) => {
+                self.live_bytes -= replaced.location.len;
+                StorageInsertionResult::Replaced
+            }
+            None => StorageInsertionResult::Inserted,
+        };
+        self.compact_if_needed()?;
+        Ok(result)
+    }
+
+    pub(crate) fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        if self.is_outdated(&key, &timestamp) {
+            return Ok(StorageInsertionResult::Outdated);
+        }
+        // Deletes are logged even for absent keys, for the tombstone to survive a restart
+        let record = Record::Delete {
+            key: key.clone(),
+            timestamp,
+        };
+        let location = self.append(&encode(&record)?)?;
+        self.live_bytes += location.len;
+        if let Some(deleted) = self.index.remove(&key) {
+            self.live_bytes -= deleted.location.len;
+        }
+        if let Some(replaced) = self.tombstones.insert(
+            key,
+            IndexEntry {
+                timestamp,
+                location,
+            },
+        ) {
+            self.live_bytes -= replaced.location.len;
+        }
+        self.compact_if_needed()?;
+        Ok(StorageInsertionResult::Deleted)
+    }
+
+    /// Returns true if the key was put or deleted more recently than `timestamp`.
+    fn is_outdated(&self, key: &Option<OwnedKeyExpr>, timestamp: &Timestamp) -> bool {
+        self.index
+            .get(key)
+            .or_else(|| self.tombstones.get(key))
+            .is_some_and(|entry| entry.timestamp > *timestamp)
+    }
+
+    pub(crate) fn get(&mut self, key: &Option<OwnedKeyExpr>) -> ZResult<Option<StoredData>> {
+        let Some(entry) = self.index.get(key).copied() else {
+            return Ok(None);
+        };
+        match decode(&self.read(entry.location)?) {
+            Some((
+                Record::Put {
+                    timestamp,
+                    encoding,
+                    payload,
+                    ..
+                },
+                _,
+            )) => Ok(Some(StoredData {
+                payload: payload.into(),
+                encoding: encoding.into(),
+                timestamp,
+            })),
+            _ => bail!(
+                "Corrupted record for key {:?} in {}",
+                key,
+                segment_path(&self.dir, entry.location.segment).display()
+            ),
+        }
+    }
+
+    /// Returns the entries ordered by key, starting after `after` if any.
+    ///
+    /// The tombstones are entries too, with the timestamp of the delete: a key is either in the
+    /// index or in the tombstones.
+    pub(crate) fn entries(
+        &self,
+        after: Option<Option<OwnedKeyExpr>>,
+    ) -> impl Iterator<Item = (Option<OwnedKeyExpr>, Timestamp)> + Send + '_ {
+        let start = match after {
+            Some(key) => Bound::Excluded(key),
+            None => Bound::Unbounded,
+        };
+        let mut puts = self
+            .index
+            .range((start.clone(), Bound::Unbounded))
+            .peekable();
+        let mut deletes = self.tombstones.range((start, Bound::Unbounded)).peekable();
+        std::iter::from_fn(move || {
+            let next = match (puts.peek(), deletes.peek()) {
+                (Some((put, _)), Some((delete, _))) if delete < put => deletes.next(),
+                (Some(_), _) => puts.next(),
+                (None, _) => deletes.next(),
+            };
+            next.map(|(key, entry)| (key.clone(), entry.timestamp))
+        })
+    }
+
+    /// Removes the tombstones older than `lifespan`, returning how many were removed.
+    ///
+    /// The puts and deletes older than a removed tombstone are no longer rejected. Its record is
+    /// reclaimed by the next compaction, until then it is restored when the log is reopened.
+    pub(crate) fn expire_tombstones(&mut self, lifespan: Duration) -> ZResult<usize> {
+        let Some(time_limit) = SystemTime::now()
+            .checked_sub(lifespan)
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(NTP64::from)
+        else {
+            return Ok(0);
+        };
+        let mut expired = 0;
+        let mut expired_bytes = 0;
+        self.tombstones.retain(|_, entry| {
+            let keep = *entry.timestamp.get_time() >= time_limit;
+            if !keep {
+                expired += 1;
+                expired_bytes += entry.location.len;
+            }
+            keep
+        });
+        self.live_bytes -= expired_bytes;
+        self.compact_if_needed()?;
+        Ok(expired)
+    }
+
+    pub(crate) fn sync(&mut self) -> ZResult<()> {
+        self.active.sync_data()?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Syncs the active segment if records were written to it since the last sync.
+    pub(crate) fn sync_if_dirty(&mut self) -> ZResult<()> {
+        if self.dirty {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, location: Location) -> ZResult<Vec<u8>> {
+        let file = match self.readers.entry(location.segment) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(File::open(segment_path(&self.dir, location.segment))?),
+        };
+        file.seek(SeekFrom::Start(location.offset))?;
+        let mut buf = vec![0; location.len as usize];
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn rotate(&mut self) -> ZResult<()> {
+        self.sync()?;
+        self.active_id += 1;
+        self.active = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&self.dir, self.active_id))?;
+        self.segments.insert(self.active_id, 0);
+        Ok(())
+    }
+
+    /// Appends an encoded record to the log, returning its location.
+    fn append(&mut self, buf: &[u8]) -> ZResult<Location> {
+        let location = self.write(buf)?;
+        if self.options.fsync == FsyncPolicy::Always {
+            self.sync()?;
+        }
+        Ok(location)
+    }
+
+    /// Writes an encoded record to the active segment, without applying the fsync policy.
+    fn write(&mut self, buf: &[u8]) -> ZResult<Location> {
+        let active_size = self.segments[&self.active_id];
+        if active_size > 0 && active_size + buf.len() as u64 > self.options.segment_size {
+            self.rotate()?;
+        }
+        let offset = self.segments[&self.active_id];
+        self.active.write_all(buf)?;
+        self.dirty = true;
+        let len = buf.len() as u64;
+        *self.segments.entry(self.active_id).or_default() += len;
+        self.total_bytes += len;
+        Ok(Location {
+            segment: self.active_id,
+            offset,
+            len,
+        })
+    }
+
+    fn compact_if_needed(&mut self) -> ZResult<()> {
+        if self.total_bytes < MIN_COMPACTION_SIZE {
+            return Ok(());
+        }
+        let obsolete_bytes = self.total_bytes - self.live_bytes;
+        if (obsolete_bytes as f64) < self.options.compaction_threshold * self.total_bytes as f64 {
+            return Ok(());
+        }
+        self.compact()
+    }
+
+    /// Rewrites the live records in new segments and removes the previous ones.
+    ///
+    /// The live records are only copied: until the previous segments are removed, a crash
+    /// leaves them duplicated in the log, which is harmless as the log is replayed in order.
+    pub(crate) fn compact(&mut self) -> ZResult<()> {
+        tracing::debug!(
+            "Compacting {}: {} live bytes out of {}",
+            self.dir.display(),
+            self.live_bytes,
+            self.total_bytes
+        );
+        self.rotate()?;
+        let first_id = self.active_id;
+
+        // The tombstones are live records too, flagged as such to update their location
+        let mut live: Vec<(bool, Option<OwnedKeyExpr>, Location)> = self
+            .index
+            .iter()
+            .map(|(key, entry)| (false, key.clone(), entry.location))
+            .chain(
+                self.tombstones
+                    .iter()
+                    .map(|(key, entry)| (true, key.clone(), entry.location)),
+            )
+            .collect();
+        // Copy the records in their log order to read the previous segments sequentially
+        live.sort_unstable_by_key(|(_, _, location)| (location.segment, location.offset));
+        for (is_tombstone, key, location) in live {
+            let buf = self.read(location)?;
+            let new_location = self.write(&buf)?;
+            let entries = if is_tombstone {
+                &mut self.tombstones
+            } else {
+                &mut self.index
+            };
+            if let Some(entry) = entries.get_mut(&key) {
+                entry.location = new_location;
+            }
+        }
+        self.sync()?;
+
+        let obsolete: Vec<u64> = self.segments.range(..first_id).map(|(id, _)| *id).collect();
+        for id in obsolete {
+            self.readers.remove(&id);
+            fs::remove_file(segment_path(&self.dir, id))?;
+            if let Some(size) = self.segments.remove(&id) {
+                self.total_bytes -= size;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SegmentLog {
+    fn drop(&mut self) {
+        if self.options.fsync != FsyncPolicy::Never {
+            if let Err(e) = self.sync() {
+                tracing::warn!("Failed to sync {}: {e}", self.dir.display());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/log.test.rs"]
+mod tests;
@@ -0,0 +1,311 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    path::PathBuf,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use serde_json::{Map, Value};
+use tokio::sync::Mutex;
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zerror},
+    key_expr::OwnedKeyExpr,
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{GarbageCollectionConfig, StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+
+use crate::FILE_LOG_BACKEND_NAME;
+
+mod log;
+use log::{FsyncPolicy, LogOptions, SegmentLog};
+
+const DIR_KEY: &str = "dir";
+const FSYNC_KEY: &str = "fsync";
+const FSYNC_PERIOD_KEY: &str = "fsync_period";
+const SEGMENT_SIZE_KEY: &str = "segment_size";
+const COMPACTION_THRESHOLD_KEY: &str = "compaction_threshold";
+
+const DEFAULT_FSYNC_PERIOD_SECS: f64 = 1.0;
+const DEFAULT_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+const DEFAULT_COMPACTION_THRESHOLD: f64 = 0.5;
+
+fn get_str<'a>(config: &'a Map<String, Value>, key: &str) -> ZResult<Option<&'a str>> {
+    match config.get(key) {
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(value) => bail!("Invalid value for field `{key}` of the file_log volume: expected a string, found {value}"),
+        None => Ok(None),
+    }
+}
+
+fn get_positive_f64(config: &Map<String, Value>, key: &str) -> ZResult<Option<f64>> {
+    match config.get(key) {
+        Some(value) => match value.as_f64() {
+            Some(value) if value.is_finite() && value > 0.0 => Ok(Some(value)),
+            _ => bail!("Invalid value for field `{key}` of the file_log volume: expected a positive number, found {value}"),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse_log_options(config: &Map<String, Value>) -> ZResult<LogOptions> {
+    let fsync_period = get_positive_f64(config, FSYNC_PERIOD_KEY)?;
+    let fsync = match get_str(config, FSYNC_KEY)? {
+        None | Some("always") => FsyncPolicy::Always,
+        Some("periodic") => FsyncPolicy::Periodic(Duration::from_secs_f64(
+            fsync_period.unwrap_or(DEFAULT_FSYNC_PERIOD_SECS),
+        )),
+        Some("never") => FsyncPolicy::Never,
+        Some(fsync) => bail!(
+            "Invalid value for field `{FSYNC_KEY}` of the file_log volume: expected one of \
+             `always`, `periodic` or `never`, found `{fsync}`"
+        ),
+    };
+    if fsync_period.is_some() && !matches!(fsync, FsyncPolicy::Periodic(_)) {
+        bail!("Field `{FSYNC_PERIOD_KEY}` of the file_log volume is only supported by the `periodic` fsync policy");
+    }
+    let segment_size = match config.get(SEGMENT_SIZE_KEY) {
+        Some(value) => match value.as_u64() {
+            Some(segment_size) if segment_size > 0 => segment_size,
+            _ => bail!("Invalid value for field `{SEGMENT_SIZE_KEY}` of the file_log volume: expected a positive integer, found {value}"),
+        },
+        None => DEFAULT_SEGMENT_SIZE,
+    };
+    let compaction_threshold =
+        get_positive_f64(config, COMPACTION_THRESHOLD_KEY)?.unwrap_or(DEFAULT_COMPACTION_THRESHOLD);
+    if compaction_threshold > 1.0 {
+        bail!("Invalid value for field `{COMPACTION_THRESHOLD_KEY}` of the file_log volume: expected a number in ]0, 1], found {compaction_threshold}");
+    }
+    Ok(LogOptions {
+        fsync,
+        segment_size,
+        compaction_threshold,
+    })
+}
+
+pub struct FileLogBackend {
+    config: VolumeConfig,
+    dir: PathBuf,
+    options: LogOptions,
+}
+
+impl Plugin for FileLogBackend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = FILE_LOG_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let Some(dir) = get_str(&args.rest, DIR_KEY)? else {
+            bail!("Field `{DIR_KEY}` of the file_log volume is mandatory");
+        };
+        Ok(Box::new(FileLogBackend {
+            config: args.clone(),
+            dir: dir.into(),
+            options: parse_log_options(&args.rest)?,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for FileLogBackend {
+    fn get_admin_status(&self) -> serde_json::Value {
+        self.config.to_json_value()
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::Latest,
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!(
+            "Create File Log Storage with configuration: {:?}",
+            properties
+        );
+        // Each storage is stored in its own sub-directory, named after the storage by default
+        let dir = match properties.volume_cfg.get(DIR_KEY) {
+            Some(Value::String(dir)) => self.dir.join(dir),
+            Some(dir) => bail!(
+                "Invalid value for field `{DIR_KEY}` of storage `{}`: expected a string, found {dir}",
+                properties.name
+            ),
+            None => self.dir.join(&properties.name),
+        };
+        let options = self.options;
+        let log = tokio::task::spawn_blocking(move || SegmentLog::open(dir, options))
+            .await
+            .map_err(|e| zerror!("Failed to open the file log: {e}"))??;
+        let log = Arc::new(Mutex::new(log));
+        if let FsyncPolicy::Periodic(period) = options.fsync {
+            spawn_periodic_sync(period, Arc::downgrade(&log));
+        }
+        spawn_periodic_gc(
+            properties.garbage_collection_config.clone(),
+            Arc::downgrade(&log),
+        );
+        Ok(Box::new(FileLogStorage {
+            config: properties,
+            log,
+        }))
+    }
+}
+
+/// Periodically syncs the records appended to the log, including when no record is appended
+/// afterwards, until the storage is dropped.
+fn spawn_periodic_sync(period: Duration, log: Weak<Mutex<SegmentLog>>) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(log) = log.upgrade() else {
+                break;
+            };
+            match tokio::task::spawn_blocking(move || log.blocking_lock().sync_if_dirty()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!("Failed to sync the file log: {e}"),
+                Err(e) => tracing::warn!("Failed to sync the file log: {e}"),
+            }
+        }
+    });
+}
+
+/// Periodically removes the tombstones older than the lifespan of the garbage collection of the
+/// storage, like the storage manager does for its own metadata, until the storage is dropped.
+fn spawn_periodic_gc(config: GarbageCollectionConfig, log: Weak<Mutex<SegmentLog>>) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(config.period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let Some(log) = log.upgrade() else {
+                break;
+            };
+            let lifespan = config.lifespan;
+            match tokio::task::spawn_blocking(move || {
+                log.blocking_lock().expire_tombstones(lifespan)
+            })
+            .await
+            {
+                Ok(Ok(expired)) => tracing::trace!("Expired {expired} tombstones of the file log"),
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to expire the tombstones of the file log: {e}")
+                }
+                Err(e) => tracing::warn!("Failed to expire the tombstones of the file log: {e}"),
+            }
+        }
+    });
+}
+
+struct FileLogStorage {
+    config: StorageConfig,
+    log: Arc<Mutex<SegmentLog>>,
+}
+
+impl FileLogStorage {
+    /// Runs an operation of the log, which performs blocking file I/O, on the blocking threads.
+    async fn with_log<T, F>(&self, f: F) -> ZResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SegmentLog) -> ZResult<T> + Send + 'static,
+    {
+        let log = self.log.clone();
+        tokio::task::spawn_blocking(move || f(&mut log.blocking_lock()))
+            .await
+            .map_err(|e| zerror!("File log operation failed: {e}"))?
+    }
+}
+
+#[async_trait]
+impl Storage for FileLogStorage {
+    fn get_admin_status(&self) -> serde_json::Value {
+        self.config.to_json_value()
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        self.with_log(move |log| log.put(key, payload, encoding, timestamp))
+            .await
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        self.with_log(move |log| log.delete(key, timestamp)).await
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        _parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        let data = {
+            let key = key.clone();
+            self.with_log(move |log| log.get(&key)).await?
+        };
+        match data {
+            Some(data) => Ok(vec![data]),
+            None => Err(format!("Key {:?} is not present", key).into()),
+        }
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        // The entries are served from the in-memory index, without any I/O
+        Ok(self.log.lock().await.entries(None).collect())
+    }
+
+    fn get_entries_stream(
+        &self,
+        cursor: Option<EntryCursor>,
+        limit: Option<usize>,
+    ) -> EntriesStream<'_> {
+        stream::once(async move {
+            self.log
+                .lock()
+                .await
+                .entries(cursor.map(|EntryCursor(key)| key))
+                .take(limit.unwrap_or(usize::MAX))
+                .collect::<Vec<_>>()
+        })
+        .flat_map(|entries| stream::iter(entries.into_iter().map(Ok)))
+        .boxed()
+    }
+
+    fn has_entries_cursor(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,376 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{fs, path::PathBuf, str::FromStr};
+
+use uhlc::HLC;
+use zenoh::{bytes::Encoding, key_expr::OwnedKeyExpr};
+
+use super::*;
+
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new() -> Self {
+        Self(std::env::temp_dir().join(format!("zenoh-file-log-{}", uuid::Uuid::new_v4().simple())))
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+const OPTIONS: LogOptions = LogOptions {
+    fsync: FsyncPolicy::Always,
+    segment_size: 64 * 1024,
+    compaction_threshold: 0.5,
+};
+
+fn key(key: &str) -> Option<OwnedKeyExpr> {
+    Some(OwnedKeyExpr::from_str(key).unwrap())
+}
+
+fn payload(log: &mut SegmentLog, key: &Option<OwnedKeyExpr>) -> Option<String> {
+    log.get(key)
+        .unwrap()
+        .map(|data| data.payload.try_to_string().unwrap().into_owned())
+}
+
+fn segment_files(dir: &TestDir) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir.0)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn test_reopen() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    log.put(
+        key("a"),
+        "1".into(),
+        Encoding::TEXT_PLAIN,
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.put(
+        key("b"),
+        "2".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.put(None, "3".into(), Encoding::default(), hlc.new_timestamp())
+        .unwrap();
+    log.delete(key("b"), hlc.new_timestamp()).unwrap();
+    let ts_a = hlc.new_timestamp();
+    log.put(key("a"), "4".into(), Encoding::TEXT_PLAIN, ts_a)
+        .unwrap();
+    drop(log);
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    assert_eq!(payload(&mut log, &key("a")), Some("4".into()));
+    assert_eq!(payload(&mut log, &key("b")), None);
+    assert_eq!(payload(&mut log, &None), Some("3".into()));
+    assert_eq!(
+        log.get(&key("a")).unwrap().unwrap().encoding,
+        Encoding::TEXT_PLAIN
+    );
+
+    // The deleted key is listed with its tombstone
+    let entries: Vec<_> = log.entries(None).collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1], (key("a"), ts_a));
+    assert_eq!(log.entries(Some(key("a"))).count(), 1);
+    assert_eq!(log.entries(Some(key("b"))).count(), 0);
+}
+
+#[test]
+fn test_outdated() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    let old = hlc.new_timestamp();
+    log.put(
+        key("a"),
+        "1".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    assert!(matches!(
+        log.put(key("a"), "0".into(), Encoding::default(), old)
+            .unwrap(),
+        StorageInsertionResult::Outdated
+    ));
+    assert!(matches!(
+        log.delete(key("a"), old).unwrap(),
+        StorageInsertionResult::Outdated
+    ));
+    assert_eq!(payload(&mut log, &key("a")), Some("1".into()));
+}
+
+#[test]
+fn test_crash_recovery() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    log.put(
+        key("a"),
+        "1".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.put(
+        key("b"),
+        "2".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    drop(log);
+
+    // Simulate a crash in the middle of the last record
+    let files = segment_files(&dir);
+    assert_eq!(files.len(), 1);
+    let len = fs::metadata(&files[0]).unwrap().len();
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&files[0])
+        .unwrap()
+        .set_len(len - 3)
+        .unwrap();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    assert_eq!(payload(&mut log, &key("a")), Some("1".into()));
+    assert_eq!(payload(&mut log, &key("b")), None);
+
+    // The records appended after the recovery are readable
+    log.put(
+        key("c"),
+        "3".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    drop(log);
+
+    // Corrupt the last record
+    let mut buf = fs::read(&files[0]).unwrap();
+    let last = buf.len() - 1;
+    buf[last] ^= 0xff;
+    fs::write(&files[0], buf).unwrap();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    assert_eq!(payload(&mut log, &key("a")), Some("1".into()));
+    assert_eq!(payload(&mut log, &key("c")), None);
+}
+
+#[test]
+fn test_compaction() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+    let value = "x".repeat(1024);
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    log.put(
+        key("kept"),
+        "1".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    for i in 0..2048 {
+        log.put(
+            key("overwritten"),
+            format!("{i}{value}").into(),
+            Encoding::default(),
+            hlc.new_timestamp(),
+        )
+        .unwrap();
+    }
+    log.put(
+        key("deleted"),
+        "2".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.delete(key("deleted"), hlc.new_timestamp()).unwrap();
+
+    // Without compaction the log would be 2 MiB large
+    let size: u64 = segment_files(&dir)
+        .iter()
+        .map(|file| fs::metadata(file).unwrap().len())
+        .sum();
+    assert!(size < MIN_COMPACTION_SIZE + OPTIONS.segment_size);
+
+    log.compact().unwrap();
+    assert_eq!(segment_files(&dir).len(), 1);
+    drop(log);
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    assert_eq!(payload(&mut log, &key("kept")), Some("1".into()));
+    assert_eq!(
+        payload(&mut log, &key("overwritten")),
+        Some(format!("2047{value}"))
+    );
+    assert_eq!(payload(&mut log, &key("deleted")), None);
+    assert_eq!(log.entries(None).count(), 3);
+}
+
+#[test]
+fn test_tombstones() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    let old = hlc.new_timestamp();
+    // A delete of an absent key is kept, e.g. when received before the put it deletes
+    log.delete(key("a"), hlc.new_timestamp()).unwrap();
+    log.put(
+        key("b"),
+        "1".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    log.delete(key("b"), hlc.new_timestamp()).unwrap();
+    drop(log);
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    for key in [key("a"), key("b")] {
+        assert!(matches!(
+            log.put(key.clone(), "0".into(), Encoding::default(), old)
+                .unwrap(),
+            StorageInsertionResult::Outdated
+        ));
+        assert!(matches!(
+            log.delete(key.clone(), old).unwrap(),
+            StorageInsertionResult::Outdated
+        ));
+        assert_eq!(payload(&mut log, &key), None);
+    }
+    // The tombstones are listed with the timestamp of their delete
+    let entries: Vec<_> = log.entries(None).map(|(key, _)| key).collect();
+    assert_eq!(entries, vec![key("a"), key("b")]);
+
+    // The tombstones survive the compaction, and a more recent put replaces them
+    log.compact().unwrap();
+    log.put(
+        key("b"),
+        "2".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    drop(log);
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    assert!(matches!(
+        log.put(key("a"), "0".into(), Encoding::default(), old)
+            .unwrap(),
+        StorageInsertionResult::Outdated
+    ));
+    assert_eq!(payload(&mut log, &key("b")), Some("2".into()));
+    assert_eq!(log.tombstones.len(), 1);
+}
+
+#[test]
+fn test_entries() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    for k in ["a", "c", "e"] {
+        log.put(key(k), "1".into(), Encoding::default(), hlc.new_timestamp())
+            .unwrap();
+    }
+    for k in ["b", "d", "e"] {
+        log.delete(key(k), hlc.new_timestamp()).unwrap();
+    }
+    let deleted = log.tombstones[&key("d")].timestamp;
+
+    // The puts and the tombstones are merged in key order
+    let entries: Vec<_> = log.entries(None).collect();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>(),
+        vec![key("a"), key("b"), key("c"), key("d"), key("e")]
+    );
+    assert_eq!(entries[3].1, deleted);
+    let entries: Vec<_> = log.entries(Some(key("b"))).map(|(key, _)| key).collect();
+    assert_eq!(entries, vec![key("c"), key("d"), key("e")]);
+}
+
+#[test]
+fn test_expire_tombstones() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+
+    let mut log = SegmentLog::open(dir.0.clone(), OPTIONS).unwrap();
+    let old = hlc.new_timestamp();
+    log.delete(key("a"), hlc.new_timestamp()).unwrap();
+    assert_eq!(
+        log.expire_tombstones(std::time::Duration::from_secs(3600))
+            .unwrap(),
+        0
+    );
+    assert_eq!(log.tombstones.len(), 1);
+
+    // An expired tombstone no longer rejects the older puts
+    assert_eq!(log.expire_tombstones(std::time::Duration::ZERO).unwrap(), 1);
+    assert_eq!(log.entries(None).count(), 0);
+    assert!(matches!(
+        log.put(key("a"), "0".into(), Encoding::default(), old)
+            .unwrap(),
+        StorageInsertionResult::Inserted
+    ));
+}
+
+#[test]
+fn test_sync_if_dirty() {
+    let dir = TestDir::new();
+    let hlc = HLC::default();
+    let options = LogOptions {
+        fsync: FsyncPolicy::Periodic(std::time::Duration::from_secs(3600)),
+        ..OPTIONS
+    };
+
+    let mut log = SegmentLog::open(dir.0.clone(), options).unwrap();
+    assert!(!log.dirty);
+    log.put(
+        key("a"),
+        "1".into(),
+        Encoding::default(),
+        hlc.new_timestamp(),
+    )
+    .unwrap();
+    // Nothing is synced on append, the periodic task syncs it
+    assert!(log.dirty);
+    log.sync_if_dirty().unwrap();
+    assert!(!log.dirty);
+}
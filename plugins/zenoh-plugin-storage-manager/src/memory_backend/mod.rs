@@ -11,13 +11,22 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{btree_map::Entry, vec_deque, BTreeMap, VecDeque},
+    ops::Bound,
+    sync::{Arc, Weak},
+    time::{Duration, SystemTime},
+};
 
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
+use serde_json::Value;
 use tokio::sync::RwLock;
 use zenoh::{
     bytes::{Encoding, ZBytes},
+    internal::bail,
     key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeRange, ZenohParameters},
     time::Timestamp,
     Result as ZResult,
 };
@@ -74,18 +83,143 @@ impl Drop for MemoryBackend {
     }
 }
 
+/// Retention of the samples of each key by a storage keeping their history.
+#[derive(Debug, Clone, Copy)]
+struct HistoryConfig {
+    max_samples: Option<usize>,
+    max_age: Option<Duration>,
+}
+
+impl HistoryConfig {
+    const HISTORY_KEY: &'static str = "history";
+    const MAX_SAMPLES_KEY: &'static str = "max_samples";
+    const MAX_AGE_KEY: &'static str = "max_age";
+
+    /// Parses the optional `history` field of the storage `volume` configuration.
+    fn from_volume_cfg(volume_cfg: &Value) -> ZResult<Option<Self>> {
+        let Some(history) = volume_cfg.get(Self::HISTORY_KEY) else {
+            return Ok(None);
+        };
+        let Some(history) = history.as_object() else {
+            bail!(
+                "Invalid type for field `{}` of the memory volume: expected an object",
+                Self::HISTORY_KEY
+            );
+        };
+        let mut config = HistoryConfig {
+            max_samples: None,
+            max_age: None,
+        };
+        for (key, value) in history {
+            match key.as_str() {
+                Self::MAX_SAMPLES_KEY => match value.as_u64() {
+                    Some(max_samples) if max_samples > 0 => {
+                        config.max_samples = Some(max_samples as usize)
+                    }
+                    _ => bail!(
+                        "Invalid value for field `{}` of the memory volume history: expected a \
+                         positive integer, found {value}",
+                        Self::MAX_SAMPLES_KEY
+                    ),
+                },
+                Self::MAX_AGE_KEY => match value.as_f64() {
+                    Some(max_age) if max_age.is_finite() && max_age > 0.0 => {
+                        config.max_age = Some(Duration::from_secs_f64(max_age))
+                    }
+                    _ => bail!(
+                        "Invalid value for field `{}` of the memory volume history: expected a \
+                         positive number of seconds, found {value}",
+                        Self::MAX_AGE_KEY
+                    ),
+                },
+                _ => bail!("Unknown field `{key}` in the memory volume history"),
+            }
+        }
+        Ok(Some(config))
+    }
+
+    /// Returns true if a sample with the given timestamp is too old to be kept.
+    fn is_expired(&self, timestamp: &Timestamp, now: SystemTime) -> bool {
+        self.max_age.is_some_and(|max_age| {
+            now.duration_since(timestamp.get_time().to_system_time())
+                .is_ok_and(|age| age > max_age)
+        })
+    }
+
+    /// Returns the samples that are not expired, whether they were pruned yet or not.
+    fn unexpired<'a>(
+        &self,
+        samples: &'a VecDeque<StoredData>,
+        now: SystemTime,
+    ) -> vec_deque::Iter<'a, StoredData> {
+        samples.range(samples.partition_point(|sample| self.is_expired(&sample.timestamp, now))..)
+    }
+
+    /// Removes the samples exceeding the retention, oldest first.
+    fn prune(&self, samples: &mut VecDeque<StoredData>, now: SystemTime) {
+        if let Some(max_samples) = self.max_samples {
+            while samples.len() > max_samples {
+                samples.pop_front();
+            }
+        }
+        while samples
+            .front()
+            .is_some_and(|sample| self.is_expired(&sample.timestamp, now))
+        {
+            samples.pop_front();
+        }
+    }
+}
+
+type HistoryMap = BTreeMap<Option<OwnedKeyExpr>, VecDeque<StoredData>>;
+
 struct MemoryStorage {
     config: StorageConfig,
-    map: Arc<RwLock<HashMap<Option<OwnedKeyExpr>, StoredData>>>,
+    history: Option<HistoryConfig>,
+    // The samples of each key are sorted by timestamp. Only the latest one is kept if the
+    // storage does not keep the history.
+    map: Arc<RwLock<HistoryMap>>,
 }
 
 impl MemoryStorage {
     async fn new(properties: StorageConfig) -> ZResult<MemoryStorage> {
+        let history = HistoryConfig::from_volume_cfg(&properties.volume_cfg)?;
+        let map = Arc::new(RwLock::new(BTreeMap::new()));
+        if let Some(
+            history @ HistoryConfig {
+                max_age: Some(max_age),
+                ..
+            },
+        ) = history
+        {
+            Self::spawn_pruning(history, max_age, Arc::downgrade(&map));
+        }
         Ok(MemoryStorage {
+            history,
             config: properties,
-            map: Arc::new(RwLock::new(HashMap::new())),
+            map,
         })
     }
+
+    /// Periodically removes the expired samples of all the keys, including the keys that are
+    /// not written anymore, until the storage is dropped.
+    fn spawn_pruning(history: HistoryConfig, period: Duration, map: Weak<RwLock<HistoryMap>>) {
+        tokio::task::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let Some(map) = map.upgrade() else {
+                    break;
+                };
+                let now = SystemTime::now();
+                map.write().await.retain(|_, samples| {
+                    history.prune(samples, now);
+                    !samples.is_empty()
+                });
+            }
+        });
+    }
 }
 
 #[async_trait]
@@ -94,6 +228,13 @@ impl Storage for MemoryStorage {
         self.config.to_json_value()
     }
 
+    fn get_capability(&self) -> Option<Capability> {
+        self.history.map(|_| Capability {
+            persistence: Persistence::Volatile,
+            history: History::All,
+        })
+    }
+
     async fn put(
         &mut self,
         key: Option<OwnedKeyExpr>,
@@ -102,58 +243,129 @@ impl Storage for MemoryStorage {
         timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("put for {:?}", key);
+        let data = StoredData {
+            payload,
+            encoding,
+            timestamp,
+        };
         let mut map = self.map.write().await;
-        match map.entry(key) {
-            std::collections::hash_map::Entry::Occupied(mut e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
-                return Ok(StorageInsertionResult::Replaced);
-            }
-            std::collections::hash_map::Entry::Vacant(e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
-                return Ok(StorageInsertionResult::Inserted);
-            }
+        let Some(history) = &self.history else {
+            return match map.entry(key) {
+                Entry::Occupied(mut e) => {
+                    e.insert(VecDeque::from([data]));
+                    Ok(StorageInsertionResult::Replaced)
+                }
+                Entry::Vacant(e) => {
+                    e.insert(VecDeque::from([data]));
+                    Ok(StorageInsertionResult::Inserted)
+                }
+            };
+        };
+
+        let now = SystemTime::now();
+        if history.is_expired(&timestamp, now) {
+            return Ok(StorageInsertionResult::Outdated);
         }
+        let samples = map.entry(key).or_default();
+        let index = samples.partition_point(|sample| sample.timestamp < timestamp);
+        if samples
+            .get(index)
+            .is_some_and(|sample| sample.timestamp == timestamp)
+        {
+            return Ok(StorageInsertionResult::Outdated);
+        }
+        if index == 0 && history.max_samples.is_some_and(|max| samples.len() >= max) {
+            return Ok(StorageInsertionResult::Outdated);
+        }
+        samples.insert(index, data);
+        history.prune(samples, now);
+        Ok(StorageInsertionResult::Inserted)
     }
 
     async fn delete(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _timestamp: Timestamp,
+        timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("delete for {:?}", key);
-        self.map.write().await.remove_entry(&key);
+        let mut map = self.map.write().await;
+        if self.history.is_none() {
+            map.remove_entry(&key);
+            return Ok(StorageInsertionResult::Deleted);
+        }
+        // Only the samples preceding the deletion are removed from the history
+        if let Entry::Occupied(mut e) = map.entry(key) {
+            e.get_mut().retain(|sample| sample.timestamp > timestamp);
+            if e.get().is_empty() {
+                e.remove();
+            }
+        }
         return Ok(StorageInsertionResult::Deleted);
     }
 
     async fn get(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _parameters: &str,
+        parameters: &str,
     ) -> ZResult<Vec<StoredData>> {
         tracing::trace!("get for {:?}", key);
-        // @TODO: use parameters???
-        match self.map.read().await.get(&key) {
-            Some(v) => Ok(vec![v.clone()]),
-            None => Err(format!("Key {:?} is not present", key).into()),
+        let time_range = Parameters::from(parameters)
+            .time_range()
+            .transpose()?
+            .map(TimeRange::resolve);
+
+        let map = self.map.read().await;
+        let samples = map.get(&key).map(|samples| match &self.history {
+            Some(history) => history.unexpired(samples, SystemTime::now()),
+            None => samples.iter(),
+        });
+        let Some(mut samples) = samples.filter(|samples| samples.len() > 0) else {
+            return Err(format!("Key {:?} is not present", key).into());
+        };
+        match time_range {
+            Some(time_range) => Ok(samples
+                .filter(|sample| time_range.contains(sample.timestamp.get_time().to_system_time()))
+                .cloned()
+                .collect()),
+            None => Ok(samples.next_back().cloned().into_iter().collect()),
         }
     }
 
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
         let map = self.map.read().await;
         let mut result = Vec::with_capacity(map.len());
-        for (k, v) in map.iter() {
-            result.push((k.clone(), v.timestamp));
+        for (k, samples) in map.iter() {
+            if let Some(latest) = samples.back() {
+                result.push((k.clone(), latest.timestamp));
+            }
         }
         Ok(result)
     }
+
+    fn get_entries_stream(
+        &self,
+        cursor: Option<EntryCursor>,
+        limit: Option<usize>,
+    ) -> EntriesStream<'_> {
+        // Only the requested entries are collected, the lock not being held across the stream
+        stream::once(async move {
+            let map = self.map.read().await;
+            let start = match cursor {
+                Some(EntryCursor(key)) => Bound::Excluded(key),
+                None => Bound::Unbounded,
+            };
+            map.range((start, Bound::Unbounded))
+                .filter_map(|(k, samples)| Some(Ok((k.clone(), samples.back()?.timestamp))))
+                .take(limit.unwrap_or(usize::MAX))
+                .collect::<Vec<_>>()
+        })
+        .flat_map(stream::iter)
+        .boxed()
+    }
+
+    fn has_entries_cursor(&self) -> bool {
+        true
+    }
 }
 
 impl Drop for MemoryStorage {
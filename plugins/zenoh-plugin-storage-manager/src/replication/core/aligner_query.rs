@@ -14,6 +14,7 @@
 
 use std::collections::{HashMap, HashSet};
 
+use futures::{future, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use zenoh::{
     bytes::{Encoding, ZBytes},
@@ -287,13 +288,17 @@ impl Replication {
             Action::Delete | Action::WildcardDelete(_) => None,
             // For a Put we need to retrieve the `Value` in the Storage.
             Action::Put => {
-                let stored_data = {
+                let requested_data = {
                     let mut storage = self.storage_service.storage.lock().await;
-                    match storage
-                        .get(event_to_retrieve.stripped_key.clone(), "")
-                        .await
-                    {
-                        Ok(stored_data) => stored_data,
+                    let result = storage
+                        .get_stream(event_to_retrieve.stripped_key.clone(), "")
+                        .try_filter(|data| {
+                            future::ready(data.timestamp == *event_to_retrieve.timestamp())
+                        })
+                        .try_next()
+                        .await;
+                    match result {
+                        Ok(requested_data) => requested_data,
                         Err(e) => {
                             tracing::error!(
                                 "Failed to retrieve data associated to key < {:?} >: {e:?}",
@@ -304,9 +309,6 @@ impl Replication {
                     }
                 };
 
-                let requested_data = stored_data
-                    .into_iter()
-                    .find(|data| data.timestamp == *event_to_retrieve.timestamp());
                 match requested_data {
                     Some(data) => Some((data.payload, data.encoding)),
                     None => {
@@ -21,7 +21,7 @@ use zenoh_backend_traits::{config::StorageConfig, History, VolumeInstance};
 use crate::replication::{Action, Event, LogLatest, LogLatestKey, ReplicationService};
 
 pub(crate) mod service;
-pub(crate) use service::StorageService;
+pub(crate) use service::{EntriesPager, StorageService, ENTRIES_PAGE_SIZE};
 
 #[derive(Clone)]
 pub enum StorageMessage {
@@ -56,8 +56,10 @@ pub(crate) async fn create_and_start_storage(
     zenoh_session: Arc<Session>,
 ) -> ZResult<Sender<StorageMessage>> {
     tracing::trace!("Create storage '{}'", &admin_key);
-    let capability = backend.get_capability();
     let storage = backend.create_storage(config.clone()).await?;
+    let capability = storage
+        .get_capability()
+        .unwrap_or_else(|| backend.get_capability());
 
     // Ex: @/390CEC11A1E34977A1C609A35BC015E6/router/status/plugins/storage_manager/storages/demo1
     // -> 390CEC11A1E34977A1C609A35BC015E6/demo1 (/<type> needed????)
@@ -69,19 +71,7 @@ pub(crate) async fn create_and_start_storage(
     let (tx, rx_storage) = tokio::sync::broadcast::channel(1);
     let rx_replication = tx.subscribe();
 
-    let mut entries = match storage.get_all_entries().await {
-        Ok(entries) => entries
-            .into_iter()
-            .map(|(stripped_key, ts)| {
-                let event = Event::new(stripped_key, ts, &Action::Put);
-                (event.log_key(), event)
-            })
-            .collect::<HashMap<_, _>>(),
-        Err(e) => bail!("`get_all_entries` failed with: {e:?}"),
-    };
-
-    let mut replication_log = None;
-    let mut latest_updates = HashMap::default();
+    let mut log_latest = None;
     if let Some(replica_config) = &config.replication {
         if capability.history != History::Latest {
             bail!(
@@ -91,17 +81,32 @@ pub(crate) async fn create_and_start_storage(
                 History::Latest
             );
         }
-        let mut log_latest = LogLatest::new(
+        log_latest = Some(LogLatest::new(
             config.key_expr.clone(),
             config.strip_prefix.clone(),
             replica_config.clone(),
-        );
-        log_latest.update(entries.drain().map(|(_, event)| event));
+        ));
+    }
 
-        replication_log = Some(Arc::new(RwLock::new(log_latest)));
-    } else {
-        latest_updates = entries;
+    // The entries are read a page at a time for the replication log to be built without
+    // materializing them all
+    let mut latest_updates = HashMap::default();
+    let mut pager = EntriesPager::new(storage.as_ref());
+    loop {
+        let page = match pager.next_page(storage.as_ref(), ENTRIES_PAGE_SIZE).await {
+            Ok(Some(page)) => page,
+            Ok(None) => break,
+            Err(e) => bail!("Failed to retrieve the entries of storage {name}: {e:?}"),
+        };
+        let events = page
+            .into_iter()
+            .map(|(stripped_key, ts)| Event::new(stripped_key, ts, &Action::Put));
+        match log_latest.as_mut() {
+            Some(log_latest) => log_latest.update(events),
+            None => latest_updates.extend(events.map(|event| (event.log_key(), event))),
+        }
     }
+    let replication_log = log_latest.map(|log_latest| Arc::new(RwLock::new(log_latest)));
 
     let latest_updates = Arc::new(RwLock::new(latest_updates));
 
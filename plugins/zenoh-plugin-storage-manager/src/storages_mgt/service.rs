@@ -13,13 +13,14 @@
 //
 
 use std::{
-    collections::HashSet,
+    collections::{BinaryHeap, HashSet},
     str::{self},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use tokio::sync::{broadcast::Receiver, Mutex, RwLock, RwLockWriteGuard};
 use zenoh::{
     bytes::{Encoding, ZBytes},
@@ -29,7 +30,7 @@ use zenoh::{
         keyexpr_tree::{
             IKeyExprTree, IKeyExprTreeMut, KeBoxTree, KeyedSetProvider, UnknownWildness,
         },
-        OwnedKeyExpr,
+        KeyExpr, OwnedKeyExpr,
     },
     sample::{Sample, SampleBuilder, SampleFields, SampleKind},
     session::Session,
@@ -38,7 +39,8 @@ use zenoh::{
 };
 use zenoh_backend_traits::{
     config::{GarbageCollectionConfig, StorageConfig},
-    Capability, History, StorageInsertionResult, StoredData,
+    Capability, EntriesOrder, EntryCursor, History, PagingParameters, Storage,
+    StorageInsertionResult, StoredData,
 };
 
 use super::LatestUpdates;
@@ -47,6 +49,67 @@ use crate::{
     storages_mgt::{CacheLatest, StorageMessage},
 };
 
+// The maximum number of entries of the storage read at once when looking for the keys matching a
+// wildcard
+pub(crate) const ENTRIES_PAGE_SIZE: usize = 1000;
+
+/// Reads the entries (key, timestamp) of a storage a page at a time, ordered by key, for the
+/// storage to be locked only while a page is read.
+pub(crate) enum EntriesPager {
+    /// The storage resumes the reading after the cursor.
+    Cursor(Option<EntryCursor>),
+    /// The storage has no cursor: its entries are read at once on the first page, then paged.
+    Snapshot(Option<std::vec::IntoIter<(Option<OwnedKeyExpr>, Timestamp)>>),
+    Done,
+}
+
+impl EntriesPager {
+    pub(crate) fn new(storage: &dyn Storage) -> Self {
+        if storage.has_entries_cursor() {
+            Self::Cursor(None)
+        } else {
+            Self::Snapshot(None)
+        }
+    }
+
+    /// Returns the next page of at most `page_size` entries, or None once all the entries were
+    /// read.
+    pub(crate) async fn next_page(
+        &mut self,
+        storage: &dyn Storage,
+        page_size: usize,
+    ) -> ZResult<Option<Vec<(Option<OwnedKeyExpr>, Timestamp)>>> {
+        let page: Vec<_> = match self {
+            Self::Cursor(cursor) => {
+                storage
+                    .get_entries_stream(cursor.take(), Some(page_size))
+                    .try_collect()
+                    .await?
+            }
+            Self::Snapshot(snapshot) => {
+                let snapshot = match snapshot {
+                    Some(snapshot) => snapshot,
+                    None => {
+                        let mut entries = storage.get_all_entries().await?;
+                        entries.sort_unstable_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                        snapshot.insert(entries.into_iter())
+                    }
+                };
+                snapshot.take(page_size).collect()
+            }
+            Self::Done => return Ok(None),
+        };
+        match (&mut *self, page.last()) {
+            (Self::Cursor(cursor), Some((key, _))) if page.len() == page_size => {
+                *cursor = Some(EntryCursor(key.clone()))
+            }
+            (Self::Snapshot(_), _) if page.len() == page_size => {}
+            _ => *self = Self::Done,
+        }
+        Ok((!page.is_empty()).then_some(page))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Update {
     kind: SampleKind,
@@ -553,108 +616,265 @@ impl StorageService {
         };
         tracing::trace!("[STORAGE] Processing query on key_expr: {}", q.key_expr());
 
-        let prefix = self.configuration.strip_prefix.as_ref();
+        let paging = match PagingParameters::try_from(q.parameters()) {
+            Ok(paging) => paging,
+            Err(e) => {
+                tracing::debug!("Storage '{}' received an invalid query: {e}", self.name);
+                if let Err(e) = q.reply_err(e.to_string()).await {
+                    tracing::warn!(
+                        "Storage '{}' raised an error replying a query: {}",
+                        self.name,
+                        e
+                    )
+                }
+                return;
+            }
+        };
 
-        if q.key_expr().is_wild() {
-            // resolve key expr into individual keys
-            let matching_keys = self.get_matching_keys(q.key_expr()).await;
-            let mut storage = self.storage.lock().await;
-            for key in matching_keys {
-                let stripped_key = match crate::strip_prefix(prefix, &key.clone().into()) {
-                    Ok(k) => k,
-                    Err(e) => {
-                        tracing::error!("{}", e);
-                        // @TODO: return error when it is supported
+        if !q.key_expr().is_wild() {
+            // The paging parameters apply to the single matching key
+            if paging.offset == 0 && paging.limit != Some(0) {
+                self.reply_key(&q, q.key_expr().clone(), paging.keys_only)
+                    .await;
+            }
+            return;
+        }
+
+        // If all the entries of the storage match the query, only the entries replied to it are
+        // read from the storage, otherwise the entries not matching it are skipped
+        let matches_all = q.key_expr().includes(&self.configuration.key_expr);
+        match paging.order {
+            EntriesOrder::Key => {
+                // resolve key expr into individual keys, a page at a time for the storage not to
+                // be locked while replying
+                let mut skip = paging.offset;
+                let mut remaining = paging.limit.unwrap_or(usize::MAX);
+                let mut pager = self.entries_pager().await;
+                while remaining > 0 {
+                    let page_size = if matches_all {
+                        skip.saturating_add(remaining).min(ENTRIES_PAGE_SIZE)
+                    } else {
+                        ENTRIES_PAGE_SIZE
+                    };
+                    let Some(entries) = self
+                        .next_matching_entries(q.key_expr(), &mut pager, page_size)
+                        .await
+                    else {
                         return;
+                    };
+                    let skipped = skip.min(entries.len());
+                    skip -= skipped;
+                    for (key, timestamp) in entries.into_iter().skip(skipped).take(remaining) {
+                        remaining -= 1;
+                        self.reply_entry(&q, key, timestamp, paging.keys_only).await;
                     }
-                };
-                match storage.get(stripped_key, q.parameters().as_str()).await {
-                    Ok(stored_data) => {
-                        for entry in stored_data {
-                            if let Err(e) = q
-                                .reply(key.clone(), entry.payload.clone())
-                                .encoding(entry.encoding.clone())
-                                .timestamp(entry.timestamp)
-                                .await
-                            {
-                                tracing::warn!(
-                                    "Storage '{}' raised an error replying a query: {}",
-                                    self.name,
-                                    e
-                                )
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!("Storage'{}' raised an error on query: {}", self.name, e)
-                    }
-                };
-            }
-            drop(storage);
-        } else {
-            let stripped_key = match crate::strip_prefix(prefix, q.key_expr()) {
-                Ok(k) => k,
-                Err(e) => {
-                    tracing::error!("{}", e);
-                    // @TODO: return error when it is supported
-                    return;
                 }
-            };
-            let mut storage = self.storage.lock().await;
-            match storage.get(stripped_key, q.parameters().as_str()).await {
-                Ok(stored_data) => {
-                    for entry in stored_data {
-                        if let Err(e) = q
-                            .reply(q.key_expr().clone(), entry.payload.clone())
-                            .encoding(entry.encoding.clone())
-                            .timestamp(entry.timestamp)
+            }
+            EntriesOrder::Time => {
+                // all the matching keys have to be read to be sorted, but only the `offset + limit`
+                // oldest ones are kept
+                let bound = paging
+                    .limit
+                    .map(|limit| paging.offset.saturating_add(limit));
+                let oldest_entries = match self
+                    .oldest_matching_entries(q.key_expr(), bound, matches_all)
+                    .await
+                {
+                    Some(oldest_entries) => oldest_entries,
+                    None => {
+                        let mut oldest_entries = BinaryHeap::new();
+                        let mut pager = self.entries_pager().await;
+                        while let Some(entries) = self
+                            .next_matching_entries(q.key_expr(), &mut pager, ENTRIES_PAGE_SIZE)
                             .await
                         {
-                            tracing::warn!(
-                                "Storage '{}' raised an error replying a query: {}",
-                                self.name,
-                                e
-                            )
+                            for (key, timestamp) in entries {
+                                oldest_entries.push((timestamp, key));
+                                if bound.is_some_and(|bound| oldest_entries.len() > bound) {
+                                    oldest_entries.pop();
+                                }
+                            }
                         }
+                        oldest_entries
+                            .into_sorted_vec()
+                            .into_iter()
+                            .map(|(timestamp, key)| (key, timestamp))
+                            .collect()
                     }
+                };
+                for (key, timestamp) in oldest_entries.into_iter().skip(paging.offset) {
+                    self.reply_entry(&q, key, timestamp, paging.keys_only).await;
                 }
+            }
+        }
+    }
+
+    /// Replies to the query with an entry of the storage, either with its key and timestamp
+    /// only or with the data stored for its key.
+    async fn reply_entry(
+        &self,
+        q: &zenoh::query::Query,
+        key: OwnedKeyExpr,
+        timestamp: Timestamp,
+        keys_only: bool,
+    ) {
+        if !keys_only {
+            self.reply_key(q, key.into(), false).await;
+            return;
+        }
+        if let Err(e) = q.reply(key, ZBytes::default()).timestamp(timestamp).await {
+            tracing::warn!(
+                "Storage '{}' raised an error replying a query: {}",
+                self.name,
+                e
+            )
+        }
+    }
+
+    /// Replies to the query with the data stored for a key, with an empty payload if
+    /// `keys_only` is set.
+    ///
+    /// The storage is only locked while its data for this key is streamed.
+    async fn reply_key(&self, q: &zenoh::query::Query, key: KeyExpr<'_>, keys_only: bool) {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let stripped_key = match crate::strip_prefix(prefix, &key) {
+            Ok(k) => k,
+            Err(e) => {
+                tracing::error!("{}", e);
+                // @TODO: return error when it is supported
+                return;
+            }
+        };
+        let parameters = PagingParameters::strip(q.parameters());
+        let mut storage = self.storage.lock().await;
+        let mut stored_data = storage.get_stream(stripped_key, parameters.as_str());
+        while let Some(entry) = stored_data.next().await {
+            let entry = match entry {
+                Ok(entry) => entry,
                 Err(e) => {
                     tracing::warn!("Storage '{}' raised an error on query: {e}", self.name);
+                    return;
                 }
             };
+            // Like in `reply_entry`, a key only reply has no payload, hence no encoding
+            let (payload, encoding) = if keys_only {
+                (ZBytes::default(), Encoding::default())
+            } else {
+                (entry.payload, entry.encoding)
+            };
+            if let Err(e) = q
+                .reply(key.clone(), payload)
+                .encoding(encoding)
+                .timestamp(entry.timestamp)
+                .await
+            {
+                tracing::warn!(
+                    "Storage '{}' raised an error replying a query: {}",
+                    self.name,
+                    e
+                )
+            }
         }
     }
 
     async fn get_matching_keys(&self, key_expr: &keyexpr) -> Vec<OwnedKeyExpr> {
         let mut result = Vec::new();
         // @TODO: if cache exists, use that to get the list
-        let storage = self.storage.lock().await;
+        let mut pager = self.entries_pager().await;
+        while let Some(entries) = self
+            .next_matching_entries(key_expr, &mut pager, ENTRIES_PAGE_SIZE)
+            .await
+        {
+            result.extend(entries.into_iter().map(|(key, _)| key));
+        }
+        result
+    }
 
-        let prefix = self.configuration.strip_prefix.as_ref();
+    async fn entries_pager(&self) -> EntriesPager {
+        EntriesPager::new(self.storage.lock().await.as_ref())
+    }
 
-        match storage.get_all_entries().await {
-            Ok(entries) => {
-                for (k, _ts) in entries {
-                    // @TODO: optimize adding back the prefix (possible inspiration from https://github.com/eclipse-zenoh/zenoh/blob/0.5.0-beta.9/backends/traits/src/utils.rs#L79)
-                    let Ok(full_key) = crate::prefix(prefix, k.as_ref()) else {
-                        tracing::error!(
-                            "Internal error: empty key with no `strip_prefix` configured"
-                        );
-                        continue;
-                    };
+    /// Returns the entries matching `key_expr` in the next page of at most `page_size` entries
+    /// of the storage.
+    ///
+    /// Returns None once all the entries were read, or if the storage failed to retrieve them.
+    async fn next_matching_entries(
+        &self,
+        key_expr: &keyexpr,
+        pager: &mut EntriesPager,
+        page_size: usize,
+    ) -> Option<Vec<(OwnedKeyExpr, Timestamp)>> {
+        let entries = {
+            let storage = self.storage.lock().await;
+            pager.next_page(storage.as_ref(), page_size).await
+        };
+        let entries = match entries {
+            Ok(entries) => entries?,
+            Err(e) => {
+                tracing::warn!(
+                    "Storage '{}' raised an error while retrieving keys: {}",
+                    self.name,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let mut result = Vec::new();
+        for (k, ts) in entries {
+            if let Some(full_key) = self.matching_key(key_expr, k) {
+                result.push((full_key, ts));
+            }
+        }
+        Some(result)
+    }
 
-                    if key_expr.intersects(&full_key.clone()) {
-                        result.push(full_key);
+    /// Returns the `bound` oldest entries matching `key_expr` if any bound, or all of them,
+    /// ordered by timestamp as read with [`Storage::get_entries_stream_by_time`].
+    ///
+    /// Returns None if the storage cannot order its entries by timestamp, or if it failed to
+    /// retrieve them, for the storage manager to sort them.
+    async fn oldest_matching_entries(
+        &self,
+        key_expr: &keyexpr,
+        bound: Option<usize>,
+        matches_all: bool,
+    ) -> Option<Vec<(OwnedKeyExpr, Timestamp)>> {
+        let bound = bound.unwrap_or(usize::MAX);
+        let storage = self.storage.lock().await;
+        let mut entries =
+            storage.get_entries_stream_by_time(Some(bound).filter(|_| matches_all))?;
+        let mut result = Vec::new();
+        while result.len() < bound {
+            match entries.next().await {
+                Some(Ok((k, ts))) => {
+                    if let Some(full_key) = self.matching_key(key_expr, k) {
+                        result.push((full_key, ts));
                     }
                 }
+                Some(Err(e)) => {
+                    tracing::warn!(
+                        "Storage '{}' raised an error while retrieving keys: {}",
+                        self.name,
+                        e
+                    );
+                    return None;
+                }
+                None => break,
             }
-            Err(e) => tracing::warn!(
-                "Storage '{}' raised an error while retrieving keys: {}",
-                self.name,
-                e
-            ),
         }
-        result
+        Some(result)
+    }
+
+    /// Returns the full key of a stored entry if it matches `key_expr`.
+    fn matching_key(&self, key_expr: &keyexpr, k: Option<OwnedKeyExpr>) -> Option<OwnedKeyExpr> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        // @TODO: optimize adding back the prefix (possible inspiration from https://github.com/eclipse-zenoh/zenoh/blob/0.5.0-beta.9/backends/traits/src/utils.rs#L79)
+        let Ok(full_key) = crate::prefix(prefix, k.as_ref()) else {
+            tracing::error!("Internal error: empty key with no `strip_prefix` configured");
+            return None;
+        };
+        key_expr.intersects(&full_key).then_some(full_key)
     }
 }
 
@@ -711,3 +931,7 @@ impl Timed for GarbageCollectionEvent {
         tracing::trace!("End garbage collection of obsolete data-infos");
     }
 }
+
+#[cfg(test)]
+#[path = "tests/service.test.rs"]
+mod tests;
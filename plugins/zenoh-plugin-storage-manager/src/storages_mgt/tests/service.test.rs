@@ -0,0 +1,154 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use uhlc::HLC;
+use zenoh_backend_traits::EntriesStream;
+
+use super::*;
+
+/// A storage of entries only, counting how many times they are read.
+struct EntriesStorage {
+    entries: Vec<(Option<OwnedKeyExpr>, Timestamp)>,
+    cursor: bool,
+    reads: AtomicUsize,
+}
+
+impl EntriesStorage {
+    fn new(len: usize, cursor: bool) -> Self {
+        let hlc = HLC::default();
+        // Stored in reverse order, the entries have to be sorted by key
+        let entries = (0..len)
+            .rev()
+            .map(|i| {
+                (
+                    Some(format!("{i:05}").try_into().unwrap()),
+                    hlc.new_timestamp(),
+                )
+            })
+            .collect();
+        Self {
+            entries,
+            cursor,
+            reads: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for EntriesStorage {
+    fn get_admin_status(&self) -> serde_json::Value {
+        serde_json::Value::Null
+    }
+
+    async fn put(
+        &mut self,
+        _key: Option<OwnedKeyExpr>,
+        _payload: ZBytes,
+        _encoding: Encoding,
+        _timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        unimplemented!()
+    }
+
+    async fn delete(
+        &mut self,
+        _key: Option<OwnedKeyExpr>,
+        _timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        unimplemented!()
+    }
+
+    async fn get(
+        &mut self,
+        _key: Option<OwnedKeyExpr>,
+        _parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        unimplemented!()
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        Ok(self.entries.clone())
+    }
+
+    fn get_entries_stream(
+        &self,
+        cursor: Option<EntryCursor>,
+        limit: Option<usize>,
+    ) -> EntriesStream<'_> {
+        if !self.cursor {
+            unimplemented!()
+        }
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.entries.clone();
+        entries.sort_unstable_by(|(k1, _), (k2, _)| k1.cmp(k2));
+        entries.retain(|(key, _)| cursor.as_ref().map_or(true, |EntryCursor(c)| key > c));
+        entries.truncate(limit.unwrap_or(usize::MAX));
+        futures::stream::iter(entries.into_iter().map(Ok)).boxed()
+    }
+
+    fn has_entries_cursor(&self) -> bool {
+        self.cursor
+    }
+}
+
+async fn read_pages(storage: &EntriesStorage) -> Vec<Vec<Option<OwnedKeyExpr>>> {
+    let mut pager = EntriesPager::new(storage);
+    let mut pages = vec![];
+    while let Some(page) = pager.next_page(storage, ENTRIES_PAGE_SIZE).await.unwrap() {
+        pages.push(page.into_iter().map(|(key, _)| key).collect());
+    }
+    pages
+}
+
+#[tokio::test]
+async fn test_entries_pager() {
+    for cursor in [false, true] {
+        let storage = EntriesStorage::new(2 * ENTRIES_PAGE_SIZE + 1, cursor);
+        let pages = read_pages(&storage).await;
+        assert_eq!(
+            pages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![ENTRIES_PAGE_SIZE, ENTRIES_PAGE_SIZE, 1]
+        );
+        let keys = pages.concat();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        // Without a cursor, the entries are read at once
+        let reads = if cursor { 3 } else { 1 };
+        assert_eq!(storage.reads.load(Ordering::Relaxed), reads);
+
+        // The last page being full, the end of the entries is only known on the next page
+        let storage = EntriesStorage::new(ENTRIES_PAGE_SIZE, cursor);
+        assert_eq!(read_pages(&storage).await.len(), 1);
+        let reads = if cursor { 2 } else { 1 };
+        assert_eq!(storage.reads.load(Ordering::Relaxed), reads);
+    }
+}
+
+#[tokio::test]
+async fn test_entries_pager_page_size() {
+    for cursor in [false, true] {
+        let storage = EntriesStorage::new(10, cursor);
+        let mut pager = EntriesPager::new(&storage);
+        // The page size is pushed down to the storages with a cursor
+        let page = pager.next_page(&storage, 3).await.unwrap().unwrap();
+        assert_eq!(page.len(), 3);
+        let page = pager.next_page(&storage, 8).await.unwrap().unwrap();
+        assert_eq!(page.len(), 7);
+        assert!(pager.next_page(&storage, 8).await.unwrap().is_none());
+        let reads = if cursor { 2 } else { 1 };
+        assert_eq!(storage.reads.load(Ordering::Relaxed), reads);
+    }
+}
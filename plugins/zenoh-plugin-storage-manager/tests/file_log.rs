@@ -0,0 +1,140 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the file_log volume -
+// 1. the samples are stored and retrieved as with the memory volume
+// 2. the samples are still available after restarting the storage
+
+use std::{path::Path, thread::sleep};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    internal::{plugins::RunningPlugin, zasync_executor_init},
+    query::Reply,
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn get_data(session: &Session, key_expr: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session.get(key_expr).await.unwrap().into_iter().collect();
+    println!("Getting replies on '{key_expr}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{key_expr}': '{samples:?}'...");
+    samples
+}
+
+async fn start_storage(dir: &Path) -> (RunningPlugin, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            &format!(
+                r#"{{
+                    volumes: {{
+                        file_log: {{
+                            dir: "{}"
+                        }}
+                    }},
+                    storages: {{
+                        file_log_test: {{
+                            key_expr: "file_log/test/**",
+                            volume: {{
+                                id: "file_log"
+                            }}
+                        }}
+                    }}
+                }}"#,
+                dir.display()
+            ),
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(std::time::Duration::from_secs(1));
+    (storage, session)
+}
+
+async fn test_file_log() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let dir = std::env::temp_dir().join(format!(
+        "zenoh-file-log-test-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+
+    let (storage, session) = start_storage(&dir).await;
+
+    session.put("file_log/test/a", "1").await.unwrap();
+    session.put("file_log/test/b", "2").await.unwrap();
+    session.put("file_log/test/a", "3").await.unwrap();
+    session.delete("file_log/test/b").await.unwrap();
+    sleep(std::time::Duration::from_millis(10));
+
+    // expects exactly one sample
+    let data = get_data(&session, "file_log/test/*").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "3");
+
+    session.close().await.unwrap();
+    drop(storage);
+    sleep(std::time::Duration::from_millis(100));
+
+    // expects the sample to be recovered from the disk
+    let (storage, session) = start_storage(&dir).await;
+    let data = get_data(&session, "file_log/test/*").await;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].key_expr().as_str(), "file_log/test/a");
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "3");
+
+    session.close().await.unwrap();
+    drop(storage);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn file_log_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_file_log().await });
+}
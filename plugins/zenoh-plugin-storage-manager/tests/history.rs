@@ -0,0 +1,136 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the history of the memory storage -
+// 1. only the latest sample is returned without time range
+// 2. the samples matching the time range are returned, within the retention limits
+
+use std::thread::sleep;
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::zasync_executor_init, query::Reply, sample::Sample, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn put_data(session: &Session, key_expr: &str, value: &str) {
+    println!("Putting Data ('{key_expr}': '{value}')...");
+    session.put(key_expr, value).await.unwrap();
+}
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    let replies: Vec<Reply> = session.get(selector).await.unwrap().into_iter().collect();
+    println!("Getting replies on '{selector}': '{replies:?}'...");
+    let mut samples = Vec::new();
+    for reply in replies {
+        if let Ok(sample) = reply.into_result() {
+            samples.push(sample);
+        }
+    }
+    println!("Getting Data on '{selector}': '{samples:?}'...");
+    samples
+}
+
+fn payloads(samples: &[Sample]) -> Vec<String> {
+    samples
+        .iter()
+        .map(|sample| sample.payload().try_to_string().unwrap().into_owned())
+        .collect()
+}
+
+async fn test_history() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        history_test: {
+                            key_expr: "history/test/**",
+                            volume: {
+                                id: "memory",
+                                history: {
+                                    max_samples: 3,
+                                    max_age: 2,
+                                }
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    for value in ["1", "2", "3", "4"] {
+        put_data(&session, "history/test/a", value).await;
+        sleep(std::time::Duration::from_millis(10));
+    }
+    put_data(&session, "history/test/b", "5").await;
+
+    sleep(std::time::Duration::from_millis(10));
+
+    // expects only the latest sample
+    let data = get_data(&session, "history/test/a").await;
+    assert_eq!(payloads(&data), ["4"]);
+
+    // expects the 3 samples retained, in order
+    let data = get_data(&session, "history/test/a?_time=[..]").await;
+    assert_eq!(payloads(&data), ["2", "3", "4"]);
+
+    let mut data = get_data(&session, "history/test/*?_time=[now(-1m)..]").await;
+    data.sort_by_key(|sample| *sample.timestamp().unwrap());
+    assert_eq!(payloads(&data), ["2", "3", "4", "5"]);
+
+    // expects no sample outside of the time range
+    let data = get_data(&session, "history/test/a?_time=[..now(-1m)]").await;
+    assert_eq!(data.len(), 0);
+
+    sleep(std::time::Duration::from_millis(2500));
+
+    // expects all the samples to have expired
+    let data = get_data(&session, "history/test/a?_time=[..]").await;
+    assert_eq!(data.len(), 0);
+
+    drop(storage);
+}
+
+#[test]
+fn history_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_history().await });
+}
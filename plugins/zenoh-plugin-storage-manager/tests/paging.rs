@@ -0,0 +1,168 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test wildcard queries on a storage holding more keys than read at once by the storage manager -
+// 1. all the matching keys are replied by default
+// 2. the keys are paged with the `_limit`, `_offset`, `_order` and `_keysonly` parameters
+
+use std::{collections::HashSet, thread::sleep};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    bytes::Encoding,
+    internal::zasync_executor_init,
+    query::{ConsolidationMode, Reply},
+    sample::Sample,
+    Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+const KEYS: usize = 2500;
+
+async fn get_data(session: &Session, selector: &str) -> Vec<Sample> {
+    session
+        .get(selector)
+        .consolidation(ConsolidationMode::None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|reply| reply.into_result().unwrap())
+        .collect()
+}
+
+fn keys(samples: &[Sample]) -> Vec<String> {
+    samples
+        .iter()
+        .map(|sample| sample.key_expr().to_string())
+        .collect()
+}
+
+async fn test_paging() {
+    async {
+        zasync_executor_init!();
+    }
+    .await;
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        paging_test: {
+                            key_expr: "paging/test/**",
+                            volume: {
+                                id: "memory"
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5(
+            "timestamping",
+            r#"{
+                    enabled: {
+                        router: true,
+                        peer: true,
+                        client: true
+                    }
+                }"#,
+        )
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+
+    let session = zenoh::session::init(runtime).await.unwrap();
+
+    sleep(std::time::Duration::from_secs(1));
+
+    for i in 0..KEYS {
+        session
+            .put(format!("paging/test/{i}"), i.to_string())
+            .encoding(Encoding::TEXT_PLAIN)
+            .await
+            .unwrap();
+    }
+    sleep(std::time::Duration::from_millis(500));
+
+    // expects one sample per key
+    let all_keys: HashSet<String> = keys(&get_data(&session, "paging/test/*").await)
+        .into_iter()
+        .collect();
+    assert_eq!(all_keys.len(), KEYS);
+    assert!(all_keys.contains("paging/test/0"));
+    assert!(all_keys.contains(&format!("paging/test/{}", KEYS - 1)));
+
+    // expects the keys to be paged in key order by default
+    let data = get_data(&session, "paging/test/*?_offset=1;_limit=4").await;
+    assert_eq!(
+        keys(&data),
+        [
+            "paging/test/1",
+            "paging/test/10",
+            "paging/test/100",
+            "paging/test/1000"
+        ]
+    );
+
+    // expects the keys to be paged in time order
+    let data = get_data(&session, "paging/test/*?_order=time;_offset=1500;_limit=3").await;
+    assert_eq!(
+        keys(&data),
+        ["paging/test/1500", "paging/test/1501", "paging/test/1502"]
+    );
+    assert_eq!(data[0].payload().try_to_string().unwrap(), "1500");
+    assert_eq!(data[0].encoding(), &Encoding::TEXT_PLAIN);
+
+    // expects the keys with an empty payload
+    let data = get_data(&session, "paging/test/*?_keysonly;_offset=2490").await;
+    assert_eq!(data.len(), 10);
+    assert!(data.iter().all(|sample| sample.payload().is_empty()));
+    assert!(data.iter().all(|sample| sample.timestamp().is_some()));
+    assert!(data
+        .iter()
+        .all(|sample| sample.encoding() == &Encoding::default()));
+
+    // expects the parameters to apply to a single key
+    let data = get_data(&session, "paging/test/42?_keysonly").await;
+    assert_eq!(keys(&data), ["paging/test/42"]);
+    assert!(data[0].payload().is_empty());
+    assert_eq!(data[0].encoding(), &Encoding::default());
+    let data = get_data(&session, "paging/test/42?_offset=1").await;
+    assert!(data.is_empty());
+
+    // expects an error on invalid parameters
+    let replies: Vec<Reply> = session
+        .get("paging/test/*?_limit=ten")
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(replies.len(), 1);
+    assert!(replies[0].result().is_err());
+
+    drop(storage);
+}
+
+#[test]
+fn paging_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async { test_paging().await });
+}
@@ -13,23 +13,21 @@
 //
 use std::{
     future::{IntoFuture, Ready},
+    num::NonZeroU32,
     sync::Arc,
     time::Duration,
 };
 
 use zenoh_core::{Resolvable, Wait};
-use zenoh_protocol::{
-    core::{CongestionControl, Parameters},
-    network::request::ext::QueryTarget,
-};
+use zenoh_protocol::core::{CongestionControl, Parameters};
 use zenoh_result::ZResult;
 
 use super::sample::QoSBuilderTrait;
 #[cfg(feature = "unstable")]
-use crate::api::query::ReplyKeyExpr;
-#[cfg(feature = "unstable")]
 use crate::api::sample::SourceInfo;
 #[cfg(feature = "unstable")]
+use crate::api::{cancellation::CancellationToken, query::ReplyKeyExpr};
+#[cfg(feature = "unstable")]
 use crate::query::ZenohParameters;
 use crate::{
     api::{
@@ -43,7 +41,7 @@ use crate::{
     bytes::OptionZBytes,
     key_expr::KeyExpr,
     qos::Priority,
-    query::{QueryConsolidation, Reply},
+    query::{QueryConsolidation, QueryTarget, Reply},
     Session,
 };
 
@@ -81,6 +79,7 @@ pub struct QuerierBuilder<'a, 'b> {
     pub(crate) qos: QoSBuilder,
     pub(crate) destination: Locality,
     pub(crate) timeout: Duration,
+    pub(crate) max_replies: Option<NonZeroU32>,
     #[cfg(feature = "unstable")]
     pub(crate) accept_replies: ReplyKeyExpr,
 }
@@ -136,6 +135,20 @@ impl QuerierBuilder<'_, '_> {
         Self { timeout, ..self }
     }
 
+    /// Limit the number of replies to the queries.
+    ///
+    /// Once `max_replies` replies have been received, routers stop routing the replies of the
+    /// query and finalize it, and queryables are notified through
+    /// [`Query::max_replies`](crate::query::Query::max_replies). `0` removes the limit.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn max_replies(self, max_replies: u32) -> Self {
+        Self {
+            max_replies: NonZeroU32::new(max_replies),
+            ..self
+        }
+    }
+
     /// By default, only replies whose key expressions intersect
     /// with the querier key expression will be received by calls to [`Querier::get`](crate::query::Querier::get) method.
     ///
@@ -174,6 +187,7 @@ impl Wait for QuerierBuilder<'_, '_> {
             target: self.target,
             consolidation: self.consolidation,
             timeout: self.timeout,
+            max_replies: self.max_replies,
             #[cfg(feature = "unstable")]
             accept_replies: self.accept_replies,
             #[cfg(feature = "unstable")]
@@ -226,6 +240,8 @@ pub struct QuerierGetBuilder<'a, 'b, Handler> {
     pub(crate) attachment: Option<ZBytes>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) cancellation_token: Option<CancellationToken>,
 }
 
 #[zenoh_macros::internal_trait]
@@ -361,6 +377,8 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            cancellation_token,
             handler: _,
         } = self;
         QuerierGetBuilder {
@@ -370,6 +388,8 @@ impl<'a, 'b> QuerierGetBuilder<'a, 'b, DefaultHandler> {
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            cancellation_token,
             handler,
         }
     }
@@ -398,6 +418,19 @@ impl<'b, Handler> QuerierGetBuilder<'_, 'b, Handler> {
         self.parameters = parameters.into();
         self
     }
+
+    /// Provide a [`CancellationToken`] to cancel the query.
+    ///
+    /// Cancelling the token stops the delivery of replies, closes the handler and notifies the
+    /// queryables that received the query, which can observe it with
+    /// [`Query::is_cancelled`](crate::query::Query::is_cancelled).
+    #[zenoh_macros::unstable]
+    pub fn cancellation_token(self, cancellation_token: CancellationToken) -> Self {
+        Self {
+            cancellation_token: Some(cancellation_token),
+            ..self
+        }
+    }
 }
 
 impl<Handler> Resolvable for QuerierGetBuilder<'_, '_, Handler>
@@ -433,10 +466,15 @@ where
                 self.querier.qos,
                 self.querier.destination,
                 self.querier.timeout,
+                self.querier.max_replies,
                 self.value,
                 self.attachment,
                 #[cfg(feature = "unstable")]
                 self.source_info,
+                #[cfg(feature = "unstable")]
+                self.cancellation_token,
+                #[cfg(feature = "unstable")]
+                None,
                 callback,
             )
             .map(|_| receiver)
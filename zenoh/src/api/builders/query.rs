@@ -13,16 +13,21 @@
 //
 use std::{
     future::{IntoFuture, Ready},
+    num::NonZeroU32,
     sync::Arc,
     time::Duration,
 };
 
 use zenoh_core::{Resolvable, Wait};
-use zenoh_protocol::{core::CongestionControl, network::request::ext::QueryTarget};
+use zenoh_protocol::core::CongestionControl;
 use zenoh_result::ZResult;
 
 #[cfg(feature = "unstable")]
-use crate::api::query::ReplyKeyExpr;
+use crate::api::{
+    cancellation::CancellationToken,
+    query::ReplyKeyExpr,
+    streaming::{ReplyCredits, ReplyStreamChannel},
+};
 #[cfg(feature = "unstable")]
 use crate::api::{sample::SourceInfo, selector::ZenohParameters};
 use crate::{
@@ -37,7 +42,7 @@ use crate::{
         session::Session,
     },
     bytes::OptionZBytes,
-    query::{QueryConsolidation, Reply},
+    query::{QueryConsolidation, QueryTarget, Reply},
 };
 
 /// A builder for initializing a `query`.
@@ -70,11 +75,16 @@ pub struct SessionGetBuilder<'a, 'b, Handler> {
     pub(crate) qos: QoSBuilder,
     pub(crate) destination: Locality,
     pub(crate) timeout: Duration,
+    pub(crate) max_replies: Option<NonZeroU32>,
     pub(crate) handler: Handler,
     pub(crate) value: Option<(ZBytes, Encoding)>,
     pub(crate) attachment: Option<ZBytes>,
     #[cfg(feature = "unstable")]
     pub(crate) source_info: SourceInfo,
+    #[cfg(feature = "unstable")]
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    #[cfg(feature = "unstable")]
+    pub(crate) credits: Option<Arc<ReplyCredits>>,
 }
 
 #[zenoh_macros::internal_trait]
@@ -208,10 +218,15 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             qos,
             destination,
             timeout,
+            max_replies,
             value,
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            cancellation_token,
+            #[cfg(feature = "unstable")]
+            credits,
             handler: _,
         } = self;
         SessionGetBuilder {
@@ -222,13 +237,50 @@ impl<'a, 'b> SessionGetBuilder<'a, 'b, DefaultHandler> {
             qos,
             destination,
             timeout,
+            max_replies,
             value,
             attachment,
             #[cfg(feature = "unstable")]
             source_info,
+            #[cfg(feature = "unstable")]
+            cancellation_token,
+            #[cfg(feature = "unstable")]
+            credits,
             handler,
         }
     }
+
+    /// Receive the replies for this query as an asynchronous [`ReplyStream`](crate::query::ReplyStream)
+    /// with credit-based flow control.
+    ///
+    /// Each replier may send `window` replies ahead of the consumption of the stream, and is
+    /// granted more credits as the replies are consumed. Queryables apply this backpressure by
+    /// replying through a [`ReplySink`](crate::query::ReplySink). Replies are not consolidated,
+    /// and dropping the stream before its end cancels the query.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use futures::StreamExt;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let mut replies = session.get("key/expression").stream(64).await.unwrap();
+    /// while let Some(reply) = replies.next().await {
+    ///     println!("Received {:?}", reply.result());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn stream(self, window: u32) -> SessionGetBuilder<'a, 'b, ReplyStreamChannel> {
+        let credits = Arc::new(ReplyCredits::new(
+            NonZeroU32::new(window).unwrap_or(NonZeroU32::MIN),
+        ));
+        SessionGetBuilder {
+            credits: Some(credits.clone()),
+            ..self.with(ReplyStreamChannel { credits })
+        }
+    }
 }
 impl<Handler> SessionGetBuilder<'_, '_, Handler> {
     #[inline]
@@ -276,6 +328,20 @@ impl<Handler> SessionGetBuilder<'_, '_, Handler> {
         Self { timeout, ..self }
     }
 
+    /// Limit the number of replies to the query.
+    ///
+    /// Once `max_replies` replies have been received, routers stop routing the replies of the
+    /// query and finalize it, and queryables are notified through
+    /// [`Query::max_replies`](crate::query::Query::max_replies). `0` removes the limit.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn max_replies(self, max_replies: u32) -> Self {
+        Self {
+            max_replies: NonZeroU32::new(max_replies),
+            ..self
+        }
+    }
+
     ///
     ///
     /// By default, `get` guarantees that it will only receive replies whose key expressions intersect
@@ -301,6 +367,19 @@ impl<Handler> SessionGetBuilder<'_, '_, Handler> {
         }
         self
     }
+
+    /// Provide a [`CancellationToken`] to cancel the query.
+    ///
+    /// Cancelling the token stops the delivery of replies, closes the handler and notifies the
+    /// queryables that received the query, which can observe it with
+    /// [`Query::is_cancelled`](crate::query::Query::is_cancelled).
+    #[zenoh_macros::unstable]
+    pub fn cancellation_token(self, cancellation_token: CancellationToken) -> Self {
+        Self {
+            cancellation_token: Some(cancellation_token),
+            ..self
+        }
+    }
 }
 
 impl<Handler> Resolvable for SessionGetBuilder<'_, '_, Handler>
@@ -332,10 +411,15 @@ where
                 self.qos.into(),
                 self.destination,
                 self.timeout,
+                self.max_replies,
                 self.value,
                 self.attachment,
                 #[cfg(feature = "unstable")]
                 self.source_info,
+                #[cfg(feature = "unstable")]
+                self.cancellation_token,
+                #[cfg(feature = "unstable")]
+                self.credits,
                 callback,
             )
             .map(|_| receiver)
@@ -13,7 +13,7 @@
 //
 
 use std::future::{IntoFuture, Ready};
-#[cfg(feature = "shared-memory")]
+#[cfg(any(feature = "shared-memory", feature = "unstable"))]
 use std::sync::Arc;
 
 use zenoh_core::{Resolvable, Wait};
@@ -24,6 +24,8 @@ use zenoh_result::ZResult;
 use zenoh_shm::api::client_storage::ShmClientStorage;
 
 use crate::api::session::Session;
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::custom::MessageInterceptorFactory;
 #[cfg(feature = "internal")]
 use crate::net::runtime::Runtime;
 
@@ -46,6 +48,8 @@ where
     config: TryIntoConfig,
     #[cfg(feature = "shared-memory")]
     shm_clients: Option<Arc<ShmClientStorage>>,
+    #[cfg(feature = "unstable")]
+    interceptor_factories: Vec<Arc<dyn MessageInterceptorFactory>>,
 }
 
 impl<TryIntoConfig> OpenBuilder<TryIntoConfig>
@@ -58,10 +62,28 @@ where
             config,
             #[cfg(feature = "shared-memory")]
             shm_clients: None,
+            #[cfg(feature = "unstable")]
+            interceptor_factories: vec![],
         }
     }
 }
 
+#[zenoh_macros::unstable]
+impl<TryIntoConfig> OpenBuilder<TryIntoConfig>
+where
+    TryIntoConfig: std::convert::TryInto<crate::config::Config> + Send + 'static,
+    <TryIntoConfig as std::convert::TryInto<crate::config::Config>>::Error: std::fmt::Debug,
+{
+    /// Registers a custom interceptor factory applied to the transports of the session.
+    pub fn with_interceptor_factory(
+        mut self,
+        interceptor_factory: Arc<dyn MessageInterceptorFactory>,
+    ) -> Self {
+        self.interceptor_factories.push(interceptor_factory);
+        self
+    }
+}
+
 #[cfg(feature = "shared-memory")]
 impl<TryIntoConfig> OpenBuilder<TryIntoConfig>
 where
@@ -96,6 +118,8 @@ where
             config,
             #[cfg(feature = "shared-memory")]
             self.shm_clients,
+            #[cfg(feature = "unstable")]
+            self.interceptor_factories,
         )
         .wait()
     }
@@ -0,0 +1,132 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, Weak},
+};
+
+type CancellationHandler = Box<dyn FnOnce() + Send + Sync>;
+
+#[derive(Default)]
+struct CancellationTokenInner {
+    token: tokio_util::sync::CancellationToken,
+    handlers: Mutex<(usize, HashMap<usize, CancellationHandler>)>,
+}
+
+/// A token to cancel operations, such as a [`Session::get`](crate::Session::get).
+///
+/// On the querier side, cancelling the token given to a get builder stops the query: the reply
+/// handler is dropped and the queryables are notified through their [`Query`](crate::query::Query).
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::query::CancellationToken;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let token = CancellationToken::default();
+/// let replies = session
+///     .get("key/expression")
+///     .cancellation_token(token.clone())
+///     .await
+///     .unwrap();
+/// tokio::spawn(async move {
+///     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+///     token.cancel();
+/// });
+/// while let Ok(reply) = replies.recv_async().await {
+///     println!(">> Received {:?}", reply.result());
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationTokenInner>,
+}
+
+impl CancellationToken {
+    /// Cancels the token and the operations it was given to.
+    ///
+    /// Cancelling an already cancelled token has no effect.
+    pub fn cancel(&self) {
+        let handlers = {
+            let mut handlers = self.inner.handlers.lock().unwrap();
+            if self.inner.token.is_cancelled() {
+                return;
+            }
+            self.inner.token.cancel();
+            std::mem::take(&mut handlers.1)
+        };
+        for (_, handler) in handlers {
+            handler();
+        }
+    }
+
+    /// Returns `true` if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.token.is_cancelled()
+    }
+
+    /// Waits until the token is cancelled.
+    pub async fn cancelled(&self) {
+        self.inner.token.cancelled().await
+    }
+
+    /// Registers a handler to be called when the token is cancelled.
+    ///
+    /// Returns `None` if the token is already cancelled, in which case the handler is not called.
+    /// The handler is unregistered when the returned guard is dropped.
+    pub(crate) fn on_cancel(
+        &self,
+        handler: impl FnOnce() + Send + Sync + 'static,
+    ) -> Option<CancellationHandlerGuard> {
+        let mut handlers = self.inner.handlers.lock().unwrap();
+        if self.inner.token.is_cancelled() {
+            return None;
+        }
+        let id = handlers.0;
+        handlers.0 = handlers.0.wrapping_add(1);
+        handlers.1.insert(id, Box::new(handler));
+        Some(CancellationHandlerGuard {
+            token: Arc::downgrade(&self.inner),
+            id,
+        })
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Unregisters a [`CancellationToken`] handler when dropped.
+pub(crate) struct CancellationHandlerGuard {
+    token: Weak<CancellationTokenInner>,
+    id: usize,
+}
+
+impl Drop for CancellationHandlerGuard {
+    fn drop(&mut self) {
+        if let Some(token) = self.token.upgrade() {
+            let handler = token.handlers.lock().unwrap().1.remove(&self.id);
+            // The handler may own a session, which must not be dropped with the lock held
+            drop(handler);
+        }
+    }
+}
@@ -15,6 +15,7 @@
 use std::{
     convert::TryInto,
     future::{IntoFuture, Ready},
+    num::NonZeroU32,
     sync::Arc,
     time::Duration,
 };
@@ -206,6 +207,7 @@ impl<'a> Liveliness<'a> {
             session: self.session,
             key_expr,
             timeout,
+            max_replies: None,
             handler: DefaultHandler::default(),
         }
     }
@@ -645,6 +647,7 @@ pub struct LivelinessGetBuilder<'a, 'b, Handler> {
     pub(crate) session: &'a Session,
     pub(crate) key_expr: ZResult<KeyExpr<'b>>,
     pub(crate) timeout: Duration,
+    pub(crate) max_replies: Option<NonZeroU32>,
     pub(crate) handler: Handler,
 }
 
@@ -729,12 +732,14 @@ impl<'a, 'b> LivelinessGetBuilder<'a, 'b, DefaultHandler> {
             session,
             key_expr,
             timeout,
+            max_replies,
             handler: _,
         } = self;
         LivelinessGetBuilder {
             session,
             key_expr,
             timeout,
+            max_replies,
             handler,
         }
     }
@@ -747,6 +752,16 @@ impl<Handler> LivelinessGetBuilder<'_, '_, Handler> {
         self.timeout = timeout;
         self
     }
+
+    /// Limit the number of tokens received by the query.
+    ///
+    /// The handler is closed once `max_replies` tokens have been received. `0` removes the limit.
+    #[zenoh_macros::unstable]
+    #[inline]
+    pub fn max_replies(mut self, max_replies: u32) -> Self {
+        self.max_replies = NonZeroU32::new(max_replies);
+        self
+    }
 }
 
 impl<Handler> Resolvable for LivelinessGetBuilder<'_, '_, Handler>
@@ -766,7 +781,7 @@ where
         let (callback, receiver) = self.handler.into_handler();
         self.session
             .0
-            .liveliness_query(&self.key_expr?, self.timeout, callback)
+            .liveliness_query(&self.key_expr?, self.timeout, self.max_replies, callback)
             .map(|_| receiver)
     }
 }
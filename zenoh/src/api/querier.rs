@@ -15,15 +15,13 @@
 use core::fmt;
 use std::{
     future::{IntoFuture, Ready},
+    num::NonZeroU32,
     time::Duration,
 };
 
 use tracing::error;
 use zenoh_core::{Resolvable, Resolve, Wait};
-use zenoh_protocol::{
-    core::{CongestionControl, Parameters},
-    network::request::ext::QueryTarget,
-};
+use zenoh_protocol::core::{CongestionControl, Parameters};
 use zenoh_result::ZResult;
 #[cfg(feature = "unstable")]
 use {
@@ -40,7 +38,7 @@ use {
 use super::{
     builders::querier::QuerierGetBuilder,
     key_expr::KeyExpr,
-    query::QueryConsolidation,
+    query::{QueryConsolidation, QueryTarget},
     sample::{Locality, QoS},
     session::{UndeclarableSealed, WeakSession},
     Id,
@@ -79,6 +77,7 @@ pub struct Querier<'a> {
     pub(crate) target: QueryTarget,
     pub(crate) consolidation: QueryConsolidation,
     pub(crate) timeout: Duration,
+    pub(crate) max_replies: Option<NonZeroU32>,
     #[cfg(feature = "unstable")]
     pub(crate) accept_replies: ReplyKeyExpr,
     pub(crate) undeclare_on_drop: bool,
@@ -165,6 +164,8 @@ impl<'a> Querier<'a> {
             querier: self,
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            cancellation_token: None,
             value: None,
             attachment: None,
             parameters: Parameters::empty(),
@@ -12,7 +12,7 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::{collections::HashMap, error::Error, fmt::Display};
+use std::{collections::HashMap, error::Error, fmt::Display, num::NonZeroU32};
 
 #[cfg(feature = "unstable")]
 use serde::Deserialize;
@@ -21,17 +21,69 @@ use zenoh_config::ZenohId;
 use zenoh_keyexpr::OwnedKeyExpr;
 use zenoh_protocol::core::Parameters;
 #[cfg(feature = "unstable")]
-use zenoh_protocol::core::ZenohIdProto;
-/// The [`Queryable`](crate::query::Queryable)s that should be target of a [`get`](crate::Session::get).
-pub use zenoh_protocol::network::request::ext::QueryTarget;
+use zenoh_protocol::core::{EntityGlobalIdProto, ZenohIdProto};
+use zenoh_protocol::network::request;
 #[doc(inline)]
 pub use zenoh_protocol::zenoh::query::ConsolidationMode;
 
+#[cfg(feature = "unstable")]
+use crate::api::cancellation::CancellationHandlerGuard;
 use crate::api::{
     bytes::ZBytes, encoding::Encoding, handlers::Callback, key_expr::KeyExpr, sample::Sample,
     selector::Selector,
 };
 
+/// The [`Queryable`](crate::query::Queryable)s that should be target of a [`get`](crate::Session::get).
+//
+// The enum is not `#[non_exhaustive]`, which would break the exhaustive matches of the stable API
+// users: only the users of the `unstable` feature see the load-balanced targets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum QueryTarget {
+    /// Let Zenoh find the BestMatching queryable capabale of serving the query.
+    #[default]
+    BestMatching,
+    /// Deliver the query to all queryables matching the query's key expression.
+    All,
+    /// Deliver the query to all queryables matching the query's key expression that are declared as complete.
+    AllComplete,
+    /// Deliver the query to exactly one of the matching queryables, picked in turn.
+    ///
+    /// <div class="warning">This API has been marked as <strong>unstable</strong>: it works as advertised, but it may be changed in a future release.</div>
+    #[cfg(feature = "unstable")]
+    RoundRobin,
+    /// Deliver the query to exactly one of the matching queryables, picked at random.
+    ///
+    /// <div class="warning">This API has been marked as <strong>unstable</strong>: it works as advertised, but it may be changed in a future release.</div>
+    #[cfg(feature = "unstable")]
+    Random,
+    /// Deliver the query to exactly one of the matching queryables, picking the one with the
+    /// least queries awaiting their final response.
+    ///
+    /// <div class="warning">This API has been marked as <strong>unstable</strong>: it works as advertised, but it may be changed in a future release.</div>
+    #[cfg(feature = "unstable")]
+    LeastOutstanding,
+}
+
+impl QueryTarget {
+    pub const DEFAULT: Self = Self::BestMatching;
+}
+
+impl From<QueryTarget> for request::ext::QueryTarget {
+    fn from(target: QueryTarget) -> Self {
+        match target {
+            QueryTarget::BestMatching => request::ext::QueryTarget::BestMatching,
+            QueryTarget::All => request::ext::QueryTarget::All,
+            QueryTarget::AllComplete => request::ext::QueryTarget::AllComplete,
+            #[cfg(feature = "unstable")]
+            QueryTarget::RoundRobin => request::ext::QueryTarget::RoundRobin,
+            #[cfg(feature = "unstable")]
+            QueryTarget::Random => request::ext::QueryTarget::Random,
+            #[cfg(feature = "unstable")]
+            QueryTarget::LeastOutstanding => request::ext::QueryTarget::LeastOutstanding,
+        }
+    }
+}
+
 /// The replies consolidation strategy to apply on replies to a [`get`](crate::Session::get).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct QueryConsolidation {
@@ -121,6 +173,9 @@ pub struct Reply {
     pub(crate) result: Result<Sample, ReplyError>,
     #[cfg(feature = "unstable")]
     pub(crate) replier_id: Option<ZenohIdProto>,
+    /// The entity that sent this Reply, granted the credits of a streamed query.
+    #[cfg(feature = "unstable")]
+    pub(crate) responder_id: Option<EntityGlobalIdProto>,
 }
 
 impl Reply {
@@ -155,7 +210,10 @@ impl From<Reply> for Result<Sample, ReplyError> {
 }
 
 pub(crate) struct LivelinessQueryState {
-    pub(crate) callback: Callback<Reply>,
+    /// `None` once the query has received as many replies as its budget.
+    pub(crate) callback: Option<Callback<Reply>>,
+    pub(crate) max_replies: Option<NonZeroU32>,
+    pub(crate) nb_replies: u32,
 }
 
 pub(crate) struct QueryState {
@@ -165,9 +223,20 @@ pub(crate) struct QueryState {
     pub(crate) reception_mode: ConsolidationMode,
     pub(crate) replies: Option<HashMap<OwnedKeyExpr, Reply>>,
     pub(crate) callback: Callback<Reply>,
+    pub(crate) max_replies: Option<NonZeroU32>,
+    pub(crate) nb_replies: u32,
+    #[cfg(feature = "unstable")]
+    pub(crate) _cancellation: Option<CancellationHandlerGuard>,
 }
 
 impl QueryState {
+    /// Counts a received reply, returning `false` if it exceeds the query budget.
+    pub(crate) fn count_reply(&mut self) -> bool {
+        self.nb_replies = self.nb_replies.saturating_add(1);
+        self.max_replies
+            .map_or(true, |max| self.nb_replies <= max.get())
+    }
+
     pub(crate) fn selector(&self) -> Selector {
         Selector::borrowed(&self.key_expr, &self.parameters)
     }
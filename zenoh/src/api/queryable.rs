@@ -28,7 +28,10 @@ use zenoh_protocol::{
 use zenoh_result::ZResult;
 #[zenoh_macros::unstable]
 use {
-    crate::api::query::ReplyKeyExpr, zenoh_config::wrappers::EntityGlobalId,
+    crate::api::{cancellation::CancellationToken, query::ReplyKeyExpr, streaming::ReplySink},
+    std::{collections::HashMap, num::NonZeroU32},
+    tokio::sync::Semaphore,
+    zenoh_config::wrappers::EntityGlobalId,
     zenoh_protocol::core::EntityGlobalIdProto,
 };
 
@@ -55,10 +58,31 @@ pub(crate) struct QueryInner {
     pub(crate) qid: RequestId,
     pub(crate) zid: ZenohIdProto,
     pub(crate) primitives: Arc<dyn Primitives>,
+    #[cfg(feature = "unstable")]
+    pub(crate) max_replies: Option<NonZeroU32>,
+    #[cfg(feature = "unstable")]
+    pub(crate) control: Option<QueryControl>,
+}
+
+impl QueryInner {
+    #[inline]
+    pub(crate) fn is_cancelled(&self) -> bool {
+        zcondfeat!(
+            "unstable",
+            self.control
+                .as_ref()
+                .is_some_and(|c| c.token.is_cancelled()),
+            false
+        )
+    }
 }
 
 impl Drop for QueryInner {
     fn drop(&mut self) {
+        // The querier does not expect a final reply for a cancelled query
+        if self.is_cancelled() {
+            return;
+        }
         self.primitives.send_response_final(ResponseFinal {
             rid: self.qid,
             ext_qos: response::ext::QoSType::RESPONSE_FINAL,
@@ -67,6 +91,34 @@ impl Drop for QueryInner {
     }
 }
 
+/// The reply credits of the queryables replying to a streamed query, by queryable id.
+#[cfg(feature = "unstable")]
+pub(crate) type QueryCredits = HashMap<EntityId, Arc<Semaphore>>;
+
+/// The [`CancellationToken`] and reply credits of a query received by a session, registered in
+/// the session for the querier to cancel the query or grant it credits.
+#[cfg(feature = "unstable")]
+pub(crate) struct QueryControl {
+    pub(crate) token: CancellationToken,
+    pub(crate) credits: QueryCredits,
+    pub(crate) session: WeakSession,
+    pub(crate) local: bool,
+    pub(crate) qid: RequestId,
+}
+
+/// The entry of a [`QueryControl`] in the session state.
+#[cfg(feature = "unstable")]
+pub(crate) type QueryControlEntry = (CancellationToken, QueryCredits);
+
+#[cfg(feature = "unstable")]
+impl Drop for QueryControl {
+    fn drop(&mut self) {
+        zwrite!(self.session.state)
+            .query_controls
+            .remove(&(self.local, self.qid));
+    }
+}
+
 /// Structs received by a [`Queryable`].
 #[derive(Clone)]
 pub struct Query {
@@ -195,6 +247,45 @@ impl Query {
             }
         })
     }
+
+    /// The maximum number of replies the querier accepts for this query, if limited.
+    ///
+    /// Replies beyond this budget are discarded by the routers.
+    #[zenoh_macros::unstable]
+    pub fn max_replies(&self) -> Option<u32> {
+        self.inner.max_replies.map(NonZeroU32::get)
+    }
+
+    /// Returns `true` if the querier has cancelled this query.
+    ///
+    /// Replies to a cancelled query are discarded, so long-running queryables can stop early.
+    #[zenoh_macros::unstable]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+
+    /// The [`CancellationToken`] of this query, cancelled when the querier cancels the query.
+    #[zenoh_macros::unstable]
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.inner
+            .control
+            .as_ref()
+            .map(|c| c.token.clone())
+            .unwrap_or_default()
+    }
+
+    /// A [`ReplySink`] to stream the replies to this query with backpressure.
+    ///
+    /// If the querier requested a [`stream`](crate::session::SessionGetBuilder::stream) of
+    /// replies, each reply sent through the sink waits for a credit granted by the querier as it
+    /// consumes the replies. Otherwise, replies are sent as with [`Query::reply`].
+    #[zenoh_macros::unstable]
+    pub fn reply_sink(&self) -> ReplySink {
+        ReplySink {
+            query: self.clone(),
+        }
+    }
+
     #[cfg(feature = "unstable")]
     fn _accepts_any_replies(&self) -> ZResult<bool> {
         Ok(self.parameters().reply_key_expr_any())
@@ -263,6 +354,9 @@ impl Query {
         let ext_sinfo = None;
         #[cfg(feature = "unstable")]
         let ext_sinfo = sample.source_info.into();
+        if self.inner.is_cancelled() {
+            return Ok(());
+        }
         self.inner.primitives.send_response(Response {
             rid: self.inner.qid,
             wire_expr: WireExpr {
@@ -15,6 +15,7 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     convert::TryInto,
     fmt,
+    num::NonZeroU32,
     ops::Deref,
     sync::{
         atomic::{AtomicU16, Ordering},
@@ -24,9 +25,12 @@ use std::{
 };
 
 use async_trait::async_trait;
+use rand::Rng;
 #[zenoh_macros::internal]
 use ref_cast::ref_cast_custom;
 use ref_cast::RefCastCustom;
+#[cfg(feature = "unstable")]
+use tokio::sync::Semaphore;
 use tracing::{error, info, trace, warn};
 use uhlc::Timestamp;
 #[cfg(feature = "internal")]
@@ -37,6 +41,8 @@ use zenoh_config::{qos::PublisherQoSConfig, unwrap_or_default, wrappers::ZenohId
 use zenoh_core::{zconfigurable, zread, Resolve, ResolveClosure, ResolveFuture, Wait};
 use zenoh_keyexpr::keyexpr_tree::KeBoxTree;
 #[cfg(feature = "unstable")]
+use zenoh_protocol::core::EntityGlobalIdProto;
+#[cfg(feature = "unstable")]
 use zenoh_protocol::network::declare::SubscriberId;
 use zenoh_protocol::{
     core::{
@@ -53,8 +59,8 @@ use zenoh_protocol::{
         },
         ext,
         interest::{InterestId, InterestMode, InterestOptions},
-        push, request, AtomicRequestId, DeclareFinal, Interest, Mapping, Push, Request, RequestId,
-        Response, ResponseFinal,
+        push, request, AtomicRequestId, DeclareFinal, Interest, Mapping, Push, Request,
+        RequestCancel, RequestCredit, RequestId, Response, ResponseFinal,
     },
     zenoh::{
         query::{self, ext::QueryBodyType},
@@ -73,11 +79,16 @@ use crate::api::selector::ZenohParameters;
 #[cfg(feature = "unstable")]
 use crate::api::{
     builders::querier::QuerierBuilder,
+    cancellation::CancellationToken,
     matching::{MatchingListenerState, MatchingStatus, MatchingStatusType},
     querier::QuerierState,
     query::ReplyKeyExpr,
+    queryable::{QueryControl, QueryControlEntry, QueryCredits},
     sample::SourceInfo,
+    streaming::{ReplyCredits, StreamedQuery},
 };
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::custom::MessageInterceptorFactory;
 use crate::{
     api::{
         admin,
@@ -110,7 +121,7 @@ use crate::{
     },
     net::{
         primitives::Primitives,
-        routing::dispatcher::face::Face,
+        routing::{dispatcher::face::Face, RoundRobinTurns},
         runtime::{Runtime, RuntimeBuilder},
     },
     query::ReplyError,
@@ -129,6 +140,8 @@ pub(crate) struct SessionState {
     pub(crate) expr_id_counter: AtomicExprId, // @TODO: manage rollover and uniqueness
     pub(crate) qid_counter: AtomicRequestId,
     pub(crate) liveliness_qid_counter: AtomicRequestId,
+    pub(crate) queryables_round_robin: RoundRobinTurns,
+    pub(crate) destinations_round_robin: RoundRobinTurns,
     pub(crate) local_resources: HashMap<ExprId, Resource>,
     pub(crate) remote_resources: HashMap<ExprId, Resource>,
     #[cfg(feature = "unstable")]
@@ -146,6 +159,8 @@ pub(crate) struct SessionState {
     #[cfg(feature = "unstable")]
     pub(crate) matching_listeners: HashMap<Id, Arc<MatchingListenerState>>,
     pub(crate) queries: HashMap<RequestId, QueryState>,
+    #[cfg(feature = "unstable")]
+    pub(crate) query_controls: HashMap<(bool, RequestId), QueryControlEntry>,
     pub(crate) liveliness_queries: HashMap<InterestId, LivelinessQueryState>,
     pub(crate) aggregated_subscribers: Vec<OwnedKeyExpr>,
     pub(crate) aggregated_publishers: Vec<OwnedKeyExpr>,
@@ -163,6 +178,8 @@ impl SessionState {
             expr_id_counter: AtomicExprId::new(1), // Note: start at 1 because 0 is reserved for NO_RESOURCE
             qid_counter: AtomicRequestId::new(0),
             liveliness_qid_counter: AtomicRequestId::new(0),
+            queryables_round_robin: RoundRobinTurns::default(),
+            destinations_round_robin: RoundRobinTurns::default(),
             local_resources: HashMap::new(),
             remote_resources: HashMap::new(),
             #[cfg(feature = "unstable")]
@@ -180,6 +197,8 @@ impl SessionState {
             #[cfg(feature = "unstable")]
             matching_listeners: HashMap::new(),
             queries: HashMap::new(),
+            #[cfg(feature = "unstable")]
+            query_controls: HashMap::new(),
             liveliness_queries: HashMap::new(),
             aggregated_subscribers,
             aggregated_publishers,
@@ -713,6 +732,12 @@ impl Session {
         self.0.runtime.hlc()
     }
 
+    /// Returns the [`Runtime`] of this Session, e.g. to update its configuration.
+    #[zenoh_macros::internal]
+    pub fn runtime(&self) -> &Runtime {
+        &self.0.runtime
+    }
+
     /// Close the zenoh [`Session`](Session).
     ///
     /// Every subscriber and queryable declared will stop receiving data, and further attempt to
@@ -1013,6 +1038,7 @@ impl Session {
             target: QueryTarget::default(),
             consolidation: QueryConsolidation::default(),
             timeout,
+            max_replies: None,
             #[cfg(feature = "unstable")]
             accept_replies: ReplyKeyExpr::default(),
         }
@@ -1212,11 +1238,16 @@ impl Session {
             qos: qos.into(),
             destination: Locality::default(),
             timeout,
+            max_replies: None,
             value: None,
             attachment: None,
             handler: DefaultHandler::default(),
             #[cfg(feature = "unstable")]
             source_info: SourceInfo::empty(),
+            #[cfg(feature = "unstable")]
+            cancellation_token: None,
+            #[cfg(feature = "unstable")]
+            credits: None,
         }
     }
 }
@@ -1226,6 +1257,7 @@ impl Session {
     pub(super) fn new(
         config: Config,
         #[cfg(feature = "shared-memory")] shm_clients: Option<Arc<ShmClientStorage>>,
+        #[cfg(feature = "unstable")] interceptor_factories: Vec<Arc<dyn MessageInterceptorFactory>>,
     ) -> impl Resolve<ZResult<Session>> {
         ResolveFuture::new(async move {
             tracing::debug!("Config: {:?}", &config);
@@ -1238,6 +1270,10 @@ impl Session {
                 runtime = runtime.shm_clients(shm_clients);
             }
             let mut runtime = runtime.build().await?;
+            #[cfg(feature = "unstable")]
+            for interceptor_factory in interceptor_factories {
+                runtime.add_interceptor_factory(interceptor_factory);
+            }
 
             let session = Self::init(
                 runtime.clone(),
@@ -1369,6 +1405,7 @@ impl SessionInner {
                 ext_qos: network::ext::QoSType::DEFAULT,
                 ext_tstamp: None,
                 ext_nodeid: network::ext::NodeIdType::DEFAULT,
+                ext_budget: None,
             });
         }
         Ok(id)
@@ -1398,6 +1435,7 @@ impl SessionInner {
                         ext_qos: declare::ext::QoSType::DEFAULT,
                         ext_tstamp: None,
                         ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+                        ext_budget: None,
                     });
                 }
             }
@@ -1428,6 +1466,7 @@ impl SessionInner {
                 ext_qos: network::ext::QoSType::DEFAULT,
                 ext_tstamp: None,
                 ext_nodeid: network::ext::NodeIdType::DEFAULT,
+                ext_budget: None,
             });
         }
         Ok(id)
@@ -1452,11 +1491,14 @@ impl SessionInner {
                     primitives.send_interest(Interest {
                         id: querier_state.remote_id,
                         mode: InterestMode::Final,
-                        options: InterestOptions::empty(),
+                        // Note: InterestMode::Final options are undefined in the current protocol specification,
+                        //       they are initialized here for internal use by local egress interceptors.
+                        options: InterestOptions::QUERYABLES,
                         wire_expr: None,
                         ext_qos: declare::ext::QoSType::DEFAULT,
                         ext_tstamp: None,
                         ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+                        ext_budget: None,
                     });
                 }
             }
@@ -1621,6 +1663,7 @@ impl SessionInner {
                             ext_qos: declare::ext::QoSType::DEFAULT,
                             ext_tstamp: None,
                             ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+                            ext_budget: None,
                         });
                     }
                 }
@@ -1839,6 +1882,7 @@ impl SessionInner {
             ext_qos: declare::ext::QoSType::DECLARE,
             ext_tstamp: None,
             ext_nodeid: declare::ext::NodeIdType::DEFAULT,
+            ext_budget: None,
         });
 
         Ok(sub_state)
@@ -2230,9 +2274,12 @@ impl SessionInner {
         qos: QoS,
         destination: Locality,
         timeout: Duration,
+        max_replies: Option<NonZeroU32>,
         value: Option<(ZBytes, Encoding)>,
         attachment: Option<ZBytes>,
         #[cfg(feature = "unstable")] source: SourceInfo,
+        #[cfg(feature = "unstable")] cancellation_token: Option<CancellationToken>,
+        #[cfg(feature = "unstable")] credits: Option<Arc<ReplyCredits>>,
         callback: Callback<Reply>,
     ) -> ZResult<()> {
         tracing::trace!(
@@ -2241,8 +2288,17 @@ impl SessionInner {
             target,
             consolidation
         );
+        let target = request::ext::QueryTarget::from(target);
+        let destination = if destination == Locality::Any && target.is_load_balanced() {
+            self.load_balanced_destination(key_expr, target)
+        } else {
+            destination
+        };
         let mut state = zwrite!(self.state);
         let consolidation = match consolidation.mode {
+            // Replies held back for consolidation would never be consumed nor granted credits
+            #[cfg(feature = "unstable")]
+            _ if credits.is_some() => ConsolidationMode::None,
             #[cfg(feature = "unstable")]
             ConsolidationMode::Auto if parameters.time_range().is_some() => ConsolidationMode::None,
             ConsolidationMode::Auto => ConsolidationMode::Latest,
@@ -2254,6 +2310,36 @@ impl SessionInner {
             _ => 1,
         };
 
+        #[cfg(feature = "unstable")]
+        let ext_credits = credits.as_ref().map(|credits| {
+            credits.bind(StreamedQuery {
+                session: WeakSession::new(self),
+                qid,
+                ext_qos: qos.into(),
+                destination,
+            });
+            credits.window
+        });
+        #[cfg(not(feature = "unstable"))]
+        let ext_credits = None;
+
+        #[cfg(feature = "unstable")]
+        let cancellation = match cancellation_token {
+            Some(cancellation_token) => {
+                let session = WeakSession::new(self);
+                let ext_qos = qos.into();
+                let cancel = move || session.cancel_query(qid, ext_qos, destination);
+                match cancellation_token.on_cancel(cancel) {
+                    Some(guard) => Some(guard),
+                    None => {
+                        tracing::debug!("Query {} cancelled before being sent", qid);
+                        return Ok(());
+                    }
+                }
+            }
+            None => None,
+        };
+
         let token = self.task_controller.get_cancellation_token();
         self.task_controller
             .spawn_with_rt(zenoh_runtime::ZRuntime::Net, {
@@ -2274,7 +2360,14 @@ impl SessionInner {
                                     result: Err(ReplyError::new("Timeout", Encoding::ZENOH_STRING)),
                                     #[cfg(feature = "unstable")]
                                     replier_id: Some(session.zid().into()),
+                                    #[cfg(feature = "unstable")]
+                                    responder_id: None,
                                 });
+                                // Repliers waiting for credits would otherwise never end the query
+                                #[cfg(feature = "unstable")]
+                                if ext_credits.is_some() {
+                                    session.send_query_cancel(qid, qos.into(), destination);
+                                }
                             }
                         }
                         _ = token.cancelled() => {}
@@ -2293,6 +2386,10 @@ impl SessionInner {
                 reception_mode: consolidation,
                 replies: (consolidation != ConsolidationMode::None).then(HashMap::new),
                 callback,
+                max_replies,
+                nb_replies: 0,
+                #[cfg(feature = "unstable")]
+                _cancellation: cancellation,
             },
         );
 
@@ -2308,8 +2405,9 @@ impl SessionInner {
                 ext_tstamp: None,
                 ext_nodeid: request::ext::NodeIdType::DEFAULT,
                 ext_target: target,
-                ext_budget: None,
+                ext_budget: max_replies,
                 ext_timeout: Some(timeout),
+                ext_credits,
                 payload: RequestBody::Query(zenoh_protocol::zenoh::Query {
                     consolidation,
                     parameters: parameters.to_string(),
@@ -2336,6 +2434,8 @@ impl SessionInner {
                 qid,
                 target,
                 consolidation,
+                max_replies,
+                ext_credits,
                 value.as_ref().map(|v| query::ext::QueryBodyType {
                     #[cfg(feature = "shared-memory")]
                     ext_shm: None,
@@ -2348,10 +2448,185 @@ impl SessionInner {
         Ok(())
     }
 
+    /// Picks whether a load-balanced query is delivered to the local or to the remote queryables,
+    /// so that a single queryable is selected among all of them.
+    fn load_balanced_destination(
+        self: &Arc<Self>,
+        key_expr: &KeyExpr<'_>,
+        target: request::ext::QueryTarget,
+    ) -> Locality {
+        let face = zread!(self.state)
+            .primitives
+            .as_ref()
+            .map(|primitives| primitives.state.clone());
+        let (remote, remote_complete) = match face {
+            Some(face) => crate::net::routing::dispatcher::queries::get_load_balanced_candidates(
+                &self.runtime.router().tables,
+                &face,
+                &key_expr.to_wire(self),
+            ),
+            None => (0, false),
+        };
+
+        let state = zread!(self.state);
+        let (mut local, mut local_complete) = (0, 0);
+        for qabl in state
+            .queryables
+            .values()
+            .filter(|qabl| qabl.origin != Locality::Remote)
+        {
+            if state
+                .local_wireexpr_to_expr(&qabl.key_expr)
+                .is_ok_and(|qablname| qablname.intersects(key_expr))
+            {
+                local += 1;
+                local_complete += qabl.complete as usize;
+            }
+        }
+        // As in the routers, complete queryables are preferred when there are some
+        let (local, remote) = if local_complete > 0 || remote_complete {
+            (local_complete, if remote_complete { remote } else { 0 })
+        } else {
+            (local, remote)
+        };
+        if local == 0 {
+            return Locality::Remote;
+        }
+        if remote == 0 {
+            return Locality::SessionLocal;
+        }
+        // Each remote candidate then picks among its own queryables, so the destinations are
+        // weighted by their number of candidates
+        let turn = match target {
+            request::ext::QueryTarget::Random => rand::thread_rng().gen(),
+            _ => state.destinations_round_robin.next(key_expr.as_str()),
+        };
+        if turn % (local + remote) < local {
+            Locality::SessionLocal
+        } else {
+            Locality::Remote
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn cancel_query(
+        self: &Arc<Self>,
+        qid: RequestId,
+        ext_qos: request::ext::QoSType,
+        destination: Locality,
+    ) {
+        let Some(query) = zwrite!(self.state).queries.remove(&qid) else {
+            return; // Query already finalized
+        };
+        tracing::debug!("Cancel query {}", qid);
+        self.send_query_cancel(qid, ext_qos, destination);
+        // dropping the callback closes the handler of the query
+        drop(query);
+    }
+
+    #[cfg(feature = "unstable")]
+    fn send_query_cancel(
+        &self,
+        qid: RequestId,
+        ext_qos: request::ext::QoSType,
+        destination: Locality,
+    ) {
+        let Some(primitives) = zread!(self.state).primitives.clone() else {
+            return; // Session closing or closed
+        };
+        if destination != Locality::SessionLocal {
+            primitives.send_request_cancel(RequestCancel {
+                id: qid,
+                ext_qos,
+                ext_tstamp: None,
+            });
+        }
+        if destination != Locality::Remote {
+            self.handle_request_cancel(true, qid);
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    fn handle_request_cancel(&self, local: bool, qid: RequestId) {
+        let token = zread!(self.state)
+            .query_controls
+            .get(&(local, qid))
+            .map(|(token, _)| token.clone());
+        if let Some(token) = token {
+            tracing::debug!("Query {} cancelled by the querier", qid);
+            token.cancel();
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn grant_query_credits(
+        &self,
+        qid: RequestId,
+        credits: u32,
+        responder: EntityGlobalIdProto,
+        ext_qos: request::ext::QoSType,
+        destination: Locality,
+    ) {
+        let state = zread!(self.state);
+        if !state.queries.contains_key(&qid) {
+            return; // Query already finalized
+        }
+        let Some(primitives) = state.primitives.clone() else {
+            return; // Session closing or closed
+        };
+        drop(state);
+        tracing::trace!(
+            "Grant {} credits to query {} for {:?}",
+            credits,
+            qid,
+            responder
+        );
+        if responder.zid == self.zid().into() {
+            if destination != Locality::Remote {
+                self.handle_request_credit(true, qid, credits, Some(responder.eid));
+            }
+        } else if destination != Locality::SessionLocal {
+            primitives.send_request_credit(RequestCredit {
+                id: qid,
+                credits,
+                ext_qos,
+                ext_tstamp: None,
+                ext_respid: Some(request::ext::ResponderIdType {
+                    zid: responder.zid,
+                    eid: responder.eid,
+                }),
+            });
+        }
+    }
+
+    /// Grants credits to the given local queryable replying to a query, or to all of them.
+    #[cfg(feature = "unstable")]
+    fn handle_request_credit(
+        &self,
+        local: bool,
+        qid: RequestId,
+        credits: u32,
+        eid: Option<EntityId>,
+    ) {
+        let semaphores: Vec<Arc<Semaphore>> =
+            match zread!(self.state).query_controls.get(&(local, qid)) {
+                Some((_, semaphores)) => match eid {
+                    Some(eid) => semaphores.get(&eid).cloned().into_iter().collect(),
+                    None => semaphores.values().cloned().collect(),
+                },
+                None => return,
+            };
+        for semaphore in semaphores {
+            let available = Semaphore::MAX_PERMITS - semaphore.available_permits();
+            semaphore.add_permits((credits as usize).min(available));
+        }
+    }
+
     pub(crate) fn liveliness_query(
         self: &Arc<Self>,
         key_expr: &KeyExpr<'_>,
         timeout: Duration,
+        max_replies: Option<NonZeroU32>,
         callback: Callback<Reply>,
     ) -> ZResult<()> {
         tracing::trace!("liveliness.get({}, {:?})", key_expr, timeout);
@@ -2365,13 +2640,15 @@ impl SessionInner {
                     tokio::select! {
                         _ = tokio::time::sleep(timeout) => {
                             let mut state = zwrite!(session.state);
-                            if let Some(query) = state.liveliness_queries.remove(&id) {
+                            if let Some(LivelinessQueryState { callback: Some(callback), .. }) = state.liveliness_queries.remove(&id) {
                                 std::mem::drop(state);
                                 tracing::debug!("Timeout on liveliness query {}! Send error and close.", id);
-                                query.callback.call(Reply {
+                                callback.call(Reply {
                                     result: Err(ReplyError::new("Timeout", Encoding::ZENOH_STRING)),
                                     #[cfg(feature = "unstable")]
                                     replier_id: Some(session.zid().into()),
+                                    #[cfg(feature = "unstable")]
+                                    responder_id: None,
                                 });
                             }
                         }
@@ -2382,9 +2659,14 @@ impl SessionInner {
 
         tracing::trace!("Register liveliness query {}", id);
         let wexpr = key_expr.to_wire(self).to_owned();
-        state
-            .liveliness_queries
-            .insert(id, LivelinessQueryState { callback });
+        state.liveliness_queries.insert(
+            id,
+            LivelinessQueryState {
+                callback: Some(callback),
+                max_replies,
+                nb_replies: 0,
+            },
+        );
 
         let primitives = state.primitives()?;
         drop(state);
@@ -2397,6 +2679,7 @@ impl SessionInner {
             ext_qos: request::ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: request::ext::NodeIdType::DEFAULT,
+            ext_budget: max_replies,
         });
 
         Ok(())
@@ -2409,8 +2692,10 @@ impl SessionInner {
         key_expr: &WireExpr,
         parameters: &str,
         qid: RequestId,
-        _target: QueryTarget,
+        target: request::ext::QueryTarget,
         _consolidation: ConsolidationMode,
+        _max_replies: Option<NonZeroU32>,
+        _credits: Option<NonZeroU32>,
         body: Option<QueryBodyType>,
         attachment: Option<ZBytes>,
     ) {
@@ -2424,7 +2709,7 @@ impl SessionInner {
             };
             match state.wireexpr_to_keyexpr(key_expr, local) {
                 Ok(key_expr) => {
-                    let queryables = state
+                    let mut queryables = state
                         .queryables
                         .iter()
                         .filter(
@@ -2445,8 +2730,21 @@ impl SessionInner {
                                     }
                                 }
                         )
-                        .map(|(id, qable)| (*id, qable.callback.clone()))
-                        .collect::<Vec<(u32, Callback<Query>)>>();
+                        .map(|(id, qable)| (*id, qable.complete, qable.callback.clone()))
+                        .collect::<Vec<(u32, bool, Callback<Query>)>>();
+                    // Local queryables do not track their outstanding queries, so they are
+                    // picked in turn unless the query targets a random one
+                    if target.is_load_balanced() && queryables.len() > 1 {
+                        if queryables.iter().any(|(_, complete, _)| *complete) {
+                            queryables.retain(|(_, complete, _)| *complete);
+                        }
+                        let turn = match target {
+                            request::ext::QueryTarget::Random => rand::thread_rng().gen(),
+                            _ => state.queryables_round_robin.next(key_expr.as_str()),
+                        };
+                        let len = queryables.len();
+                        queryables = vec![queryables.swap_remove(turn % len)];
+                    }
                     (primitives, key_expr.into_owned(), queryables)
                 }
                 Err(err) => {
@@ -2458,6 +2756,29 @@ impl SessionInner {
 
         let zid = self.zid();
 
+        #[cfg(feature = "unstable")]
+        let control = {
+            let token = CancellationToken::default();
+            // Each queryable is a distinct responder, granted its own credits by the querier
+            let credits: QueryCredits = _credits
+                .map(|credits| {
+                    queryables
+                        .iter()
+                        .map(|(eid, _, _)| (*eid, Arc::new(Semaphore::new(credits.get() as usize))))
+                        .collect()
+                })
+                .unwrap_or_default();
+            zwrite!(self.state)
+                .query_controls
+                .insert((local, qid), (token.clone(), credits.clone()));
+            QueryControl {
+                token,
+                credits,
+                session: WeakSession::new(self),
+                local,
+                qid,
+            }
+        };
         let query_inner = Arc::new(QueryInner {
             key_expr,
             parameters: parameters.to_owned().into(),
@@ -2468,6 +2789,10 @@ impl SessionInner {
             } else {
                 primitives
             },
+            #[cfg(feature = "unstable")]
+            max_replies: _max_replies,
+            #[cfg(feature = "unstable")]
+            control: Some(control),
         });
         let mut query = Query {
             inner: query_inner,
@@ -2475,7 +2800,7 @@ impl SessionInner {
             value: body.map(|b| (b.payload.into(), b.encoding.into())),
             attachment,
         };
-        for (eid, cb) in queryables {
+        for (eid, _, cb) in queryables {
             query.eid = eid;
             cb.call(query.clone());
         }
@@ -2634,7 +2959,20 @@ impl Primitives for WeakSession {
                 {
                     Ok(key_expr) => {
                         if let Some(interest_id) = msg.interest_id {
-                            if let Some(query) = state.liveliness_queries.get(&interest_id) {
+                            if let Some(query) = state.liveliness_queries.get_mut(&interest_id) {
+                                // The tokens replied beyond the budget of the query are ignored,
+                                // and its handler closed
+                                query.nb_replies = query.nb_replies.saturating_add(1);
+                                let callback = match query.max_replies {
+                                    Some(max) if query.nb_replies >= max.get() => {
+                                        query.callback.take()
+                                    }
+                                    _ => query.callback.clone(),
+                                };
+                                drop(state);
+                                let Some(callback) = callback else {
+                                    return;
+                                };
                                 let reply = Reply {
                                     result: Ok(Sample {
                                         key_expr,
@@ -2651,9 +2989,11 @@ impl Primitives for WeakSession {
                                     }),
                                     #[cfg(feature = "unstable")]
                                     replier_id: None,
+                                    #[cfg(feature = "unstable")]
+                                    responder_id: None,
                                 };
 
-                                query.callback.call(reply);
+                                callback.call(reply);
                                 return;
                             }
                         }
@@ -2803,6 +3143,8 @@ impl Primitives for WeakSession {
                 msg.id,
                 msg.ext_target,
                 m.consolidation,
+                msg.ext_budget,
+                msg.ext_credits,
                 m.ext_body,
                 m.ext_attachment.map(Into::into),
             ),
@@ -2819,6 +3161,10 @@ impl Primitives for WeakSession {
                 }
                 match state.queries.get_mut(&msg.rid) {
                     Some(query) => {
+                        if !query.count_reply() {
+                            trace!("Drop ReplyError for Query {}: budget exhausted", msg.rid);
+                            return;
+                        }
                         let callback = query.callback.clone();
                         std::mem::drop(state);
                         let new_reply = Reply {
@@ -2828,6 +3174,11 @@ impl Primitives for WeakSession {
                             }),
                             #[cfg(feature = "unstable")]
                             replier_id: e.ext_sinfo.map(|info| info.id.zid),
+                            #[cfg(feature = "unstable")]
+                            responder_id: msg.ext_respid.as_ref().map(|rid| EntityGlobalIdProto {
+                                zid: rid.zid,
+                                eid: rid.eid,
+                            }),
                         };
                         callback.call(new_reply);
                     }
@@ -2861,6 +3212,10 @@ impl Primitives for WeakSession {
                             );
                             return;
                         }
+                        if !query.count_reply() {
+                            trace!("Drop Reply for Query {}: budget exhausted", msg.rid);
+                            return;
+                        }
 
                         struct Ret {
                             payload: ZBuf,
@@ -2920,6 +3275,11 @@ impl Primitives for WeakSession {
                             result: Ok(sample),
                             #[cfg(feature = "unstable")]
                             replier_id: None,
+                            #[cfg(feature = "unstable")]
+                            responder_id: msg.ext_respid.as_ref().map(|rid| EntityGlobalIdProto {
+                                zid: rid.zid,
+                                eid: rid.eid,
+                            }),
                         };
                         let callback =
                             match query.reception_mode {
@@ -3013,6 +3373,22 @@ impl Primitives for WeakSession {
         }
     }
 
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        trace!("recv RequestCancel {:?}", msg);
+        #[cfg(feature = "unstable")]
+        self.handle_request_cancel(false, msg.id);
+    }
+
+    fn send_request_credit(&self, msg: RequestCredit) {
+        trace!("recv RequestCredit {:?}", msg);
+        #[cfg(feature = "unstable")]
+        match msg.ext_respid {
+            // Credits granted to a responder of another session
+            Some(respid) if respid.zid != self.zid().into() => {}
+            respid => self.handle_request_credit(false, msg.id, msg.credits, respid.map(|r| r.eid)),
+        }
+    }
+
     fn send_response_final(&self, msg: ResponseFinal) {
         trace!("recv ResponseFinal {:?}", msg);
         let mut state = zwrite!(self.state);
@@ -3070,6 +3446,16 @@ impl crate::net::primitives::EPrimitives for WeakSession {
         (self as &dyn Primitives).send_response(msg)
     }
 
+    #[inline]
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        (self as &dyn Primitives).send_request_cancel(msg)
+    }
+
+    #[inline]
+    fn send_request_credit(&self, msg: RequestCredit) {
+        (self as &dyn Primitives).send_request_credit(msg)
+    }
+
     #[inline]
     fn send_response_final(&self, msg: ResponseFinal) {
         (self as &dyn Primitives).send_response_final(msg)
@@ -0,0 +1,276 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::HashMap,
+    fmt,
+    num::NonZeroU32,
+    pin::Pin,
+    sync::{Arc, Mutex, OnceLock},
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use zenoh_core::zlock;
+use zenoh_protocol::{
+    core::EntityGlobalIdProto,
+    network::{request, RequestId},
+};
+use zenoh_result::ZResult;
+
+use crate::api::{
+    bytes::ZBytes,
+    handlers::{Callback, IntoHandler},
+    key_expr::KeyExpr,
+    query::Reply,
+    queryable::Query,
+    sample::Locality,
+    session::WeakSession,
+};
+
+/// The query a [`ReplyStream`] grants credits to, bound once the query is sent.
+#[derive(Debug)]
+pub(crate) struct StreamedQuery {
+    pub(crate) session: WeakSession,
+    pub(crate) qid: RequestId,
+    pub(crate) ext_qos: request::ext::QoSType,
+    pub(crate) destination: Locality,
+}
+
+/// The reply credits of a streamed query, granted to each replier as its replies are consumed.
+#[derive(Debug)]
+pub(crate) struct ReplyCredits {
+    pub(crate) window: NonZeroU32,
+    /// The replies consumed by replier since their last grant.
+    consumed: Mutex<HashMap<EntityGlobalIdProto, u32>>,
+    query: OnceLock<StreamedQuery>,
+}
+
+impl ReplyCredits {
+    pub(crate) fn new(window: NonZeroU32) -> Self {
+        Self {
+            window,
+            consumed: Mutex::new(HashMap::new()),
+            query: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn bind(&self, query: StreamedQuery) {
+        let _ = self.query.set(query);
+    }
+
+    fn consume(&self, reply: &Reply) {
+        // Replies made up by the session, like timeouts, were not sent against credits
+        let Some(responder) = reply.responder_id else {
+            return;
+        };
+        // Credits are granted by batches of half the window, to limit the number of messages
+        // while keeping the repliers busy
+        let batch = (self.window.get() / 2).max(1);
+        {
+            let mut consumed = zlock!(self.consumed);
+            let consumed = consumed.entry(responder).or_default();
+            *consumed += 1;
+            if *consumed < batch {
+                return;
+            }
+            *consumed = 0;
+        }
+        if let Some(query) = self.query.get() {
+            query.session.grant_query_credits(
+                query.qid,
+                batch,
+                responder,
+                query.ext_qos,
+                query.destination,
+            );
+        }
+    }
+
+    fn close(&self) {
+        if let Some(query) = self.query.get() {
+            query
+                .session
+                .cancel_query(query.qid, query.ext_qos, query.destination);
+        }
+    }
+}
+
+/// The handler of a streamed query, created with
+/// [`SessionGetBuilder::stream`](crate::session::SessionGetBuilder::stream).
+#[derive(Debug)]
+pub struct ReplyStreamChannel {
+    pub(crate) credits: Arc<ReplyCredits>,
+}
+
+impl IntoHandler<Reply> for ReplyStreamChannel {
+    type Handler = ReplyStream;
+
+    fn into_handler(self) -> (Callback<Reply>, Self::Handler) {
+        // The pending replies are bounded by the window of each replier
+        let (sender, receiver) = flume::unbounded();
+        (
+            Callback::new(Arc::new(move |reply| {
+                if let Err(error) = sender.send(reply) {
+                    tracing::error!(%error)
+                }
+            })),
+            ReplyStream {
+                stream: receiver.into_stream(),
+                credits: self.credits,
+                terminated: false,
+            },
+        )
+    }
+}
+
+/// An asynchronous [`Stream`] of the replies to a streamed query.
+///
+/// Each consumed reply grants a credit back to the queryable that sent it, so that at most the
+/// window given to [`SessionGetBuilder::stream`](crate::session::SessionGetBuilder::stream)
+/// replies per queryable are in flight. Dropping the stream before its end cancels the query.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use futures::StreamExt;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let mut replies = session.get("key/expression").stream(64).await.unwrap();
+/// while let Some(reply) = replies.next().await {
+///     println!(">> Received {:?}", reply.result());
+/// }
+/// # }
+/// ```
+pub struct ReplyStream {
+    stream: flume::r#async::RecvStream<'static, Reply>,
+    credits: Arc<ReplyCredits>,
+    terminated: bool,
+}
+
+impl fmt::Debug for ReplyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplyStream")
+            .field("window", &self.credits.window)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
+
+impl Stream for ReplyStream {
+    type Item = Reply;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.stream.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(reply)) => self.credits.consume(reply),
+            Poll::Ready(None) => self.terminated = true,
+            Poll::Pending => {}
+        }
+        poll
+    }
+}
+
+impl Drop for ReplyStream {
+    fn drop(&mut self) {
+        if !self.terminated {
+            self.credits.close();
+        }
+    }
+}
+
+/// A sink to stream the replies to a [`Query`] with backpressure, obtained with
+/// [`Query::reply_sink`].
+///
+/// Each reply waits for a credit granted by the querier, and fails if the query is cancelled.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let queryable = session.declare_queryable("key/expression").await.unwrap();
+/// while let Ok(query) = queryable.recv_async().await {
+///     let sink = query.reply_sink();
+///     for i in 0..1_000_000 {
+///         if sink.reply(query.key_expr().clone(), i.to_string()).await.is_err() {
+///             break;
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplySink {
+    pub(crate) query: Query,
+}
+
+impl ReplySink {
+    async fn acquire(&self) -> ZResult<()> {
+        let Some(control) = self.query.inner.control.as_ref() else {
+            return Ok(());
+        };
+        let Some(credits) = control.credits.get(&self.query.eid) else {
+            return Ok(());
+        };
+        tokio::select! {
+            biased;
+            _ = control.token.cancelled() => bail!("Query cancelled by the querier"),
+            permit = credits.acquire() => {
+                permit.map_err(|e| zerror!("{}", e))?.forget();
+                Ok(())
+            }
+        }
+    }
+
+    /// Sends a [`crate::sample::Sample`] of kind [`crate::sample::SampleKind::Put`] as a reply,
+    /// once the querier granted a credit for it.
+    pub async fn reply<'b, TryIntoKeyExpr, IntoZBytes>(
+        &self,
+        key_expr: TryIntoKeyExpr,
+        payload: IntoZBytes,
+    ) -> ZResult<()>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<zenoh_result::Error>,
+        IntoZBytes: Into<ZBytes>,
+    {
+        let key_expr: KeyExpr<'b> = key_expr.try_into().map_err(Into::into)?;
+        let payload = payload.into();
+        self.acquire().await?;
+        self.query.reply(key_expr, payload).await
+    }
+
+    /// Sends a [`crate::query::ReplyError`] as a reply, once the querier granted a credit for it.
+    pub async fn reply_err<IntoZBytes>(&self, payload: IntoZBytes) -> ZResult<()>
+    where
+        IntoZBytes: Into<ZBytes>,
+    {
+        let payload = payload.into();
+        self.acquire().await?;
+        self.query.reply_err(payload).await
+    }
+
+    /// Sends a [`crate::sample::Sample`] of kind [`crate::sample::SampleKind::Delete`] as a reply,
+    /// once the querier granted a credit for it.
+    pub async fn reply_del<'b, TryIntoKeyExpr>(&self, key_expr: TryIntoKeyExpr) -> ZResult<()>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<zenoh_result::Error>,
+    {
+        let key_expr: KeyExpr<'b> = key_expr.try_into().map_err(Into::into)?;
+        self.acquire().await?;
+        self.query.reply_del(key_expr).await
+    }
+}
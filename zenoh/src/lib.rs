@@ -293,9 +293,11 @@ pub mod query {
     #[zenoh_macros::unstable]
     pub use crate::api::{
         builders::querier::{QuerierBuilder, QuerierGetBuilder},
+        cancellation::CancellationToken,
         querier::Querier,
         query::ReplyKeyExpr,
         selector::ZenohParameters,
+        streaming::{ReplySink, ReplyStream, ReplyStreamChannel},
     };
     pub use crate::api::{
         builders::{
@@ -473,6 +475,21 @@ compile_error!(
     "The plugins support is internal and unstable. The `unstable` and `internal` features must be enabled to use `plugins`."
 );
 
+/// User-provided interceptors of the routing layer
+///
+/// A [`MessageInterceptorFactory`](crate::interceptor::MessageInterceptorFactory) is registered
+/// with [`OpenBuilder::with_interceptor_factory`](crate::session::OpenBuilder::with_interceptor_factory),
+/// and instantiates [`MessageInterceptor`](crate::interceptor::MessageInterceptor)s for each transport.
+#[zenoh_macros::unstable]
+pub mod interceptor {
+    pub use zenoh_config::InterceptorFlow;
+
+    pub use crate::net::routing::interceptor::custom::{
+        BoxedMessageInterceptor, InterceptedMessage, MessageInterceptor, MessageInterceptorFactory,
+        MessageKind, TransportMetadata,
+    };
+}
+
 #[zenoh_macros::internal]
 pub mod internal {
     pub mod traits {
@@ -511,6 +528,11 @@ pub mod internal {
 
         pub use crate::net::runtime::{AdminSpace, Runtime, RuntimeBuilder};
     }
+    /// Access control of the messages sent on behalf of users by plugins
+    #[zenoh_macros::internal]
+    pub mod access_control {
+        pub use zenoh_config::AclMessage;
+    }
     /// Plugins support
     #[cfg(feature = "plugins")]
     pub mod plugins {
@@ -49,6 +49,9 @@ where
         if x.locators.is_some() {
             options |= linkstate::LOC;
         }
+        if x.link_weights.is_some() {
+            options |= linkstate::WGT;
+        }
         codec.write(&mut *writer, options)?;
 
         // Body
@@ -68,6 +71,14 @@ where
         for l in x.links.iter() {
             codec.write(&mut *writer, *l)?;
         }
+        if let Some(link_weights) = x.link_weights.as_ref() {
+            if link_weights.len() != x.links.len() {
+                return Err(DidntWrite);
+            }
+            for w in link_weights.iter() {
+                codec.write(&mut *writer, *w)?;
+            }
+        }
 
         Ok(())
     }
@@ -108,6 +119,16 @@ where
             let l: u64 = codec.read(&mut *reader)?;
             links.push(l);
         }
+        let link_weights = if imsg::has_option(options, linkstate::WGT) {
+            let mut link_weights: Vec<u16> = Vec::with_capacity(len);
+            for _ in 0..len {
+                let w: u16 = codec.read(&mut *reader)?;
+                link_weights.push(w);
+            }
+            Some(link_weights)
+        } else {
+            None
+        };
 
         Ok(LinkState {
             psid,
@@ -116,6 +137,7 @@ where
             whatami,
             locators,
             links,
+            link_weights,
         })
     }
 }
@@ -14,16 +14,15 @@
 use std::{any::Any, sync::Arc};
 
 use zenoh_link::Link;
-use zenoh_protocol::network::{NetworkBody, NetworkMessage};
+use zenoh_protocol::{
+    core::Reliability,
+    network::{NetworkBody, NetworkMessage},
+};
 use zenoh_result::ZResult;
 use zenoh_transport::{unicast::TransportUnicast, TransportPeerEventHandler};
 
 use super::Primitives;
-use crate::net::routing::{
-    dispatcher::face::Face,
-    interceptor::{InterceptorTrait, InterceptorsChain},
-    RoutingContext,
-};
+use crate::net::routing::{dispatcher::face::Face, interceptor::InterceptorsChain, RoutingContext};
 
 pub struct DeMux {
     face: Face,
@@ -50,15 +49,7 @@ impl TransportPeerEventHandler for DeMux {
     fn handle_message(&self, mut msg: NetworkMessage) -> ZResult<()> {
         if !self.interceptor.interceptors.is_empty() {
             let ctx = RoutingContext::new_in(msg, self.face.clone());
-            let prefix = ctx
-                .wire_expr()
-                .and_then(|we| (!we.has_suffix()).then(|| ctx.prefix()))
-                .flatten()
-                .cloned();
-            let cache = prefix
-                .as_ref()
-                .and_then(|p| p.get_ingress_cache(&self.face));
-            let ctx = match self.interceptor.intercept(ctx, cache) {
+            let ctx = match intercept_ingress(&self.interceptor, &self.face, 0, ctx) {
                 Some(ctx) => ctx,
                 None => return Ok(()),
             };
@@ -66,12 +57,6 @@ impl TransportPeerEventHandler for DeMux {
         }
 
         match msg.body {
-            NetworkBody::Push(m) => self.face.send_push(m, msg.reliability),
-            NetworkBody::Declare(m) => self.face.send_declare(m),
-            NetworkBody::Interest(m) => self.face.send_interest(m),
-            NetworkBody::Request(m) => self.face.send_request(m),
-            NetworkBody::Response(m) => self.face.send_response(m),
-            NetworkBody::ResponseFinal(m) => self.face.send_response_final(m),
             NetworkBody::OAM(m) => {
                 if let Some(transport) = self.transport.as_ref() {
                     let mut declares = vec![];
@@ -91,6 +76,7 @@ impl TransportPeerEventHandler for DeMux {
                     }
                 }
             }
+            body => route(&self.face, body, msg.reliability),
         }
 
         Ok(())
@@ -108,3 +94,57 @@ impl TransportPeerEventHandler for DeMux {
         self
     }
 }
+
+fn intercept_ingress(
+    interceptor: &InterceptorsChain,
+    face: &Face,
+    start: usize,
+    ctx: RoutingContext<NetworkMessage>,
+) -> Option<RoutingContext<NetworkMessage>> {
+    let prefix = ctx
+        .wire_expr()
+        .and_then(|we| (!we.has_suffix()).then(|| ctx.prefix()))
+        .flatten()
+        .cloned();
+    let cache = prefix.as_ref().and_then(|p| p.get_ingress_cache(face));
+    interceptor.intercept_from(start, ctx, cache)
+}
+
+fn route(face: &Face, body: NetworkBody, reliability: Reliability) {
+    match body {
+        NetworkBody::Push(m) => face.send_push(m, reliability),
+        NetworkBody::Declare(m) => face.send_declare(m),
+        NetworkBody::Interest(m) => face.send_interest(m),
+        NetworkBody::Request(m) => face.send_request(m),
+        NetworkBody::Response(m) => face.send_response(m),
+        NetworkBody::ResponseFinal(m) => face.send_response_final(m),
+        NetworkBody::RequestCancel(m) => face.send_request_cancel(m),
+        NetworkBody::RequestCredit(m) => face.send_request_credit(m),
+        NetworkBody::OAM(_) => tracing::trace!("Ignoring OAM message outside of its transport"),
+    }
+}
+
+/// Resumes the ingress processing of a message held back by one of the interceptors of its
+/// incoming face: the interceptors following it run before the message gets routed.
+pub(crate) fn resume_ingress(ctx: RoutingContext<NetworkMessage>) {
+    let Some(face) = ctx.inface().cloned() else {
+        return;
+    };
+    if !zread!(face.tables.tables)
+        .faces
+        .contains_key(&face.state.id)
+    {
+        tracing::trace!("Dropping message held back for closed {}", face);
+        return;
+    }
+    let ctx = match face.state.in_interceptors.as_ref() {
+        Some(interceptor) => {
+            match intercept_ingress(interceptor, &face, ctx.interceptor_idx + 1, ctx) {
+                Some(ctx) => ctx,
+                None => return,
+            }
+        }
+        None => ctx,
+    };
+    route(&face, ctx.msg.body, ctx.msg.reliability);
+}
@@ -20,7 +20,10 @@ pub use demux::*;
 pub use mux::*;
 use zenoh_protocol::{
     core::Reliability,
-    network::{interest::Interest, Declare, Push, Request, Response, ResponseFinal},
+    network::{
+        interest::Interest, Declare, Push, Request, RequestCancel, RequestCredit, Response,
+        ResponseFinal,
+    },
 };
 
 use super::routing::RoutingContext;
@@ -34,6 +37,10 @@ pub trait Primitives: Send + Sync {
 
     fn send_request(&self, msg: Request);
 
+    fn send_request_cancel(&self, msg: RequestCancel);
+
+    fn send_request_credit(&self, msg: RequestCredit);
+
     fn send_response(&self, msg: Response);
 
     fn send_response_final(&self, msg: ResponseFinal);
@@ -52,6 +59,10 @@ pub(crate) trait EPrimitives: Send + Sync {
 
     fn send_request(&self, msg: Request);
 
+    fn send_request_cancel(&self, msg: RequestCancel);
+
+    fn send_request_credit(&self, msg: RequestCredit);
+
     fn send_response(&self, msg: Response);
 
     fn send_response_final(&self, msg: ResponseFinal);
@@ -69,6 +80,10 @@ impl Primitives for DummyPrimitives {
 
     fn send_request(&self, _msg: Request) {}
 
+    fn send_request_cancel(&self, _msg: RequestCancel) {}
+
+    fn send_request_credit(&self, _msg: RequestCredit) {}
+
     fn send_response(&self, _msg: Response) {}
 
     fn send_response_final(&self, _msg: ResponseFinal) {}
@@ -85,6 +100,10 @@ impl EPrimitives for DummyPrimitives {
 
     fn send_request(&self, _msg: Request) {}
 
+    fn send_request_cancel(&self, _msg: RequestCancel) {}
+
+    fn send_request_credit(&self, _msg: RequestCredit) {}
+
     fn send_response(&self, _msg: Response) {}
 
     fn send_response_final(&self, _msg: ResponseFinal) {}
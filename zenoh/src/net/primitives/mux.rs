@@ -16,8 +16,8 @@ use std::sync::OnceLock;
 use zenoh_protocol::{
     core::Reliability,
     network::{
-        interest::Interest, Declare, NetworkBody, NetworkMessage, Push, Request, Response,
-        ResponseFinal,
+        interest::Interest, request::ext::QueryTarget, Declare, NetworkBody, NetworkMessage, Push,
+        Request, RequestCancel, RequestCredit, Response, ResponseFinal,
     },
 };
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
@@ -58,6 +58,7 @@ impl EPrimitives for Mux {
             outface: ctx.outface,
             prefix: ctx.prefix,
             full_expr: ctx.full_expr,
+            interceptor_idx: ctx.interceptor_idx,
         };
         let prefix = ctx
             .wire_expr()
@@ -84,6 +85,7 @@ impl EPrimitives for Mux {
             outface: ctx.outface,
             prefix: ctx.prefix,
             full_expr: ctx.full_expr,
+            interceptor_idx: ctx.interceptor_idx,
         };
         let prefix = ctx
             .wire_expr()
@@ -123,7 +125,17 @@ impl EPrimitives for Mux {
         }
     }
 
-    fn send_request(&self, msg: Request) {
+    fn send_request(&self, mut msg: Request) {
+        // Peers that did not negotiate the load-balanced targets capability would fail to
+        // decode them, while the query was already routed to a single direction
+        if msg.ext_target.is_load_balanced()
+            && !self
+                .handler
+                .get_capabilities()
+                .is_ok_and(|capabilities| capabilities.has_load_balanced_query_targets())
+        {
+            msg.ext_target = QueryTarget::BestMatching;
+        }
         let msg = NetworkMessage {
             body: NetworkBody::Request(msg),
             reliability: Reliability::Reliable,
@@ -148,6 +160,62 @@ impl EPrimitives for Mux {
         }
     }
 
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        // Peers that did not negotiate the cancellation capability would fail to decode the
+        // message and rely on the timeout of the request instead
+        if !self
+            .handler
+            .get_capabilities()
+            .is_ok_and(|capabilities| capabilities.has_request_cancel())
+        {
+            return;
+        }
+        let msg = NetworkMessage {
+            body: NetworkBody::RequestCancel(msg),
+            reliability: Reliability::Reliable,
+            #[cfg(feature = "stats")]
+            size: None,
+        };
+        if self.interceptor.interceptors.is_empty() {
+            let _ = self.handler.schedule(msg);
+        } else if let Some(face) = self.face.get().and_then(|f| f.upgrade()) {
+            let ctx = RoutingContext::new_out(msg, face.clone());
+            if let Some(ctx) = self.interceptor.intercept(ctx, None) {
+                let _ = self.handler.schedule(ctx.msg);
+            }
+        } else {
+            tracing::error!("Uninitialized multiplexer!");
+        }
+    }
+
+    fn send_request_credit(&self, msg: RequestCredit) {
+        // Peers that did not negotiate the credits capability would fail to decode the
+        // message, they ignore the credits extension of the request and reply freely
+        if !self
+            .handler
+            .get_capabilities()
+            .is_ok_and(|capabilities| capabilities.has_request_credit())
+        {
+            return;
+        }
+        let msg = NetworkMessage {
+            body: NetworkBody::RequestCredit(msg),
+            reliability: Reliability::Reliable,
+            #[cfg(feature = "stats")]
+            size: None,
+        };
+        if self.interceptor.interceptors.is_empty() {
+            let _ = self.handler.schedule(msg);
+        } else if let Some(face) = self.face.get().and_then(|f| f.upgrade()) {
+            let ctx = RoutingContext::new_out(msg, face.clone());
+            if let Some(ctx) = self.interceptor.intercept(ctx, None) {
+                let _ = self.handler.schedule(ctx.msg);
+            }
+        } else {
+            tracing::error!("Uninitialized multiplexer!");
+        }
+    }
+
     fn send_response(&self, msg: Response) {
         let msg = NetworkMessage {
             body: NetworkBody::Response(msg),
@@ -232,6 +300,7 @@ impl EPrimitives for McastMux {
             outface: ctx.outface,
             prefix: ctx.prefix,
             full_expr: ctx.full_expr,
+            interceptor_idx: ctx.interceptor_idx,
         };
         let prefix = ctx
             .wire_expr()
@@ -258,6 +327,7 @@ impl EPrimitives for McastMux {
             outface: ctx.outface,
             prefix: ctx.prefix,
             full_expr: ctx.full_expr,
+            interceptor_idx: ctx.interceptor_idx,
         };
         let prefix = ctx
             .wire_expr()
@@ -297,7 +367,14 @@ impl EPrimitives for McastMux {
         }
     }
 
-    fn send_request(&self, msg: Request) {
+    fn send_request(&self, mut msg: Request) {
+        // Credits are never granted over multicast, the responders must not wait for them
+        msg.ext_credits = None;
+        // The capabilities are negotiated with each peer of a multicast group, which may not all
+        // decode load-balanced targets
+        if msg.ext_target.is_load_balanced() {
+            msg.ext_target = QueryTarget::BestMatching;
+        }
         let msg = NetworkMessage {
             body: NetworkBody::Request(msg),
             reliability: Reliability::Reliable,
@@ -322,6 +399,16 @@ impl EPrimitives for McastMux {
         }
     }
 
+    fn send_request_cancel(&self, _msg: RequestCancel) {
+        // The capabilities are negotiated with each peer of a multicast group, the requests
+        // sent over it are only ended by their timeout
+    }
+
+    fn send_request_credit(&self, _msg: RequestCredit) {
+        // The capabilities are negotiated with each peer of a multicast group, the requests
+        // sent over it are never granted credits
+    }
+
     fn send_response(&self, msg: Response) {
         let msg = NetworkMessage {
             body: NetworkBody::Response(msg),
@@ -16,10 +16,11 @@ use zenoh_protocol::core::{Locator, WhatAmI, ZenohIdProto};
 pub const PID: u64 = 1; // 0x01
 pub const WAI: u64 = 1 << 1; // 0x02
 pub const LOC: u64 = 1 << 2; // 0x04
+pub const WGT: u64 = 1 << 3; // 0x08
 
 //  7 6 5 4 3 2 1 0
 // +-+-+-+-+-+-+-+-+
-// ~X|X|X|X|X|L|W|P~
+// ~X|X|X|X|G|L|W|P~
 // +-+-+-+-+-+-+-+-+
 // ~     psid      ~
 // +---------------+
@@ -33,6 +34,10 @@ pub const LOC: u64 = 1 << 2; // 0x04
 // +---------------+
 // ~    [links]    ~
 // +---------------+
+// ~   [weights]   ~ if G == 1
+// +---------------+
+//
+// The weights are given in the order of the links, 0 meaning the link has no configured weight.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LinkState {
     pub(crate) psid: u64,
@@ -41,6 +46,7 @@ pub(crate) struct LinkState {
     pub(crate) whatami: Option<WhatAmI>,
     pub(crate) locators: Option<Vec<Locator>>,
     pub(crate) links: Vec<u64>,
+    pub(crate) link_weights: Option<Vec<u16>>,
 }
 
 impl LinkState {
@@ -74,6 +80,11 @@ impl LinkState {
         };
         let n = rng.gen_range(MIN..=MAX);
         let links = (0..n).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let link_weights = if rng.gen_bool(0.5) {
+            Some((0..n).map(|_| rng.gen()).collect::<Vec<u16>>())
+        } else {
+            None
+        };
 
         Self {
             psid,
@@ -82,6 +93,7 @@ impl LinkState {
             whatami,
             locators,
             links,
+            link_weights,
         }
     }
 }
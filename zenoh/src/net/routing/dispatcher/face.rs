@@ -24,7 +24,8 @@ use zenoh_protocol::{
     core::{ExprId, Reliability, WhatAmI, WireExpr, ZenohIdProto},
     network::{
         interest::{InterestId, InterestMode, InterestOptions},
-        push, Mapping, Push, Request, RequestId, Response, ResponseFinal,
+        push, Mapping, Push, Request, RequestCancel, RequestCredit, RequestId, Response,
+        ResponseFinal,
     },
     zenoh::{PushBody, RequestBody},
 };
@@ -72,6 +73,8 @@ pub struct FaceState {
     pub(crate) remote_mappings: HashMap<ExprId, Arc<Resource>>,
     pub(crate) next_qid: RequestId,
     pub(crate) pending_queries: HashMap<RequestId, (Arc<Query>, CancellationToken)>,
+    // The faces and request ids the queries received from this face were propagated to.
+    pub(crate) propagated_queries: HashMap<RequestId, Vec<(Weak<FaceState>, RequestId)>>,
     pub(crate) mcast_group: Option<TransportMulticast>,
     pub(crate) in_interceptors: Option<Arc<InterceptorsChain>>,
     pub(crate) hat: Box<dyn Any + Send + Sync>,
@@ -104,6 +107,7 @@ impl FaceState {
             remote_mappings: HashMap::new(),
             next_qid: 0,
             pending_queries: HashMap::new(),
+            propagated_queries: HashMap::new(),
             mcast_group,
             in_interceptors,
             hat,
@@ -249,6 +253,7 @@ impl Primitives for Face {
                 msg.wire_expr.as_ref(),
                 msg.mode,
                 msg.options,
+                msg.ext_budget,
                 &mut |p, m| declares.push((p.clone(), m)),
             );
             drop(ctrl_lock);
@@ -434,6 +439,7 @@ impl Primitives for Face {
                     msg.ext_target,
                     msg.ext_budget,
                     msg.ext_timeout,
+                    msg.ext_credits,
                     msg.payload,
                     msg.ext_nodeid.node_id,
                 );
@@ -441,6 +447,21 @@ impl Primitives for Face {
         }
     }
 
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        route_cancel_query(&self.tables, &self.state, msg.id, msg.ext_qos);
+    }
+
+    fn send_request_credit(&self, msg: RequestCredit) {
+        route_query_credit(
+            &self.tables,
+            &self.state,
+            msg.id,
+            msg.credits,
+            msg.ext_qos,
+            msg.ext_respid,
+        );
+    }
+
     fn send_response(&self, msg: Response) {
         route_send_response(
             &self.tables,
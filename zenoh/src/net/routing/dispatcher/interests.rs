@@ -14,7 +14,10 @@
 
 use std::{
     fmt,
-    sync::{Arc, Weak},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Weak,
+    },
     time::Duration,
 };
 
@@ -25,7 +28,7 @@ use zenoh_protocol::{
     core::WireExpr,
     network::{
         declare::ext,
-        interest::{InterestId, InterestMode, InterestOptions},
+        interest::{ext::BudgetType, InterestId, InterestMode, InterestOptions},
         Declare, DeclareBody, DeclareFinal,
     },
 };
@@ -46,6 +49,35 @@ pub(crate) struct CurrentInterest {
     pub(crate) src_face: Arc<FaceState>,
     pub(crate) src_interest_id: InterestId,
     pub(crate) mode: InterestMode,
+    pub(crate) budget: Arc<InterestBudget>,
+}
+
+/// The maximum number of tokens sent in reply to a current interest, shared between
+/// the tokens answered from the local tables and the ones forwarded from other faces.
+pub(crate) struct InterestBudget {
+    limit: Option<BudgetType>,
+    nb_replies: AtomicU32,
+}
+
+impl InterestBudget {
+    pub(crate) fn new(limit: Option<BudgetType>) -> Self {
+        InterestBudget {
+            limit,
+            nb_replies: AtomicU32::new(0),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> Option<BudgetType> {
+        self.limit
+    }
+
+    /// Counts a reply and returns whether it is still within the budget.
+    pub(crate) fn count_reply(&self) -> bool {
+        match self.limit {
+            Some(limit) => self.nb_replies.fetch_add(1, Ordering::Relaxed) < limit.get(),
+            None => true,
+        }
+    }
 }
 
 #[derive(PartialEq, Clone)]
@@ -188,12 +220,30 @@ pub(crate) fn declare_interest(
     expr: Option<&WireExpr>,
     mode: InterestMode,
     options: InterestOptions,
+    budget: Option<BudgetType>,
     send_declare: &mut SendDeclare,
 ) {
     if options.keyexprs() && mode != InterestMode::Current {
         register_expr_interest(tables_ref, face, id, expr);
     }
 
+    let face_id = face.id;
+    let budget = Arc::new(InterestBudget::new(budget));
+    let send_declare = &mut |p: &_, m: RoutingContext<Declare>| {
+        if m.msg.interest_id == Some(id)
+            && matches!(m.msg.body, DeclareBody::DeclareToken(_))
+            && !budget.count_reply()
+        {
+            tracing::trace!(
+                "Drop token for interest {}:{}: budget exhausted",
+                face_id,
+                id
+            );
+            return;
+        }
+        send_declare(p, m)
+    };
+
     if let Some(expr) = expr {
         let rtables = zread!(tables_ref.tables);
         match rtables
@@ -240,6 +290,7 @@ pub(crate) fn declare_interest(
                     Some(&mut res),
                     mode,
                     options,
+                    &budget,
                     send_declare,
                 );
             }
@@ -260,6 +311,7 @@ pub(crate) fn declare_interest(
             None,
             mode,
             options,
+            &budget,
             send_declare,
         );
     }
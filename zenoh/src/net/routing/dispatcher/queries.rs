@@ -13,11 +13,15 @@
 //
 use std::{
     collections::HashMap,
-    sync::{Arc, Weak},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Weak,
+    },
     time::Duration,
 };
 
 use async_trait::async_trait;
+use rand::seq::SliceRandom;
 use tokio_util::sync::CancellationToken;
 use zenoh_buffers::ZBuf;
 #[cfg(feature = "stats")]
@@ -27,8 +31,9 @@ use zenoh_protocol::{
     network::{
         declare::{ext, queryable::ext::QueryableInfoType, QueryableId},
         request::{
-            ext::{BudgetType, QueryTarget, TimeoutType},
-            Request, RequestId,
+            self,
+            ext::{BudgetType, CreditsType, QueryTarget, TimeoutType},
+            Request, RequestCancel, RequestCredit, RequestId,
         },
         response::{self, ext::ResponderIdType, Response, ResponseFinal},
     },
@@ -39,7 +44,7 @@ use zenoh_util::Timed;
 
 use super::{
     face::FaceState,
-    resource::{QueryRoute, QueryRoutes, QueryTargetQablSet, Resource},
+    resource::{QueryRoute, QueryRoutes, QueryTargetQabl, QueryTargetQablSet, Resource},
     tables::{NodeId, RoutingExpr, Tables, TablesLock},
 };
 #[cfg(feature = "unstable")]
@@ -49,6 +54,27 @@ use crate::net::routing::hat::{HatTrait, SendDeclare};
 pub(crate) struct Query {
     src_face: Arc<FaceState>,
     src_qid: RequestId,
+    ext_qos: ext::QoSType,
+    budget: Option<BudgetType>,
+    nb_replies: AtomicU32,
+}
+
+/// A query pending on a face, with the cancellation token of its cleanup task.
+type PendingQuery = (Arc<Query>, CancellationToken);
+
+impl Query {
+    /// Counts a reply to this query, returning whether it should be routed and whether it
+    /// exhausts the budget of the query.
+    #[inline]
+    fn count_reply(&self) -> (bool, bool) {
+        match self.budget {
+            Some(budget) => {
+                let n = self.nb_replies.fetch_add(1, Ordering::Relaxed) + 1;
+                (n <= budget.get(), n == budget.get())
+            }
+            None => (true, false),
+        }
+    }
 }
 
 #[zenoh_macros::unstable]
@@ -267,9 +293,15 @@ pub(crate) fn update_matches_query_routes(tables: &Tables, res: &Arc<Resource>)
 
 #[inline]
 fn insert_pending_query(outface: &mut Arc<FaceState>, query: Arc<Query>) -> RequestId {
+    let qid = outface.next_qid + 1;
+    let mut src_face = query.src_face.clone();
+    get_mut_unchecked(&mut src_face)
+        .propagated_queries
+        .entry(query.src_qid)
+        .or_default()
+        .push((Arc::downgrade(outface), qid));
     let outface_mut = get_mut_unchecked(outface);
-    outface_mut.next_qid += 1;
-    let qid = outface_mut.next_qid;
+    outface_mut.next_qid = qid;
     outface_mut.pending_queries.insert(
         qid,
         (query, outface_mut.task_controller.get_cancellation_token()),
@@ -277,9 +309,135 @@ fn insert_pending_query(outface: &mut Arc<FaceState>, query: Arc<Query>) -> Requ
     qid
 }
 
+/// Removes the pending query `outqid` of `outface`, and its entry in the propagated queries of
+/// its source face. Must be called with the queries lock held.
+fn remove_pending_query(outface: &mut Arc<FaceState>, outqid: RequestId) -> Option<PendingQuery> {
+    let query = get_mut_unchecked(outface).pending_queries.remove(&outqid)?;
+    forget_propagated_query(outface, outqid, &query.0);
+    Some(query)
+}
+
+fn forget_propagated_query(outface: &Arc<FaceState>, outqid: RequestId, query: &Query) {
+    let mut src_face = query.src_face.clone();
+    let propagated_queries = &mut get_mut_unchecked(&mut src_face).propagated_queries;
+    if let Some(outqids) = propagated_queries.get_mut(&query.src_qid) {
+        outqids.retain(|(face, qid)| !(*qid == outqid && face.as_ptr() == Arc::as_ptr(outface)));
+        if outqids.is_empty() {
+            propagated_queries.remove(&query.src_qid);
+        }
+    }
+}
+
+/// Returns the faces and request ids the query `qid` received from `face` was propagated to.
+/// Must be called with the queries lock held.
+fn get_propagated_queries(face: &FaceState, qid: RequestId) -> Vec<(Arc<FaceState>, RequestId)> {
+    face.propagated_queries
+        .get(&qid)
+        .map(|outqids| {
+            outqids
+                .iter()
+                .filter_map(|(outface, outqid)| outface.upgrade().map(|outface| (outface, *outqid)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the queryables a load-balanced query received from `src_face` may be routed to.
+///
+/// The query routes computed by the HATs are cached per resource and shared by all the queries
+/// matching it, so the per-query choice among these candidates cannot be made in
+/// `compute_query_route`: the HATs provide the candidates, sorted by distance, and the choice
+/// is made here when computing the final route of each query.
+fn load_balanced_candidates<'a>(
+    tables: &Tables,
+    qabls: &'a QueryTargetQablSet,
+    src_face: &Arc<FaceState>,
+    expr: &mut RoutingExpr,
+) -> Vec<&'a QueryTargetQabl> {
+    // Queryables reached through the same face are a single candidate: the next hop picks
+    // among them in turn.
+    let mut candidates: Vec<&QueryTargetQabl> = vec![];
+    for qabl in qabls.iter() {
+        if qabl.direction.0.id != src_face.id
+            && !candidates
+                .iter()
+                .any(|c| c.direction.0.id == qabl.direction.0.id)
+            && tables
+                .hat_code
+                .egress_filter(tables, src_face, &qabl.direction.0, expr)
+        {
+            candidates.push(qabl);
+        }
+    }
+    // As for BestMatching, complete queryables are preferred when there are some
+    let complete = |qabl: &&QueryTargetQabl| qabl.info.is_some_and(|info| info.complete);
+    if candidates.iter().any(complete) {
+        candidates.retain(complete);
+    }
+    candidates
+}
+
+/// Picks the single queryable a load-balanced query is routed to.
+fn select_load_balanced_qabl<'a>(
+    tables: &Tables,
+    res: &Option<Arc<Resource>>,
+    qabls: &'a QueryTargetQablSet,
+    src_face: &Arc<FaceState>,
+    expr: &mut RoutingExpr,
+    target: &QueryTarget,
+) -> Option<&'a QueryTargetQabl> {
+    let candidates = load_balanced_candidates(tables, qabls, src_face, expr);
+    if candidates.is_empty() {
+        return None;
+    }
+    match target {
+        QueryTarget::RoundRobin => {
+            // The key expression of the resource, when there is one, avoids building it
+            let turn = match res {
+                Some(res) => tables.queries_round_robin.next(res.expr()),
+                None => tables.queries_round_robin.next(expr.full_expr()),
+            };
+            Some(candidates[turn % candidates.len()])
+        }
+        QueryTarget::Random => candidates.choose(&mut rand::thread_rng()).copied(),
+        // Candidates are sorted by distance, so the nearest wins ties
+        _ => candidates
+            .into_iter()
+            .min_by_key(|qabl| qabl.direction.0.pending_queries.len()),
+    }
+}
+
+/// Returns the number of candidates a load-balanced query on `expr` received from `face`
+/// may be routed to, and whether they are complete queryables.
+pub(crate) fn get_load_balanced_candidates(
+    tables_ref: &TablesLock,
+    face: &Arc<FaceState>,
+    expr: &WireExpr,
+) -> (usize, bool) {
+    let rtables = zread!(tables_ref.tables);
+    let Some(prefix) = rtables
+        .get_mapping(face, &expr.scope, expr.mapping)
+        .cloned()
+    else {
+        return (0, false);
+    };
+    let mut expr = RoutingExpr::new(&prefix, expr.suffix.as_ref());
+    if !rtables.hat_code.ingress_filter(&rtables, face, &mut expr) {
+        return (0, false);
+    }
+    let res = Resource::get_resource(&prefix, expr.suffix);
+    let route = get_query_route(&rtables, face, &res, &mut expr, NodeId::default());
+    let candidates = load_balanced_candidates(&rtables, &route, face, &mut expr);
+    let complete = candidates
+        .iter()
+        .any(|qabl| qabl.info.is_some_and(|info| info.complete));
+    (candidates.len(), complete)
+}
+
 #[inline]
 fn compute_final_route(
     tables: &Tables,
+    res: &Option<Arc<Resource>>,
     qabls: &Arc<QueryTargetQablSet>,
     src_face: &Arc<FaceState>,
     expr: &mut RoutingExpr,
@@ -332,9 +490,20 @@ fn compute_final_route(
 
                 route
             } else {
-                compute_final_route(tables, qabls, src_face, expr, &QueryTarget::All, query)
+                compute_final_route(tables, res, qabls, src_face, expr, &QueryTarget::All, query)
             }
         }
+        QueryTarget::RoundRobin | QueryTarget::Random | QueryTarget::LeastOutstanding => {
+            let mut route = HashMap::new();
+            if let Some(qabl) =
+                select_load_balanced_qabl(tables, res, qabls, src_face, expr, target)
+            {
+                let mut direction = qabl.direction.clone();
+                let qid = insert_pending_query(&mut direction.0, query);
+                route.insert(direction.0.id, (direction, qid));
+            }
+            route
+        }
     }
 }
 
@@ -398,10 +567,7 @@ impl Timed for QueryCleanup {
                 }),
             );
             let queries_lock = zwrite!(self.tables.queries_lock);
-            if let Some(query) = get_mut_unchecked(&mut face)
-                .pending_queries
-                .remove(&self.qid)
-            {
+            if let Some(query) = remove_pending_query(&mut face, self.qid) {
                 drop(queries_lock);
                 tracing::warn!(
                     "Didn't receive final reply {}:{} for {}:{}: Timeout({:#?})!",
@@ -528,6 +694,7 @@ pub fn route_query(
     ext_target: QueryTarget,
     ext_budget: Option<BudgetType>,
     ext_timeout: Option<TimeoutType>,
+    ext_credits: Option<CreditsType>,
     body: RequestBody,
     routing_context: NodeId,
 ) {
@@ -561,11 +728,21 @@ pub fn route_query(
                 let query = Arc::new(Query {
                     src_face: face.clone(),
                     src_qid: qid,
+                    ext_qos,
+                    budget: ext_budget,
+                    nb_replies: AtomicU32::new(0),
                 });
 
                 let queries_lock = zwrite!(tables_ref.queries_lock);
-                let route =
-                    compute_final_route(&rtables, &route, face, &mut expr, &ext_target, query);
+                let route = compute_final_route(
+                    &rtables,
+                    &res,
+                    &route,
+                    face,
+                    &mut expr,
+                    &ext_target,
+                    query,
+                );
                 let timeout = ext_timeout.unwrap_or(rtables.queries_default_timeout);
                 drop(queries_lock);
                 drop(rtables);
@@ -609,6 +786,7 @@ pub fn route_query(
                             ext_target,
                             ext_budget,
                             ext_timeout,
+                            ext_credits,
                             payload: body.clone(),
                         });
                     }
@@ -662,8 +840,21 @@ pub(crate) fn route_send_response(
 
     match face.pending_queries.get(&qid) {
         Some((query, _)) => {
+            let query = query.clone();
             drop(queries_lock);
 
+            let (route, exhausted) = query.count_reply();
+            if !route {
+                tracing::trace!(
+                    "Drop reply {}:{} for {}:{}: budget exhausted",
+                    face,
+                    qid,
+                    query.src_face,
+                    query.src_qid
+                );
+                return;
+            }
+
             #[cfg(feature = "stats")]
             if !admin {
                 inc_res_stats!(query.src_face, tx, user, body)
@@ -679,8 +870,13 @@ pub(crate) fn route_send_response(
                 ext_tstamp,
                 ext_respid,
             });
+
+            if exhausted {
+                finalize_exhausted_query(tables_ref, query);
+            }
         }
-        None => tracing::warn!(
+        // Replies may still arrive after a query was cancelled or exhausted its budget
+        None => tracing::debug!(
             "Route reply {}:{} from {}: Query not found!",
             face,
             qid,
@@ -695,7 +891,7 @@ pub(crate) fn route_send_response_final(
     qid: RequestId,
 ) {
     let queries_lock = zwrite!(tables_ref.queries_lock);
-    match get_mut_unchecked(face).pending_queries.remove(&qid) {
+    match remove_pending_query(face, qid) {
         Some(query) => {
             drop(queries_lock);
             tracing::debug!(
@@ -707,19 +903,125 @@ pub(crate) fn route_send_response_final(
             );
             finalize_pending_query(query);
         }
-        None => tracing::warn!("Route final reply {}:{}: Query not found!", face, qid,),
+        None => tracing::debug!("Route final reply {}:{}: Query not found!", face, qid,),
+    }
+}
+
+/// Removes the pending queries the query `qid` received from `face` was propagated to.
+fn remove_propagated_queries(
+    tables_ref: &TablesLock,
+    face: &FaceState,
+    qid: RequestId,
+) -> Vec<(Arc<FaceState>, RequestId, PendingQuery)> {
+    let queries_lock = zwrite!(tables_ref.queries_lock);
+    let mut removed = vec![];
+    for (mut outface, outqid) in get_propagated_queries(face, qid) {
+        if let Some(query) = remove_pending_query(&mut outface, outqid) {
+            removed.push((outface, outqid, query));
+        }
+    }
+    drop(queries_lock);
+    removed
+}
+
+pub(crate) fn route_cancel_query(
+    tables_ref: &Arc<TablesLock>,
+    face: &Arc<FaceState>,
+    qid: RequestId,
+    ext_qos: ext::QoSType,
+) {
+    let cancelled = remove_propagated_queries(tables_ref, face, qid);
+    if cancelled.is_empty() {
+        tracing::debug!("Route cancel query {}:{}: Query not found!", face, qid);
+    }
+    for (outface, outqid, (_, cancellation_token)) in cancelled {
+        // The querier does not expect a final reply for a cancelled query
+        cancellation_token.cancel();
+        tracing::trace!(
+            "Propagate cancel query {}:{} to {}:{}",
+            face,
+            qid,
+            outface,
+            outqid
+        );
+        outface.primitives.send_request_cancel(RequestCancel {
+            id: outqid,
+            ext_qos,
+            ext_tstamp: None,
+        });
+    }
+}
+
+pub(crate) fn route_query_credit(
+    tables_ref: &Arc<TablesLock>,
+    face: &Arc<FaceState>,
+    qid: RequestId,
+    credits: u32,
+    ext_qos: ext::QoSType,
+    ext_respid: Option<request::ext::ResponderIdType>,
+) {
+    let queries_lock = zread!(tables_ref.queries_lock);
+    // Credits are granted by the querier to a given responder, which only the responder applies,
+    // so they are forwarded as is to every face the query was propagated to.
+    let outfaces = get_propagated_queries(face, qid);
+    drop(queries_lock);
+    if outfaces.is_empty() {
+        tracing::debug!("Route query credit {}:{}: Query not found!", face, qid);
+    }
+    for (outface, outqid) in outfaces {
+        tracing::trace!(
+            "Propagate {} credits for query {}:{} to {}:{}",
+            credits,
+            face,
+            qid,
+            outface,
+            outqid
+        );
+        outface.primitives.send_request_credit(RequestCredit {
+            id: outqid,
+            credits,
+            ext_qos,
+            ext_tstamp: None,
+            ext_respid: ext_respid.clone(),
+        });
+    }
+}
+
+/// Cancels a query which has received as many replies as its budget, and sends its final reply.
+fn finalize_exhausted_query(tables_ref: &TablesLock, query: Arc<Query>) {
+    tracing::debug!(
+        "Budget exhausted for query {}:{}",
+        query.src_face,
+        query.src_qid
+    );
+    let ext_qos = query.ext_qos;
+    let exhausted = remove_propagated_queries(tables_ref, &query.src_face, query.src_qid);
+    // The final reply is sent once the last reference to the query is dropped
+    drop(query);
+    for (outface, outqid, pending_query) in exhausted {
+        outface.primitives.send_request_cancel(RequestCancel {
+            id: outqid,
+            ext_qos,
+            ext_tstamp: None,
+        });
+        finalize_pending_query(pending_query);
     }
 }
 
 pub(crate) fn finalize_pending_queries(tables_ref: &TablesLock, face: &mut Arc<FaceState>) {
     let queries_lock = zwrite!(tables_ref.queries_lock);
-    for (_, query) in get_mut_unchecked(face).pending_queries.drain() {
+    let queries = get_mut_unchecked(face)
+        .pending_queries
+        .drain()
+        .collect::<Vec<_>>();
+    for (outqid, query) in queries {
+        forget_propagated_query(face, outqid, &query.0);
         finalize_pending_query(query);
     }
     drop(queries_lock);
 }
 
-pub(crate) fn finalize_pending_query(query: (Arc<Query>, CancellationToken)) {
+pub(crate) fn finalize_pending_query(query: PendingQuery) {
     let (query, cancellation_token) = query;
     cancellation_token.cancel();
     if let Some(query) = Arc::into_inner(query) {
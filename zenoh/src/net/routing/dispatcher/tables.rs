@@ -32,7 +32,8 @@ pub use super::{pubsub::*, queries::*, resource::*};
 use crate::net::{
     routing::{
         hat::{self, HatTrait},
-        interceptor::{interceptor_factories, InterceptorFactory},
+        interceptor::{audit::AuditLog, interceptor_factories, AccessControl, InterceptorFactory},
+        RoundRobinTurns,
     },
     runtime::WeakRuntime,
 };
@@ -77,6 +78,11 @@ pub struct Tables {
     pub(crate) mcast_groups: Vec<Arc<FaceState>>,
     pub(crate) mcast_faces: Vec<Arc<FaceState>>,
     pub(crate) interceptors: Vec<InterceptorFactory>,
+    pub(crate) audit: Option<Arc<AuditLog>>,
+    pub(crate) access_control: Option<Arc<AccessControl>>,
+    /// The turns of the next [`QueryTarget::RoundRobin`](zenoh_protocol::network::request::ext::QueryTarget::RoundRobin)
+    /// queries routed by these tables, by key expression.
+    pub(crate) queries_round_robin: RoundRobinTurns,
     pub(crate) hat: Box<dyn Any + Send + Sync>,
     pub(crate) hat_code: Arc<dyn HatTrait + Send + Sync>, // @TODO make this a Box
 }
@@ -97,6 +103,8 @@ impl Tables {
         let interests_timeout =
             Duration::from_millis(unwrap_or_default!(config.routing().interests().timeout()));
         let hat_code = hat::new_hat(whatami, config);
+        let audit = AuditLog::new(zid, whatami, config.audit());
+        let access_control = AccessControl::new(config.access_control())?;
         Ok(Tables {
             zid,
             whatami,
@@ -110,7 +118,10 @@ impl Tables {
             faces: HashMap::new(),
             mcast_groups: vec![],
             mcast_faces: vec![],
-            interceptors: interceptor_factories(config)?,
+            interceptors: interceptor_factories(config, audit.as_ref(), access_control.as_ref())?,
+            audit,
+            access_control,
+            queries_round_robin: RoundRobinTurns::default(),
             hat: hat_code.new_tables(router_peers_failover_brokering),
             hat_code: hat_code.into(),
         })
@@ -27,7 +27,7 @@ use super::{face_hat, face_hat_mut, token::declare_token_interest, HatCode, HatF
 use crate::net::routing::{
     dispatcher::{
         face::{FaceState, InterestState},
-        interests::{CurrentInterest, CurrentInterestCleanup, RemoteInterest},
+        interests::{CurrentInterest, CurrentInterestCleanup, InterestBudget, RemoteInterest},
         resource::Resource,
         tables::{Tables, TablesLock},
     },
@@ -65,6 +65,7 @@ pub(super) fn interests_new_face(tables: &mut Tables, face: &mut Arc<FaceState>)
                         ext_qos: ext::QoSType::DECLARE,
                         ext_tstamp: None,
                         ext_nodeid: ext::NodeIdType::DEFAULT,
+                        ext_budget: None,
                     },
                     res.as_ref()
                         .map(|res| res.expr().to_string())
@@ -85,6 +86,7 @@ impl HatInterestTrait for HatCode {
         res: Option<&mut Arc<Resource>>,
         mode: InterestMode,
         options: InterestOptions,
+        budget: &Arc<InterestBudget>,
         send_declare: &mut SendDeclare,
     ) {
         if options.tokens() {
@@ -111,6 +113,7 @@ impl HatInterestTrait for HatCode {
             src_face: face.clone(),
             src_interest_id: id,
             mode,
+            budget: budget.clone(),
         });
 
         for dst_face in tables
@@ -152,6 +155,7 @@ impl HatInterestTrait for HatCode {
                     ext_qos: ext::QoSType::DECLARE,
                     ext_tstamp: None,
                     ext_nodeid: ext::NodeIdType::DEFAULT,
+                    ext_budget: budget.limit(),
                 },
                 res.as_ref()
                     .map(|res| res.expr().to_string())
@@ -228,6 +232,7 @@ impl HatInterestTrait for HatCode {
                                     ext_qos: ext::QoSType::DECLARE,
                                     ext_tstamp: None,
                                     ext_nodeid: ext::NodeIdType::DEFAULT,
+                                    ext_budget: None,
                                 },
                                 local_interest
                                     .res
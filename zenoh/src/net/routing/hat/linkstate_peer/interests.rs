@@ -27,7 +27,7 @@ use super::{
 use crate::net::routing::{
     dispatcher::{
         face::FaceState,
-        interests::RemoteInterest,
+        interests::{InterestBudget, RemoteInterest},
         resource::Resource,
         tables::{Tables, TablesLock},
     },
@@ -45,6 +45,7 @@ impl HatInterestTrait for HatCode {
         res: Option<&mut Arc<Resource>>,
         mode: InterestMode,
         options: InterestOptions,
+        _budget: &Arc<InterestBudget>,
         send_declare: &mut SendDeclare,
     ) {
         if options.subscribers() {
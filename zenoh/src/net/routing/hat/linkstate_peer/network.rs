@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::convert::TryInto;
+use std::{collections::HashMap, convert::TryInto, num::NonZeroU16};
 
 use petgraph::{
     graph::NodeIndex,
@@ -24,6 +24,7 @@ use zenoh_buffers::{
     ZBuf,
 };
 use zenoh_codec::WCodec;
+use zenoh_config::TransportWeightConf;
 use zenoh_link::Locator;
 use zenoh_protocol::{
     common::ZExtBody,
@@ -39,11 +40,15 @@ use crate::net::{
     runtime::{Runtime, WeakRuntime},
 };
 
+/// The weight of the links without a configured weight.
+const DEFAULT_LINK_WEIGHT: u16 = 100;
+
 #[derive(Clone, Default)]
 struct Details {
     zid: bool,
     locators: bool,
     links: bool,
+    link_weights: bool,
 }
 
 #[derive(Clone)]
@@ -53,6 +58,9 @@ pub(super) struct Node {
     pub(super) locators: Option<Vec<Locator>>,
     pub(super) sn: u64,
     pub(super) links: Vec<ZenohIdProto>,
+    // The weights the node configured for its links, or None if it did not advertise any,
+    // i.e. if it may be unaware of link weights.
+    pub(super) link_weights: Option<HashMap<ZenohIdProto, NonZeroU16>>,
 }
 
 impl std::fmt::Debug for Node {
@@ -121,6 +129,8 @@ pub(super) struct Network {
     pub(super) gossip_multihop: bool,
     pub(super) gossip_target: WhatAmIMatcher,
     pub(super) autoconnect: WhatAmIMatcher,
+    pub(super) transport_weights: Vec<TransportWeightConf>,
+    pub(super) link_weights_enabled: bool,
     pub(super) idx: NodeIndex,
     pub(super) links: VecMap<Link>,
     pub(super) trees: Vec<Tree>,
@@ -141,6 +151,7 @@ impl Network {
         gossip_multihop: bool,
         gossip_target: WhatAmIMatcher,
         autoconnect: WhatAmIMatcher,
+        transport_weights: Vec<TransportWeightConf>,
     ) -> Self {
         let mut graph = petgraph::stable_graph::StableGraph::default();
         tracing::debug!("{} Add node (self) {}", name, zid);
@@ -150,6 +161,7 @@ impl Network {
             locators: None,
             sn: 1,
             links: vec![],
+            link_weights: Some(HashMap::new()),
         });
         Network {
             name,
@@ -159,6 +171,8 @@ impl Network {
             gossip_multihop,
             gossip_target,
             autoconnect,
+            transport_weights,
+            link_weights_enabled: true,
             idx,
             links: VecMap::new(),
             trees: vec![Tree {
@@ -229,13 +243,19 @@ impl Network {
     }
 
     fn make_link_state(&self, idx: NodeIndex, details: &Details) -> LinkState {
-        let links = if details.links {
+        let (links, link_weights): (Vec<u64>, Vec<u16>) = if details.links {
             self.graph[idx]
                 .links
                 .iter()
                 .filter_map(|zid| {
                     if let Some(idx2) = self.get_idx(zid) {
-                        Some(idx2.index().try_into().unwrap())
+                        let psid: u64 = idx2.index().try_into().unwrap();
+                        let weight = self.graph[idx]
+                            .link_weights
+                            .as_ref()
+                            .and_then(|weights| weights.get(zid))
+                            .map_or(0, |w| w.get());
+                        Some((psid, weight))
                     } else {
                         tracing::error!(
                             "{} Internal error building link state: cannot get index of {}",
@@ -245,10 +265,14 @@ impl Network {
                         None
                     }
                 })
-                .collect()
+                .unzip()
         } else {
-            vec![]
+            (vec![], vec![])
         };
+        // Weights are sent even when none is configured, to advertise that the node is aware of
+        // them, but only for the nodes that advertised them
+        let link_weights = (details.link_weights && self.graph[idx].link_weights.is_some())
+            .then_some(link_weights);
         LinkState {
             psid: idx.index().try_into().unwrap(),
             sn: self.graph[idx].sn,
@@ -268,6 +292,7 @@ impl Network {
                 None
             },
             links,
+            link_weights,
         }
     }
 
@@ -291,6 +316,7 @@ impl Network {
     fn send_on_link(&self, mut idxs: Vec<(NodeIndex, Details)>, transport: &TransportUnicast) {
         for idx in &mut idxs {
             idx.1.locators = self.propagate_locators(idx.0, transport);
+            idx.1.link_weights = idx.1.links && Self::propagate_link_weights(transport);
         }
         if let Ok(msg) = self.make_msg(&idxs) {
             tracing::trace!("{} Send to {:?} {:?}", self.name, transport.get_zid(), msg);
@@ -309,6 +335,7 @@ impl Network {
         for link in self.links.values() {
             for idx in &mut idxs {
                 idx.1.locators = self.propagate_locators(idx.0, &link.transport);
+                idx.1.link_weights = idx.1.links && Self::propagate_link_weights(&link.transport);
             }
             if let Ok(msg) = self.make_msg(&idxs) {
                 if parameters(link) {
@@ -341,6 +368,41 @@ impl Network {
                 }))
     }
 
+    // Indicates if link weights can be included in Linkstate messages sent to the given
+    // transport, i.e. if the remote negotiated the capability to decode them.
+    fn propagate_link_weights(target: &TransportUnicast) -> bool {
+        target
+            .get_capabilities()
+            .is_ok_and(|capabilities| capabilities.has_link_weights())
+    }
+
+    // Returns the weight of the first configured transport weight matching the given transport.
+    fn transport_weight(&self, transport: &TransportUnicast) -> Option<NonZeroU16> {
+        let zid = transport.get_zid().ok()?;
+        let links = transport.get_links().unwrap_or_default();
+        self.transport_weights
+            .iter()
+            .find(|conf| {
+                conf.zids.as_ref().map_or(true, |zids| {
+                    zids.iter().any(|z| ZenohIdProto::from(*z) == zid)
+                }) && conf.interfaces.as_ref().map_or(true, |interfaces| {
+                    links.iter().any(|link| {
+                        link.interfaces
+                            .iter()
+                            .any(|interface| interfaces.contains(interface))
+                    })
+                }) && conf.endpoints.as_ref().map_or(true, |endpoints| {
+                    endpoints.iter().any(|endpoint| {
+                        let locator = endpoint.to_locator();
+                        links
+                            .iter()
+                            .any(|link| link.src == locator || link.dst == locator)
+                    })
+                })
+            })
+            .map(|conf| conf.weight)
+    }
+
     fn update_edge(&mut self, idx1: NodeIndex, idx2: NodeIndex) {
         use std::hash::Hasher;
         let mut hasher = std::collections::hash_map::DefaultHasher::default();
@@ -351,10 +413,52 @@ impl Network {
             hasher.write(&self.graph[idx1].zid.to_le_bytes());
             hasher.write(&self.graph[idx2].zid.to_le_bytes());
         }
-        let weight = 100.0 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
+        // When both ends configured a weight for the link, the highest one applies so that
+        // all nodes compute the same weight for this edge.
+        let weight = if self.link_weights_enabled {
+            let weight = |idx: NodeIndex, other: NodeIndex| {
+                self.graph[idx]
+                    .link_weights
+                    .as_ref()
+                    .and_then(|weights| weights.get(&self.graph[other].zid))
+            };
+            weight(idx1, idx2)
+                .max(weight(idx2, idx1))
+                .map_or(DEFAULT_LINK_WEIGHT, |w| w.get())
+        } else {
+            DEFAULT_LINK_WEIGHT
+        };
+        let weight = weight as f64 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
         self.graph.update_edge(idx1, idx2, weight);
     }
 
+    // Configured link weights only apply when all the nodes of the graph advertised them.
+    // Nodes unaware of them weigh all edges with the default weight, and drop them when
+    // relaying link states, so that nodes computing trees from different weights could route
+    // messages in loops or deliver them twice.
+    fn update_link_weights_enabled(&mut self) {
+        let enabled = self
+            .graph
+            .node_indices()
+            .all(|idx| self.graph[idx].link_weights.is_some());
+        if enabled != self.link_weights_enabled {
+            tracing::debug!(
+                "{} {} configured link weights",
+                self.name,
+                if enabled { "Enable" } else { "Disable" }
+            );
+            self.link_weights_enabled = enabled;
+            let edges = self
+                .graph
+                .edge_indices()
+                .filter_map(|edge| self.graph.edge_endpoints(edge))
+                .collect::<Vec<_>>();
+            for (idx1, idx2) in edges {
+                self.update_edge(idx1, idx2);
+            }
+        }
+    }
+
     pub(super) fn link_states(
         &mut self,
         link_states: Vec<LinkState>,
@@ -396,6 +500,7 @@ impl Network {
                         link_state.locators,
                         link_state.sn,
                         link_state.links,
+                        link_state.link_weights,
                     ))
                 } else {
                     match src_link.get_zid(&link_state.psid) {
@@ -405,6 +510,7 @@ impl Network {
                             link_state.locators,
                             link_state.sn,
                             link_state.links,
+                            link_state.link_weights,
                         )),
                         None => {
                             tracing::error!(
@@ -423,11 +529,23 @@ impl Network {
         let src_link = self.get_link_from_zid(&src).unwrap();
         let link_states = link_states
             .into_iter()
-            .map(|(zid, wai, locs, sn, links)| {
+            .map(|(zid, wai, locs, sn, links, link_weights)| {
+                // Nodes aware of link weights always send them, even when none is configured
+                let mut weights = link_weights.as_ref().map(|_| HashMap::new());
                 let links: Vec<ZenohIdProto> = links
                     .iter()
-                    .filter_map(|l| {
+                    .enumerate()
+                    .filter_map(|(i, l)| {
                         if let Some(zid) = src_link.get_zid(l) {
+                            if let Some(weight) = link_weights
+                                .as_ref()
+                                .and_then(|w| w.get(i))
+                                .and_then(|w| NonZeroU16::new(*w))
+                            {
+                                if let Some(weights) = weights.as_mut() {
+                                    weights.insert(*zid, weight);
+                                }
+                            }
                             Some(*zid)
                         } else {
                             tracing::error!(
@@ -440,7 +558,7 @@ impl Network {
                         }
                     })
                     .collect();
-                (zid, wai, locs, sn, links)
+                (zid, wai, locs, sn, links, weights)
             })
             .collect::<Vec<_>>();
 
@@ -464,7 +582,7 @@ impl Network {
                 updated_nodes: vec![],
                 removed_nodes: vec![],
             };
-            for (zid, whatami, locators, sn, links) in link_states.into_iter() {
+            for (zid, whatami, locators, sn, links, link_weights) in link_states.into_iter() {
                 let idx = match self.get_idx(&zid) {
                     None => {
                         let idx = self.add_node(Node {
@@ -473,6 +591,7 @@ impl Network {
                             locators: locators.clone(),
                             sn,
                             links,
+                            link_weights,
                         });
                         changes.updated_nodes.push((idx, self.graph[idx].clone()));
                         locators.is_some().then_some(idx)
@@ -484,6 +603,7 @@ impl Network {
                             .then(|| {
                                 node.sn = sn;
                                 node.links.clone_from(&links);
+                                node.link_weights = link_weights;
                                 changes.updated_nodes.push((idx, node.clone()));
                                 (node.locators != locators && locators.is_some()).then(|| {
                                     node.locators.clone_from(&locators);
@@ -540,14 +660,15 @@ impl Network {
         // Add nodes to graph & filter out up to date states
         let mut link_states = link_states
             .into_iter()
-            .filter_map(
-                |(zid, whatami, locators, sn, links)| match self.get_idx(&zid) {
+            .filter_map(|(zid, whatami, locators, sn, links, link_weights)| {
+                match self.get_idx(&zid) {
                     Some(idx) => {
                         let node = &mut self.graph[idx];
                         let oldsn = node.sn;
                         if oldsn < sn {
                             node.sn = sn;
                             node.links.clone_from(&links);
+                            node.link_weights = link_weights;
                             if locators.is_some() {
                                 node.locators = locators;
                             }
@@ -567,13 +688,14 @@ impl Network {
                             locators,
                             sn,
                             links: links.clone(),
+                            link_weights,
                         };
                         tracing::debug!("{} Add node (state) {}", self.name, zid);
                         let idx = self.add_node(node);
                         Some((links, idx, true))
                     }
-                },
-            )
+                }
+            })
             .collect::<Vec<(Vec<ZenohIdProto>, NodeIndex, bool)>>();
 
         // Add/remove edges from graph
@@ -597,6 +719,7 @@ impl Network {
                         locators: None,
                         sn: 0,
                         links: vec![],
+                        link_weights: None,
                     };
                     tracing::debug!("{} Add node (reintroduced) {}", self.name, link.clone());
                     let idx = self.add_node(node);
@@ -743,11 +866,19 @@ impl Network {
                             locators: None,
                             sn: 0,
                             links: vec![],
+                            link_weights: None,
                         }),
                         true,
                     )
                 }
             };
+            let weight = self.transport_weight(&transport);
+            if let Some(weights) = self.graph[self.idx].link_weights.as_mut() {
+                match weight {
+                    Some(weight) => weights.insert(zid, weight),
+                    None => weights.remove(&zid),
+                };
+            }
             if self.full_linkstate && self.graph[idx].links.contains(&self.graph[self.idx].zid) {
                 tracing::trace!("Update edge (link) {} {}", self.graph[self.idx].zid, zid);
                 self.update_edge(self.idx, idx);
@@ -834,6 +965,9 @@ impl Network {
         tracing::trace!("{} remove_link {}", self.name, zid);
         self.links.retain(|_, link| link.zid != *zid);
         self.graph[self.idx].links.retain(|link| *link != *zid);
+        if let Some(weights) = self.graph[self.idx].link_weights.as_mut() {
+            weights.remove(zid);
+        }
 
         if self.full_linkstate {
             if let Some((edge, _)) = self
@@ -910,6 +1044,8 @@ impl Network {
     }
 
     pub(super) fn compute_trees(&mut self) -> Vec<Vec<NodeIndex>> {
+        self.update_link_weights_enabled();
+
         let indexes = self.graph.node_indices().collect::<Vec<NodeIndex>>();
         let max_idx = indexes.iter().max().unwrap();
 
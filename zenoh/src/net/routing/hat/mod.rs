@@ -36,6 +36,7 @@ use {crate::key_expr::KeyExpr, std::collections::HashMap};
 use super::{
     dispatcher::{
         face::{Face, FaceState},
+        interests::InterestBudget,
         pubsub::SubscriberInfo,
         tables::{NodeId, QueryTargetQablSet, Resource, Route, RoutingExpr, Tables, TablesLock},
     },
@@ -151,6 +152,7 @@ pub(crate) trait HatInterestTrait {
         res: Option<&mut Arc<Resource>>,
         mode: InterestMode,
         options: InterestOptions,
+        budget: &Arc<InterestBudget>,
         send_declare: &mut SendDeclare,
     );
     fn undeclare_interest(&self, tables: &mut Tables, face: &mut Arc<FaceState>, id: InterestId);
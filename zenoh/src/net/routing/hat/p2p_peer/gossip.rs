@@ -213,6 +213,7 @@ impl Network {
                 None
             },
             links,
+            link_weights: None,
         }
     }
 
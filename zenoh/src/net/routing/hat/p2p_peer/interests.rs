@@ -31,7 +31,7 @@ use super::{
 use crate::net::routing::{
     dispatcher::{
         face::{FaceState, InterestState},
-        interests::{CurrentInterest, CurrentInterestCleanup, RemoteInterest},
+        interests::{CurrentInterest, CurrentInterestCleanup, InterestBudget, RemoteInterest},
         resource::Resource,
         tables::{Tables, TablesLock},
     },
@@ -72,6 +72,7 @@ pub(super) fn interests_new_face(tables: &mut Tables, face: &mut Arc<FaceState>)
                             ext_qos: ext::QoSType::DECLARE,
                             ext_tstamp: None,
                             ext_nodeid: ext::NodeIdType::DEFAULT,
+                            ext_budget: None,
                         },
                         res.as_ref()
                             .map(|res| res.expr().to_string())
@@ -93,6 +94,7 @@ impl HatInterestTrait for HatCode {
         res: Option<&mut Arc<Resource>>,
         mode: InterestMode,
         options: InterestOptions,
+        budget: &Arc<InterestBudget>,
         send_declare: &mut SendDeclare,
     ) {
         if options.subscribers() {
@@ -141,6 +143,7 @@ impl HatInterestTrait for HatCode {
             src_face: face.clone(),
             src_interest_id: id,
             mode,
+            budget: budget.clone(),
         });
 
         if face.whatami == WhatAmI::Client {
@@ -190,6 +193,7 @@ impl HatInterestTrait for HatCode {
                         ext_qos: ext::QoSType::DECLARE,
                         ext_tstamp: None,
                         ext_nodeid: ext::NodeIdType::DEFAULT,
+                        ext_budget: budget.limit(),
                     },
                     res.as_ref()
                         .map(|res| res.expr().to_string())
@@ -254,6 +258,7 @@ impl HatInterestTrait for HatCode {
                                     ext_qos: ext::QoSType::DECLARE,
                                     ext_tstamp: None,
                                     ext_nodeid: ext::NodeIdType::DEFAULT,
+                                    ext_budget: None,
                                 },
                                 local_interest
                                     .res
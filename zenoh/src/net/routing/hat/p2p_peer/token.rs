@@ -197,6 +197,16 @@ fn declare_simple_token(
             if interest.mode == InterestMode::CurrentFuture {
                 register_simple_token(tables, &mut face.clone(), id, res);
             }
+            if !interest.budget.count_reply() {
+                tracing::trace!(
+                    "Drop DeclareToken for {} from {}: budget of interest {}:{} exhausted",
+                    res.expr(),
+                    face,
+                    interest.src_face,
+                    interest.src_interest_id,
+                );
+                return;
+            }
             let id = make_token_id(res, &mut interest.src_face.clone(), interest.mode);
             let wire_expr = Resource::get_best_key(res, "", interest.src_face.id);
             send_declare(
@@ -30,7 +30,7 @@ use super::{
 use crate::net::routing::{
     dispatcher::{
         face::FaceState,
-        interests::RemoteInterest,
+        interests::{InterestBudget, RemoteInterest},
         resource::Resource,
         tables::{Tables, TablesLock},
     },
@@ -48,6 +48,7 @@ impl HatInterestTrait for HatCode {
         res: Option<&mut Arc<Resource>>,
         mode: InterestMode,
         mut options: InterestOptions,
+        _budget: &Arc<InterestBudget>,
         send_declare: &mut SendDeclare,
     ) {
         if options.aggregate() && face.whatami == WhatAmI::Peer {
@@ -327,6 +327,8 @@ impl HatBaseTrait for HatCode {
             unwrap_or_default!(config.routing().peer().mode()) == *"linkstate";
         let router_peers_failover_brokering =
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
+        let router_transport_weights = config.routing().router().transport_weights().clone();
+        let peer_transport_weights = config.routing().peer().transport_weights().clone();
         drop(config_guard);
 
         if router_full_linkstate | gossip {
@@ -340,6 +342,7 @@ impl HatBaseTrait for HatCode {
                 gossip_multihop,
                 gossip_target,
                 autoconnect,
+                router_transport_weights,
             ));
         }
         if peer_full_linkstate | gossip {
@@ -353,6 +356,7 @@ impl HatBaseTrait for HatCode {
                 gossip_multihop,
                 gossip_target,
                 autoconnect,
+                peer_transport_weights,
             ));
         }
         if router_full_linkstate && peer_full_linkstate {
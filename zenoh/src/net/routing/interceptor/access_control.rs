@@ -18,12 +18,14 @@
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
 
-use std::{any::Any, collections::HashSet, iter, sync::Arc};
-
-use itertools::Itertools;
-use zenoh_config::{
-    AclConfig, AclMessage, CertCommonName, InterceptorFlow, Interface, Permission, Username,
+use std::{
+    any::Any,
+    collections::HashSet,
+    sync::{Arc, Mutex, RwLock, Weak},
 };
+
+use zenoh_config::{AclConfig, AclMessage, InterceptorFlow, Permission};
+use zenoh_core::{zlock, zread, zwrite};
 use zenoh_protocol::{
     core::ZenohIdProto,
     network::{
@@ -33,21 +35,141 @@ use zenoh_protocol::{
     zenoh::{PushBody, RequestBody},
 };
 use zenoh_result::ZResult;
-use zenoh_transport::{
-    multicast::TransportMulticast,
-    unicast::{authentication::AuthId, TransportUnicast},
-};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
 
 use super::{
-    authorization::PolicyEnforcer, EgressInterceptor, IngressInterceptor, InterceptorFactory,
-    InterceptorFactoryTrait, InterceptorTrait,
+    audit::{AuditLog, AuditPeer},
+    authorization::{PolicyDecision, PolicyEnforcer, PolicyMap},
+    EgressInterceptor, IngressInterceptor, InterceptorFactory, InterceptorFactoryTrait,
+    InterceptorTrait,
 };
 use crate::{
     api::key_expr::KeyExpr,
     net::routing::{interceptor::authorization::SubjectQuery, RoutingContext},
 };
-pub struct AclEnforcer {
+
+/// The access control policy enforced on the unicast transports.
+///
+/// The policy can be replaced at runtime with [`AccessControl::reload`], in which case the
+/// subjects of the existing transports are re-evaluated against the new policy.
+pub(crate) struct AccessControl {
+    inner: Mutex<AccessControlInner>,
+}
+
+struct AccessControlInner {
     enforcer: Arc<PolicyEnforcer>,
+    transports: Vec<Weak<AclTransport>>,
+}
+
+impl AccessControl {
+    /// Returns the access control policy of the given configuration, if enabled.
+    pub(crate) fn new(acl_config: &AclConfig) -> ZResult<Option<Arc<Self>>> {
+        if !acl_config.enabled {
+            tracing::debug!("Access control is disabled");
+            return Ok(None);
+        }
+        let enforcer = Self::validate(acl_config)
+            .map_err(|e| zerror!("Access control not enabled due to: {}", e))?;
+        tracing::debug!("Access control is enabled");
+        Ok(Some(Arc::new(Self {
+            inner: Mutex::new(AccessControlInner {
+                enforcer: Arc::new(enforcer),
+                transports: vec![],
+            }),
+        })))
+    }
+
+    /// Checks that the given configuration is a valid access control policy.
+    pub(crate) fn validate(acl_config: &AclConfig) -> ZResult<PolicyEnforcer> {
+        let mut policy_enforcer = PolicyEnforcer::new();
+        policy_enforcer.init(acl_config)?;
+        Ok(policy_enforcer)
+    }
+
+    /// Atomically replaces the enforced policy with the one of the given configuration.
+    ///
+    /// The subjects of the existing transports are re-evaluated against the new policy.
+    /// Returns the transports that are denied any message by the new policy if
+    /// `close_denied_transports` is configured, for them to be closed.
+    pub(crate) fn reload(&self, acl_config: &AclConfig) -> ZResult<Vec<TransportUnicast>> {
+        let enforcer = Arc::new(Self::validate(acl_config)?);
+
+        let mut inner = zlock!(self.inner);
+        inner.enforcer = enforcer.clone();
+        inner
+            .transports
+            .retain(|transport| transport.strong_count() > 0);
+        let mut denied = vec![];
+        for transport in inner.transports.iter().filter_map(Weak::upgrade) {
+            let state = AclTransportState::new(&enforcer, &transport.subject_queries);
+            if acl_config.close_denied_transports && state.denies_all() {
+                tracing::info!(
+                    "{} is denied any message by the reloaded access control policy, closing its transport",
+                    transport.zid
+                );
+                denied.push(transport.transport.clone());
+            }
+            *zwrite!(transport.state) = state;
+        }
+        tracing::info!("Access control policy has been reloaded");
+        Ok(denied)
+    }
+
+    /// Returns whether a message sent on behalf of a user not connected through a transport,
+    /// e.g. an HTTP client of a plugin, is allowed by the enforced policy.
+    ///
+    /// The message is checked as if it was received (ingress) on a transport authenticated with
+    /// the given username from the given address. Puts, deletes and queries on the admin space
+    /// are also checked against the admin space actions.
+    #[cfg(feature = "internal")]
+    pub(crate) fn authorize(
+        &self,
+        username: Option<&str>,
+        remote_ip: Option<std::net::IpAddr>,
+        message: AclMessage,
+        key_expr: &str,
+    ) -> bool {
+        let enforcer = zlock!(self.inner).enforcer.clone();
+        if !enforcer.interface_enabled.ingress {
+            return true;
+        }
+        let query = SubjectQuery {
+            interface: None,
+            cert_common_name: None,
+            username: username.map(|username| zenoh_config::Username(username.to_owned())),
+            zid: None,
+            pubkey_fingerprint: None,
+            link_protocol: None,
+            remote_ip,
+        };
+        let state = AclTransportState::new(&enforcer, &[query]);
+        let admin_space_message = match message {
+            AclMessage::Put | AclMessage::Delete => Some(AclMessage::AdminSpaceWrite),
+            AclMessage::Query => Some(AclMessage::AdminSpaceRead),
+            _ => None,
+        }
+        .filter(|_| key_expr.starts_with("@/"));
+        let permission = |message| state.permission(InterceptorFlow::Ingress, message, key_expr);
+        let allowed = permission(message) == Permission::Allow
+            && admin_space_message.map_or(true, |message| permission(message) == Permission::Allow);
+        tracing::debug!(
+            "User {:?} is {} to {:?} on {}",
+            username,
+            if allowed {
+                "authorized"
+            } else {
+                "unauthorized"
+            },
+            message,
+            key_expr
+        );
+        allowed
+    }
+}
+
+pub struct AclEnforcer {
+    access_control: Arc<AccessControl>,
+    audit: Option<Arc<AuditLog>>,
 }
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AuthSubject {
@@ -55,39 +177,115 @@ pub struct AuthSubject {
     name: String,
 }
 
-struct EgressAclEnforcer {
-    policy_enforcer: Arc<PolicyEnforcer>,
-    subject: Vec<AuthSubject>,
+/// The audit log ACL decisions are recorded to, along with the remote they relate to.
+#[derive(Clone)]
+pub(crate) struct AclAudit {
+    log: Arc<AuditLog>,
+    peer: Arc<AuditPeer>,
+}
+
+/// The ACL state of a transport, shared by its ingress and egress interceptors.
+struct AclTransport {
+    transport: TransportUnicast,
     zid: ZenohIdProto,
+    subject_queries: Vec<SubjectQuery>,
+    state: RwLock<AclTransportState>,
 }
 
-struct IngressAclEnforcer {
+/// The policy enforced on a transport, along with the subjects of the transport in this policy.
+#[derive(Clone)]
+pub(crate) struct AclTransportState {
     policy_enforcer: Arc<PolicyEnforcer>,
     subject: Vec<AuthSubject>,
-    zid: ZenohIdProto,
+    /// The rules of the subjects whose key expression templates are expanded for this transport
+    templated: Arc<PolicyMap>,
+}
+
+impl AclTransportState {
+    fn new(policy_enforcer: &Arc<PolicyEnforcer>, subject_queries: &[SubjectQuery]) -> Self {
+        let mut auth_subjects = HashSet::new();
+        let mut templated = PolicyMap::default();
+
+        for query in subject_queries {
+            if let Some(entry) = policy_enforcer.subject_store.query(query) {
+                auth_subjects.insert(AuthSubject {
+                    id: entry.id,
+                    name: format!("{query}"),
+                });
+                policy_enforcer.expand_templates(entry.id, query, &mut templated);
+            }
+        }
+
+        // FIXME: Investigate if `AuthSubject` can have duplicates above and try to avoid this conversion
+        Self {
+            policy_enforcer: policy_enforcer.clone(),
+            subject: auth_subjects.into_iter().collect(),
+            templated: Arc::new(templated),
+        }
+    }
+
+    fn flow_enabled(&self, flow: InterceptorFlow) -> bool {
+        match flow {
+            InterceptorFlow::Ingress => self.policy_enforcer.interface_enabled.ingress,
+            InterceptorFlow::Egress => self.policy_enforcer.interface_enabled.egress,
+        }
+    }
+
+    fn denies_all(&self) -> bool {
+        let subjects = self.subject.iter().map(|s| s.id).collect::<Vec<_>>();
+        self.policy_enforcer.denies_all(&subjects, &self.templated)
+    }
+
+    /// Returns the permission of a message, which is allowed if any of the subjects allows it.
+    #[cfg(feature = "internal")]
+    fn permission(&self, flow: InterceptorFlow, message: AclMessage, key_expr: &str) -> Permission {
+        let mut permission = self.policy_enforcer.default_permission;
+        for subject in &self.subject {
+            match self.policy_enforcer.policy_decision_point(
+                subject.id,
+                &self.templated,
+                flow,
+                message,
+                key_expr,
+            ) {
+                Ok(decision) if decision.permission == Permission::Allow => {
+                    return Permission::Allow
+                }
+                Ok(decision) => permission = decision.permission,
+                Err(e) => {
+                    tracing::debug!("Authorization error on {}: {}", key_expr, e);
+                    return Permission::Deny;
+                }
+            }
+        }
+        permission
+    }
+}
+
+struct EgressAclEnforcer {
+    transport: Arc<AclTransport>,
+    audit: Option<AclAudit>,
+}
+
+struct IngressAclEnforcer {
+    transport: Arc<AclTransport>,
+    audit: Option<AclAudit>,
 }
 
 pub(crate) fn acl_interceptor_factories(
-    acl_config: &AclConfig,
-) -> ZResult<Vec<InterceptorFactory>> {
+    access_control: Option<&Arc<AccessControl>>,
+    audit: Option<&Arc<AuditLog>>,
+) -> Vec<InterceptorFactory> {
     let mut res: Vec<InterceptorFactory> = vec![];
 
-    if acl_config.enabled {
-        let mut policy_enforcer = PolicyEnforcer::new();
-        match policy_enforcer.init(acl_config) {
-            Ok(_) => {
-                tracing::debug!("Access control is enabled");
-                res.push(Box::new(AclEnforcer {
-                    enforcer: Arc::new(policy_enforcer),
-                }))
-            }
-            Err(e) => bail!("Access control not enabled due to: {}", e),
-        }
-    } else {
-        tracing::debug!("Access control is disabled");
+    if let Some(access_control) = access_control {
+        res.push(Box::new(AclEnforcer {
+            access_control: access_control.clone(),
+            audit: audit.filter(|audit| audit.records_acl_decisions()).cloned(),
+        }))
     }
 
-    Ok(res)
+    res
 }
 
 impl InterceptorFactoryTrait for AclEnforcer {
@@ -95,77 +293,14 @@ impl InterceptorFactoryTrait for AclEnforcer {
         &self,
         transport: &TransportUnicast,
     ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
-        let auth_ids = match transport.get_auth_ids() {
-            Ok(auth_ids) => auth_ids,
+        let subject_queries = match SubjectQuery::from_transport_unicast(transport) {
+            Ok(subject_queries) => subject_queries,
             Err(err) => {
-                tracing::error!("Couldn't get Transport Auth IDs: {}", err);
+                tracing::error!("{}", err);
                 return (None, None);
             }
         };
 
-        let mut cert_common_names = Vec::new();
-        let mut username = None;
-
-        for auth_id in auth_ids {
-            match auth_id {
-                AuthId::CertCommonName(value) => {
-                    cert_common_names.push(Some(CertCommonName(value)));
-                }
-                AuthId::Username(value) => {
-                    if username.is_some() {
-                        tracing::error!("Transport should not report more than one username");
-                        return (None, None);
-                    }
-                    username = Some(Username(value));
-                }
-                AuthId::None => {}
-            }
-        }
-        if cert_common_names.is_empty() {
-            cert_common_names.push(None);
-        }
-
-        let links = match transport.get_links() {
-            Ok(links) => links,
-            Err(err) => {
-                tracing::error!("Couldn't get Transport links: {}", err);
-                return (None, None);
-            }
-        };
-        let mut interfaces = links
-            .into_iter()
-            .flat_map(|link| {
-                link.interfaces
-                    .into_iter()
-                    .map(|interface| Some(Interface(interface)))
-            })
-            .collect::<Vec<_>>();
-        if interfaces.is_empty() {
-            interfaces.push(None);
-        } else if interfaces.len() > 1 {
-            tracing::warn!("Transport returned multiple network interfaces, current ACL logic might incorrectly apply filters in this case!");
-        }
-
-        let mut auth_subjects = HashSet::new();
-
-        for ((username, interface), cert_common_name) in iter::once(username)
-            .cartesian_product(interfaces.into_iter())
-            .cartesian_product(cert_common_names.into_iter())
-        {
-            let query = SubjectQuery {
-                interface,
-                cert_common_name,
-                username,
-            };
-
-            if let Some(entry) = self.enforcer.subject_store.query(&query) {
-                auth_subjects.insert(AuthSubject {
-                    id: entry.id,
-                    name: format!("{query}"),
-                });
-            }
-        }
-
         let zid = match transport.get_zid() {
             Ok(zid) => zid,
             Err(err) => {
@@ -173,34 +308,45 @@ impl InterceptorFactoryTrait for AclEnforcer {
                 return (None, None);
             }
         };
-        // FIXME: Investigate if `AuthSubject` can have duplicates above and try to avoid this conversion
-        let auth_subjects = auth_subjects.into_iter().collect::<Vec<AuthSubject>>();
-        if auth_subjects.is_empty() {
+
+        // The transport is registered while holding the lock for its state
+        // to be updated by any reload happening after it has been computed
+        let mut access_control = zlock!(self.access_control.inner);
+        let state = AclTransportState::new(&access_control.enforcer, &subject_queries);
+        if state.subject.is_empty() {
             tracing::info!(
                 "{zid} did not match any configured ACL subject. Default permission `{:?}` will be applied on all messages",
-                self.enforcer.default_permission
+                state.policy_enforcer.default_permission
             );
         }
-        let ingress_interceptor = Box::new(IngressAclEnforcer {
-            policy_enforcer: self.enforcer.clone(),
+        let acl_transport = Arc::new(AclTransport {
+            transport: transport.clone(),
             zid,
-            subject: auth_subjects.clone(),
+            subject_queries,
+            state: RwLock::new(state),
+        });
+        access_control
+            .transports
+            .retain(|transport| transport.strong_count() > 0);
+        access_control
+            .transports
+            .push(Arc::downgrade(&acl_transport));
+        drop(access_control);
+
+        let audit = self.audit.as_ref().map(|log| AclAudit {
+            log: log.clone(),
+            peer: Arc::new(AuditPeer::from_transport_unicast(transport)),
+        });
+        // Both interceptors are always created, since the enabled flows may change upon reload
+        let ingress_interceptor = Box::new(IngressAclEnforcer {
+            transport: acl_transport.clone(),
+            audit: audit.clone(),
         });
         let egress_interceptor = Box::new(EgressAclEnforcer {
-            policy_enforcer: self.enforcer.clone(),
-            zid,
-            subject: auth_subjects,
+            transport: acl_transport,
+            audit,
         });
-        (
-            self.enforcer
-                .interface_enabled
-                .ingress
-                .then_some(ingress_interceptor),
-            self.enforcer
-                .interface_enabled
-                .egress
-                .then_some(egress_interceptor),
-        )
+        (Some(ingress_interceptor), Some(egress_interceptor))
     }
 
     fn new_transport_multicast(
@@ -227,6 +373,10 @@ impl InterceptorTrait for IngressAclEnforcer {
         ctx: RoutingContext<NetworkMessage>,
         cache: Option<&Box<dyn Any + Send + Sync>>,
     ) -> Option<RoutingContext<NetworkMessage>> {
+        if !zread!(self.transport.state).flow_enabled(InterceptorFlow::Ingress) {
+            return Some(ctx);
+        }
+
         let key_expr = cache
             .and_then(|i| match i.downcast_ref::<String>() {
                 Some(e) => Some(e.as_str()),
@@ -242,7 +392,13 @@ impl InterceptorTrait for IngressAclEnforcer {
                 payload: RequestBody::Query(_),
                 ..
             }) => {
-                if self.action(AclMessage::Query, "Query (ingress)", key_expr?) == Permission::Deny
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Query, "Query (ingress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceRead,
+                        "Admin Space Read (ingress)",
+                        key_expr,
+                    ) == Permission::Deny
                 {
                     return None;
                 }
@@ -257,7 +413,14 @@ impl InterceptorTrait for IngressAclEnforcer {
                 payload: PushBody::Put(_),
                 ..
             }) => {
-                if self.action(AclMessage::Put, "Put (ingress)", key_expr?) == Permission::Deny {
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Put, "Put (ingress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceWrite,
+                        "Admin Space Write (ingress)",
+                        key_expr,
+                    ) == Permission::Deny
+                {
                     return None;
                 }
             }
@@ -265,8 +428,13 @@ impl InterceptorTrait for IngressAclEnforcer {
                 payload: PushBody::Del(_),
                 ..
             }) => {
-                if self.action(AclMessage::Delete, "Delete (ingress)", key_expr?)
-                    == Permission::Deny
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Delete, "Delete (ingress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceWrite,
+                        "Admin Space Write (ingress)",
+                        key_expr,
+                    ) == Permission::Deny
                 {
                     return None;
                 }
@@ -399,6 +567,29 @@ impl InterceptorTrait for IngressAclEnforcer {
                     return None;
                 }
             }
+            NetworkBody::Interest(Interest {
+                mode: InterestMode::Future | InterestMode::CurrentFuture,
+                options,
+                ..
+            }) if options.subscribers() || options.queryables() => {
+                // Interests that do not restrict the key expression cover the whole key space
+                let key_expr = key_expr.unwrap_or("**");
+                if (options.subscribers()
+                    && self.action(
+                        AclMessage::DeclarePublisher,
+                        "Declare Publisher (ingress)",
+                        key_expr,
+                    ) == Permission::Deny)
+                    || (options.queryables()
+                        && self.action(
+                            AclMessage::DeclareQuerier,
+                            "Declare Querier (ingress)",
+                            key_expr,
+                        ) == Permission::Deny)
+                {
+                    return None;
+                }
+            }
             NetworkBody::Interest(Interest {
                 mode: InterestMode::Final,
                 ..
@@ -406,6 +597,16 @@ impl InterceptorTrait for IngressAclEnforcer {
                 // InterestMode::Final filtering diverges between ingress and egress:
                 // InterestMode::Final ingress is always allowed, it will be rejected by routing logic if its associated Interest was denied
             }
+            NetworkBody::Interest(_) => {
+                if self.action(
+                    AclMessage::Interest,
+                    "Interest (ingress)",
+                    key_expr.unwrap_or("**"),
+                ) == Permission::Deny
+                {
+                    return None;
+                }
+            }
             // Unfiltered Declare messages
             NetworkBody::Declare(Declare {
                 body: DeclareBody::DeclareKeyExpr(_),
@@ -421,7 +622,10 @@ impl InterceptorTrait for IngressAclEnforcer {
                 ..
             }) => {}
             // Unfiltered remaining message types
-            NetworkBody::Interest(_) | NetworkBody::OAM(_) | NetworkBody::ResponseFinal(_) => {}
+            NetworkBody::OAM(_)
+            | NetworkBody::ResponseFinal(_)
+            | NetworkBody::RequestCancel(_)
+            | NetworkBody::RequestCredit(_) => {}
         }
         Some(ctx)
     }
@@ -437,6 +641,10 @@ impl InterceptorTrait for EgressAclEnforcer {
         ctx: RoutingContext<NetworkMessage>,
         cache: Option<&Box<dyn Any + Send + Sync>>,
     ) -> Option<RoutingContext<NetworkMessage>> {
+        if !zread!(self.transport.state).flow_enabled(InterceptorFlow::Egress) {
+            return Some(ctx);
+        }
+
         let key_expr = cache
             .and_then(|i| match i.downcast_ref::<String>() {
                 Some(e) => Some(e.as_str()),
@@ -452,7 +660,14 @@ impl InterceptorTrait for EgressAclEnforcer {
                 payload: RequestBody::Query(_),
                 ..
             }) => {
-                if self.action(AclMessage::Query, "Query (egress)", key_expr?) == Permission::Deny {
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Query, "Query (egress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceRead,
+                        "Admin Space Read (egress)",
+                        key_expr,
+                    ) == Permission::Deny
+                {
                     return None;
                 }
             }
@@ -465,7 +680,14 @@ impl InterceptorTrait for EgressAclEnforcer {
                 payload: PushBody::Put(_),
                 ..
             }) => {
-                if self.action(AclMessage::Put, "Put (egress)", key_expr?) == Permission::Deny {
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Put, "Put (egress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceWrite,
+                        "Admin Space Write (egress)",
+                        key_expr,
+                    ) == Permission::Deny
+                {
                     return None;
                 }
             }
@@ -473,7 +695,13 @@ impl InterceptorTrait for EgressAclEnforcer {
                 payload: PushBody::Del(_),
                 ..
             }) => {
-                if self.action(AclMessage::Delete, "Delete (egress)", key_expr?) == Permission::Deny
+                let key_expr = key_expr?;
+                if self.action(AclMessage::Delete, "Delete (egress)", key_expr) == Permission::Deny
+                    || self.admin_space_action(
+                        AclMessage::AdminSpaceWrite,
+                        "Admin Space Write (egress)",
+                        key_expr,
+                    ) == Permission::Deny
                 {
                     return None;
                 }
@@ -608,6 +836,66 @@ impl InterceptorTrait for EgressAclEnforcer {
                     return None;
                 }
             }
+            NetworkBody::Interest(Interest {
+                mode: InterestMode::Future | InterestMode::CurrentFuture,
+                options,
+                ..
+            }) if options.subscribers() || options.queryables() => {
+                // Interests that do not restrict the key expression cover the whole key space
+                let key_expr = key_expr.unwrap_or("**");
+                if (options.subscribers()
+                    && self.action(
+                        AclMessage::DeclarePublisher,
+                        "Declare Publisher (egress)",
+                        key_expr,
+                    ) == Permission::Deny)
+                    || (options.queryables()
+                        && self.action(
+                            AclMessage::DeclareQuerier,
+                            "Declare Querier (egress)",
+                            key_expr,
+                        ) == Permission::Deny)
+                {
+                    return None;
+                }
+            }
+            NetworkBody::Interest(Interest {
+                mode: InterestMode::Final,
+                options,
+                ..
+            }) if options.subscribers() || options.queryables() => {
+                // Interests that do not restrict the key expression cover the whole key space
+                let key_expr = key_expr.unwrap_or("**");
+                if (options.subscribers()
+                    && self.action(
+                        AclMessage::DeclarePublisher,
+                        "Undeclare Publisher (egress)",
+                        key_expr,
+                    ) == Permission::Deny)
+                    || (options.queryables()
+                        && self.action(
+                            AclMessage::DeclareQuerier,
+                            "Undeclare Querier (egress)",
+                            key_expr,
+                        ) == Permission::Deny)
+                {
+                    return None;
+                }
+            }
+            NetworkBody::Interest(Interest {
+                mode: InterestMode::Final,
+                ..
+            }) => {}
+            NetworkBody::Interest(_) => {
+                if self.action(
+                    AclMessage::Interest,
+                    "Interest (egress)",
+                    key_expr.unwrap_or("**"),
+                ) == Permission::Deny
+                {
+                    return None;
+                }
+            }
             // Unfiltered Declare messages
             NetworkBody::Declare(Declare {
                 body: DeclareBody::DeclareKeyExpr(_),
@@ -623,24 +911,57 @@ impl InterceptorTrait for EgressAclEnforcer {
                 ..
             }) => {}
             // Unfiltered remaining message types
-            NetworkBody::Interest(_) | NetworkBody::OAM(_) | NetworkBody::ResponseFinal(_) => {}
+            NetworkBody::OAM(_)
+            | NetworkBody::ResponseFinal(_)
+            | NetworkBody::RequestCancel(_)
+            | NetworkBody::RequestCredit(_) => {}
         }
         Some(ctx)
     }
 }
 pub trait AclActionMethods {
-    fn policy_enforcer(&self) -> Arc<PolicyEnforcer>;
+    fn state(&self) -> AclTransportState;
     fn zid(&self) -> ZenohIdProto;
     fn flow(&self) -> InterceptorFlow;
-    fn authn_ids(&self) -> Vec<AuthSubject>;
+    fn audit(&self) -> Option<&AclAudit>;
+    /// Checks an access to the admin space (`@/**`), which comes on top of the regular
+    /// [`AclMessage`] check of the message.
+    fn admin_space_action(&self, action: AclMessage, log_msg: &str, key_expr: &str) -> Permission {
+        if key_expr.starts_with("@/") {
+            self.action(action, log_msg, key_expr)
+        } else {
+            Permission::Allow
+        }
+    }
+
     fn action(&self, action: AclMessage, log_msg: &str, key_expr: &str) -> Permission {
-        let policy_enforcer = self.policy_enforcer();
-        let authn_ids: Vec<AuthSubject> = self.authn_ids();
+        let AclTransportState {
+            policy_enforcer,
+            subject: authn_ids,
+            templated,
+        } = self.state();
+        if !policy_enforcer.enforces(action) {
+            return Permission::Allow;
+        }
         let zid = self.zid();
-        let mut decision = policy_enforcer.default_permission;
+        let mut decision = PolicyDecision {
+            permission: policy_enforcer.default_permission,
+            rule_id: None,
+        };
         for subject in &authn_ids {
-            match policy_enforcer.policy_decision_point(subject.id, self.flow(), action, key_expr) {
-                Ok(Permission::Allow) => {
+            match policy_enforcer.policy_decision_point(
+                subject.id,
+                &templated,
+                self.flow(),
+                action,
+                key_expr,
+            ) {
+                Ok(
+                    subject_decision @ PolicyDecision {
+                        permission: Permission::Allow,
+                        ..
+                    },
+                ) => {
                     tracing::trace!(
                         "{} on {} is authorized to {} on {}",
                         zid,
@@ -648,10 +969,15 @@ pub trait AclActionMethods {
                         log_msg,
                         key_expr
                     );
-                    decision = Permission::Allow;
+                    decision = subject_decision;
                     break;
                 }
-                Ok(Permission::Deny) => {
+                Ok(
+                    subject_decision @ PolicyDecision {
+                        permission: Permission::Deny,
+                        ..
+                    },
+                ) => {
                     tracing::debug!(
                         "{} on {} is unauthorized to {} on {}",
                         zid,
@@ -660,7 +986,7 @@ pub trait AclActionMethods {
                         key_expr
                     );
 
-                    decision = Permission::Deny;
+                    decision = subject_decision;
                     continue;
                 }
                 Err(e) => {
@@ -672,46 +998,55 @@ pub trait AclActionMethods {
                         key_expr,
                         e
                     );
-                    return Permission::Deny;
+                    decision = PolicyDecision {
+                        permission: Permission::Deny,
+                        rule_id: None,
+                    };
+                    break;
                 }
             }
         }
-        decision
+        if let Some(audit) = self.audit() {
+            audit
+                .log
+                .record_acl_decision(&audit.peer, self.flow(), action, key_expr, &decision);
+        }
+        decision.permission
     }
 }
 
 impl AclActionMethods for EgressAclEnforcer {
-    fn policy_enforcer(&self) -> Arc<PolicyEnforcer> {
-        self.policy_enforcer.clone()
+    fn state(&self) -> AclTransportState {
+        zread!(self.transport.state).clone()
     }
 
     fn zid(&self) -> ZenohIdProto {
-        self.zid
+        self.transport.zid
     }
 
     fn flow(&self) -> InterceptorFlow {
         InterceptorFlow::Egress
     }
 
-    fn authn_ids(&self) -> Vec<AuthSubject> {
-        self.subject.clone()
+    fn audit(&self) -> Option<&AclAudit> {
+        self.audit.as_ref()
     }
 }
 
 impl AclActionMethods for IngressAclEnforcer {
-    fn policy_enforcer(&self) -> Arc<PolicyEnforcer> {
-        self.policy_enforcer.clone()
+    fn state(&self) -> AclTransportState {
+        zread!(self.transport.state).clone()
     }
 
     fn zid(&self) -> ZenohIdProto {
-        self.zid
+        self.transport.zid
     }
 
     fn flow(&self) -> InterceptorFlow {
         InterceptorFlow::Ingress
     }
 
-    fn authn_ids(&self) -> Vec<AuthSubject> {
-        self.subject.clone()
+    fn audit(&self) -> Option<&AclAudit> {
+        self.audit.as_ref()
     }
 }
@@ -0,0 +1,550 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
+
+use zenoh_config::{
+    AclMessage, AuditAclDecisions, AuditConf, AuditFileConf, AuditMessagesConf, InterceptorFlow,
+    Permission, WhatAmI,
+};
+use zenoh_keyexpr::OwnedKeyExpr;
+use zenoh_protocol::{
+    core::ZenohIdProto,
+    network::{push, DeclareBody, NetworkBody, Push, Request, Response},
+    zenoh::{PushBody, Put, RequestBody, ResponseBody},
+};
+use zenoh_transport::unicast::authentication::AuthId;
+
+use super::authorization::PolicyDecision;
+use crate::{
+    api::encoding::Encoding,
+    net::{
+        primitives::{DummyPrimitives, Primitives},
+        routing::{dispatcher::face::Face, interceptor::*},
+        runtime::Runtime,
+    },
+};
+
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 5;
+
+/// The remote a record relates to.
+#[derive(Debug)]
+pub(crate) struct AuditPeer {
+    zid: String,
+    usernames: Vec<String>,
+    cert_common_names: Vec<String>,
+    interfaces: Vec<String>,
+}
+
+impl AuditPeer {
+    pub(crate) fn from_transport_unicast(transport: &TransportUnicast) -> Self {
+        let mut usernames = vec![];
+        let mut cert_common_names = vec![];
+        for auth_id in transport.get_auth_ids().unwrap_or_default() {
+            match auth_id {
+                AuthId::Username(username) => usernames.push(username),
+                AuthId::CertCommonName(cert_common_name) => {
+                    cert_common_names.push(cert_common_name)
+                }
+                AuthId::PubKeyFingerprint(_) | AuthId::None => {}
+            }
+        }
+        Self {
+            zid: transport
+                .get_zid()
+                .map(|zid| zid.to_string())
+                .unwrap_or_default(),
+            usernames,
+            cert_common_names,
+            interfaces: transport
+                .get_links()
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|link| link.interfaces)
+                .collect(),
+        }
+    }
+}
+
+enum AuditEvent {
+    Acl {
+        message: AclMessage,
+        permission: Permission,
+        rule_id: Option<Arc<str>>,
+    },
+    Message {
+        kind: &'static str,
+    },
+}
+
+pub(crate) struct AuditRecord {
+    timestamp: SystemTime,
+    peer: Arc<AuditPeer>,
+    flow: InterceptorFlow,
+    key_expr: String,
+    event: AuditEvent,
+}
+
+impl AuditRecord {
+    /// The last chunk of the key expression the record is published on.
+    fn topic(&self) -> &'static str {
+        match self.event {
+            AuditEvent::Acl { .. } => "acl",
+            AuditEvent::Message { .. } => "messages",
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let (kind, decision, rule_id) = match &self.event {
+            AuditEvent::Acl {
+                message,
+                permission,
+                rule_id,
+            } => (
+                serde_json::json!(message),
+                serde_json::json!(permission),
+                serde_json::json!(rule_id.as_deref()),
+            ),
+            AuditEvent::Message { kind } => (
+                serde_json::json!(kind),
+                serde_json::Value::Null,
+                serde_json::Value::Null,
+            ),
+        };
+        serde_json::json!({
+            "timestamp": humantime::format_rfc3339_millis(self.timestamp).to_string(),
+            "event": self.topic(),
+            "zid": self.peer.zid,
+            "usernames": self.peer.usernames,
+            "cert_common_names": self.peer.cert_common_names,
+            "interfaces": self.peer.interfaces,
+            "flow": self.flow,
+            "kind": kind,
+            "key_expr": self.key_expr,
+            "decision": decision,
+            "rule_id": rule_id,
+        })
+    }
+}
+
+/// The audit log, recording ACL decisions and message flow.
+///
+/// Records are queued and written by a background task started with [`AuditLog::start`],
+/// to keep file and network I/O out of the routing path.
+pub(crate) struct AuditLog {
+    prefix: String,
+    acl_decisions: AuditAclDecisions,
+    messages: AuditMessagesConf,
+    sampling_rate: f64,
+    file: Option<AuditFileConf>,
+    publish: bool,
+    tx: flume::Sender<AuditRecord>,
+    rx: flume::Receiver<AuditRecord>,
+    dropped: AtomicUsize,
+}
+
+impl AuditLog {
+    pub(crate) fn new(zid: ZenohIdProto, whatami: WhatAmI, conf: &AuditConf) -> Option<Arc<Self>> {
+        if !conf.enabled {
+            return None;
+        }
+        let (tx, rx) = flume::bounded(conf.queue_size);
+        Some(Arc::new(Self {
+            prefix: format!("@/{zid}/{}/audit", whatami.to_str()),
+            acl_decisions: conf.acl_decisions,
+            messages: conf.messages.clone(),
+            sampling_rate: conf.sampling_rate,
+            file: conf.file.clone(),
+            publish: conf.publish,
+            tx,
+            rx,
+            dropped: AtomicUsize::new(0),
+        }))
+    }
+
+    pub(crate) fn records_acl_decisions(&self) -> bool {
+        self.acl_decisions != AuditAclDecisions::None
+    }
+
+    pub(crate) fn record_acl_decision(
+        &self,
+        peer: &Arc<AuditPeer>,
+        flow: InterceptorFlow,
+        message: AclMessage,
+        key_expr: &str,
+        decision: &PolicyDecision,
+    ) {
+        let sampled = match (self.acl_decisions, decision.permission) {
+            (AuditAclDecisions::None, _) | (AuditAclDecisions::Deny, Permission::Allow) => return,
+            (_, Permission::Deny) => false,
+            (AuditAclDecisions::All, Permission::Allow) => true,
+        };
+        self.record(
+            AuditRecord {
+                timestamp: SystemTime::now(),
+                peer: peer.clone(),
+                flow,
+                key_expr: key_expr.to_string(),
+                event: AuditEvent::Acl {
+                    message,
+                    permission: decision.permission,
+                    rule_id: decision.rule_id.clone(),
+                },
+            },
+            sampled,
+        );
+    }
+
+    fn record(&self, record: AuditRecord, sampled: bool) {
+        if sampled && self.sampling_rate < 1.0 && rand::random::<f64>() >= self.sampling_rate {
+            return;
+        }
+        // Do not audit the publication of the audit records
+        if record
+            .key_expr
+            .strip_prefix(self.prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        {
+            return;
+        }
+        if self.tx.try_send(record).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Starts the task writing the records to the audit file and publishing them.
+    pub(crate) fn start(self: &Arc<Self>, runtime: &Runtime) {
+        let mut file = self
+            .file
+            .as_ref()
+            .and_then(|conf| match AuditFile::open(conf) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    tracing::error!("Unable to open audit file {}: {}", conf.path, e);
+                    None
+                }
+            });
+        let face = self
+            .publish
+            .then(|| runtime.router().new_primitives(Arc::new(DummyPrimitives)));
+        let audit = self.clone();
+        let token = runtime.get_cancellation_token();
+        runtime.spawn(async move {
+            loop {
+                tokio::select! {
+                    res = audit.rx.recv_async() => {
+                        let Ok(record) = res else { break };
+                        let mut lines = vec![audit.publish(record, face.as_deref())];
+                        // Write the records queued meanwhile before flushing
+                        while let Ok(record) = audit.rx.try_recv() {
+                            lines.push(audit.publish(record, face.as_deref()));
+                        }
+                        if let Some(f) = file.take() {
+                            file = AuditFile::write_lines(f, lines).await;
+                        }
+                        let dropped = audit.dropped.swap(0, Ordering::Relaxed);
+                        if dropped > 0 {
+                            tracing::warn!("Audit log queue is full: {} records were dropped", dropped);
+                        }
+                    }
+                    _ = token.cancelled() => break,
+                }
+            }
+            // Each batch of records is flushed once written, the file has nothing left to flush
+            drop(file);
+        });
+    }
+
+    /// Publishes the record if enabled, returning its JSON line.
+    fn publish(&self, record: AuditRecord, face: Option<&Face>) -> String {
+        let json = record.to_json().to_string();
+        if let Some(face) = face {
+            face.send_push(
+                Push {
+                    wire_expr: format!("{}/{}", self.prefix, record.topic()).into(),
+                    ext_qos: push::ext::QoSType::DEFAULT,
+                    ext_tstamp: None,
+                    ext_nodeid: push::ext::NodeIdType::DEFAULT,
+                    payload: PushBody::Put(Put {
+                        timestamp: None,
+                        encoding: Encoding::APPLICATION_JSON.into(),
+                        ext_sinfo: None,
+                        #[cfg(feature = "shared-memory")]
+                        ext_shm: None,
+                        ext_attachment: None,
+                        ext_unknown: vec![],
+                        payload: json.clone().into_bytes().into(),
+                    }),
+                },
+                zenoh_protocol::core::Reliability::Reliable,
+            );
+        }
+        json
+    }
+}
+
+/// An append-only file, rotated when it exceeds its maximum size.
+struct AuditFile {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+impl AuditFile {
+    fn open(conf: &AuditFileConf) -> io::Result<Self> {
+        let path = PathBuf::from(&conf.path);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_size: conf.max_size.unwrap_or(DEFAULT_MAX_FILE_SIZE),
+            max_files: conf.max_files.unwrap_or(DEFAULT_MAX_FILES),
+            writer: BufWriter::new(file),
+            size,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Renames `<path>.<n>` to `<path>.<n+1>`, dropping the oldest file, and starts a new file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        if self.max_files > 0 {
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.writer = BufWriter::new(file);
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_size {
+            self.rotate()?;
+        }
+        self.writer.write_all(line)?;
+        self.writer.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Writes the lines and flushes the file on the blocking threads, as the writes and the
+    /// rotations of the file may block. The file is dropped if the writing task panics.
+    async fn write_lines(mut file: AuditFile, lines: Vec<String>) -> Option<AuditFile> {
+        let res = tokio::task::spawn_blocking(move || {
+            for line in lines {
+                if let Err(e) = file.write_line(line.as_bytes()) {
+                    tracing::error!("Unable to write audit file: {}", e);
+                }
+            }
+            if let Err(e) = file.flush() {
+                tracing::error!("Unable to write audit file: {}", e);
+            }
+            file
+        })
+        .await;
+        match res {
+            Ok(file) => Some(file),
+            Err(e) => {
+                tracing::error!("Unable to write audit file: {}", e);
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn audit_interceptor_factories(
+    audit: Option<&Arc<AuditLog>>,
+) -> Vec<InterceptorFactory> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    if let Some(audit) = audit.filter(|audit| audit.messages.enabled) {
+        res.push(Box::new(AuditInterceptorFactory {
+            audit: audit.clone(),
+        }));
+    }
+
+    res
+}
+
+pub struct AuditInterceptorFactory {
+    audit: Arc<AuditLog>,
+}
+
+impl AuditInterceptorFactory {
+    fn interceptor(&self, peer: &Arc<AuditPeer>, flow: InterceptorFlow) -> Option<Interceptor> {
+        let recorded = self.audit.messages.flows.as_ref().map_or(true, |flows| {
+            flows.iter().any(|f| {
+                matches!(
+                    (f, flow),
+                    (InterceptorFlow::Ingress, InterceptorFlow::Ingress)
+                        | (InterceptorFlow::Egress, InterceptorFlow::Egress)
+                )
+            })
+        });
+        recorded.then(|| {
+            Box::new(ComputeOnMiss::new(AuditInterceptor {
+                audit: self.audit.clone(),
+                peer: peer.clone(),
+                flow,
+                key_exprs: self.audit.messages.key_exprs.clone(),
+            })) as Interceptor
+        })
+    }
+}
+
+impl InterceptorFactoryTrait for AuditInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New audit transport unicast {:?}", transport);
+        if let Some(interfaces) = &self.audit.messages.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+
+        let peer = Arc::new(AuditPeer::from_transport_unicast(transport));
+        (
+            self.interceptor(&peer, InterceptorFlow::Ingress),
+            self.interceptor(&peer, InterceptorFlow::Egress),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct AuditInterceptor {
+    audit: Arc<AuditLog>,
+    peer: Arc<AuditPeer>,
+    flow: InterceptorFlow,
+    key_exprs: Option<Vec<OwnedKeyExpr>>,
+}
+
+/// Returns the kind of the recorded messages, or None for messages that are not recorded.
+fn message_kind(body: &NetworkBody) -> Option<&'static str> {
+    match body {
+        NetworkBody::Push(Push { payload, .. }) => match payload {
+            PushBody::Put(_) => Some("put"),
+            PushBody::Del(_) => Some("delete"),
+        },
+        NetworkBody::Request(Request {
+            payload: RequestBody::Query(_),
+            ..
+        }) => Some("query"),
+        NetworkBody::Response(Response { payload, .. }) => match payload {
+            ResponseBody::Reply(_) => Some("reply"),
+            ResponseBody::Err(_) => Some("reply_err"),
+        },
+        NetworkBody::Interest(_) => Some("interest"),
+        NetworkBody::Declare(declare) => match &declare.body {
+            DeclareBody::DeclareSubscriber(_) => Some("declare_subscriber"),
+            DeclareBody::UndeclareSubscriber(_) => Some("undeclare_subscriber"),
+            DeclareBody::DeclareQueryable(_) => Some("declare_queryable"),
+            DeclareBody::UndeclareQueryable(_) => Some("undeclare_queryable"),
+            DeclareBody::DeclareToken(_) => Some("declare_token"),
+            DeclareBody::UndeclareToken(_) => Some("undeclare_token"),
+            DeclareBody::DeclareKeyExpr(_)
+            | DeclareBody::UndeclareKeyExpr(_)
+            | DeclareBody::DeclareFinal(_) => None,
+        },
+        NetworkBody::ResponseFinal(_)
+        | NetworkBody::RequestCancel(_)
+        | NetworkBody::RequestCredit(_)
+        | NetworkBody::OAM(_) => None,
+    }
+}
+
+impl InterceptorTrait for AuditInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        let matching = self.key_exprs.as_ref().map_or(true, |key_exprs| {
+            key_exprs.iter().any(|ke| key_expr.intersects(ke))
+        });
+        Some(Box::new(matching.then(|| key_expr.to_string())))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let Some(Some(key_expr)) = cache.and_then(|c| c.downcast_ref::<Option<String>>()) else {
+            return Some(ctx);
+        };
+        if let Some(kind) = message_kind(&ctx.msg.body) {
+            self.audit.record(
+                AuditRecord {
+                    timestamp: SystemTime::now(),
+                    peer: self.peer.clone(),
+                    flow: self.flow,
+                    key_expr: key_expr.clone(),
+                    event: AuditEvent::Message { kind },
+                },
+                true,
+            );
+        }
+        Some(ctx)
+    }
+}
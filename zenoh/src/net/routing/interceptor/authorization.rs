@@ -17,28 +17,51 @@
 //! This module is intended for Zenoh's internal use.
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 
 use ahash::RandomState;
 use itertools::Itertools;
 use zenoh_config::{
     AclConfig, AclConfigPolicyEntry, AclConfigRule, AclConfigSubjects, AclMessage, CertCommonName,
-    InterceptorFlow, Interface, Permission, PolicyRule, Username,
+    InterceptorFlow, Interface, IpCidr, LinkProtocol, Permission, PolicyRule, PubKeyFingerprint,
+    Username, ZenohId,
 };
 use zenoh_keyexpr::{
     keyexpr,
-    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, KeBoxTree},
+    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, IKeyExprTreeNode, KeBoxTree},
 };
 use zenoh_result::ZResult;
+use zenoh_transport::unicast::{authentication::AuthId, TransportUnicast};
 type PolicyForSubject = FlowPolicy;
 
-type PolicyMap = HashMap<usize, PolicyForSubject, RandomState>;
+pub(crate) type PolicyMap = HashMap<usize, PolicyForSubject, RandomState>;
+
+type TemplateMap = HashMap<usize, Vec<TemplateRule>, RandomState>;
+
+/// The messages only filtered once a rule lists them, for them not to be denied by the
+/// configurations written before they could be filtered.
+const OPT_IN_MESSAGES: [AclMessage; 5] = [
+    AclMessage::DeclarePublisher,
+    AclMessage::DeclareQuerier,
+    AclMessage::Interest,
+    AclMessage::AdminSpaceRead,
+    AclMessage::AdminSpaceWrite,
+];
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct Subject {
     pub(crate) interface: SubjectProperty<Interface>,
     pub(crate) cert_common_name: SubjectProperty<CertCommonName>,
     pub(crate) username: SubjectProperty<Username>,
+    pub(crate) zid: SubjectProperty<ZenohId>,
+    pub(crate) pubkey_fingerprint: SubjectProperty<PubKeyFingerprint>,
+    pub(crate) link_protocol: SubjectProperty<LinkProtocol>,
+    pub(crate) cidr: SubjectProperty<IpCidr>,
 }
 
 impl Subject {
@@ -48,6 +71,14 @@ impl Subject {
             && self
                 .cert_common_name
                 .matches(query.cert_common_name.as_ref())
+            && self.zid.matches(query.zid.as_ref())
+            && self
+                .pubkey_fingerprint
+                .matches(query.pubkey_fingerprint.as_ref())
+            && self.link_protocol.matches(query.link_protocol.as_ref())
+            && self
+                .cidr
+                .matches_by(query.remote_ip.as_ref(), IpCidr::contains)
     }
 }
 
@@ -59,14 +90,27 @@ pub(crate) enum SubjectProperty<T> {
 
 impl<T: PartialEq + Eq> SubjectProperty<T> {
     fn matches(&self, other: Option<&T>) -> bool {
+        self.matches_by(other, T::eq)
+    }
+}
+
+impl<T> SubjectProperty<T> {
+    fn matches_by<U>(&self, other: Option<&U>, f: impl FnOnce(&T, &U) -> bool) -> bool {
         match (self, other) {
             (SubjectProperty::Wildcard, None) => true,
             // NOTE: This match arm is the reason why `SubjectProperty` cannot simply be `Option`
             (SubjectProperty::Wildcard, Some(_)) => true,
             (SubjectProperty::Exactly(_), None) => false,
-            (SubjectProperty::Exactly(lhs), Some(rhs)) => lhs == rhs,
+            (SubjectProperty::Exactly(lhs), Some(rhs)) => f(lhs, rhs),
         }
     }
+
+    /// Maps the configured values of a subject property, `None` being a wildcard
+    fn from_config(values: Option<Vec<T>>) -> Vec<Self> {
+        values
+            .map(|values| values.into_iter().map(SubjectProperty::Exactly).collect())
+            .unwrap_or(vec![SubjectProperty::Wildcard])
+    }
 }
 
 #[derive(Debug)]
@@ -74,6 +118,103 @@ pub(crate) struct SubjectQuery {
     pub(crate) interface: Option<Interface>,
     pub(crate) cert_common_name: Option<CertCommonName>,
     pub(crate) username: Option<Username>,
+    pub(crate) zid: Option<ZenohId>,
+    pub(crate) pubkey_fingerprint: Option<PubKeyFingerprint>,
+    pub(crate) link_protocol: Option<LinkProtocol>,
+    pub(crate) remote_ip: Option<IpAddr>,
+}
+
+impl SubjectQuery {
+    /// Returns the subject queries matching the authentication ids and interfaces of a transport
+    pub(crate) fn from_transport_unicast(transport: &TransportUnicast) -> ZResult<Vec<Self>> {
+        let auth_ids = transport
+            .get_auth_ids()
+            .map_err(|err| zerror!("Couldn't get Transport Auth IDs: {}", err))?;
+
+        let mut cert_common_names = Vec::new();
+        let mut username = None;
+        let mut pubkey_fingerprint = None;
+
+        for auth_id in auth_ids {
+            match auth_id {
+                AuthId::CertCommonName(value) => {
+                    cert_common_names.push(Some(CertCommonName(value)));
+                }
+                AuthId::Username(value) => {
+                    if username.is_some() {
+                        bail!("Transport should not report more than one username");
+                    }
+                    username = Some(Username(value));
+                }
+                AuthId::PubKeyFingerprint(value) => {
+                    if pubkey_fingerprint.is_some() {
+                        bail!("Transport should not report more than one public key");
+                    }
+                    pubkey_fingerprint = Some(PubKeyFingerprint(value));
+                }
+                AuthId::None => {}
+            }
+        }
+        if cert_common_names.is_empty() {
+            cert_common_names.push(None);
+        }
+
+        let zid = transport
+            .get_zid()
+            .map_err(|err| zerror!("Couldn't get Transport zid: {}", err))?;
+
+        let links = transport
+            .get_links()
+            .map_err(|err| zerror!("Couldn't get Transport links: {}", err))?;
+        // The interface, protocol and remote address of each link
+        let mut link_properties = links
+            .into_iter()
+            .flat_map(|link| {
+                let link_protocol = link.dst.protocol().as_str().parse::<LinkProtocol>().ok();
+                let remote_ip = link
+                    .dst
+                    .address()
+                    .as_str()
+                    .parse::<SocketAddr>()
+                    .ok()
+                    .map(|addr| addr.ip());
+                let mut interfaces = link
+                    .interfaces
+                    .into_iter()
+                    .map(|interface| Some(Interface(interface)))
+                    .collect::<Vec<_>>();
+                if interfaces.is_empty() {
+                    interfaces.push(None);
+                }
+                interfaces
+                    .into_iter()
+                    .map(move |interface| (interface, link_protocol, remote_ip))
+            })
+            .collect::<Vec<_>>();
+        if link_properties.is_empty() {
+            link_properties.push((None, None, None));
+        } else if link_properties.len() > 1 {
+            tracing::warn!("Transport returned multiple network interfaces, current subject matching logic might incorrectly apply filters in this case!");
+        }
+
+        Ok(iter::once(username)
+            .cartesian_product(link_properties)
+            .cartesian_product(cert_common_names)
+            .map(
+                |((username, (interface, link_protocol, remote_ip)), cert_common_name)| {
+                    SubjectQuery {
+                        interface,
+                        cert_common_name,
+                        username,
+                        zid: Some(zid.into()),
+                        pubkey_fingerprint: pubkey_fingerprint.clone(),
+                        link_protocol,
+                        remote_ip,
+                    }
+                },
+            )
+            .collect())
+    }
 }
 
 impl std::fmt::Display for SubjectQuery {
@@ -82,6 +223,14 @@ impl std::fmt::Display for SubjectQuery {
             self.interface.as_ref().map(|face| format!("{face}")),
             self.cert_common_name.as_ref().map(|ccn| format!("{ccn}")),
             self.username.as_ref().map(|username| format!("{username}")),
+            self.zid.as_ref().map(|zid| format!("ZenohId({zid})")),
+            self.pubkey_fingerprint
+                .as_ref()
+                .map(|fingerprint| format!("{fingerprint}")),
+            self.link_protocol
+                .as_ref()
+                .map(|protocol| format!("{protocol}")),
+            self.remote_ip.as_ref().map(|ip| format!("RemoteIp({ip})")),
         ];
         write!(
             f,
@@ -144,6 +293,106 @@ impl SubjectMapBuilder {
         }
     }
 
+    /// Inserts all the subject combinations of a configured subject and returns their ids
+    pub(crate) fn insert_config_subject(
+        &mut self,
+        config_subject: AclConfigSubjects,
+    ) -> ZResult<Vec<usize>> {
+        // validate subject config fields
+        if config_subject
+            .interfaces
+            .as_ref()
+            .is_some_and(|interfaces| interfaces.iter().any(|face| face.0.trim().is_empty()))
+        {
+            bail!(
+                "Found empty interface value in subject '{}'",
+                config_subject.id
+            );
+        }
+        if config_subject
+            .cert_common_names
+            .as_ref()
+            .is_some_and(|cert_common_names| {
+                cert_common_names.iter().any(|ccn| ccn.0.trim().is_empty())
+            })
+        {
+            bail!(
+                "Found empty cert_common_name value in subject '{}'",
+                config_subject.id
+            );
+        }
+        if config_subject.usernames.as_ref().is_some_and(|usernames| {
+            usernames
+                .iter()
+                .any(|username| username.0.trim().is_empty())
+        }) {
+            bail!(
+                "Found empty username value in subject '{}'",
+                config_subject.id
+            );
+        }
+        if config_subject
+            .pubkey_fingerprints
+            .as_ref()
+            .is_some_and(|fingerprints| {
+                fingerprints
+                    .iter()
+                    .any(|fingerprint| fingerprint.0.trim().is_empty())
+            })
+        {
+            bail!(
+                "Found empty pubkey_fingerprint value in subject '{}'",
+                config_subject.id
+            );
+        }
+        // Map properties to SubjectProperty type
+        let interfaces = SubjectProperty::from_config(config_subject.interfaces);
+        let cert_common_names = SubjectProperty::from_config(config_subject.cert_common_names);
+        let usernames = SubjectProperty::from_config(config_subject.usernames);
+        let zids = SubjectProperty::from_config(config_subject.zids);
+        // Fingerprints are hexadecimal digests, compare them case-insensitively
+        let pubkey_fingerprints =
+            SubjectProperty::from_config(config_subject.pubkey_fingerprints.map(|fingerprints| {
+                fingerprints
+                    .into_iter()
+                    .map(|fingerprint| PubKeyFingerprint(fingerprint.0.trim().to_lowercase()))
+                    .collect()
+            }));
+        let link_protocols = SubjectProperty::from_config(config_subject.link_protocols);
+        let cidrs = SubjectProperty::from_config(config_subject.cidrs);
+
+        // create ACL subject combinations
+        Ok(interfaces
+            .into_iter()
+            .cartesian_product(cert_common_names)
+            .cartesian_product(usernames)
+            .cartesian_product(zids)
+            .cartesian_product(pubkey_fingerprints)
+            .cartesian_product(link_protocols)
+            .cartesian_product(cidrs)
+            .map(
+                |(
+                    (
+                        ((((interface, cert_common_name), username), zid), pubkey_fingerprint),
+                        link_protocol,
+                    ),
+                    cidr,
+                )| {
+                    let subject = Subject {
+                        interface,
+                        cert_common_name,
+                        username,
+                        zid,
+                        pubkey_fingerprint,
+                        link_protocol,
+                        cidr,
+                    };
+                    self.insert_or_get(subject)
+                },
+            )
+            .collect())
+    }
+
     /// Assumes subject contains at most one instance of each Subject variant
     pub(crate) fn insert_or_get(&mut self, subject: Subject) -> usize {
         match self.builder.get(&subject).copied() {
@@ -157,7 +406,90 @@ impl SubjectMapBuilder {
     }
 }
 
-type KeTreeRule = KeBoxTree<bool>;
+/// The rule ids indexed by key expression
+type KeTreeRule = KeBoxTree<Arc<str>>;
+
+/// The variables that can be used in the key expressions of ACL rules, e.g. `devices/${username}/**`
+const TEMPLATE_VARIABLES: [&str; 4] = ["username", "cert_common_name", "zid", "pubkey_fingerprint"];
+
+/// Returns true if the key expression of a rule is a template referencing the subject.
+fn is_template(key_expr: &str) -> bool {
+    key_expr.contains("${")
+}
+
+/// Replaces the variables of a key expression template with the values returned by `value`.
+///
+/// Returns `Ok(None)` if the value of a variable is not available.
+fn expand_template(
+    template: &str,
+    mut value: impl FnMut(&str) -> Option<String>,
+) -> ZResult<Option<String>> {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            bail!("Unterminated variable in key expression template '{template}'");
+        };
+        let variable = &rest[start + 2..start + len];
+        if !TEMPLATE_VARIABLES.contains(&variable) {
+            bail!(
+                "Unknown variable '{variable}' in key expression template '{template}', expected one of {}",
+                TEMPLATE_VARIABLES.join(", ")
+            );
+        }
+        let Some(value) = value(variable) else {
+            return Ok(None);
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[start + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(Some(expanded))
+}
+
+/// Checks that a key expression template is valid, i.e. only uses known variables and expands
+/// to a valid key expression.
+fn validate_template(template: &str) -> ZResult<()> {
+    let expanded = expand_template(template, |_| Some("x".to_string()))?
+        .expect("all template variables should have a value");
+    keyexpr::new(&expanded)
+        .map_err(|e| zerror!("Invalid key expression template '{template}': {e}"))?;
+    Ok(())
+}
+
+/// Returns the value of a template variable for a subject query.
+///
+/// Values that are not a single verbatim chunk (e.g. containing `/` or wildcards) are discarded,
+/// as they would extend the key expressions the rule applies to.
+fn template_value(query: &SubjectQuery, variable: &str) -> Option<String> {
+    let value = match variable {
+        "username" => query.username.as_ref().map(|username| username.0.clone()),
+        "cert_common_name" => query.cert_common_name.as_ref().map(|ccn| ccn.0.clone()),
+        "zid" => query.zid.as_ref().map(|zid| zid.to_string()),
+        "pubkey_fingerprint" => query
+            .pubkey_fingerprint
+            .as_ref()
+            .map(|fingerprint| fingerprint.0.clone()),
+        _ => None,
+    }?;
+    if value.is_empty() || value.contains(['/', '*', '$', '?', '#']) {
+        tracing::warn!(
+            "Value '{value}' of `{variable}` cannot be used in key expression templates of ACL rules"
+        );
+        return None;
+    }
+    Some(value)
+}
+
+/// A rule whose key expression is a template, expanded for each transport of its subject.
+struct TemplateRule {
+    rule_id: Arc<str>,
+    flow: InterceptorFlow,
+    message: AclMessage,
+    permission: Permission,
+    key_expr: String,
+}
 
 #[derive(Default)]
 struct PermissionPolicy {
@@ -191,9 +523,32 @@ struct ActionPolicy {
     liveliness_token: PermissionPolicy,
     declare_liveliness_sub: PermissionPolicy,
     liveliness_query: PermissionPolicy,
+    declare_publisher: PermissionPolicy,
+    declare_querier: PermissionPolicy,
+    interest: PermissionPolicy,
+    admin_space_read: PermissionPolicy,
+    admin_space_write: PermissionPolicy,
 }
 
 impl ActionPolicy {
+    fn actions(&self) -> [&PermissionPolicy; 14] {
+        [
+            &self.query,
+            &self.put,
+            &self.delete,
+            &self.declare_subscriber,
+            &self.declare_queryable,
+            &self.reply,
+            &self.liveliness_token,
+            &self.declare_liveliness_sub,
+            &self.liveliness_query,
+            &self.declare_publisher,
+            &self.declare_querier,
+            &self.interest,
+            &self.admin_space_read,
+            &self.admin_space_write,
+        ]
+    }
     fn action(&self, action: AclMessage) -> &PermissionPolicy {
         match action {
             AclMessage::Query => &self.query,
@@ -205,6 +560,11 @@ impl ActionPolicy {
             AclMessage::LivelinessToken => &self.liveliness_token,
             AclMessage::DeclareLivelinessSubscriber => &self.declare_liveliness_sub,
             AclMessage::LivelinessQuery => &self.liveliness_query,
+            AclMessage::DeclarePublisher => &self.declare_publisher,
+            AclMessage::DeclareQuerier => &self.declare_querier,
+            AclMessage::Interest => &self.interest,
+            AclMessage::AdminSpaceRead => &self.admin_space_read,
+            AclMessage::AdminSpaceWrite => &self.admin_space_write,
         }
     }
     fn action_mut(&mut self, action: AclMessage) -> &mut PermissionPolicy {
@@ -218,6 +578,11 @@ impl ActionPolicy {
             AclMessage::LivelinessToken => &mut self.liveliness_token,
             AclMessage::DeclareLivelinessSubscriber => &mut self.declare_liveliness_sub,
             AclMessage::LivelinessQuery => &mut self.liveliness_query,
+            AclMessage::DeclarePublisher => &mut self.declare_publisher,
+            AclMessage::DeclareQuerier => &mut self.declare_querier,
+            AclMessage::Interest => &mut self.interest,
+            AclMessage::AdminSpaceRead => &mut self.admin_space_read,
+            AclMessage::AdminSpaceWrite => &mut self.admin_space_write,
         }
     }
 }
@@ -241,6 +606,12 @@ impl FlowPolicy {
             InterceptorFlow::Egress => &mut self.egress,
         }
     }
+    fn allows_any(&self) -> bool {
+        [&self.ingress, &self.egress]
+            .into_iter()
+            .flat_map(ActionPolicy::actions)
+            .any(|policy| policy.allow.key_value_pairs().next().is_some())
+    }
 }
 
 #[derive(Default, Debug)]
@@ -254,6 +625,9 @@ pub struct PolicyEnforcer {
     pub(crate) default_permission: Permission,
     pub(crate) subject_store: SubjectStore,
     pub(crate) policy_map: PolicyMap,
+    template_map: TemplateMap,
+    // The opt-in messages listed by a rule
+    enforced_opt_in_messages: HashSet<AclMessage>,
     pub(crate) interface_enabled: InterfaceEnabled,
 }
 
@@ -270,6 +644,8 @@ impl PolicyEnforcer {
             default_permission: Permission::Deny,
             subject_store: SubjectStore::default(),
             policy_map: PolicyMap::default(),
+            template_map: TemplateMap::default(),
+            enforced_opt_in_messages: HashSet::new(),
             interface_enabled: InterfaceEnabled::default(),
         }
     }
@@ -298,6 +674,8 @@ impl PolicyEnforcer {
                         tracing::warn!("Access control policies list is empty in config file")
                     });
                     self.policy_map = PolicyMap::default();
+                    self.template_map = TemplateMap::default();
+                    self.enforced_opt_in_messages = HashSet::new();
                     self.subject_store = SubjectStore::default();
                     if self.default_permission == Permission::Deny {
                         self.interface_enabled = InterfaceEnabled {
@@ -338,18 +716,56 @@ impl PolicyEnforcer {
                         if subject.interfaces.as_ref().is_some_and(Vec::is_empty) {
                             bail!("Subject property `interfaces` cannot be empty");
                         }
+
+                        if subject.zids.as_ref().is_some_and(Vec::is_empty) {
+                            bail!("Subject property `zids` cannot be empty");
+                        }
+
+                        if subject
+                            .pubkey_fingerprints
+                            .as_ref()
+                            .is_some_and(Vec::is_empty)
+                        {
+                            bail!("Subject property `pubkey_fingerprints` cannot be empty");
+                        }
+
+                        if subject.link_protocols.as_ref().is_some_and(Vec::is_empty) {
+                            bail!("Subject property `link_protocols` cannot be empty");
+                        }
+
+                        if subject.cidrs.as_ref().is_some_and(Vec::is_empty) {
+                            bail!("Subject property `cidrs` cannot be empty");
+                        }
                     }
                     let policy_information =
                         self.policy_information_point(subjects, rules, policies)?;
 
                     let mut main_policy: PolicyMap = PolicyMap::default();
+                    let mut template_map: TemplateMap = TemplateMap::default();
+                    let mut enforced_opt_in_messages = HashSet::new();
                     for rule in policy_information.policy_rules {
-                        let subject_policy = main_policy.entry(rule.subject_id).or_default();
-                        subject_policy
-                            .flow_mut(rule.flow)
-                            .action_mut(rule.message)
-                            .permission_mut(rule.permission)
-                            .insert(keyexpr::new(&rule.key_expr)?, true);
+                        if OPT_IN_MESSAGES.contains(&rule.message) {
+                            enforced_opt_in_messages.insert(rule.message);
+                        }
+                        if is_template(&rule.key_expr) {
+                            template_map
+                                .entry(rule.subject_id)
+                                .or_default()
+                                .push(TemplateRule {
+                                    rule_id: rule.rule_id.into(),
+                                    flow: rule.flow,
+                                    message: rule.message,
+                                    permission: rule.permission,
+                                    key_expr: rule.key_expr,
+                                });
+                        } else {
+                            let subject_policy = main_policy.entry(rule.subject_id).or_default();
+                            subject_policy
+                                .flow_mut(rule.flow)
+                                .action_mut(rule.message)
+                                .permission_mut(rule.permission)
+                                .insert(keyexpr::new(&rule.key_expr)?, rule.rule_id.into());
+                        }
 
                         if self.default_permission == Permission::Deny {
                             self.interface_enabled = InterfaceEnabled {
@@ -368,6 +784,8 @@ impl PolicyEnforcer {
                         }
                     }
                     self.policy_map = main_policy;
+                    self.template_map = template_map;
+                    self.enforced_opt_in_messages = enforced_opt_in_messages;
                     self.subject_store = policy_information.subject_map;
                 }
             } else {
@@ -377,6 +795,76 @@ impl PolicyEnforcer {
         Ok(())
     }
 
+    /// Returns true if the message is filtered, i.e. if it is not an opt-in message or if a rule
+    /// lists it.
+    pub(crate) fn enforces(&self, message: AclMessage) -> bool {
+        !OPT_IN_MESSAGES.contains(&message) || self.enforced_opt_in_messages.contains(&message)
+    }
+
+    /// Returns true if no message at all is allowed for the given subjects, along with the
+    /// policies expanded from key expression templates for them.
+    pub(crate) fn denies_all(&self, subjects: &[usize], templated: &PolicyMap) -> bool {
+        self.acl_enabled
+            && self.default_permission == Permission::Deny
+            && subjects.iter().all(|subject| {
+                self.policy_map
+                    .get(subject)
+                    .into_iter()
+                    .chain(templated.get(subject))
+                    .all(|policy| !policy.allows_any())
+            })
+    }
+
+    /// Expands the key expression templates of the rules of a subject with the values of a
+    /// subject query, into the policies of a transport.
+    ///
+    /// Rules referencing a value the query does not have do not apply.
+    pub(crate) fn expand_templates(
+        &self,
+        subject: usize,
+        query: &SubjectQuery,
+        templated: &mut PolicyMap,
+    ) {
+        let Some(rules) = self.template_map.get(&subject) else {
+            return;
+        };
+        for rule in rules {
+            let key_expr = match expand_template(&rule.key_expr, |variable| {
+                template_value(query, variable)
+            }) {
+                Ok(Some(key_expr)) => key_expr,
+                Ok(None) => {
+                    tracing::debug!(
+                        "Rule '{}' does not apply to {query}: key expression template '{}' cannot be expanded",
+                        rule.rule_id,
+                        rule.key_expr
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!("{e}");
+                    continue;
+                }
+            };
+            match keyexpr::new(&key_expr) {
+                Ok(key_expr) => {
+                    templated
+                        .entry(subject)
+                        .or_default()
+                        .flow_mut(rule.flow)
+                        .action_mut(rule.message)
+                        .permission_mut(rule.permission)
+                        .insert(key_expr, rule.rule_id.clone());
+                }
+                Err(e) => tracing::warn!(
+                    "Rule '{}' does not apply to {query}: key expression template '{}' expands to an invalid key expression: {e}",
+                    rule.rule_id,
+                    rule.key_expr
+                ),
+            }
+        }
+    }
+
     /*
        converts the sets of rules from config format into individual rules for each subject, key-expr, action, permission
     */
@@ -417,6 +905,10 @@ impl PolicyEnforcer {
                 if key_expr.trim().is_empty() {
                     bail!("Found empty key expression in rule '{}'", config_rule.id);
                 }
+                if is_template(key_expr) {
+                    validate_template(key_expr)
+                        .map_err(|e| zerror!("Rule '{}' is malformed: {e}", config_rule.id))?;
+                }
             }
             rule_map.insert(config_rule.id.clone(), config_rule);
         }
@@ -428,86 +920,10 @@ impl PolicyEnforcer {
                     config_subject.id
                 );
             }
-            // validate subject config fields
-            if config_subject
-                .interfaces
-                .as_ref()
-                .is_some_and(|interfaces| interfaces.iter().any(|face| face.0.trim().is_empty()))
-            {
-                bail!(
-                    "Found empty interface value in subject '{}'",
-                    config_subject.id
-                );
-            }
-            if config_subject
-                .cert_common_names
-                .as_ref()
-                .is_some_and(|cert_common_names| {
-                    cert_common_names.iter().any(|ccn| ccn.0.trim().is_empty())
-                })
-            {
-                bail!(
-                    "Found empty cert_common_name value in subject '{}'",
-                    config_subject.id
-                );
-            }
-            if config_subject.usernames.as_ref().is_some_and(|usernames| {
-                usernames
-                    .iter()
-                    .any(|username| username.0.trim().is_empty())
-            }) {
-                bail!(
-                    "Found empty username value in subject '{}'",
-                    config_subject.id
-                );
-            }
-            // Map properties to SubjectProperty type
-            // FIXME: Unnecessary .collect() because of different iterator types
-            let interfaces = config_subject
-                .interfaces
-                .map(|interfaces| {
-                    interfaces
-                        .into_iter()
-                        .map(SubjectProperty::Exactly)
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or(vec![SubjectProperty::Wildcard]);
-            // FIXME: Unnecessary .collect() because of different iterator types
-            let cert_common_names = config_subject
-                .cert_common_names
-                .map(|cert_common_names| {
-                    cert_common_names
-                        .into_iter()
-                        .map(SubjectProperty::Exactly)
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or(vec![SubjectProperty::Wildcard]);
-            // FIXME: Unnecessary .collect() because of different iterator types
-            let usernames = config_subject
-                .usernames
-                .map(|usernames| {
-                    usernames
-                        .into_iter()
-                        .map(SubjectProperty::Exactly)
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or(vec![SubjectProperty::Wildcard]);
-
-            // create ACL subject combinations
-            let subject_combination_ids = interfaces
-                .into_iter()
-                .cartesian_product(cert_common_names)
-                .cartesian_product(usernames)
-                .map(|((interface, cert_common_name), username)| {
-                    let subject = Subject {
-                        interface,
-                        cert_common_name,
-                        username,
-                    };
-                    subject_map_builder.insert_or_get(subject)
-                })
-                .collect();
-            subject_id_map.insert(config_subject.id.clone(), subject_combination_ids);
+            let subject_id = config_subject.id.clone();
+            let subject_combination_ids =
+                subject_map_builder.insert_config_subject(config_subject)?;
+            subject_id_map.insert(subject_id, subject_combination_ids);
         }
         // finally, handle policy content
         for (entry_id, entry) in policies.iter().enumerate() {
@@ -553,6 +969,7 @@ impl PolicyEnforcer {
                             for message in &rule.messages {
                                 for key_expr in &rule.key_exprs {
                                     policy_rules.push(PolicyRule {
+                                        rule_id: rule.id.clone(),
                                         subject_id: *subject_id,
                                         key_expr: key_expr.clone(),
                                         message: *message,
@@ -574,47 +991,65 @@ impl PolicyEnforcer {
 
     /**
      * Check each msg against the ACL ruleset for allow/deny
+     *
+     * The policies expanded from key expression templates for the transport are checked along
+     * with the ones of the subject.
      */
     pub fn policy_decision_point(
         &self,
         subject: usize,
+        templated: &PolicyMap,
         flow: InterceptorFlow,
         message: AclMessage,
         key_expr: &str,
-    ) -> ZResult<Permission> {
-        let policy_map = &self.policy_map;
-        if policy_map.is_empty() {
-            return Ok(self.default_permission);
+    ) -> ZResult<PolicyDecision> {
+        let default_decision = PolicyDecision {
+            permission: self.default_permission,
+            rule_id: None,
+        };
+        let mut policies = self
+            .policy_map
+            .get(&subject)
+            .into_iter()
+            .chain(templated.get(&subject))
+            .map(|policy| policy.flow(flow).action(message));
+        if policies.clone().next().is_none() {
+            return Ok(default_decision);
         }
-        match policy_map.get(&subject) {
-            Some(single_policy) => {
-                let deny_result = single_policy
-                    .flow(flow)
-                    .action(message)
-                    .deny
-                    .nodes_including(keyexpr::new(&key_expr)?)
-                    .count();
-                if deny_result != 0 {
-                    return Ok(Permission::Deny);
-                }
-                if self.default_permission == Permission::Allow {
-                    Ok(Permission::Allow)
-                } else {
-                    let allow_result = single_policy
-                        .flow(flow)
-                        .action(message)
-                        .allow
-                        .nodes_including(keyexpr::new(&key_expr)?)
-                        .count();
-
-                    if allow_result != 0 {
-                        Ok(Permission::Allow)
-                    } else {
-                        Ok(Permission::Deny)
-                    }
-                }
+        let key_expr = keyexpr::new(key_expr)?;
+        if let Some(rule_id) = policies
+            .clone()
+            .find_map(|policy| matching_rule(&policy.deny, key_expr))
+        {
+            return Ok(PolicyDecision {
+                permission: Permission::Deny,
+                rule_id: Some(rule_id),
+            });
+        }
+        if self.default_permission == Permission::Allow {
+            Ok(default_decision)
+        } else {
+            match policies.find_map(|policy| matching_rule(&policy.allow, key_expr)) {
+                Some(rule_id) => Ok(PolicyDecision {
+                    permission: Permission::Allow,
+                    rule_id: Some(rule_id),
+                }),
+                None => Ok(default_decision),
             }
-            None => Ok(self.default_permission),
         }
     }
 }
+
+/// Returns the id of a rule matching the key expression, if any.
+fn matching_rule(rules: &KeTreeRule, key_expr: &keyexpr) -> Option<Arc<str>> {
+    rules
+        .nodes_including(key_expr)
+        .find_map(|node| node.weight().cloned())
+}
+
+/// The permission of a message, along with the id of the rule that decided it if any.
+#[derive(Debug, Clone)]
+pub struct PolicyDecision {
+    pub permission: Permission,
+    pub rule_id: Option<Arc<str>>,
+}
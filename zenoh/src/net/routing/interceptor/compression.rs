@@ -0,0 +1,321 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::io::Read;
+
+use zenoh_buffers::{
+    buffer::{Buffer, SplitBuffer},
+    ZBuf, ZSlice,
+};
+use zenoh_config::{
+    CompressionAlgorithm, InterceptorFlow, PayloadCompressionAction, PayloadCompressionItemConf,
+};
+use zenoh_keyexpr::OwnedKeyExpr;
+use zenoh_protocol::{
+    network::{NetworkBody, Push, Response},
+    zenoh::{PushBody, Put, Reply, ResponseBody},
+};
+use zenoh_result::{bail, zerror, ZResult};
+
+use crate::net::routing::interceptor::*;
+
+/// Prefix of the encoding schema of compressed payloads, followed by the algorithm name.
+/// The original schema, if any, is appended after a `;` separator.
+const SCHEMA_PREFIX: &str = "zenoh-compression/";
+const SCHEMA_SEPARATOR: u8 = b';';
+/// The default maximum size of a decompressed payload.
+const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+pub(crate) fn compression_interceptor_factories(
+    config: &Vec<PayloadCompressionItemConf>,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    for pc in config {
+        res.push(Box::new(PayloadCompressionInterceptorFactory::new(
+            pc.clone(),
+        )?));
+    }
+
+    Ok(res)
+}
+
+pub struct PayloadCompressionInterceptorFactory {
+    interfaces: Option<Vec<String>>,
+    flow: InterceptorFlow,
+    action: PayloadCompressionAction,
+    key_exprs: Vec<OwnedKeyExpr>,
+    codec: PayloadCodec,
+    max_decompressed_size: usize,
+}
+
+impl PayloadCompressionInterceptorFactory {
+    pub fn new(conf: PayloadCompressionItemConf) -> ZResult<Self> {
+        let algorithm = conf.algorithm.unwrap_or(CompressionAlgorithm::Lz4);
+        if conf.level.is_some() && algorithm != CompressionAlgorithm::Zstd {
+            bail!(
+                "Payload compression: level is not supported by the {:?} algorithm",
+                algorithm
+            );
+        }
+        Ok(Self {
+            interfaces: conf.interfaces,
+            flow: conf.flow,
+            action: conf.action,
+            key_exprs: conf.key_exprs,
+            codec: PayloadCodec::new(algorithm, conf.level),
+            max_decompressed_size: conf
+                .max_decompressed_size
+                .unwrap_or(DEFAULT_MAX_DECOMPRESSED_SIZE),
+        })
+    }
+
+    fn interceptor(&self) -> Interceptor {
+        Box::new(ComputeOnMiss::new(PayloadCompressionInterceptor {
+            key_exprs: self.key_exprs.clone(),
+            action: self.action,
+            codec: self.codec,
+            max_decompressed_size: self.max_decompressed_size,
+        }))
+    }
+}
+
+impl InterceptorFactoryTrait for PayloadCompressionInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New payload compression transport unicast {:?}", transport);
+        if let Some(interfaces) = &self.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+
+        match self.flow {
+            InterceptorFlow::Ingress => (Some(self.interceptor()), None),
+            InterceptorFlow::Egress => (None, Some(self.interceptor())),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PayloadCodec {
+    Lz4,
+    Zstd { level: i32 },
+}
+
+impl PayloadCodec {
+    fn new(algorithm: CompressionAlgorithm, level: Option<i32>) -> Self {
+        match algorithm {
+            CompressionAlgorithm::Lz4 => Self::Lz4,
+            CompressionAlgorithm::Zstd => Self::Zstd {
+                level: level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL),
+            },
+        }
+    }
+
+    fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"lz4" => Some(Self::Lz4),
+            b"zstd" => Some(Self::Zstd {
+                level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            }),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Lz4 => "lz4",
+            Self::Zstd { .. } => "zstd",
+        }
+    }
+
+    fn compress(&self, input: &[u8]) -> ZResult<Vec<u8>> {
+        match self {
+            Self::Lz4 => Ok(lz4_flex::block::compress_prepend_size(input)),
+            Self::Zstd { level } => zstd::bulk::compress(input, *level)
+                .map_err(|e| zerror!("Compression error: {e}").into()),
+        }
+    }
+
+    /// Decompresses a payload, failing if it would decompress beyond `max_size` bytes.
+    fn decompress(&self, input: &[u8], max_size: usize) -> ZResult<Vec<u8>> {
+        match self {
+            Self::Lz4 => {
+                let (size, input) = lz4_flex::block::uncompressed_size(input)
+                    .map_err(|e| zerror!("Decompression error: {e}"))?;
+                if size > max_size {
+                    bail!("Decompressed size {size} exceeds the maximum of {max_size} bytes");
+                }
+                lz4_flex::block::decompress(input, size)
+                    .map_err(|e| zerror!("Decompression error: {e}").into())
+            }
+            Self::Zstd { .. } => {
+                let mut output = vec![];
+                zstd::stream::Decoder::new(input)
+                    .map_err(|e| zerror!("Decompression error: {e}"))?
+                    .take(max_size as u64 + 1)
+                    .read_to_end(&mut output)
+                    .map_err(|e| zerror!("Decompression error: {e}"))?;
+                if output.len() > max_size {
+                    bail!("Decompressed size exceeds the maximum of {max_size} bytes");
+                }
+                Ok(output)
+            }
+        }
+    }
+
+    /// Builds the encoding schema of a payload compressed with this codec.
+    fn tag(&self, schema: Option<&ZSlice>) -> ZSlice {
+        let mut tagged = format!("{SCHEMA_PREFIX}{}", self.name()).into_bytes();
+        if let Some(schema) = schema {
+            tagged.push(SCHEMA_SEPARATOR);
+            tagged.extend_from_slice(schema.as_slice());
+        }
+        tagged.into()
+    }
+
+    /// Retrieves the codec and the original encoding schema of a compressed payload.
+    fn untag(schema: &ZSlice) -> Option<(Self, Option<ZSlice>)> {
+        let tag = schema.as_slice().strip_prefix(SCHEMA_PREFIX.as_bytes())?;
+        match tag.iter().position(|b| *b == SCHEMA_SEPARATOR) {
+            Some(idx) => Some((
+                Self::from_name(&tag[..idx])?,
+                Some(tag[idx + 1..].to_vec().into()),
+            )),
+            None => Some((Self::from_name(tag)?, None)),
+        }
+    }
+}
+
+pub(crate) struct PayloadCompressionInterceptor {
+    key_exprs: Vec<OwnedKeyExpr>,
+    action: PayloadCompressionAction,
+    codec: PayloadCodec,
+    max_decompressed_size: usize,
+}
+
+impl PayloadCompressionInterceptor {
+    fn compress(&self, put: &mut Put) -> ZResult<()> {
+        #[cfg(feature = "shared-memory")]
+        if put.ext_shm.is_some() {
+            return Ok(());
+        }
+        if put
+            .encoding
+            .schema
+            .as_ref()
+            .is_some_and(|s| s.as_slice().starts_with(SCHEMA_PREFIX.as_bytes()))
+        {
+            // Already compressed
+            return Ok(());
+        }
+        let compressed = self.codec.compress(&put.payload.contiguous())?;
+        if compressed.len() < put.payload.len() {
+            put.encoding.schema = Some(self.codec.tag(put.encoding.schema.as_ref()));
+            put.payload = ZBuf::from(compressed);
+        }
+        Ok(())
+    }
+
+    fn decompress(&self, put: &mut Put) -> ZResult<()> {
+        let Some((codec, schema)) = put.encoding.schema.as_ref().and_then(PayloadCodec::untag)
+        else {
+            return Ok(());
+        };
+        put.payload =
+            ZBuf::from(codec.decompress(&put.payload.contiguous(), self.max_decompressed_size)?);
+        put.encoding.schema = schema;
+        Ok(())
+    }
+}
+
+impl InterceptorTrait for PayloadCompressionInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(
+            self.key_exprs.iter().any(|ke| key_expr.intersects(ke)),
+        ))
+    }
+
+    fn intercept(
+        &self,
+        mut ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        if !cache
+            .and_then(|c| c.downcast_ref::<bool>())
+            .is_some_and(|matching| *matching)
+        {
+            return Some(ctx);
+        }
+        let put = match &mut ctx.msg.body {
+            NetworkBody::Push(Push {
+                payload: PushBody::Put(put),
+                ..
+            })
+            | NetworkBody::Response(Response {
+                payload:
+                    ResponseBody::Reply(Reply {
+                        payload: PushBody::Put(put),
+                        ..
+                    }),
+                ..
+            }) => put,
+            _ => return Some(ctx),
+        };
+        match self.action {
+            PayloadCompressionAction::Compress => {
+                if let Err(e) = self.compress(put) {
+                    tracing::warn!("Payload compression failed on {:?}: {}", ctx.full_expr(), e);
+                }
+            }
+            PayloadCompressionAction::Decompress => {
+                // Payloads that cannot be decompressed within the limits are dropped rather
+                // than forwarded compressed to remotes unable to read them
+                if let Err(e) = self.decompress(put) {
+                    tracing::warn!(
+                        "Payload decompression failed on {:?}, dropping it: {}",
+                        ctx.full_expr(),
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(ctx)
+    }
+}
@@ -0,0 +1,351 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! User-provided interceptors.
+//!
+//! Custom interceptors are registered on a [`Runtime`](crate::net::runtime::Runtime),
+//! either by a plugin or when opening a [`Session`](crate::Session), and are
+//! instantiated for each transport established afterwards.
+
+use std::sync::Arc;
+
+use zenoh_buffers::ZBuf;
+use zenoh_config::{InterceptorFlow, WhatAmI, ZenohId};
+use zenoh_protocol::{
+    network::{DeclareBody, NetworkBody, Push, Request, Response},
+    zenoh::{PushBody, Reply, RequestBody, ResponseBody},
+};
+use zenoh_transport::unicast::authentication::AuthId;
+
+use crate::{bytes::ZBytes, net::routing::interceptor::*};
+
+/// The kind of an intercepted message.
+///
+/// New kinds may be added as the protocol evolves, matches on it should have a wildcard arm.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    Put,
+    Delete,
+    Query,
+    QueryCancel,
+    QueryCredit,
+    Reply,
+    ReplyErr,
+    ResponseFinal,
+    DeclareKeyExpr,
+    UndeclareKeyExpr,
+    DeclareSubscriber,
+    UndeclareSubscriber,
+    DeclareQueryable,
+    UndeclareQueryable,
+    DeclareToken,
+    UndeclareToken,
+    DeclareFinal,
+    Interest,
+    Oam,
+}
+
+impl MessageKind {
+    fn of(msg: &NetworkMessage) -> Self {
+        match &msg.body {
+            NetworkBody::Push(Push { payload, .. }) => match payload {
+                PushBody::Put(_) => Self::Put,
+                PushBody::Del(_) => Self::Delete,
+            },
+            NetworkBody::Request(Request {
+                payload: RequestBody::Query(_),
+                ..
+            }) => Self::Query,
+            NetworkBody::RequestCancel(_) => Self::QueryCancel,
+            NetworkBody::RequestCredit(_) => Self::QueryCredit,
+            NetworkBody::Response(Response { payload, .. }) => match payload {
+                ResponseBody::Reply(_) => Self::Reply,
+                ResponseBody::Err(_) => Self::ReplyErr,
+            },
+            NetworkBody::ResponseFinal(_) => Self::ResponseFinal,
+            NetworkBody::Interest(_) => Self::Interest,
+            NetworkBody::Declare(declare) => match &declare.body {
+                DeclareBody::DeclareKeyExpr(_) => Self::DeclareKeyExpr,
+                DeclareBody::UndeclareKeyExpr(_) => Self::UndeclareKeyExpr,
+                DeclareBody::DeclareSubscriber(_) => Self::DeclareSubscriber,
+                DeclareBody::UndeclareSubscriber(_) => Self::UndeclareSubscriber,
+                DeclareBody::DeclareQueryable(_) => Self::DeclareQueryable,
+                DeclareBody::UndeclareQueryable(_) => Self::UndeclareQueryable,
+                DeclareBody::DeclareToken(_) => Self::DeclareToken,
+                DeclareBody::UndeclareToken(_) => Self::UndeclareToken,
+                DeclareBody::DeclareFinal(_) => Self::DeclareFinal,
+            },
+            NetworkBody::OAM(_) => Self::Oam,
+        }
+    }
+}
+
+/// The metadata of the transport an interceptor is instantiated for.
+#[derive(Debug, Clone)]
+pub struct TransportMetadata {
+    zid: Option<ZenohId>,
+    whatami: Option<WhatAmI>,
+    is_multicast: bool,
+    interfaces: Vec<String>,
+    usernames: Vec<String>,
+    cert_common_names: Vec<String>,
+}
+
+impl TransportMetadata {
+    fn from_unicast(transport: &TransportUnicast) -> Self {
+        let mut usernames = vec![];
+        let mut cert_common_names = vec![];
+        for auth_id in transport.get_auth_ids().unwrap_or_default() {
+            match auth_id {
+                AuthId::Username(username) => usernames.push(username),
+                AuthId::CertCommonName(cert_common_name) => {
+                    cert_common_names.push(cert_common_name)
+                }
+                AuthId::PubKeyFingerprint(_) | AuthId::None => {}
+            }
+        }
+        Self {
+            zid: transport.get_zid().ok().map(Into::into),
+            whatami: transport.get_whatami().ok(),
+            is_multicast: false,
+            interfaces: transport
+                .get_links()
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|link| link.interfaces)
+                .collect(),
+            usernames,
+            cert_common_names,
+        }
+    }
+
+    fn from_multicast(transport: &TransportMulticast) -> Self {
+        Self {
+            zid: None,
+            whatami: None,
+            is_multicast: true,
+            interfaces: transport
+                .get_link()
+                .map(|link| link.interfaces)
+                .unwrap_or_default(),
+            usernames: vec![],
+            cert_common_names: vec![],
+        }
+    }
+
+    /// The zenoh id of the remote, None for multicast transports.
+    pub fn zid(&self) -> Option<ZenohId> {
+        self.zid
+    }
+
+    /// The kind of the remote, None for multicast transports.
+    pub fn whatami(&self) -> Option<WhatAmI> {
+        self.whatami
+    }
+
+    /// Whether the transport is a multicast transport.
+    pub fn is_multicast(&self) -> bool {
+        self.is_multicast
+    }
+
+    /// The network interfaces of the transport links.
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// The usernames the remote authenticated with.
+    pub fn usernames(&self) -> &[String] {
+        &self.usernames
+    }
+
+    /// The common names of the certificates the remote authenticated with.
+    pub fn cert_common_names(&self) -> &[String] {
+        &self.cert_common_names
+    }
+}
+
+/// A message going through a [`MessageInterceptor`].
+pub struct InterceptedMessage<'a> {
+    key_expr: Option<&'a KeyExpr<'static>>,
+    kind: MessageKind,
+    flow: InterceptorFlow,
+    msg: &'a mut NetworkMessage,
+}
+
+impl InterceptedMessage<'_> {
+    /// The full key expression of the message, if any and known by the router.
+    pub fn key_expr(&self) -> Option<&KeyExpr<'static>> {
+        self.key_expr
+    }
+
+    /// The kind of the message.
+    pub fn kind(&self) -> MessageKind {
+        self.kind
+    }
+
+    /// Whether the message is received from or sent to the transport.
+    pub fn flow(&self) -> InterceptorFlow {
+        self.flow
+    }
+
+    /// The payload of the message, if any.
+    pub fn payload(&self) -> Option<ZBytes> {
+        let payload = match &self.msg.body {
+            NetworkBody::Push(Push {
+                payload: PushBody::Put(put),
+                ..
+            }) => Some(&put.payload),
+            NetworkBody::Request(Request {
+                payload: RequestBody::Query(query),
+                ..
+            }) => query.ext_body.as_ref().map(|body| &body.payload),
+            NetworkBody::Response(Response { payload, .. }) => match payload {
+                ResponseBody::Reply(Reply {
+                    payload: PushBody::Put(put),
+                    ..
+                }) => Some(&put.payload),
+                ResponseBody::Reply(_) => None,
+                ResponseBody::Err(err) => Some(&err.payload),
+            },
+            _ => None,
+        };
+        payload.cloned().map(ZBytes::from)
+    }
+
+    /// Replaces the payload of the message.
+    ///
+    /// Returns false, leaving the message untouched, if the message has no payload.
+    pub fn set_payload<IntoZBytes: Into<ZBytes>>(&mut self, payload: IntoZBytes) -> bool {
+        let current = match &mut self.msg.body {
+            NetworkBody::Push(Push {
+                payload: PushBody::Put(put),
+                ..
+            }) => Some(&mut put.payload),
+            NetworkBody::Request(Request {
+                payload: RequestBody::Query(query),
+                ..
+            }) => query.ext_body.as_mut().map(|body| &mut body.payload),
+            NetworkBody::Response(Response { payload, .. }) => match payload {
+                ResponseBody::Reply(Reply {
+                    payload: PushBody::Put(put),
+                    ..
+                }) => Some(&mut put.payload),
+                ResponseBody::Reply(_) => None,
+                ResponseBody::Err(err) => Some(&mut err.payload),
+            },
+            _ => None,
+        };
+        match current {
+            Some(current) => {
+                *current = ZBuf::from(payload.into());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// An interceptor processing the messages of one transport in one direction.
+pub trait MessageInterceptor: Send + Sync {
+    /// Processes a message, which is dropped if false is returned.
+    fn intercept(&self, msg: &mut InterceptedMessage<'_>) -> bool;
+}
+
+pub type BoxedMessageInterceptor = Box<dyn MessageInterceptor>;
+
+/// A factory instantiating [`MessageInterceptor`]s for each new transport.
+pub trait MessageInterceptorFactory: Send + Sync {
+    /// Returns the ingress and egress interceptors of a new transport.
+    ///
+    /// Only the egress interceptor of multicast transports and the ingress interceptor
+    /// of multicast peers are used.
+    fn new_transport(
+        &self,
+        transport: &TransportMetadata,
+    ) -> (
+        Option<BoxedMessageInterceptor>,
+        Option<BoxedMessageInterceptor>,
+    );
+}
+
+pub(crate) struct CustomInterceptorFactory {
+    factory: Arc<dyn MessageInterceptorFactory>,
+}
+
+impl CustomInterceptorFactory {
+    pub(crate) fn new(factory: Arc<dyn MessageInterceptorFactory>) -> Self {
+        Self { factory }
+    }
+}
+
+fn custom_interceptor(flow: InterceptorFlow, interceptor: BoxedMessageInterceptor) -> Interceptor {
+    Box::new(ComputeOnMiss::new(CustomInterceptor { flow, interceptor }))
+}
+
+impl InterceptorFactoryTrait for CustomInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let (ingress, egress) = self
+            .factory
+            .new_transport(&TransportMetadata::from_unicast(transport));
+        (
+            ingress.map(|i| custom_interceptor(InterceptorFlow::Ingress, i)),
+            egress.map(|i| custom_interceptor(InterceptorFlow::Egress, i)),
+        )
+    }
+
+    fn new_transport_multicast(&self, transport: &TransportMulticast) -> Option<EgressInterceptor> {
+        let (_, egress) = self
+            .factory
+            .new_transport(&TransportMetadata::from_multicast(transport));
+        egress.map(|i| custom_interceptor(InterceptorFlow::Egress, i))
+    }
+
+    fn new_peer_multicast(&self, transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        let (ingress, _) = self
+            .factory
+            .new_transport(&TransportMetadata::from_multicast(transport));
+        ingress.map(|i| custom_interceptor(InterceptorFlow::Ingress, i))
+    }
+}
+
+struct CustomInterceptor {
+    flow: InterceptorFlow,
+    interceptor: BoxedMessageInterceptor,
+}
+
+impl InterceptorTrait for CustomInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(key_expr.clone().into_owned()))
+    }
+
+    fn intercept(
+        &self,
+        mut ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let key_expr = cache.and_then(|c| c.downcast_ref::<KeyExpr<'static>>());
+        let kind = MessageKind::of(&ctx.msg);
+        let mut msg = InterceptedMessage {
+            key_expr,
+            kind,
+            flow: self.flow,
+            msg: &mut ctx.msg,
+        };
+        self.interceptor.intercept(&mut msg).then_some(ctx)
+    }
+}
@@ -20,21 +20,41 @@
 //!
 mod access_control;
 use access_control::acl_interceptor_factories;
+pub(crate) use access_control::AccessControl;
 
 mod authorization;
-use std::any::Any;
+use std::{any::Any, sync::Arc};
 
 use zenoh_config::Config;
 use zenoh_protocol::network::NetworkMessage;
+#[cfg(not(feature = "transport_compression"))]
+use zenoh_result::bail;
 use zenoh_result::ZResult;
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
 
 use super::RoutingContext;
 use crate::api::key_expr::KeyExpr;
 
+pub(crate) mod audit;
+use crate::net::routing::interceptor::audit::{audit_interceptor_factories, AuditLog};
+
 pub mod downsampling;
 use crate::net::routing::interceptor::downsampling::downsampling_interceptor_factories;
 
+#[cfg(feature = "unstable")]
+pub mod custom;
+
+pub mod rate_limit;
+use crate::net::routing::interceptor::rate_limit::rate_limit_interceptor_factories;
+
+pub mod remapping;
+use crate::net::routing::interceptor::remapping::remapping_interceptor_factories;
+
+#[cfg(feature = "transport_compression")]
+pub mod compression;
+#[cfg(feature = "transport_compression")]
+use crate::net::routing::interceptor::compression::compression_interceptor_factories;
+
 pub(crate) trait InterceptorTrait {
     fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>>;
 
@@ -60,12 +80,31 @@ pub(crate) trait InterceptorFactoryTrait {
 
 pub(crate) type InterceptorFactory = Box<dyn InterceptorFactoryTrait + Send + Sync>;
 
-pub(crate) fn interceptor_factories(config: &Config) -> ZResult<Vec<InterceptorFactory>> {
+pub(crate) fn interceptor_factories(
+    config: &Config,
+    audit: Option<&Arc<AuditLog>>,
+    access_control: Option<&Arc<AccessControl>>,
+) -> ZResult<Vec<InterceptorFactory>> {
     let mut res: Vec<InterceptorFactory> = vec![];
     // Uncomment to log the interceptors initialisation
     // res.push(Box::new(LoggerInterceptor {}));
     res.extend(downsampling_interceptor_factories(config.downsampling())?);
-    res.extend(acl_interceptor_factories(config.access_control())?);
+    res.extend(acl_interceptor_factories(access_control, audit));
+    // Compression comes after access control for denied payloads not to be (de)compressed
+    #[cfg(feature = "transport_compression")]
+    res.extend(compression_interceptor_factories(
+        config.payload_compression(),
+    )?);
+    #[cfg(not(feature = "transport_compression"))]
+    if !config.payload_compression().is_empty() {
+        bail!("Payload compression requires the `transport_compression` feature");
+    }
+    res.extend(rate_limit_interceptor_factories(config.rate_limit())?);
+    res.extend(audit_interceptor_factories(audit));
+    // Remapping comes last for the other interceptors to see the key expressions before remapping
+    res.extend(remapping_interceptor_factories(
+        config.key_expr_remapping(),
+    )?);
     Ok(res)
 }
 
@@ -80,6 +119,31 @@ impl InterceptorsChain {
             interceptors: vec![],
         }
     }
+
+    /// Runs the interceptors of the chain starting from the one at position `start`.
+    pub(crate) fn intercept_from(
+        &self,
+        start: usize,
+        mut ctx: RoutingContext<NetworkMessage>,
+        caches: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let caches =
+            caches.and_then(|i| i.downcast_ref::<Vec<Option<Box<dyn Any + Send + Sync>>>>());
+        for (idx, interceptor) in self.interceptors.iter().enumerate().skip(start) {
+            let cache = caches
+                .and_then(|caches| caches.get(idx).map(|k| k.as_ref()))
+                .flatten();
+            ctx.interceptor_idx = idx;
+            match interceptor.intercept(ctx, cache) {
+                Some(newctx) => ctx = newctx,
+                None => {
+                    tracing::trace!("Msg intercepted!");
+                    return None;
+                }
+            }
+        }
+        Some(ctx)
+    }
 }
 
 impl From<Vec<Interceptor>> for InterceptorsChain {
@@ -100,24 +164,10 @@ impl InterceptorTrait for InterceptorsChain {
 
     fn intercept<'a>(
         &self,
-        mut ctx: RoutingContext<NetworkMessage>,
+        ctx: RoutingContext<NetworkMessage>,
         caches: Option<&Box<dyn Any + Send + Sync>>,
     ) -> Option<RoutingContext<NetworkMessage>> {
-        let caches =
-            caches.and_then(|i| i.downcast_ref::<Vec<Option<Box<dyn Any + Send + Sync>>>>());
-        for (idx, interceptor) in self.interceptors.iter().enumerate() {
-            let cache = caches
-                .and_then(|caches| caches.get(idx).map(|k| k.as_ref()))
-                .flatten();
-            match interceptor.intercept(ctx, cache) {
-                Some(newctx) => ctx = newctx,
-                None => {
-                    tracing::trace!("Msg intercepted!");
-                    return None;
-                }
-            }
-        }
-        Some(ctx)
+        self.intercept_from(0, ctx, caches)
     }
 }
 
@@ -0,0 +1,376 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::sync::Mutex;
+
+use tokio::time::{Duration, Instant};
+use zenoh_buffers::buffer::Buffer;
+use zenoh_config::{InterceptorFlow, RateLimitItemConf, RateLimitStrategy};
+use zenoh_core::zlock;
+use zenoh_keyexpr::OwnedKeyExpr;
+use zenoh_protocol::{
+    network::{NetworkBody, NetworkMessage, Push, Request, Response},
+    zenoh::{PushBody, Reply, RequestBody, ResponseBody},
+};
+use zenoh_result::{bail, ZResult};
+
+use crate::net::{
+    primitives::resume_ingress,
+    routing::interceptor::{
+        authorization::{SubjectMapBuilder, SubjectQuery, SubjectStore},
+        *,
+    },
+};
+
+const DEFAULT_MAX_DELAY_MS: u64 = 1000;
+
+pub(crate) fn rate_limit_interceptor_factories(
+    config: &Vec<RateLimitItemConf>,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    for rl in config {
+        res.push(Box::new(RateLimitInterceptorFactory::new(rl.clone())?));
+    }
+
+    Ok(res)
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limit {
+    rate: f64,
+    burst: f64,
+}
+
+impl Limit {
+    fn new(name: &str, rate: Option<f64>, burst: Option<f64>) -> ZResult<Option<Self>> {
+        let Some(rate) = rate else {
+            if burst.is_some() {
+                bail!("Rate limit: burst of {name} is set without a rate of {name}");
+            }
+            return Ok(None);
+        };
+        if !(rate.is_finite() && rate > 0.0) {
+            bail!("Rate limit: rate of {name} must be a positive number, got {rate}");
+        }
+        let burst = burst.unwrap_or(rate);
+        if !(burst.is_finite() && burst >= 1.0) {
+            bail!("Rate limit: burst of {name} must be a number greater than or equal to 1, got {burst}");
+        }
+        Ok(Some(Self { rate, burst }))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RateLimits {
+    messages: Option<Limit>,
+    bytes: Option<Limit>,
+    strategy: RateLimitStrategy,
+    max_delay: Duration,
+}
+
+pub struct RateLimitInterceptorFactory {
+    interfaces: Option<Vec<String>>,
+    subjects: Option<SubjectStore>,
+    flow: InterceptorFlow,
+    key_exprs: Option<Vec<OwnedKeyExpr>>,
+    limits: RateLimits,
+}
+
+impl RateLimitInterceptorFactory {
+    pub fn new(conf: RateLimitItemConf) -> ZResult<Self> {
+        let messages = Limit::new("messages", conf.messages_per_second, conf.burst_messages)?;
+        let bytes = Limit::new("bytes", conf.bytes_per_second, conf.burst_bytes)?;
+        if messages.is_none() && bytes.is_none() {
+            bail!("Rate limit: at least one of `messages_per_second` or `bytes_per_second` must be set");
+        }
+        match conf.strategy {
+            RateLimitStrategy::Drop => {
+                if conf.max_delay_ms.is_some() {
+                    bail!("Rate limit: `max_delay_ms` is only supported by the `delay` strategy");
+                }
+            }
+            RateLimitStrategy::Delay => {
+                // Delayed messages are re-injected in the routing from their incoming face
+                if matches!(conf.flow, InterceptorFlow::Egress) {
+                    bail!("Rate limit: the `delay` strategy is only supported on the ingress flow");
+                }
+            }
+        }
+
+        let subjects = match conf.subjects {
+            Some(subjects) => {
+                if subjects.is_empty() {
+                    bail!("Rate limit: subjects list cannot be empty");
+                }
+                let mut subject_map_builder = SubjectMapBuilder::new();
+                for subject in subjects {
+                    subject_map_builder.insert_config_subject(subject)?;
+                }
+                Some(subject_map_builder.build())
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            interfaces: conf.interfaces,
+            subjects,
+            flow: conf.flow,
+            key_exprs: conf.key_exprs,
+            limits: RateLimits {
+                messages,
+                bytes,
+                strategy: conf.strategy,
+                max_delay: Duration::from_millis(conf.max_delay_ms.unwrap_or(DEFAULT_MAX_DELAY_MS)),
+            },
+        })
+    }
+
+    fn interceptor(&self) -> Interceptor {
+        Box::new(ComputeOnMiss::new(RateLimitInterceptor::new(
+            self.key_exprs.clone(),
+            self.limits,
+        )))
+    }
+}
+
+impl InterceptorFactoryTrait for RateLimitInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New rate limiter transport unicast {:?}", transport);
+        if let Some(interfaces) = &self.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+
+        if let Some(subjects) = &self.subjects {
+            match SubjectQuery::from_transport_unicast(transport) {
+                Ok(queries) => {
+                    if !queries.iter().any(|query| subjects.query(query).is_some()) {
+                        return (None, None);
+                    }
+                }
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    return (None, None);
+                }
+            }
+        }
+
+        match self.flow {
+            InterceptorFlow::Ingress => (Some(self.interceptor()), None),
+            InterceptorFlow::Egress => (None, Some(self.interceptor())),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+struct TokenBucket {
+    limit: Limit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: Limit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.rate).min(self.limit.burst);
+        self.last_refill = now;
+    }
+
+    /// Time to wait before `amount` tokens are available.
+    /// Amounts larger than the burst only wait for a full bucket.
+    fn wait(&self, amount: f64) -> Duration {
+        let missing = amount.min(self.limit.burst) - self.tokens;
+        if missing > 0.0 {
+            Duration::from_secs_f64(missing / self.limit.rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    fn consume(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+}
+
+struct RateLimitState {
+    messages: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+pub(crate) struct RateLimitInterceptor {
+    key_exprs: Option<Vec<OwnedKeyExpr>>,
+    strategy: RateLimitStrategy,
+    max_delay: Duration,
+    state: Mutex<RateLimitState>,
+    delayed: Option<flume::Sender<(Instant, RoutingContext<NetworkMessage>)>>,
+}
+
+impl RateLimitInterceptor {
+    fn new(key_exprs: Option<Vec<OwnedKeyExpr>>, limits: RateLimits) -> Self {
+        // Delayed messages are queued in the order they consumed the budget, so that their
+        // deadlines are increasing, and re-injected by a task once their deadline is reached.
+        let delayed = (limits.strategy == RateLimitStrategy::Delay).then(|| {
+            let (tx, rx) = flume::unbounded::<(Instant, RoutingContext<NetworkMessage>)>();
+            zenoh_runtime::ZRuntime::Net.spawn(async move {
+                while let Ok((deadline, ctx)) = rx.recv_async().await {
+                    tokio::time::sleep_until(deadline).await;
+                    resume_ingress(ctx);
+                }
+            });
+            tx
+        });
+        Self {
+            key_exprs,
+            strategy: limits.strategy,
+            max_delay: limits.max_delay,
+            state: Mutex::new(RateLimitState {
+                messages: limits.messages.map(TokenBucket::new),
+                bytes: limits.bytes.map(TokenBucket::new),
+            }),
+            delayed,
+        }
+    }
+
+    /// Returns the time the message has to be delayed, or None if it has to be dropped.
+    fn admit(&self, state: &mut RateLimitState, now: Instant, len: usize) -> Option<Duration> {
+        let mut wait = Duration::ZERO;
+        if let Some(messages) = state.messages.as_mut() {
+            messages.refill(now);
+            wait = wait.max(messages.wait(1.0));
+        }
+        if let Some(bytes) = state.bytes.as_mut() {
+            bytes.refill(now);
+            wait = wait.max(bytes.wait(len as f64));
+        }
+        if !wait.is_zero() && (self.strategy == RateLimitStrategy::Drop || wait > self.max_delay) {
+            return None;
+        }
+        if let Some(messages) = state.messages.as_mut() {
+            messages.consume(1.0);
+        }
+        if let Some(bytes) = state.bytes.as_mut() {
+            bytes.consume(len as f64);
+        }
+        Some(wait)
+    }
+}
+
+/// Returns the payload length of the data messages, or None for other messages.
+fn payload_len(body: &NetworkBody) -> Option<usize> {
+    match body {
+        NetworkBody::Push(Push { payload, .. }) => match payload {
+            PushBody::Put(put) => Some(put.payload.len()),
+            PushBody::Del(_) => Some(0),
+        },
+        NetworkBody::Request(Request {
+            payload: RequestBody::Query(query),
+            ..
+        }) => Some(query.ext_body.as_ref().map_or(0, |body| body.payload.len())),
+        NetworkBody::Response(Response { payload, .. }) => match payload {
+            ResponseBody::Reply(Reply {
+                payload: PushBody::Put(put),
+                ..
+            }) => Some(put.payload.len()),
+            ResponseBody::Reply(_) => Some(0),
+            ResponseBody::Err(err) => Some(err.payload.len()),
+        },
+        _ => None,
+    }
+}
+
+impl InterceptorTrait for RateLimitInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(
+            self.key_exprs.as_ref().map_or(true, |key_exprs| {
+                key_exprs.iter().any(|ke| key_expr.intersects(ke))
+            }),
+        ))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let matching = cache
+            .and_then(|c| c.downcast_ref::<bool>())
+            .copied()
+            .unwrap_or(self.key_exprs.is_none());
+        if !matching {
+            return Some(ctx);
+        }
+        let Some(len) = payload_len(&ctx.msg.body) else {
+            return Some(ctx);
+        };
+        let now = Instant::now();
+        let mut state = zlock!(self.state);
+        match self.admit(&mut state, now, len) {
+            Some(wait) if wait.is_zero() => Some(ctx),
+            Some(wait) => {
+                tracing::trace!(
+                    "Rate limit exceeded, delaying message {:?} by {:?}",
+                    ctx.full_expr(),
+                    wait
+                );
+                if let Some(delayed) = self.delayed.as_ref() {
+                    // Sent while holding the state lock to keep the queue ordered by deadline
+                    let _ = delayed.send((now + wait, ctx));
+                }
+                None
+            }
+            None => {
+                tracing::trace!(
+                    "Rate limit exceeded, dropping message {:?}",
+                    ctx.full_expr()
+                );
+                None
+            }
+        }
+    }
+}
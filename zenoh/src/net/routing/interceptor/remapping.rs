@@ -0,0 +1,230 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::cell::OnceCell;
+
+use zenoh_config::{InterceptorFlow, KeyExprRemappingItemConf, KeyExprRemappingRuleConf};
+use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
+use zenoh_protocol::{
+    core::WireExpr,
+    network::{DeclareBody, NetworkBody},
+};
+use zenoh_result::{bail, ZResult};
+
+use crate::net::routing::interceptor::{
+    authorization::{SubjectMapBuilder, SubjectQuery, SubjectStore},
+    *,
+};
+
+pub(crate) fn remapping_interceptor_factories(
+    config: &Vec<KeyExprRemappingItemConf>,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    for km in config {
+        res.push(Box::new(RemappingInterceptorFactory::new(km.clone())?));
+    }
+
+    Ok(res)
+}
+
+pub struct RemappingInterceptorFactory {
+    interfaces: Option<Vec<String>>,
+    subjects: Option<SubjectStore>,
+    flow: InterceptorFlow,
+    rules: Vec<KeyExprRemappingRuleConf>,
+}
+
+impl RemappingInterceptorFactory {
+    pub fn new(conf: KeyExprRemappingItemConf) -> ZResult<Self> {
+        if conf.rules.is_empty() {
+            bail!("Key expression remapping: rules list cannot be empty");
+        }
+        for rule in &conf.rules {
+            for prefix in [&rule.from, &rule.to] {
+                if prefix.is_wild() {
+                    bail!(
+                        "Key expression remapping: prefix `{}` cannot contain wildcards",
+                        prefix
+                    );
+                }
+            }
+        }
+
+        let subjects = match conf.subjects {
+            Some(subjects) => {
+                if subjects.is_empty() {
+                    bail!("Key expression remapping: subjects list cannot be empty");
+                }
+                let mut subject_map_builder = SubjectMapBuilder::new();
+                for subject in subjects {
+                    subject_map_builder.insert_config_subject(subject)?;
+                }
+                Some(subject_map_builder.build())
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            interfaces: conf.interfaces,
+            subjects,
+            flow: conf.flow,
+            rules: conf.rules,
+        })
+    }
+
+    fn interceptor(&self) -> Interceptor {
+        Box::new(ComputeOnMiss::new(RemappingInterceptor {
+            rules: self.rules.clone(),
+        }))
+    }
+}
+
+impl InterceptorFactoryTrait for RemappingInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!(
+            "New key expression remapping transport unicast {:?}",
+            transport
+        );
+        if let Some(interfaces) = &self.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+
+        if let Some(subjects) = &self.subjects {
+            match SubjectQuery::from_transport_unicast(transport) {
+                Ok(queries) => {
+                    if !queries.iter().any(|query| subjects.query(query).is_some()) {
+                        return (None, None);
+                    }
+                }
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    return (None, None);
+                }
+            }
+        }
+
+        match self.flow {
+            InterceptorFlow::Ingress => (Some(self.interceptor()), None),
+            InterceptorFlow::Egress => (None, Some(self.interceptor())),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct RemappingInterceptor {
+    rules: Vec<KeyExprRemappingRuleConf>,
+}
+
+impl RemappingInterceptor {
+    /// Returns the key expression remapped by the first matching rule, if any.
+    fn remap(&self, key_expr: &keyexpr) -> Option<OwnedKeyExpr> {
+        for rule in &self.rules {
+            let Some(rest) = key_expr.as_str().strip_prefix(rule.from.as_str()) else {
+                continue;
+            };
+            if rest.is_empty() {
+                return Some(rule.to.clone());
+            }
+            // Only match whole chunks: `a/b` is a prefix of `a/b/c` but not of `a/bc`
+            if let Some(rest) = rest.strip_prefix('/') {
+                return keyexpr::new(rest).ok().map(|rest| &rule.to / rest);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the wire expression to be remapped in a message.
+///
+/// Key expression declarations are left untouched for the mappings to remain
+/// in the original namespace, the messages using them being sent with their
+/// full remapped key expression instead.
+fn remappable_wire_expr(body: &mut NetworkBody) -> Option<&mut WireExpr<'static>> {
+    match body {
+        NetworkBody::Push(m) => Some(&mut m.wire_expr),
+        NetworkBody::Request(m) => Some(&mut m.wire_expr),
+        NetworkBody::Response(m) => Some(&mut m.wire_expr),
+        NetworkBody::Interest(m) => m.wire_expr.as_mut(),
+        NetworkBody::Declare(m) => match &mut m.body {
+            DeclareBody::DeclareSubscriber(m) => Some(&mut m.wire_expr),
+            DeclareBody::UndeclareSubscriber(m) => Some(&mut m.ext_wire_expr.wire_expr),
+            DeclareBody::DeclareQueryable(m) => Some(&mut m.wire_expr),
+            DeclareBody::UndeclareQueryable(m) => Some(&mut m.ext_wire_expr.wire_expr),
+            DeclareBody::DeclareToken(m) => Some(&mut m.wire_expr),
+            DeclareBody::UndeclareToken(m) => Some(&mut m.ext_wire_expr.wire_expr),
+            DeclareBody::DeclareKeyExpr(_)
+            | DeclareBody::UndeclareKeyExpr(_)
+            | DeclareBody::DeclareFinal(_) => None,
+        },
+        NetworkBody::ResponseFinal(_)
+        | NetworkBody::RequestCancel(_)
+        | NetworkBody::RequestCredit(_)
+        | NetworkBody::OAM(_) => None,
+    }
+}
+
+impl InterceptorTrait for RemappingInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.remap(key_expr)))
+    }
+
+    fn intercept(
+        &self,
+        mut ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let Some(Some(remapped)) = cache.and_then(|c| c.downcast_ref::<Option<OwnedKeyExpr>>())
+        else {
+            return Some(ctx);
+        };
+        let Some(wire_expr) = remappable_wire_expr(&mut ctx.msg.body) else {
+            return Some(ctx);
+        };
+        tracing::trace!("Remapping {:?} to {}", ctx.full_expr.get(), remapped);
+        *wire_expr = WireExpr {
+            scope: 0,
+            suffix: remapped.to_string().into(),
+            mapping: wire_expr.mapping,
+        };
+        ctx.prefix = OnceCell::new();
+        ctx.full_expr = OnceCell::from(remapped.to_string());
+        Some(ctx)
+    }
+}
@@ -22,8 +22,13 @@ pub mod hat;
 pub mod interceptor;
 pub mod router;
 
-use std::{cell::OnceCell, sync::Arc};
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
+use zenoh_core::zlock;
 use zenoh_protocol::{
     core::{key_expr::OwnedKeyExpr, WireExpr},
     network::NetworkMessage,
@@ -32,12 +37,54 @@ use zenoh_protocol::{
 use self::{dispatcher::face::Face, router::Resource};
 use super::runtime;
 
+/// The turns of the next round-robin queries, by key expression.
+#[derive(Default)]
+pub(crate) struct RoundRobinTurns(Mutex<RoundRobinTurnsInner>);
+
+#[derive(Default)]
+struct RoundRobinTurnsInner {
+    /// The next turn and the last use of each key expression.
+    turns: HashMap<String, (usize, u64)>,
+    uses: u64,
+}
+
+impl RoundRobinTurns {
+    /// The number of key expressions beyond which the least recently queried one is forgotten.
+    const CAPACITY: usize = 1024;
+
+    /// Returns the turn of the next round-robin query on `key_expr`.
+    pub(crate) fn next(&self, key_expr: &str) -> usize {
+        let mut inner = zlock!(self.0);
+        inner.uses += 1;
+        let uses = inner.uses;
+        if let Some((turn, last_use)) = inner.turns.get_mut(key_expr) {
+            *turn = turn.wrapping_add(1);
+            *last_use = uses;
+            return *turn;
+        }
+        if inner.turns.len() >= Self::CAPACITY {
+            let lru = inner
+                .turns
+                .iter()
+                .min_by_key(|(_, (_, last_use))| *last_use)
+                .map(|(key_expr, _)| key_expr.clone());
+            if let Some(lru) = lru {
+                inner.turns.remove(&lru);
+            }
+        }
+        inner.turns.insert(key_expr.to_owned(), (0, uses));
+        0
+    }
+}
+
 pub(crate) struct RoutingContext<Msg> {
     pub(crate) msg: Msg,
     pub(crate) inface: OnceCell<Face>,
     pub(crate) outface: OnceCell<Face>,
     pub(crate) prefix: OnceCell<Arc<Resource>>,
     pub(crate) full_expr: OnceCell<String>,
+    /// The position of the interceptor currently processing the message in its chain.
+    pub(crate) interceptor_idx: usize,
 }
 
 impl<Msg> RoutingContext<Msg> {
@@ -49,6 +96,7 @@ impl<Msg> RoutingContext<Msg> {
             outface: OnceCell::new(),
             prefix: OnceCell::new(),
             full_expr: OnceCell::new(),
+            interceptor_idx: 0,
         }
     }
 
@@ -60,6 +108,7 @@ impl<Msg> RoutingContext<Msg> {
             outface: OnceCell::new(),
             prefix: OnceCell::new(),
             full_expr: OnceCell::new(),
+            interceptor_idx: 0,
         }
     }
 
@@ -71,6 +120,7 @@ impl<Msg> RoutingContext<Msg> {
             outface: OnceCell::from(outface),
             prefix: OnceCell::new(),
             full_expr: OnceCell::new(),
+            interceptor_idx: 0,
         }
     }
 
@@ -82,6 +132,7 @@ impl<Msg> RoutingContext<Msg> {
             outface: OnceCell::new(),
             prefix: OnceCell::new(),
             full_expr: OnceCell::from(expr),
+            interceptor_idx: 0,
         }
     }
 
@@ -105,6 +156,8 @@ impl RoutingContext<NetworkMessage> {
             NetworkBody::Request(m) => Some(&m.wire_expr),
             NetworkBody::Response(m) => Some(&m.wire_expr),
             NetworkBody::ResponseFinal(_) => None,
+            NetworkBody::RequestCancel(_) => None,
+            NetworkBody::RequestCredit(_) => None,
             NetworkBody::Interest(m) => m.wire_expr.as_ref(),
             NetworkBody::Declare(m) => match &m.body {
                 DeclareBody::DeclareKeyExpr(m) => Some(&m.wire_expr),
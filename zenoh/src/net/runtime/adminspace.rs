@@ -30,14 +30,17 @@ use zenoh_protocol::{
     network::{
         declare::{queryable::ext::QueryableInfoType, QueryableId},
         ext, Declare, DeclareBody, DeclareQueryable, DeclareSubscriber, Interest, Push, Request,
-        Response, ResponseFinal,
+        RequestCancel, RequestCredit, Response, ResponseFinal,
     },
     zenoh::{PushBody, RequestBody},
 };
 use zenoh_result::ZResult;
 use zenoh_transport::unicast::TransportUnicast;
 
-use super::{routing::dispatcher::face::Face, Runtime};
+use super::{
+    routing::{dispatcher::face::Face, interceptor::AccessControl},
+    Runtime,
+};
 #[cfg(feature = "plugins")]
 use crate::api::plugins::PluginsManager;
 use crate::{
@@ -160,6 +163,12 @@ impl AdminSpace {
                 .unwrap(),
             Arc::new(metrics),
         );
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/certificates")
+                .try_into()
+                .unwrap(),
+            Arc::new(certificates_data),
+        );
         if runtime.state.whatami == WhatAmI::Router {
             handlers.insert(
                 format!("@/{zid_str}/{whatami_str}/linkstate/routers")
@@ -341,6 +350,21 @@ impl AdminSpace {
         });
     }
 
+    /// Checks that a change of the `access_control` configuration results in a valid policy,
+    /// for an invalid change to be rejected rather than being ignored upon reload.
+    fn validate_access_control_change(&self, key: &str, json: Option<&str>) -> ZResult<()> {
+        if !key.trim_start_matches('/').starts_with("access_control") {
+            return Ok(());
+        }
+        let mut config = self.context.runtime.state.config.lock().clone();
+        match json {
+            Some(json) => config.insert_json5(key, json)?,
+            None => config.0.remove(key)?,
+        }
+        AccessControl::validate(config.0.access_control())?;
+        Ok(())
+    }
+
     pub fn key_expr_to_string<'a>(&self, key_expr: &'a WireExpr) -> ZResult<KeyExpr<'a>> {
         if key_expr.scope == EMPTY_EXPR_ID {
             key_expr.suffix.as_ref().try_into()
@@ -402,7 +426,10 @@ impl Primitives for AdminSpace {
                             key,
                             json
                         );
-                        if let Err(e) = self.context.runtime.state.config.insert_json5(key, json) {
+                        if let Err(e) = self
+                            .validate_access_control_change(key, Some(json))
+                            .and_then(|_| self.context.runtime.state.config.insert_json5(key, json))
+                        {
                             error!(
                                 "Error inserting conf value @/{}/{}/config/{} : {} - {}",
                                 self.context.runtime.state.zid,
@@ -425,7 +452,10 @@ impl Primitives for AdminSpace {
                         self.context.runtime.state.whatami,
                         key
                     );
-                    if let Err(e) = self.context.runtime.state.config.remove(key) {
+                    if let Err(e) = self
+                        .validate_access_control_change(key, None)
+                        .and_then(|_| self.context.runtime.state.config.remove(key))
+                    {
                         tracing::error!("Error deleting conf value {} : {}", msg.wire_expr, e)
                     }
                 }
@@ -475,6 +505,10 @@ impl Primitives for AdminSpace {
                         qid: msg.id,
                         zid: zid.into(),
                         primitives,
+                        #[cfg(feature = "unstable")]
+                        max_replies: msg.ext_budget,
+                        #[cfg(feature = "unstable")]
+                        control: None,
                     }),
                     eid: self.queryable_id,
                     value: query
@@ -496,6 +530,14 @@ impl Primitives for AdminSpace {
         trace!("recv Response {:?}", msg);
     }
 
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        trace!("recv RequestCancel {:?}", msg);
+    }
+
+    fn send_request_credit(&self, msg: RequestCredit) {
+        trace!("recv RequestCredit {:?}", msg);
+    }
+
     fn send_response_final(&self, msg: ResponseFinal) {
         trace!("recv ResponseFinal {:?}", msg);
     }
@@ -531,6 +573,16 @@ impl crate::net::primitives::EPrimitives for AdminSpace {
         (self as &dyn Primitives).send_response(msg)
     }
 
+    #[inline]
+    fn send_request_cancel(&self, msg: RequestCancel) {
+        (self as &dyn Primitives).send_request_cancel(msg)
+    }
+
+    #[inline]
+    fn send_request_credit(&self, msg: RequestCredit) {
+        (self as &dyn Primitives).send_request_credit(msg)
+    }
+
     #[inline]
     fn send_response_final(&self, msg: ResponseFinal) {
         (self as &dyn Primitives).send_response_final(msg)
@@ -680,6 +732,44 @@ zenoh_build{{version="{}"}} 1
     }
 }
 
+fn certificates_data(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/certificates",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+
+    let certificates: Vec<serde_json::Value> = zenoh_runtime::ZRuntime::Net
+        .block_in_place(context.runtime.manager().get_certificates_unicast())
+        .iter()
+        .map(|certificate| {
+            json!({
+                "locator": certificate.locator.as_str(),
+                "subject": certificate.subject,
+                "not_after": humantime::format_rfc3339_seconds(certificate.not_after).to_string(),
+                "loaded_at": humantime::format_rfc3339_seconds(certificate.loaded_at).to_string(),
+            })
+        })
+        .collect();
+
+    tracing::trace!("AdminSpace certificates_data: {:?}", certificates);
+    let payload = match serde_json::to_vec(&certificates) {
+        Ok(bytes) => ZBytes::from(bytes),
+        Err(e) => {
+            tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = query
+        .reply(reply_key, payload)
+        .encoding(Encoding::APPLICATION_JSON)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
 fn routers_linkstate_data(context: &AdminContext, query: Query) {
     let reply_key: OwnedKeyExpr = format!(
         "@/{}/{}/linkstate/routers",
@@ -37,7 +37,7 @@ use futures::{stream::StreamExt, Future};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uhlc::{HLCBuilder, HLC};
-use zenoh_config::{unwrap_or_default, ModeDependent, ZenohId};
+use zenoh_config::{unwrap_or_default, AclConfig, ModeDependent, ZenohId};
 use zenoh_link::{EndPoint, Link};
 use zenoh_plugin_trait::{PluginStartArgs, StructVersion};
 use zenoh_protocol::{
@@ -62,6 +62,10 @@ use super::{primitives::DeMux, routing, routing::router::Router};
 use crate::api::loader::{load_plugins, start_plugins};
 #[cfg(feature = "plugins")]
 use crate::api::plugins::PluginsManager;
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::custom::{
+    CustomInterceptorFactory, MessageInterceptorFactory,
+};
 #[cfg(feature = "internal")]
 use crate::session::CloseBuilder;
 use crate::{
@@ -195,6 +199,12 @@ impl RuntimeBuilder {
         *handler.runtime.write().unwrap() = Runtime::downgrade(&runtime);
         get_mut_unchecked(&mut runtime.state.router.clone()).init_link_state(runtime.clone())?;
 
+        // Audit log
+        let audit = zread!(runtime.state.router.tables.tables).audit.clone();
+        if let Some(audit) = audit {
+            audit.start(&runtime);
+        }
+
         // Admin space
         if start_admin_space {
             AdminSpace::start(&runtime, LONG_VERSION.clone()).await;
@@ -221,6 +231,12 @@ impl RuntimeBuilder {
                                             tracing::error!("Error updating peers: {}", e);
                                         }
                                     }
+                                    if event.trim_start_matches('/').starts_with("access_control") {
+                                        let acl_config = runtime2.config().lock().0.access_control().clone();
+                                        if let Err(e) = runtime2.update_access_control(acl_config).await {
+                                            tracing::error!("Error updating access control: {}", e);
+                                        }
+                                    }
                                 },
                                 None => { break; }
                             }
@@ -267,6 +283,15 @@ impl Runtime {
         zwrite!(self.state.transport_handlers).push(handler);
     }
 
+    /// Registers a custom interceptor factory.
+    /// It only applies to the transports established after its registration.
+    #[cfg(feature = "unstable")]
+    pub fn add_interceptor_factory(&self, factory: Arc<dyn MessageInterceptorFactory>) {
+        zwrite!(self.state.router.tables.tables)
+            .interceptors
+            .push(Box::new(CustomInterceptorFactory::new(factory)));
+    }
+
     #[inline]
     pub fn next_id(&self) -> u32 {
         self.state.next_id.fetch_add(1, Ordering::SeqCst)
@@ -324,6 +349,58 @@ impl Runtime {
         &self.state.config
     }
 
+    /// Reloads the access control policy from the given configuration, which replaces the
+    /// `access_control` section of the runtime configuration once the policy is applied.
+    ///
+    /// Both the policy and the configuration are left unchanged if the new one is invalid.
+    /// A default configuration disables the access control, as removing the section would.
+    pub async fn update_access_control(&self, acl_config: AclConfig) -> ZResult<()> {
+        let access_control = zread!(self.state.router.tables.tables)
+            .access_control
+            .clone();
+        let denied = match access_control {
+            Some(access_control) => access_control.reload(&acl_config)?,
+            None if acl_config.enabled => {
+                bail!("Access control can only be reloaded if it was enabled at startup")
+            }
+            None => vec![],
+        };
+        self.state
+            .config
+            .lock()
+            .0
+            .set_access_control(acl_config)
+            .map_err(|_| zerror!("Invalid access control configuration"))?;
+        for transport in denied {
+            if let Err(e) = transport.close().await {
+                tracing::error!("Error closing denied transport: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the access control policy allows a user to send a message on a key
+    /// expression, which is always the case when access control is disabled.
+    ///
+    /// This is meant for plugins sending messages on behalf of users that are not connected
+    /// through a transport (e.g. HTTP clients): the message is checked as if it was received on
+    /// a transport authenticated with `username` from `remote_ip`.
+    #[cfg(feature = "internal")]
+    pub fn authorize(
+        &self,
+        username: Option<&str>,
+        remote_ip: Option<std::net::IpAddr>,
+        message: zenoh_config::AclMessage,
+        key_expr: &zenoh_protocol::core::key_expr::keyexpr,
+    ) -> bool {
+        let access_control = zread!(self.state.router.tables.tables)
+            .access_control
+            .clone();
+        access_control.map_or(true, |access_control| {
+            access_control.authorize(username, remote_ip, message, key_expr.as_str())
+        })
+    }
+
     pub fn hlc(&self) -> Option<&HLC> {
         self.state.hlc.as_ref().map(Arc::as_ref)
     }
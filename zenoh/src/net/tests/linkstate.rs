@@ -0,0 +1,64 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use zenoh_buffers::{reader::HasReader, writer::HasWriter, ZBuf};
+use zenoh_codec::{RCodec, WCodec};
+use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+
+use crate::net::{
+    codec::Zenoh080Routing,
+    protocol::linkstate::{LinkState, LinkStateList},
+};
+
+fn roundtrip(x: &LinkStateList) -> LinkStateList {
+    let codec = Zenoh080Routing::new();
+    let mut buf = ZBuf::empty();
+    codec.write(&mut buf.writer(), x).unwrap();
+    codec.read(&mut buf.reader()).unwrap()
+}
+
+#[test]
+fn link_weights_test() {
+    let weighted = LinkState {
+        psid: 0,
+        sn: 2,
+        zid: Some(ZenohIdProto::default()),
+        whatami: Some(WhatAmI::Router),
+        locators: None,
+        links: vec![1, 2, 3],
+        link_weights: Some(vec![10, 0, 1000]),
+    };
+    let unweighted = LinkState {
+        psid: 1,
+        sn: 1,
+        zid: None,
+        whatami: None,
+        locators: None,
+        links: vec![0],
+        link_weights: None,
+    };
+    let x = LinkStateList {
+        link_states: vec![weighted.clone(), unweighted],
+    };
+    assert_eq!(roundtrip(&x), x);
+
+    // Weights must match the links
+    let x = LinkStateList {
+        link_states: vec![LinkState {
+            link_weights: Some(vec![10]),
+            ..weighted
+        }],
+    };
+    let mut buf = ZBuf::empty();
+    assert!(Zenoh080Routing::new().write(&mut buf.writer(), &x).is_err());
+}
@@ -530,6 +530,10 @@ impl Primitives for ClientPrimitives {
 
     fn send_response(&self, _msg: zenoh_protocol::network::Response) {}
 
+    fn send_request_cancel(&self, _msg: zenoh_protocol::network::RequestCancel) {}
+
+    fn send_request_credit(&self, _msg: zenoh_protocol::network::RequestCredit) {}
+
     fn send_response_final(&self, _msg: zenoh_protocol::network::ResponseFinal) {}
 
     fn send_close(&self) {}
@@ -563,6 +567,10 @@ impl EPrimitives for ClientPrimitives {
         *zlock!(self.data) = Some(msg.wire_expr.to_owned());
     }
 
+    fn send_request_cancel(&self, _msg: zenoh_protocol::network::RequestCancel) {}
+
+    fn send_request_credit(&self, _msg: zenoh_protocol::network::RequestCredit) {}
+
     fn send_response_final(&self, _msg: zenoh_protocol::network::ResponseFinal) {}
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -828,3 +836,60 @@ fn client_test() {
     // mapping strategy check
     // assert_eq!(primitives2.get_last_key().unwrap(), KeyExpr::IdWithSuffix(31, "/z2_pub1".to_string()));
 }
+
+#[test]
+fn token_interest_budget_test() {
+    use std::num::NonZeroU32;
+
+    use zenoh_protocol::network::interest::{InterestMode, InterestOptions};
+
+    use crate::net::routing::dispatcher::interests::declare_interest;
+
+    let config = Config::default();
+    let router = Router::new(
+        ZenohIdProto::try_from([1]).unwrap(),
+        WhatAmI::Peer,
+        Some(Arc::new(HLC::default())),
+        &config,
+    )
+    .unwrap();
+    let tables = router.tables.clone();
+
+    let primitives = Arc::new(DummyPrimitives {});
+    let face0 = Arc::downgrade(&router.new_primitives(primitives.clone()).state);
+    for id in 0..3 {
+        declare_token(
+            zlock!(tables.ctrl_lock).as_ref(),
+            &tables,
+            &mut face0.upgrade().unwrap(),
+            id,
+            &format!("test/budget/{id}").as_str().into(),
+            NodeId::default(),
+            None,
+            &mut |p, m| p.send_declare(m),
+        );
+    }
+
+    let face1 = Arc::downgrade(&router.new_primitives(primitives).state);
+    let mut declares = vec![];
+    declare_interest(
+        zlock!(tables.ctrl_lock).as_ref(),
+        &tables,
+        &mut face1.upgrade().unwrap(),
+        1,
+        Some(&"test/budget/*".into()),
+        InterestMode::Current,
+        InterestOptions::KEYEXPRS + InterestOptions::TOKENS,
+        NonZeroU32::new(2),
+        &mut |_, m| declares.push(m.msg),
+    );
+
+    let nb_tokens = declares
+        .iter()
+        .filter(|d| matches!(d.body, DeclareBody::DeclareToken(_)))
+        .count();
+    assert_eq!(nb_tokens, 2);
+    assert!(declares
+        .iter()
+        .any(|d| matches!(d.body, DeclareBody::DeclareFinal(_))));
+}
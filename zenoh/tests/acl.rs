@@ -72,6 +72,58 @@ async fn test_acl_liveliness() {
     test_liveliness_deny_allow_query(27450).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_audit() {
+    zenoh::init_log_from_env_or("error");
+    test_audit_deny(27464).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_reload() {
+    zenoh::init_log_from_env_or("error");
+    test_reload(27465).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_subject_properties() {
+    zenoh::init_log_from_env_or("error");
+    test_zid_link_protocol_cidr(27484).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_key_expr_templates() {
+    zenoh::init_log_from_env_or("error");
+    test_key_expr_templates(27486).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_discovery_admin_space() {
+    zenoh::init_log_from_env_or("error");
+    test_discovery_admin_space(27487, "deny").await;
+    test_discovery_admin_space(27487, "allow").await;
+    test_opt_in_messages(27488).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_undeclare_querier() {
+    zenoh::init_log_from_env_or("error");
+    test_undeclare_querier(27489).await;
+}
+
+#[cfg(feature = "internal")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_runtime_authorize() {
+    zenoh::init_log_from_env_or("error");
+    test_runtime_authorize().await;
+}
+
+#[cfg(feature = "internal")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_runtime_update() {
+    zenoh::init_log_from_env_or("error");
+    test_runtime_update_access_control().await;
+}
+
 async fn get_basic_router_config(port: u16) -> Config {
     let mut config = Config::default();
     config.set_mode(Some(WhatAmI::Router)).unwrap();
@@ -1529,3 +1581,762 @@ async fn test_liveliness_deny_allow_query(port: u16) {
     close_sessions(reader_session, writer_session).await;
     close_router_session(session).await;
 }
+
+async fn test_audit_deny(port: u16) {
+    println!("test_audit_deny");
+    let audit_path = std::env::temp_dir().join(format!("zenoh-test-audit-{port}.log"));
+    let _ = std::fs::remove_file(&audit_path);
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5(
+            "access_control",
+            r#"{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": [
+                        {
+                            "id": "r1",
+                            "permission": "deny",
+                            "flows": ["ingress"],
+                            "messages": ["put"],
+                            "key_exprs": ["test/demo"],
+                        },
+                    ],
+                    "subjects": [
+                        {
+                            "id": "s1",
+                            "interfaces": ["lo", "lo0"],
+                        }
+                    ],
+                    "policies": [
+                        {
+                            "rules": ["r1"],
+                            "subjects": ["s1"],
+                        }
+                    ]
+                }"#,
+        )
+        .unwrap();
+    config_router
+        .insert_json5(
+            "audit",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "acl_decisions": "deny",
+                    "file": {{ "path": {:?} }},
+                    "publish": true,
+                }}"#,
+                audit_path.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    {
+        let published = Arc::new(Mutex::new(vec![]));
+        let published_clone = published.clone();
+        let subscriber = sub_session
+            .declare_subscriber(format!("@/{}/router/audit/**", session.zid()))
+            .callback(move |sample| {
+                zlock!(published_clone).push((
+                    sample.key_expr().to_string(),
+                    sample.payload().try_to_string().unwrap().into_owned(),
+                ));
+            })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(SLEEP).await;
+        // Denied by r1
+        pub_session.put(KEY_EXPR, VALUE).await.unwrap();
+        // Allowed by default, not recorded
+        pub_session.put("test/other", VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        let check_record = |record: &str| {
+            let record: serde_json::Value = serde_json::from_str(record).unwrap();
+            assert_eq!(record["event"], "acl");
+            assert_eq!(record["zid"], pub_session.zid().to_string());
+            assert_eq!(record["flow"], "ingress");
+            assert_eq!(record["kind"], "put");
+            assert_eq!(record["key_expr"], KEY_EXPR);
+            assert_eq!(record["decision"], "deny");
+            assert_eq!(record["rule_id"], "r1");
+        };
+
+        let records = std::fs::read_to_string(&audit_path).unwrap();
+        let records = records.lines().collect::<Vec<_>>();
+        assert_eq!(records.len(), 1);
+        check_record(records[0]);
+
+        let published = zlock!(published).clone();
+        assert_eq!(published.len(), 1);
+        assert_eq!(
+            published[0].0,
+            format!("@/{}/router/audit/acl", session.zid())
+        );
+        check_record(&published[0].1);
+
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+    let _ = std::fs::remove_file(&audit_path);
+}
+
+async fn test_reload(port: u16) {
+    println!("test_reload");
+    const DENY_PUT: &str = r#"{
+            "enabled": true,
+            "default_permission": "allow",
+            "rules": [
+                {
+                    "id": "r1",
+                    "permission": "deny",
+                    "flows": ["ingress"],
+                    "messages": ["put"],
+                    "key_exprs": ["test/demo"],
+                },
+            ],
+            "subjects": [
+                {
+                    "id": "s1",
+                }
+            ],
+            "policies": [
+                {
+                    "rules": ["r1"],
+                    "subjects": ["s1"],
+                }
+            ]
+        }"#;
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5("access_control", DENY_PUT)
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    {
+        let received_value = Arc::new(Mutex::new(String::new()));
+        let temp_recv_value = received_value.clone();
+        let subscriber = sub_session
+            .declare_subscriber(KEY_EXPR)
+            .callback(move |sample| {
+                *zlock!(temp_recv_value) = sample.payload().try_to_string().unwrap().into_owned();
+            })
+            .await
+            .unwrap();
+        let dropped_token = Arc::new(AtomicBool::new(false));
+        let cloned_dropped_token = dropped_token.clone();
+        let token_subscriber = session
+            .liveliness()
+            .declare_subscriber(KEY_EXPR)
+            .callback(move |sample| {
+                if sample.kind() == SampleKind::Delete {
+                    cloned_dropped_token.store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+            })
+            .await
+            .unwrap();
+        let token = pub_session
+            .liveliness()
+            .declare_token(KEY_EXPR)
+            .await
+            .unwrap();
+
+        tokio::time::sleep(SLEEP).await;
+        pub_session.put(KEY_EXPR, "denied").await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), "");
+
+        // Allow everything
+        session
+            .config()
+            .insert_json5(
+                "access_control",
+                r#"{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": [],
+                    "subjects": [],
+                    "policies": [],
+                }"#,
+            )
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        pub_session.put(KEY_EXPR, "allowed").await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), "allowed");
+
+        // A policy referring to an unknown subject is not applied
+        session
+            .config()
+            .insert_json5(
+                "access_control",
+                DENY_PUT.replace("\"s1\"]", "\"s2\"]").as_str(),
+            )
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        pub_session.put(KEY_EXPR, "still allowed").await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), "still allowed");
+        assert!(!dropped_token.load(std::sync::atomic::Ordering::Relaxed));
+
+        // Deny everything and close the denied transports
+        session
+            .config()
+            .insert_json5(
+                "access_control",
+                r#"{
+                    "enabled": true,
+                    "default_permission": "deny",
+                    "rules": [],
+                    "subjects": [],
+                    "policies": [],
+                    "close_denied_transports": true,
+                }"#,
+            )
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert!(dropped_token.load(std::sync::atomic::Ordering::Relaxed));
+        pub_session.put(KEY_EXPR, "denied").await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), "still allowed");
+
+        ztimeout!(token.undeclare()).unwrap();
+        ztimeout!(token_subscriber.undeclare()).unwrap();
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+}
+
+async fn test_zid_link_protocol_cidr(port: u16) {
+    println!("test_zid_link_protocol_cidr");
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5(
+            "access_control",
+            r#"{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": [],
+                    "subjects": [],
+                    "policies": [],
+                }"#,
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    let deny_put_for = |subject: String| {
+        format!(
+            r#"{{
+                "enabled": true,
+                "default_permission": "allow",
+                "rules": [
+                    {{
+                        "id": "r1",
+                        "permission": "deny",
+                        "flows": ["ingress"],
+                        "messages": ["put"],
+                        "key_exprs": ["test/demo"],
+                    }},
+                ],
+                "subjects": [
+                    {{
+                        "id": "s1",
+                        {subject}
+                    }}
+                ],
+                "policies": [
+                    {{
+                        "rules": ["r1"],
+                        "subjects": ["s1"],
+                    }}
+                ]
+            }}"#
+        )
+    };
+    let pub_zid = pub_session.zid();
+    {
+        let received_value = Arc::new(Mutex::new(String::new()));
+        let temp_recv_value = received_value.clone();
+        let subscriber = sub_session
+            .declare_subscriber(KEY_EXPR)
+            .callback(move |sample| {
+                *zlock!(temp_recv_value) = sample.payload().try_to_string().unwrap().into_owned();
+            })
+            .await
+            .unwrap();
+
+        for (subject, allowed) in [
+            (
+                format!(
+                    r#""zids": ["{pub_zid}"], "link_protocols": ["tcp"], "cidrs": ["127.0.0.0/8"]"#
+                ),
+                false,
+            ),
+            (
+                format!(r#""zids": ["{pub_zid}"], "link_protocols": ["quic"]"#),
+                true,
+            ),
+            (r#""cidrs": ["::1/128", "10.0.0.0/8"]"#.to_string(), true),
+            (r#""cidrs": ["::ffff:127.0.0.1/128"]"#.to_string(), true),
+            (r#""cidrs": ["127.0.0.1"]"#.to_string(), false),
+        ] {
+            session
+                .config()
+                .insert_json5("access_control", &deny_put_for(subject))
+                .unwrap();
+            tokio::time::sleep(SLEEP).await;
+            *zlock!(received_value) = String::new();
+            pub_session.put(KEY_EXPR, VALUE).await.unwrap();
+            tokio::time::sleep(SLEEP).await;
+            assert_eq!(*zlock!(received_value) == VALUE, allowed);
+        }
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+}
+
+async fn test_key_expr_templates(port: u16) {
+    println!("test_key_expr_templates");
+    const ACL_CONFIG: &str = r#"{
+            "enabled": true,
+            "default_permission": "deny",
+            "rules": [
+                {
+                    "id": "own_keys",
+                    "permission": "allow",
+                    "flows": ["ingress"],
+                    "messages": ["put"],
+                    "key_exprs": ["test/${zid}/**"],
+                },
+                {
+                    "id": "subscribe",
+                    "permission": "allow",
+                    "flows": ["ingress"],
+                    "messages": ["declare_subscriber"],
+                    "key_exprs": ["test/**"],
+                },
+                {
+                    "id": "deliver",
+                    "permission": "allow",
+                    "flows": ["egress"],
+                    "messages": ["put"],
+                    "key_exprs": ["test/**"],
+                },
+            ],
+            "subjects": [
+                {
+                    "id": "all",
+                }
+            ],
+            "policies": [
+                {
+                    "rules": ["own_keys", "subscribe", "deliver"],
+                    "subjects": ["all"],
+                }
+            ]
+        }"#;
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5(
+            "access_control",
+            &ACL_CONFIG.replace("${zid}", "${unknown}"),
+        )
+        .unwrap();
+    assert!(ztimeout!(zenoh::open(config_router)).is_err());
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5("access_control", ACL_CONFIG)
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    {
+        let received_key = Arc::new(Mutex::new(String::new()));
+        let temp_recv_key = received_key.clone();
+        let subscriber = sub_session
+            .declare_subscriber("test/**")
+            .callback(move |sample| {
+                *zlock!(temp_recv_key) = sample.key_expr().to_string();
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        // The publisher can only publish on its own keys
+        let own_key = format!("test/{}/value", pub_session.zid());
+        pub_session.put(&own_key, VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_key), own_key);
+
+        let other_key = format!("test/{}/value", sub_session.zid());
+        pub_session.put(&other_key, VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_key), own_key);
+
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+}
+
+async fn test_discovery_admin_space(port: u16, permission: &str) {
+    println!("test_discovery_admin_space {permission}");
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router.adminspace.set_enabled(true).unwrap();
+    config_router
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": [
+                        {{
+                            "id": "r1",
+                            "permission": "{permission}",
+                            "flows": ["ingress"],
+                            "messages": ["declare_publisher", "admin_space_read"],
+                            "key_exprs": ["test/demo", "@/**"],
+                        }},
+                    ],
+                    "subjects": [
+                        {{
+                            "id": "all",
+                        }}
+                    ],
+                    "policies": [
+                        {{
+                            "rules": ["r1"],
+                            "subjects": ["all"],
+                        }}
+                    ]
+                }}"#
+            ),
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    {
+        let subscriber = ztimeout!(sub_session.declare_subscriber(KEY_EXPR)).unwrap();
+        let publisher = ztimeout!(pub_session.declare_publisher(KEY_EXPR)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        // The matching subscribers are only discovered if the publisher declaration is allowed
+        let matching = ztimeout!(publisher.matching_status()).unwrap().matching();
+        assert_eq!(matching, permission == "allow");
+
+        // The admin space of the router is only readable if admin space reads are allowed
+        let replies = ztimeout!(pub_session.get(format!("@/{}/router", session.zid()))).unwrap();
+        let mut received = false;
+        while let Ok(reply) = ztimeout!(replies.recv_async()) {
+            received |= reply.result().is_ok();
+        }
+        assert_eq!(received, permission == "allow");
+
+        ztimeout!(publisher.undeclare()).unwrap();
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+}
+
+async fn test_opt_in_messages(port: u16) {
+    println!("test_opt_in_messages");
+
+    let mut config_router = get_basic_router_config(port).await;
+    config_router
+        .insert_json5(
+            "access_control",
+            r#"{
+                    "enabled": true,
+                    "default_permission": "deny",
+                    "rules": [
+                        {
+                            "id": "r1",
+                            "permission": "allow",
+                            "flows": ["ingress", "egress"],
+                            "messages": ["put", "declare_subscriber"],
+                            "key_exprs": ["test/demo"],
+                        },
+                    ],
+                    "subjects": [
+                        {
+                            "id": "all",
+                        }
+                    ],
+                    "policies": [
+                        {
+                            "rules": ["r1"],
+                            "subjects": ["all"],
+                        }
+                    ]
+                }"#,
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+    let (sub_session, pub_session) = get_client_sessions(port).await;
+    {
+        let subscriber = ztimeout!(sub_session.declare_subscriber(KEY_EXPR)).unwrap();
+        let publisher = ztimeout!(pub_session.declare_publisher(KEY_EXPR)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        // No rule lists publisher declarations, they are not denied by default
+        let matching = ztimeout!(publisher.matching_status()).unwrap().matching();
+        assert!(matching);
+
+        ztimeout!(publisher.undeclare()).unwrap();
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    close_sessions(sub_session, pub_session).await;
+    close_router_session(session).await;
+}
+
+async fn test_undeclare_querier(port: u16) {
+    println!("test_undeclare_querier");
+    let audit_path = std::env::temp_dir().join(format!("zenoh-test-audit-{port}.log"));
+    let _ = std::fs::remove_file(&audit_path);
+
+    let config_router = get_basic_router_config(port).await;
+    println!("Opening router session");
+    let session = ztimeout!(zenoh::open(config_router)).unwrap();
+
+    // The querier declarations and undeclarations are filtered by the egress ACL of the client
+    let mut config_client = Config::default();
+    config_client.set_mode(Some(WhatAmI::Client)).unwrap();
+    config_client
+        .connect
+        .set_endpoints(ModeDependentValue::Unique(vec![format!(
+            "tcp/127.0.0.1:{port}"
+        )
+        .parse::<EndPoint>()
+        .unwrap()]))
+        .unwrap();
+    config_client
+        .insert_json5(
+            "access_control",
+            r#"{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": [
+                        {
+                            "id": "r1",
+                            "permission": "deny",
+                            "flows": ["egress"],
+                            "messages": ["declare_querier"],
+                            "key_exprs": ["test/demo"],
+                        },
+                    ],
+                    "subjects": [
+                        {
+                            "id": "all",
+                        }
+                    ],
+                    "policies": [
+                        {
+                            "rules": ["r1"],
+                            "subjects": ["all"],
+                        }
+                    ]
+                }"#,
+        )
+        .unwrap();
+    config_client
+        .insert_json5(
+            "audit",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "acl_decisions": "deny",
+                    "file": {{ "path": {:?} }},
+                }}"#,
+                audit_path.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+    let client_session = ztimeout!(zenoh::open(config_client)).unwrap();
+    {
+        let querier = ztimeout!(client_session.declare_querier(KEY_EXPR)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+        ztimeout!(querier.undeclare()).unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        // Both the declaration and the undeclaration are denied
+        let records = std::fs::read_to_string(&audit_path).unwrap();
+        let records = records
+            .lines()
+            .map(|record| serde_json::from_str::<serde_json::Value>(record).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        for record in records {
+            assert_eq!(record["flow"], "egress");
+            assert_eq!(record["kind"], "declare_querier");
+            assert_eq!(record["key_expr"], KEY_EXPR);
+            assert_eq!(record["decision"], "deny");
+            assert_eq!(record["rule_id"], "r1");
+        }
+    }
+    ztimeout!(client_session.close()).unwrap();
+    close_router_session(session).await;
+    let _ = std::fs::remove_file(&audit_path);
+}
+
+#[cfg(feature = "internal")]
+async fn test_runtime_authorize() {
+    use zenoh::{internal::access_control::AclMessage, key_expr::keyexpr};
+
+    println!("test_runtime_authorize");
+    let mut config = Config::default();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .insert_json5(
+            "access_control",
+            r#"{
+                "enabled": true,
+                "default_permission": "deny",
+                "rules": [
+                    {
+                        "id": "own_keys",
+                        "permission": "allow",
+                        "flows": ["ingress"],
+                        "messages": ["put", "delete"],
+                        "key_exprs": ["test/${username}/**"],
+                    },
+                    {
+                        "id": "read",
+                        "permission": "allow",
+                        "flows": ["ingress"],
+                        "messages": ["query"],
+                        "key_exprs": ["test/**", "@/**"],
+                    },
+                ],
+                "subjects": [
+                    {
+                        "id": "users",
+                        "usernames": ["alice", "bob"],
+                    }
+                ],
+                "policies": [
+                    {
+                        "rules": ["own_keys", "read"],
+                        "subjects": ["users"],
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+    let runtime = ztimeout!(zenoh::internal::runtime::RuntimeBuilder::new(config).build()).unwrap();
+    let authorize = |username: Option<&str>, message: AclMessage, key_expr: &str| {
+        runtime.authorize(username, None, message, keyexpr::new(key_expr).unwrap())
+    };
+
+    assert!(authorize(
+        Some("alice"),
+        AclMessage::Put,
+        "test/alice/value"
+    ));
+    assert!(authorize(
+        Some("alice"),
+        AclMessage::Delete,
+        "test/alice/value"
+    ));
+    assert!(!authorize(Some("alice"), AclMessage::Put, "test/bob/value"));
+    assert!(authorize(Some("bob"), AclMessage::Put, "test/bob/value"));
+    assert!(authorize(Some("alice"), AclMessage::Query, "test/**"));
+    // Unknown and anonymous users get the default permission
+    assert!(!authorize(Some("eve"), AclMessage::Query, "test/**"));
+    assert!(!authorize(None, AclMessage::Put, "test/alice/value"));
+    // The admin space requires the admin space actions on top of the regular ones
+    assert!(!authorize(
+        Some("alice"),
+        AclMessage::Query,
+        &format!("@/{}/router", runtime.zid())
+    ));
+}
+
+#[cfg(feature = "internal")]
+async fn test_runtime_update_access_control() {
+    use zenoh::{internal::access_control::AclMessage, key_expr::keyexpr};
+    use zenoh_config::AclConfig;
+
+    const DENY_ALL: &str = r#"{
+            "enabled": true,
+            "default_permission": "deny",
+            "rules": [
+                {
+                    "id": "r1",
+                    "permission": "allow",
+                    "flows": ["ingress"],
+                    "messages": ["put"],
+                    "key_exprs": ["test/demo"],
+                },
+            ],
+            "subjects": [
+                {
+                    "id": "s1",
+                    "usernames": ["alice"],
+                }
+            ],
+            "policies": [
+                {
+                    "rules": ["r1"],
+                    "subjects": ["s1"],
+                }
+            ]
+        }"#;
+    let acl_config = |json: &str| -> AclConfig {
+        let mut config = Config::default();
+        config.insert_json5("access_control", json).unwrap();
+        config.access_control().clone()
+    };
+
+    println!("test_runtime_update_access_control");
+    let mut config = Config::default();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.insert_json5("access_control", DENY_ALL).unwrap();
+    let runtime = ztimeout!(zenoh::internal::runtime::RuntimeBuilder::new(config).build()).unwrap();
+    let authorize = |username: &str| {
+        runtime.authorize(
+            Some(username),
+            None,
+            AclMessage::Put,
+            keyexpr::new(KEY_EXPR).unwrap(),
+        )
+    };
+    let stored = || runtime.config().lock().get_json("access_control").unwrap();
+    assert!(authorize("alice"));
+    assert!(!authorize("bob"));
+
+    // A policy referring to an unknown subject is neither applied nor stored
+    let before = stored();
+    let invalid = acl_config(&DENY_ALL.replace("\"s1\"]", "\"s2\"]"));
+    assert!(ztimeout!(runtime.update_access_control(invalid)).is_err());
+    assert_eq!(stored(), before);
+    assert!(!authorize("bob"));
+
+    // Removing the section disables the access control
+    ztimeout!(runtime.update_access_control(AclConfig::default())).unwrap();
+    assert!(!runtime.config().lock().access_control().enabled);
+    assert!(authorize("bob"));
+}
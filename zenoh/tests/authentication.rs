@@ -47,6 +47,15 @@ mod test {
         test_get_qbl_deny_then_allow_usrpswd(29447).await;
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_authentication_usrpwd_verifier() {
+        zenoh_util::init_log_from_env_or("error");
+        create_new_files(TESTFILES_PATH.to_path_buf())
+            .await
+            .unwrap();
+        test_usrpwd_verifier(29459).await;
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn test_authentication_tls() {
         zenoh_util::init_log_from_env_or("error");
@@ -224,8 +233,10 @@ IZx/qrP32PmWX0IVj3pbfKHQSpOKNGzL9xUJ/FIycZWyT3yGf24KBuJwIx7xSrRx
 qNsoty1gY/y3n7SN/iMZo8lO
 -----END PRIVATE KEY-----";
 
+        // client3name's entry is a salted verifier of "client3passwd"
         let credentials_txt = b"client1name:client1passwd
-client2name:client2passwd";
+client2name:client2passwd
+client3name:$scram-sha3-256$64$RVb9+1CyZZDK7P0RiprgPg==$NLVV1gAu8Rh43ob13zzBB+mR5Gt8CYbJUV9uUssJyQQ=";
 
         struct Testfile<'a> {
             name: &'a str,
@@ -731,6 +742,35 @@ client2name:client2passwd";
         (s01, s02)
     }
 
+    async fn get_client_config_usrpswd(port: u16, user: &str, password: &str) -> Config {
+        let mut config = zenoh::Config::default();
+        config.set_mode(Some(WhatAmI::Client)).unwrap();
+        config
+            .connect
+            .set_endpoints(ModeDependentValue::Unique(vec![format!(
+                "tcp/127.0.0.1:{port}"
+            )
+            .parse::<EndPoint>()
+            .unwrap()]))
+            .unwrap();
+        config
+            .insert_json5(
+                "transport",
+                &format!(
+                    r#"{{
+                        "auth": {{
+                            usrpwd: {{
+                                user: "{user}",
+                                password: "{password}",
+                            }},
+                        }}
+                    }}"#
+                ),
+            )
+            .unwrap();
+        config
+    }
+
     async fn get_client_sessions_quic_usrpswd(port: u16) -> (Session, Session) {
         let cert_path = TESTFILES_PATH.to_string_lossy();
         println!("Opening client sessions");
@@ -1541,6 +1581,25 @@ client2name:client2passwd";
         close_router_session(session).await;
     }
 
+    async fn test_usrpwd_verifier(port: u16) {
+        println!("test_usrpwd_verifier");
+
+        let config_router = get_basic_router_config_usrpswd(port).await;
+        println!("Opening router session");
+        let session = ztimeout!(zenoh::open(config_router)).unwrap();
+
+        // The password matching the verifier of the dictionary is accepted
+        let config = get_client_config_usrpswd(port, "client3name", "client3passwd").await;
+        let client_session = ztimeout!(zenoh::open(config)).unwrap();
+        ztimeout!(client_session.close()).unwrap();
+
+        // Any other password is rejected
+        let config = get_client_config_usrpswd(port, "client3name", "client2passwd").await;
+        assert!(ztimeout!(zenoh::open(config)).is_err());
+
+        close_router_session(session).await;
+    }
+
     async fn test_pub_sub_allow_then_deny_usrpswd(port: u16) {
         println!("test_pub_sub_allow_then_deny_usrpswd");
 
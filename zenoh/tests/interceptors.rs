@@ -15,6 +15,8 @@
 #![cfg(feature = "internal_config")]
 #![cfg(unix)]
 
+#[cfg(feature = "unstable")]
+use std::sync::Mutex;
 use std::{
     collections::HashMap,
     sync::{
@@ -23,8 +25,23 @@ use std::{
     },
 };
 
+#[cfg(feature = "transport_compression")]
+use zenoh::{bytes::Encoding, sample::Sample};
+#[cfg(feature = "unstable")]
+use zenoh::{
+    config::ZenohId,
+    interceptor::{
+        BoxedMessageInterceptor, InterceptedMessage, MessageInterceptor, MessageInterceptorFactory,
+        MessageKind, TransportMetadata,
+    },
+};
 use zenoh::{key_expr::KeyExpr, Config, Wait};
-use zenoh_config::{DownsamplingItemConf, DownsamplingRuleConf, InterceptorFlow};
+#[cfg(feature = "transport_compression")]
+use zenoh_config::{CompressionAlgorithm, PayloadCompressionAction, PayloadCompressionItemConf};
+use zenoh_config::{
+    DownsamplingItemConf, DownsamplingRuleConf, InterceptorFlow, KeyExprRemappingItemConf,
+    RateLimitItemConf, RateLimitStrategy,
+};
 
 // Tokio's time granularity on different platforms
 #[cfg(target_os = "windows")]
@@ -248,6 +265,509 @@ fn downsampling_by_interface() {
     downsampling_by_interface_impl(InterceptorFlow::Egress);
 }
 
+fn rate_limit_by_messages_impl(flow: InterceptorFlow, strategy: RateLimitStrategy, port: u16) {
+    let ke_prefix = "test/rate_limit_by_messages";
+    let locator = format!("tcp/127.0.0.1:{port}");
+
+    let ke_limited: KeyExpr = format!("{ke_prefix}/limited").try_into().unwrap();
+    let ke_unlimited: KeyExpr = format!("{ke_prefix}/unlimited").try_into().unwrap();
+
+    let rl_config = RateLimitItemConf {
+        interfaces: None,
+        subjects: None,
+        flow,
+        key_exprs: Some(vec![ke_limited.clone().into()]),
+        messages_per_second: Some(10.0),
+        bytes_per_second: None,
+        burst_messages: Some(1.0),
+        burst_bytes: None,
+        strategy,
+        max_delay_ms: None,
+    };
+
+    let ke_of_rates = vec![ke_limited.clone(), ke_unlimited.clone()];
+
+    let rate_check = move |ke: KeyExpr, rate: usize| -> bool {
+        tracing::info!("keyexpr: {ke}, rate: {rate}");
+        if ke == ke_limited {
+            rate > 0 && rate <= 10 + 1
+        } else if ke == ke_unlimited {
+            // Delayed messages are queued without holding back the other key expressions
+            rate > 10 + 1
+        } else {
+            tracing::error!("Shouldn't reach this case. Invalid keyexpr {ke} detected.");
+            false
+        }
+    };
+
+    let (mut pub_config, mut sub_config) = build_config(&locator, vec![], flow);
+    match flow {
+        InterceptorFlow::Egress => pub_config.set_rate_limit(vec![rl_config]).unwrap(),
+        InterceptorFlow::Ingress => sub_config.set_rate_limit(vec![rl_config]).unwrap(),
+    };
+
+    downsampling_test(pub_config, sub_config, ke_prefix, ke_of_rates, rate_check);
+}
+
+#[test]
+fn rate_limit_by_messages() {
+    zenoh::init_log_from_env_or("error");
+    rate_limit_by_messages_impl(InterceptorFlow::Ingress, RateLimitStrategy::Drop, 31449);
+    rate_limit_by_messages_impl(InterceptorFlow::Egress, RateLimitStrategy::Drop, 31450);
+    rate_limit_by_messages_impl(InterceptorFlow::Ingress, RateLimitStrategy::Delay, 31451);
+}
+
+#[test]
+fn rate_limit_by_subject() {
+    zenoh::init_log_from_env_or("error");
+    let ke_prefix = "test/rate_limit_by_subject";
+    let locator = "tcp/127.0.0.1:31452";
+
+    let ke_unmatched: KeyExpr = format!("{ke_prefix}/unmatched").try_into().unwrap();
+
+    // The publisher is not authenticated, hence does not match the subject
+    let rl_config: RateLimitItemConf = json5::from_str(
+        r#"{
+          subjects: [ { id: "client", usernames: [ "client" ] } ],
+          flow: "ingress",
+          messages_per_second: 10,
+          burst_messages: 1,
+        }"#,
+    )
+    .unwrap();
+
+    let ke_of_rates = vec![ke_unmatched.clone()];
+
+    let rate_check = move |ke: KeyExpr, rate: usize| -> bool {
+        tracing::info!("keyexpr: {ke}, rate: {rate}");
+        rate > 10 + 1
+    };
+
+    let (pub_config, mut sub_config) = build_config(locator, vec![], InterceptorFlow::Ingress);
+    sub_config.set_rate_limit(vec![rl_config]).unwrap();
+
+    downsampling_test(pub_config, sub_config, ke_prefix, ke_of_rates, rate_check);
+}
+
+#[test]
+#[should_panic(expected = "the `delay` strategy is only supported on the ingress flow")]
+fn rate_limit_config_error_egress_delay() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "rate_limit",
+            r#"
+              [
+                {
+                  flow: "egress",
+                  messages_per_second: 10,
+                  strategy: "delay",
+                },
+              ]
+            "#,
+        )
+        .unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
+#[test]
+fn key_expr_remapping() {
+    zenoh::init_log_from_env_or("error");
+    let ke_prefix = "test/key_expr_remapping";
+    let locator = "tcp/127.0.0.1:31454";
+
+    // The publisher side exposes `site-a/**` as `fleet/site-a/**` and reverts it on ingress
+    let km_config: Vec<KeyExprRemappingItemConf> = json5::from_str(&format!(
+        r#"[
+          {{
+            flow: "egress",
+            rules: [ {{ from: "{ke_prefix}/site-a", to: "{ke_prefix}/fleet/site-a" }} ],
+          }},
+          {{
+            flow: "ingress",
+            rules: [ {{ from: "{ke_prefix}/fleet/site-a", to: "{ke_prefix}/site-a" }} ],
+          }},
+        ]"#
+    ))
+    .unwrap();
+
+    let (mut pub_config, sub_config) = build_config(locator, vec![], InterceptorFlow::Egress);
+    pub_config.set_key_expr_remapping(km_config).unwrap();
+
+    let sub_session = zenoh::open(sub_config).wait().unwrap();
+    let sub = sub_session
+        .declare_subscriber(format!("{ke_prefix}/**"))
+        .wait()
+        .unwrap();
+
+    let pub_session = zenoh::open(pub_config).wait().unwrap();
+    let ke_state = format!("{ke_prefix}/site-a/robot/state");
+    let _queryable = pub_session
+        .declare_queryable(ke_state.clone())
+        .callback(move |query| query.reply(&ke_state, "state").wait().unwrap())
+        .wait()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+
+    // Publications are remapped
+    for suffix in ["site-a/robot/position", "site-ab/robot/position", "other"] {
+        pub_session
+            .put(format!("{ke_prefix}/{suffix}"), "message")
+            .wait()
+            .unwrap();
+    }
+    let mut received = vec![];
+    while let Ok(Some(sample)) = sub.recv_timeout(std::time::Duration::from_secs(1)) {
+        received.push(sample.key_expr().to_string());
+    }
+    assert_eq!(
+        received,
+        vec![
+            format!("{ke_prefix}/fleet/site-a/robot/position"),
+            format!("{ke_prefix}/site-ab/robot/position"),
+            format!("{ke_prefix}/other"),
+        ]
+    );
+
+    // Queryable declarations, queries and replies are remapped
+    let replies = sub_session
+        .get(format!("{ke_prefix}/fleet/site-a/robot/*"))
+        .wait()
+        .unwrap();
+    let mut received = vec![];
+    while let Ok(reply) = replies.recv() {
+        received.push(reply.result().unwrap().key_expr().to_string());
+    }
+    assert_eq!(
+        received,
+        vec![format!("{ke_prefix}/fleet/site-a/robot/state")]
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot contain wildcards")]
+fn key_expr_remapping_config_error_wildcard() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "key_expr_remapping",
+            r#"
+              [
+                {
+                  flow: "egress",
+                  rules: [ { from: "test/key_expr_remapping/*", to: "test/fleet" } ],
+                },
+              ]
+            "#,
+        )
+        .unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
+#[test]
+fn audit_messages() {
+    zenoh::init_log_from_env_or("error");
+    let ke_prefix = "test/audit_messages";
+    let locator = "tcp/127.0.0.1:31455";
+    let audit_path = std::env::temp_dir().join("zenoh-test-audit-messages.log");
+    let _ = std::fs::remove_file(&audit_path);
+
+    let (pub_config, mut sub_config) = build_config(locator, vec![], InterceptorFlow::Ingress);
+    sub_config
+        .insert_json5(
+            "audit",
+            &format!(
+                r#"{{
+                  enabled: true,
+                  messages: {{
+                    enabled: true,
+                    flows: ["ingress"],
+                    key_exprs: ["{ke_prefix}/audited/**"],
+                  }},
+                  file: {{ path: {:?} }},
+                }}"#,
+                audit_path.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+
+    let sub_session = zenoh::open(sub_config).wait().unwrap();
+    let sub = sub_session
+        .declare_subscriber(format!("{ke_prefix}/**"))
+        .wait()
+        .unwrap();
+    let pub_session = zenoh::open(pub_config).wait().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+
+    for suffix in ["audited/a", "other", "audited/b"] {
+        pub_session
+            .put(format!("{ke_prefix}/{suffix}"), "message")
+            .wait()
+            .unwrap();
+    }
+    let mut received = 0;
+    while let Ok(Some(_)) = sub.recv_timeout(std::time::Duration::from_secs(1)) {
+        received += 1;
+    }
+    assert_eq!(received, 3);
+
+    let records = std::fs::read_to_string(&audit_path).unwrap();
+    let records = records
+        .lines()
+        .map(|record| serde_json::from_str::<serde_json::Value>(record).unwrap())
+        .filter(|record| record["kind"] == "put")
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 2);
+    for (record, suffix) in records.iter().zip(["audited/a", "audited/b"]) {
+        assert_eq!(record["event"], "messages");
+        assert_eq!(record["zid"], pub_session.zid().to_string());
+        assert_eq!(record["flow"], "ingress");
+        assert_eq!(record["key_expr"], format!("{ke_prefix}/{suffix}"));
+    }
+    let _ = std::fs::remove_file(&audit_path);
+}
+
+#[cfg(feature = "unstable")]
+struct PayloadFilterFactory {
+    zids: Arc<Mutex<Vec<ZenohId>>>,
+}
+
+#[cfg(feature = "unstable")]
+impl MessageInterceptorFactory for PayloadFilterFactory {
+    fn new_transport(
+        &self,
+        transport: &TransportMetadata,
+    ) -> (
+        Option<BoxedMessageInterceptor>,
+        Option<BoxedMessageInterceptor>,
+    ) {
+        self.zids.lock().unwrap().extend(transport.zid());
+        (None, Some(Box::new(PayloadFilter)))
+    }
+}
+
+#[cfg(feature = "unstable")]
+struct PayloadFilter;
+
+#[cfg(feature = "unstable")]
+impl MessageInterceptor for PayloadFilter {
+    fn intercept(&self, msg: &mut InterceptedMessage<'_>) -> bool {
+        if msg.kind() != MessageKind::Put {
+            return true;
+        }
+        match msg.key_expr().map(|ke| ke.as_str()) {
+            Some("test/custom_interceptor/drop") => false,
+            Some("test/custom_interceptor/rewrite") => {
+                assert_eq!(msg.flow(), InterceptorFlow::Egress);
+                assert_eq!(msg.payload().unwrap().to_bytes().as_ref(), b"message");
+                msg.set_payload("rewritten")
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn custom_interceptor() {
+    zenoh::init_log_from_env_or("error");
+    let ke_prefix = "test/custom_interceptor";
+    let locator = "tcp/127.0.0.1:31453";
+
+    let (pub_config, sub_config) = build_config(locator, vec![], InterceptorFlow::Egress);
+
+    let sub_session = zenoh::open(sub_config).wait().unwrap();
+    let sub = sub_session
+        .declare_subscriber(format!("{ke_prefix}/*"))
+        .wait()
+        .unwrap();
+
+    let zids = Arc::new(Mutex::new(vec![]));
+    let pub_session = zenoh::open(pub_config)
+        .with_interceptor_factory(Arc::new(PayloadFilterFactory { zids: zids.clone() }))
+        .wait()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+
+    for suffix in ["drop", "rewrite", "pass"] {
+        pub_session
+            .put(format!("{ke_prefix}/{suffix}"), "message")
+            .wait()
+            .unwrap();
+    }
+
+    let mut received = HashMap::new();
+    while let Ok(Some(sample)) = sub.recv_timeout(std::time::Duration::from_secs(1)) {
+        received.insert(
+            sample.key_expr().to_string(),
+            sample.payload().try_to_string().unwrap().into_owned(),
+        );
+    }
+    assert_eq!(
+        received,
+        HashMap::from([
+            (format!("{ke_prefix}/rewrite"), "rewritten".to_string()),
+            (format!("{ke_prefix}/pass"), "message".to_string()),
+        ])
+    );
+    assert_eq!(*zids.lock().unwrap(), vec![sub_session.zid()]);
+}
+
+#[cfg(feature = "transport_compression")]
+fn payload_compression_test(
+    locator: &str,
+    ke: &str,
+    pub_pc_config: Vec<PayloadCompressionItemConf>,
+    sub_pc_config: Vec<PayloadCompressionItemConf>,
+) -> Option<Sample> {
+    let (mut pub_config, mut sub_config) = build_config(locator, vec![], InterceptorFlow::Egress);
+    pub_config.set_payload_compression(pub_pc_config).unwrap();
+    sub_config.set_payload_compression(sub_pc_config).unwrap();
+
+    let sub_session = zenoh::open(sub_config).wait().unwrap();
+    let sub = sub_session.declare_subscriber(ke).wait().unwrap();
+    let pub_session = zenoh::open(pub_config).wait().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+
+    pub_session
+        .put(ke, PAYLOAD.repeat(64))
+        .encoding(Encoding::TEXT_PLAIN.with_schema("utf-8"))
+        .wait()
+        .unwrap();
+    sub.recv_timeout(std::time::Duration::from_secs(5)).unwrap()
+}
+
+#[cfg(feature = "transport_compression")]
+static PAYLOAD: &str = "zenoh-payload-compression";
+
+#[cfg(feature = "transport_compression")]
+#[test]
+fn payload_compression_by_keyexpr() {
+    zenoh::init_log_from_env_or("error");
+    let ke_prefix = "test/payload_compression";
+    let locator = "tcp/127.0.0.1:31448";
+
+    let compress = PayloadCompressionItemConf {
+        interfaces: None,
+        flow: InterceptorFlow::Egress,
+        action: PayloadCompressionAction::Compress,
+        key_exprs: vec![format!("{ke_prefix}/compressed/**").try_into().unwrap()],
+        algorithm: Some(CompressionAlgorithm::Zstd),
+        level: Some(3),
+        max_decompressed_size: None,
+    };
+    let decompress = PayloadCompressionItemConf {
+        interfaces: None,
+        flow: InterceptorFlow::Ingress,
+        action: PayloadCompressionAction::Decompress,
+        key_exprs: vec![format!("{ke_prefix}/**").try_into().unwrap()],
+        algorithm: None,
+        level: None,
+        max_decompressed_size: None,
+    };
+
+    // Compressed payloads are tagged in the encoding schema
+    let sample = payload_compression_test(
+        locator,
+        &format!("{ke_prefix}/compressed/a"),
+        vec![compress.clone()],
+        vec![],
+    )
+    .expect("No sample received");
+    assert_eq!(
+        sample.encoding(),
+        &Encoding::TEXT_PLAIN.with_schema("zenoh-compression/zstd;utf-8")
+    );
+    assert!(sample.payload().len() < PAYLOAD.len() * 64);
+
+    // Compressed payloads are restored by the decompression
+    let sample = payload_compression_test(
+        locator,
+        &format!("{ke_prefix}/compressed/b"),
+        vec![compress.clone()],
+        vec![decompress.clone()],
+    )
+    .expect("No sample received");
+    assert_eq!(
+        sample.encoding(),
+        &Encoding::TEXT_PLAIN.with_schema("utf-8")
+    );
+    assert_eq!(
+        sample.payload().to_bytes().as_ref(),
+        PAYLOAD.repeat(64).as_bytes()
+    );
+
+    // Payloads of non matching key expressions are left untouched
+    let sample = payload_compression_test(
+        locator,
+        &format!("{ke_prefix}/plain"),
+        vec![compress.clone()],
+        vec![decompress.clone()],
+    )
+    .expect("No sample received");
+    assert_eq!(
+        sample.encoding(),
+        &Encoding::TEXT_PLAIN.with_schema("utf-8")
+    );
+    assert_eq!(
+        sample.payload().to_bytes().as_ref(),
+        PAYLOAD.repeat(64).as_bytes()
+    );
+
+    // Payloads decompressing beyond the maximum size are dropped
+    for algorithm in [CompressionAlgorithm::Lz4, CompressionAlgorithm::Zstd] {
+        let compress = PayloadCompressionItemConf {
+            algorithm: Some(algorithm),
+            level: None,
+            ..compress.clone()
+        };
+        let decompress = PayloadCompressionItemConf {
+            max_decompressed_size: Some(PAYLOAD.len() * 64 - 1),
+            ..decompress.clone()
+        };
+        let sample = payload_compression_test(
+            locator,
+            &format!("{ke_prefix}/compressed/c"),
+            vec![compress],
+            vec![decompress],
+        );
+        assert!(sample.is_none());
+    }
+}
+
+#[cfg(feature = "transport_compression")]
+#[test]
+#[should_panic(expected = "level is not supported by the Lz4 algorithm")]
+fn payload_compression_config_error_lz4_level() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "payload_compression",
+            r#"
+              [
+                {
+                  flow: "egress",
+                  action: "compress",
+                  key_exprs: ["test/payload_compression/**"],
+                  algorithm: "lz4",
+                  level: 3,
+                },
+              ]
+            "#,
+        )
+        .unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
 #[test]
 #[should_panic(expected = "unknown variant `down`")]
 fn downsampling_config_error_wrong_strategy() {
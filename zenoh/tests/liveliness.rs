@@ -357,6 +357,50 @@ async fn test_liveliness_query_local() {
     peer.close().await.unwrap();
 }
 
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_liveliness_query_max_replies() {
+    use std::time::Duration;
+
+    use zenoh::config::WhatAmI;
+    const TIMEOUT: Duration = Duration::from_secs(60);
+    const SLEEP: Duration = Duration::from_secs(1);
+    const LIVELINESS_KEYEXPR: &str = "test/liveliness/query/max_replies";
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let peer = {
+        let mut c = zenoh::Config::default();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        let _ = c.set_mode(Some(WhatAmI::Peer));
+        let s = ztimeout!(zenoh::open(c)).unwrap();
+        tracing::info!("Peer (1) ZID: {}", s.zid());
+        s
+    };
+
+    let mut tokens = vec![];
+    for i in 0..3 {
+        let key_expr = format!("{LIVELINESS_KEYEXPR}/{i}");
+        tokens.push(ztimeout!(peer.liveliness().declare_token(key_expr)).unwrap());
+    }
+    tokio::time::sleep(SLEEP).await;
+
+    let get = ztimeout!(peer
+        .liveliness()
+        .get(format!("{LIVELINESS_KEYEXPR}/*"))
+        .max_replies(2))
+    .unwrap();
+    for _ in 0..2 {
+        ztimeout!(get.recv_async()).unwrap().into_result().unwrap();
+    }
+    assert!(ztimeout!(get.recv_async()).is_err());
+
+    for token in tokens {
+        token.undeclare().await.unwrap();
+    }
+    peer.close().await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn test_liveliness_after_close() {
     use std::time::Duration;
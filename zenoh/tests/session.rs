@@ -442,3 +442,402 @@ async fn test_undeclare_subscribers_same_keyexpr() {
     ztimeout!(sub1.undeclare()).unwrap();
     ztimeout!(sub2.undeclare()).unwrap();
 }
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_cancellation() {
+    use zenoh::query::CancellationToken;
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/cancellation";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17477"]).await;
+
+    // The queryable waits for the queries to be cancelled instead of replying
+    let cancelled = Arc::new(AtomicUsize::new(0));
+    let c_cancelled = cancelled.clone();
+    let _qbl = ztimeout!(peer01.declare_queryable(key_expr).callback(move |query| {
+        let c_cancelled = c_cancelled.clone();
+        tokio::spawn(async move {
+            query.cancellation_token().cancelled().await;
+            assert!(query.is_cancelled());
+            c_cancelled.fetch_add(1, Ordering::Relaxed);
+        });
+    }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // Remote and local queries
+    for (i, session) in [&peer02, &peer01].into_iter().enumerate() {
+        let token = CancellationToken::default();
+        let replies = ztimeout!(session
+            .get(key_expr)
+            .timeout(TIMEOUT)
+            .cancellation_token(token.clone()))
+        .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(cancelled.load(Ordering::Relaxed), i);
+        token.cancel();
+        assert!(token.is_cancelled());
+        // The handler is closed well before the query timeout
+        assert!(ztimeout!(replies.recv_async()).is_err());
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(cancelled.load(Ordering::Relaxed), i + 1);
+    }
+
+    // A query with an already cancelled token is not sent
+    let token = CancellationToken::default();
+    token.cancel();
+    let replies = ztimeout!(peer02.get(key_expr).cancellation_token(token)).unwrap();
+    assert!(ztimeout!(replies.recv_async()).is_err());
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(cancelled.load(Ordering::Relaxed), 2);
+
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_max_replies() {
+    use zenoh::{query::ConsolidationMode, Wait};
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/max_replies";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17487"]).await;
+
+    let max_replies = Arc::new(AtomicUsize::new(0));
+    let c_max_replies = max_replies.clone();
+    let _qbl = ztimeout!(peer01.declare_queryable(key_expr).callback(move |query| {
+        c_max_replies.store(query.max_replies().unwrap() as usize, Ordering::Relaxed);
+        for i in 0..10 {
+            query.reply(key_expr, vec![i]).wait().unwrap();
+        }
+        // The query is finalized by the routers, before the queryable drops it
+        std::thread::sleep(5 * SLEEP);
+    }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let replies = ztimeout!(peer02
+        .get(key_expr)
+        .consolidation(ConsolidationMode::None)
+        .max_replies(3)
+        .timeout(TIMEOUT))
+    .unwrap();
+    let start = std::time::Instant::now();
+    let mut received = 0;
+    while ztimeout!(replies.recv_async()).is_ok() {
+        received += 1;
+    }
+    assert_eq!(received, 3);
+    assert!(start.elapsed() < 4 * SLEEP);
+    assert_eq!(max_replies.load(Ordering::Relaxed), 3);
+
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_stream() {
+    use futures::StreamExt;
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/stream";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17497"]).await;
+
+    const WINDOW: usize = 4;
+    const COUNT: usize = 20;
+    let sent = Arc::new(AtomicUsize::new(0));
+    let aborted = Arc::new(AtomicUsize::new(0));
+    let (c_sent, c_aborted) = (sent.clone(), aborted.clone());
+    let _qbl = ztimeout!(peer01.declare_queryable(key_expr).callback(move |query| {
+        let (sent, aborted) = (c_sent.clone(), c_aborted.clone());
+        tokio::spawn(async move {
+            let sink = query.reply_sink();
+            for i in 0..COUNT {
+                if sink.reply(key_expr, vec![i as u8]).await.is_err() {
+                    aborted.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                sent.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // A slow consumer bounds the replies in flight to the window
+    let mut replies = ztimeout!(peer02.get(key_expr).stream(WINDOW as u32)).unwrap();
+    let mut received = 0;
+    while let Some(reply) = ztimeout!(replies.next()) {
+        assert!(reply.result().is_ok());
+        received += 1;
+        tokio::time::sleep(SLEEP / 20).await;
+        assert!(sent.load(Ordering::Relaxed) <= received + WINDOW);
+    }
+    assert_eq!(received, COUNT);
+
+    // Dropping the stream cancels the query and aborts the replies
+    let mut replies = ztimeout!(peer02.get(key_expr).stream(WINDOW as u32)).unwrap();
+    for _ in 0..2 {
+        ztimeout!(replies.next()).unwrap();
+    }
+    drop(replies);
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(aborted.load(Ordering::Relaxed), 1);
+
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_stream_idle_replier() {
+    use futures::StreamExt;
+    use zenoh::query::QueryTarget;
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/stream/idle";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17527"]).await;
+
+    const WINDOW: usize = 4;
+    const COUNT: usize = 20;
+    let _busy = ztimeout!(peer01.declare_queryable(key_expr).callback(move |query| {
+        tokio::spawn(async move {
+            let sink = query.reply_sink();
+            for i in 0..COUNT {
+                if sink.reply(key_expr, vec![i as u8]).await.is_err() {
+                    return;
+                }
+            }
+        });
+    }))
+    .unwrap();
+    let wakeup = Arc::new(tokio::sync::Notify::new());
+    let burst = Arc::new(AtomicUsize::new(0));
+    let (c_wakeup, c_burst) = (wakeup.clone(), burst.clone());
+    let _idle = ztimeout!(peer01.declare_queryable(key_expr).callback(move |query| {
+        let (wakeup, burst) = (c_wakeup.clone(), c_burst.clone());
+        tokio::spawn(async move {
+            let sink = query.reply_sink();
+            wakeup.notified().await;
+            for i in 0..COUNT {
+                match tokio::time::timeout(SLEEP, sink.reply(key_expr, vec![i as u8])).await {
+                    Ok(Ok(())) => burst.fetch_add(1, Ordering::Relaxed),
+                    _ => return,
+                };
+            }
+        });
+    }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The replies consumed from the busy replier do not grant credits to the idle one
+    let mut replies = ztimeout!(peer02
+        .get(key_expr)
+        .target(QueryTarget::All)
+        .stream(WINDOW as u32))
+    .unwrap();
+    for _ in 0..COUNT {
+        assert!(ztimeout!(replies.next()).unwrap().result().is_ok());
+    }
+    wakeup.notify_one();
+    tokio::time::sleep(2 * SLEEP).await;
+    assert_eq!(burst.load(Ordering::Relaxed), WINDOW);
+
+    // The replies sent by the idle replier within its window are delivered
+    for _ in 0..WINDOW {
+        assert!(ztimeout!(replies.next()).unwrap().result().is_ok());
+    }
+    drop(replies);
+
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_load_balancing() {
+    use zenoh::{
+        query::{ConsolidationMode, QueryTarget},
+        Wait,
+    };
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/load_balancing";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17507"]).await;
+    let mut config = zenoh::Config::default();
+    config
+        .connect
+        .endpoints
+        .set(vec!["tcp/127.0.0.1:17507".parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let peer03 = ztimeout!(zenoh::open(config)).unwrap();
+
+    let _qbl02 = ztimeout!(peer02
+        .declare_queryable(key_expr)
+        .callback(move |query| query.reply(key_expr, "02").wait().unwrap()))
+    .unwrap();
+    let _qbl03 = ztimeout!(peer03
+        .declare_queryable(key_expr)
+        .callback(move |query| query.reply(key_expr, "03").wait().unwrap()))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    for target in [
+        QueryTarget::RoundRobin,
+        QueryTarget::Random,
+        QueryTarget::LeastOutstanding,
+    ] {
+        let mut replied = std::collections::HashMap::<String, usize>::new();
+        for _ in 0..10 {
+            let replies = ztimeout!(peer01
+                .get(key_expr)
+                .target(target)
+                .consolidation(ConsolidationMode::None))
+            .unwrap();
+            let mut received = 0;
+            while let Ok(reply) = ztimeout!(replies.recv_async()) {
+                let payload = reply.result().unwrap().payload().try_to_string().unwrap();
+                *replied.entry(payload.into_owned()).or_default() += 1;
+                received += 1;
+            }
+            // Each query is delivered to exactly one of the equivalent queryables
+            assert_eq!(received, 1);
+        }
+        if target == QueryTarget::RoundRobin {
+            assert_eq!(replied.get("02"), Some(&5));
+            assert_eq!(replied.get("03"), Some(&5));
+        }
+    }
+
+    ztimeout!(peer03.close()).unwrap();
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_round_robin_interleaved() {
+    use zenoh::{
+        query::{ConsolidationMode, QueryTarget},
+        Wait,
+    };
+
+    zenoh::init_log_from_env_or("error");
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17537"]).await;
+    let mut config = zenoh::Config::default();
+    config
+        .connect
+        .endpoints
+        .set(vec!["tcp/127.0.0.1:17537".parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let peer03 = ztimeout!(zenoh::open(config)).unwrap();
+
+    // Remote queryables declared on the queried key expressions or on a wildcard one, and local
+    // queryables
+    let remote = "test/session/round_robin/remote";
+    let wildcard = "test/session/round_robin/wildcard";
+    let local = "test/session/round_robin/local";
+    let mut queryables = vec![];
+    for (session, key_expr, replier) in [
+        (&peer02, format!("{remote}/a"), "02"),
+        (&peer03, format!("{remote}/a"), "03"),
+        (&peer02, format!("{remote}/b"), "02"),
+        (&peer03, format!("{remote}/b"), "03"),
+        (&peer02, format!("{wildcard}/*"), "02"),
+        (&peer03, format!("{wildcard}/*"), "03"),
+        (&peer01, format!("{local}/a"), "01"),
+        (&peer01, format!("{local}/a"), "01bis"),
+        (&peer01, format!("{local}/b"), "01"),
+        (&peer01, format!("{local}/b"), "01bis"),
+    ] {
+        queryables.push(
+            ztimeout!(session
+                .declare_queryable(key_expr)
+                .callback(move |query| query
+                    .reply(query.key_expr().clone(), replier)
+                    .wait()
+                    .unwrap()))
+            .unwrap(),
+        );
+    }
+    tokio::time::sleep(SLEEP).await;
+
+    // Queries interleaved on two key expressions rotate on the replica set of each
+    for (prefix, repliers) in [
+        (remote, ["02", "03"]),
+        (wildcard, ["02", "03"]),
+        (local, ["01", "01bis"]),
+    ] {
+        let mut replied = std::collections::HashMap::<(&str, String), usize>::new();
+        for _ in 0..10 {
+            for suffix in ["a", "b"] {
+                let replies = ztimeout!(peer01
+                    .get(format!("{prefix}/{suffix}"))
+                    .target(QueryTarget::RoundRobin)
+                    .consolidation(ConsolidationMode::None))
+                .unwrap();
+                while let Ok(reply) = ztimeout!(replies.recv_async()) {
+                    let payload = reply.result().unwrap().payload().try_to_string().unwrap();
+                    *replied.entry((suffix, payload.into_owned())).or_default() += 1;
+                }
+            }
+        }
+        for suffix in ["a", "b"] {
+            for replier in repliers {
+                assert_eq!(
+                    replied.get(&(suffix, replier.to_string())),
+                    Some(&5),
+                    "{prefix}/{suffix}"
+                );
+            }
+        }
+    }
+
+    drop(queryables);
+    ztimeout!(peer03.close()).unwrap();
+    close_session(peer01, peer02).await;
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_query_load_balancing_local_remote() {
+    use zenoh::{
+        query::{ConsolidationMode, QueryTarget},
+        Wait,
+    };
+
+    zenoh::init_log_from_env_or("error");
+    let key_expr = "test/session/load_balancing/local_remote";
+    let (peer01, peer02) = open_session_unicast(&["tcp/127.0.0.1:17517"]).await;
+
+    let _qbl01 = ztimeout!(peer01
+        .declare_queryable(key_expr)
+        .callback(move |query| query.reply(key_expr, "01").wait().unwrap()))
+    .unwrap();
+    let _qbl02 = ztimeout!(peer02
+        .declare_queryable(key_expr)
+        .callback(move |query| query.reply(key_expr, "02").wait().unwrap()))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let mut replied = std::collections::HashMap::<String, usize>::new();
+    for _ in 0..10 {
+        let replies = ztimeout!(peer01
+            .get(key_expr)
+            .target(QueryTarget::RoundRobin)
+            .consolidation(ConsolidationMode::None))
+        .unwrap();
+        let mut received = 0;
+        while let Ok(reply) = ztimeout!(replies.recv_async()) {
+            let payload = reply.result().unwrap().payload().try_to_string().unwrap();
+            *replied.entry(payload.into_owned()).or_default() += 1;
+            received += 1;
+        }
+        // A single selection is made among the local and the remote queryables
+        assert_eq!(received, 1);
+    }
+    assert_eq!(replied.get("01"), Some(&5));
+    assert_eq!(replied.get("02"), Some(&5));
+
+    close_session(peer01, peer02).await;
+}
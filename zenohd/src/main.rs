@@ -11,23 +11,31 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use clap::Parser;
+use std::{io::BufRead, time::Duration};
+
+use clap::{Parser, Subcommand};
 use futures::future;
 use git_version::git_version;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-use zenoh::{config::WhatAmI, Config, Result};
+use zenoh::{config::WhatAmI, Config, Result, Session};
 use zenoh_config::{EndPoint, ModeDependentValue, PermissionsConf};
+use zenoh_transport::unicast::establishment::ext::auth::{
+    UsrPwdVerifier, USRPWD_DEFAULT_ITERATIONS,
+};
 use zenoh_util::LibSearchDirs;
 
 const GIT_VERSION: &str = git_version!(prefix = "v", cargo_prefix = "v");
+const WATCH_CONFIG_PERIOD: Duration = Duration::from_secs(1);
 
 lazy_static::lazy_static!(
     static ref LONG_VERSION: String = format!("{} built with {}", GIT_VERSION, env!("RUSTC_VERSION"));
 );
 
 #[derive(Debug, Parser)]
-#[command(version=GIT_VERSION, long_version=LONG_VERSION.as_str(), about="The zenoh router")]
+#[command(version=GIT_VERSION, long_version=LONG_VERSION.as_str(), about="The zenoh router", args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// The configuration file. Currently, this file must be a valid JSON5 or YAML file.
     #[arg(short, long, value_name = "PATH")]
     config: Option<String>,
@@ -74,9 +82,38 @@ struct Args {
     /// Configure the read and/or write permissions on the admin space. Default is read only.
     #[arg(long, value_name = "[r|w|rw|none]")]
     adminspace_permissions: Option<String>,
+    /// Watch the configuration file and apply the changes of its `access_control` section without restarting.
+    /// The changes of the other sections are ignored.
+    #[arg(long, requires = "config")]
+    watch_config: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a `<user>:<verifier>` entry for the user-password dictionary file, reading the password from the standard input.
+    /// The entry only contains a salted verifier of the password, which can't be used to recover it.
+    UsrpwdVerifier {
+        /// The user name.
+        user: String,
+        /// The number of PBKDF2 iterations used to derive the verifier.
+        #[arg(long, default_value_t = USRPWD_DEFAULT_ITERATIONS)]
+        iterations: u32,
+    },
 }
 
 fn main() {
+    let args = Args::parse();
+    if let Some(command) = &args.command {
+        match command {
+            Command::UsrpwdVerifier { user, iterations } => usrpwd_verifier(user, *iterations)
+                .unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    std::process::exit(-1);
+                }),
+        }
+        return;
+    }
+
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
@@ -86,11 +123,10 @@ fn main() {
 
             tracing::info!("zenohd {}", *LONG_VERSION);
 
-            let args = Args::parse();
             let config = config_from_args(&args);
             tracing::info!("Initial conf: {}", &config);
 
-            let _session = match zenoh::open(config).await {
+            let session = match zenoh::open(config).await {
                 Ok(runtime) => runtime,
                 Err(e) => {
                     println!("{e}. Exiting...");
@@ -98,10 +134,65 @@ fn main() {
                 }
             };
 
+            if args.watch_config {
+                if let Some(path) = &args.config {
+                    tokio::spawn(watch_config(session.clone(), path.clone()));
+                }
+            }
+
             future::pending::<()>().await;
         });
 }
 
+/// Polls the configuration file and applies the changes of its `access_control` section.
+async fn watch_config(session: Session, path: String) {
+    let modified = || std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let mut last_modified = modified();
+    let mut access_control = session.config().lock().get_json("access_control").ok();
+    loop {
+        tokio::time::sleep(WATCH_CONFIG_PERIOD).await;
+        let now_modified = modified();
+        if now_modified == last_modified {
+            continue;
+        }
+        last_modified = now_modified;
+        let config = match Config::from_file(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::error!("Couldn't reload configuration file {}: {}", path, e);
+                continue;
+            }
+        };
+        let new_access_control = config.get_json("access_control").ok();
+        if new_access_control == access_control {
+            continue;
+        }
+        // A removed section is applied as the default one, i.e. disabling the access control
+        tracing::info!("Access control configuration of {} has changed", path);
+        let acl_config = config.access_control().clone();
+        match session.runtime().update_access_control(acl_config).await {
+            Ok(()) => access_control = new_access_control,
+            Err(e) => tracing::error!("Couldn't update access control configuration: {}", e),
+        }
+    }
+}
+
+fn usrpwd_verifier(user: &str, iterations: u32) -> Result<()> {
+    if user.is_empty() || user.contains(':') {
+        zenoh::internal::bail!("Invalid user: must be non-empty and must not contain ':'");
+    }
+    let mut password = String::new();
+    std::io::stdin().lock().read_line(&mut password)?;
+    // Surrounding spaces are trimmed, as for the cleartext passwords of the dictionary file
+    let password = password.trim();
+    if password.is_empty() {
+        zenoh::internal::bail!("Invalid password: must be non-empty");
+    }
+    let verifier = UsrPwdVerifier::new(password.as_bytes(), iterations, &mut rand::thread_rng())?;
+    println!("{user}:{verifier}");
+    Ok(())
+}
+
 fn config_from_args(args: &Args) -> Config {
     let mut config = args
         .config